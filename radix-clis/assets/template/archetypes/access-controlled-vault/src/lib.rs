@@ -0,0 +1,79 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod access_controlled_vault {
+    enable_method_auth! {
+        roles {
+            withdrawer => updatable_by: [OWNER];
+        },
+        methods {
+            deposit => PUBLIC;
+            withdraw => restrict_to: [withdrawer];
+            set_withdrawal_limit => restrict_to: [OWNER];
+            balance => PUBLIC;
+        }
+    }
+
+    struct AccessControlledVault {
+        // The resources guarded by the component
+        vault: FungibleVault,
+        // The maximum amount which can be withdrawn at once
+        withdrawal_limit: Decimal,
+    }
+
+    impl AccessControlledVault {
+        // Creates a vault of the given resource, which anyone can deposit into but only the
+        // holders of the withdrawer badge can withdraw from. Returns the component and its owner
+        // badge, which can change the withdrawal limit and the withdrawer role.
+        pub fn instantiate_access_controlled_vault(
+            resource_address: ResourceAddress,
+            withdrawer_badge: ResourceAddress,
+            withdrawal_limit: Decimal,
+        ) -> (Global<AccessControlledVault>, FungibleBucket) {
+            let owner_badge: FungibleBucket = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata! {
+                    init {
+                        "name" => "Vault Owner Badge", locked;
+                    }
+                })
+                .mint_initial_supply(1);
+            let owner_role = OwnerRole::Updatable(rule!(require(owner_badge.resource_address())));
+
+            let component = Self {
+                vault: FungibleVault::new(resource_address),
+                withdrawal_limit,
+            }
+            .instantiate()
+            .prepare_to_globalize(owner_role)
+            .roles(roles! {
+                withdrawer => rule!(require(withdrawer_badge));
+            })
+            .globalize();
+
+            (component, owner_badge)
+        }
+
+        pub fn deposit(&mut self, bucket: FungibleBucket) {
+            self.vault.put(bucket);
+        }
+
+        // Withdraws at most the withdrawal limit, which requires the withdrawer role
+        pub fn withdraw(&mut self, amount: Decimal) -> FungibleBucket {
+            assert!(
+                amount <= self.withdrawal_limit,
+                "The amount exceeds the withdrawal limit of {}",
+                self.withdrawal_limit
+            );
+            self.vault.take(amount)
+        }
+
+        pub fn set_withdrawal_limit(&mut self, withdrawal_limit: Decimal) {
+            self.withdrawal_limit = withdrawal_limit;
+        }
+
+        pub fn balance(&self) -> Decimal {
+            self.vault.amount()
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use scrypto_test::prelude::*;
+
+use ${wasm_name}::access_controlled_vault_test::*;
+
+#[test]
+fn test_access_controlled_vault() {
+    // Setup the environment
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    // Create an account, a token to guard and a withdrawer badge
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
+    let token = ledger.create_fungible_resource(dec!(1000), 18, account);
+    let withdrawer_badge = ledger.create_fungible_resource(dec!(1), 0, account);
+
+    // Publish package
+    let package_address = ledger.compile_and_publish(this_package!());
+
+    // Test the `instantiate_access_controlled_vault` function.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "AccessControlledVault",
+            "instantiate_access_controlled_vault",
+            manifest_args!(token, withdrawer_badge, dec!(150)),
+        )
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    let component = receipt.expect_commit(true).new_component_addresses()[0];
+
+    // Test the `deposit` method.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, token, dec!(500))
+        .take_all_from_worktop(token, "tokens")
+        .call_method_with_name_lookup(component, "deposit", |lookup| (lookup.bucket("tokens"),))
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+
+    // Test the `withdraw` method, presenting the withdrawer badge.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_amount(account, withdrawer_badge, dec!(1))
+        .call_method(component, "withdraw", manifest_args!(dec!(100)))
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    assert_eq!(ledger.get_component_balance(account, token), dec!(600));
+
+    // Withdrawing without presenting the withdrawer badge is rejected.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component, "withdraw", manifest_args!(dec!(100)))
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn test_access_controlled_vault_with_test_environment() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address =
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let tokens =
+        ResourceBuilder::new_fungible(OwnerRole::None).mint_initial_supply(dec!(1000), &mut env)?;
+    let withdrawer_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+        .divisibility(0)
+        .mint_initial_supply(1, &mut env)?;
+    let token = tokens.resource_address(&mut env)?;
+    let withdrawer_badge = withdrawer_badge.resource_address(&mut env)?;
+
+    let (mut vault, _owner_badge) = AccessControlledVault::instantiate_access_controlled_vault(
+        token,
+        withdrawer_badge,
+        dec!(150),
+        package_address,
+        &mut env,
+    )?;
+    vault.deposit(tokens, &mut env)?;
+
+    // Act
+    let withdrawn = env.with_auth_module_disabled(|env| vault.withdraw(dec!(100), env))?;
+
+    // Assert
+    assert_eq!(withdrawn.amount(&mut env)?, dec!(100));
+    assert_eq!(vault.balance(&mut env)?, dec!(900));
+
+    // Amounts above the withdrawal limit can't be withdrawn.
+    let result = env.with_auth_module_disabled(|env| vault.withdraw(dec!(200), env));
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod airdrop {
+    enable_method_auth! {
+        methods {
+            airdrop => restrict_to: [OWNER];
+            get_claimable_amount => PUBLIC;
+        }
+    }
+
+    struct Airdrop {
+        // The native account locker which holds the airdropped resources until they are claimed
+        locker: Global<AccountLocker>,
+    }
+
+    impl Airdrop {
+        // Creates an airdrop component and its account locker, and returns the component and its
+        // owner badge, which is required to airdrop and to recover unclaimed resources
+        pub fn instantiate_airdrop() -> (Global<Airdrop>, FungibleBucket) {
+            // Reserve the address of the component, so that the locker can allow it to store
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(Airdrop::blueprint_id());
+
+            let owner_badge: FungibleBucket = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata! {
+                    init {
+                        "name" => "Airdrop Owner Badge", locked;
+                    }
+                })
+                .mint_initial_supply(1);
+            let owner_rule = rule!(require(owner_badge.resource_address()));
+
+            let locker = Blueprint::<AccountLocker>::instantiate(
+                OwnerRole::Fixed(owner_rule.clone()),
+                // Only this component can store resources in the locker
+                rule!(require(global_caller(component_address))),
+                rule!(deny_all),
+                // The owner can recover the resources which were not claimed
+                owner_rule.clone(),
+                rule!(deny_all),
+                None,
+            );
+
+            let component = Self { locker }
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::Fixed(owner_rule))
+                .with_address(address_reservation)
+                .globalize();
+
+            (component, owner_badge)
+        }
+
+        // Airdrops the given amount of tokens to each claimant, which requires the owner role.
+        //
+        // With `try_direct_send`, the tokens are deposited into the accounts which accept them,
+        // and stored in the locker for the others. The tokens which aren't airdropped are returned.
+        pub fn airdrop(
+            &mut self,
+            claimants: IndexMap<Global<Account>, Decimal>,
+            tokens: FungibleBucket,
+            try_direct_send: bool,
+        ) -> Option<Bucket> {
+            let claimants = claimants
+                .into_iter()
+                .map(|(claimant, amount)| (claimant, ResourceSpecifier::Fungible(amount)))
+                .collect();
+            self.locker
+                .airdrop(claimants, tokens.into(), try_direct_send)
+        }
+
+        // The amount of a resource stored in the locker for a claimant, which the claimant can
+        // claim from the locker
+        pub fn get_claimable_amount(
+            &self,
+            claimant: Global<Account>,
+            resource_address: ResourceAddress,
+        ) -> Decimal {
+            self.locker.get_amount(claimant, resource_address)
+        }
+    }
+}
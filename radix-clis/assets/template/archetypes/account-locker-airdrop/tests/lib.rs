@@ -0,0 +1,117 @@
+use scrypto_test::prelude::*;
+
+use ${wasm_name}::airdrop_test::*;
+
+#[test]
+fn test_airdrop_and_claim() {
+    // Setup the environment
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    // Create the account of the owner, the account of a claimant and a token to airdrop
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
+    let (claimant_public_key, _claimant_private_key, claimant) = ledger.new_allocated_account();
+    let token = ledger.create_fungible_resource(dec!(1000), 18, account);
+
+    // Publish package
+    let package_address = ledger.compile_and_publish(this_package!());
+
+    // Test the `instantiate_airdrop` function.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "Airdrop",
+            "instantiate_airdrop",
+            manifest_args!(),
+        )
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    let commit = receipt.expect_commit(true);
+    let (component, _owner_badge): (ComponentAddress, Own) = commit.output(1);
+    let owner_badge = commit.new_resource_addresses()[0];
+    let locker =
+        ComponentAddress::try_from(ledger.component_state::<AirdropState>(component).locker.0)
+            .unwrap();
+
+    // Test the `airdrop` method, storing the tokens in the locker.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_amount(account, owner_badge, dec!(1))
+        .withdraw_from_account(account, token, dec!(100))
+        .take_all_from_worktop(token, "tokens")
+        .call_method_with_name_lookup(component, "airdrop", |lookup| {
+            (
+                indexmap!(claimant => dec!(100)),
+                lookup.bucket("tokens"),
+                false,
+            )
+        })
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    assert_eq!(ledger.get_component_balance(claimant, token), dec!(0));
+
+    // Test claiming the tokens from the locker, which the claimant does with its own signature.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(locker, "claim", manifest_args!(claimant, token, dec!(100)))
+        .deposit_entire_worktop(claimant)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(claimant_public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    assert_eq!(ledger.get_component_balance(claimant, token), dec!(100));
+}
+
+#[test]
+fn test_airdrop_with_test_environment() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address =
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let (mut airdrop, _owner_badge) = Airdrop::instantiate_airdrop(package_address, &mut env)?;
+    let claimant: ComponentAddress = env.call_function_typed(
+        ACCOUNT_PACKAGE,
+        ACCOUNT_BLUEPRINT,
+        ACCOUNT_CREATE_ADVANCED_IDENT,
+        &AccountCreateAdvancedInput {
+            owner_role: OwnerRole::None,
+            address_reservation: None,
+        },
+    )?;
+    let tokens =
+        ResourceBuilder::new_fungible(OwnerRole::None).mint_initial_supply(dec!(1000), &mut env)?;
+    let token = tokens.resource_address(&mut env)?;
+
+    // Act
+    let remainder = env.with_auth_module_disabled(|env| {
+        airdrop.airdrop(
+            indexmap!(Reference(claimant.into_node_id()) => dec!(100)),
+            tokens,
+            false,
+            env,
+        )
+    })?;
+
+    // Assert
+    assert_eq!(remainder.unwrap().amount(&mut env)?, dec!(900));
+    let claimable_amount =
+        airdrop.get_claimable_amount(Reference(claimant.into_node_id()), token, &mut env)?;
+    assert_eq!(claimable_amount, dec!(100));
+
+    Ok(())
+}
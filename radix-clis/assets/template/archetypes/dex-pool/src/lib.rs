@@ -0,0 +1,113 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+#[events(SwapEvent)]
+mod dex_pool {
+    struct DexPool {
+        // The native two-resource pool which holds the liquidity and mints the pool units
+        pool_component: Global<TwoResourcePool>,
+        // The fraction of each swap input which is kept by the pool, e.g. 0.003 for 0.3%
+        fee: Decimal,
+    }
+
+    impl DexPool {
+        // Creates a constant product pool of two fungible resources, which charges the given fee
+        // on swaps
+        pub fn instantiate_dex_pool(
+            resource_address1: ResourceAddress,
+            resource_address2: ResourceAddress,
+            fee: Decimal,
+        ) -> Global<DexPool> {
+            assert!(
+                fee >= Decimal::ZERO && fee < Decimal::ONE,
+                "The fee must be in the [0, 1) range"
+            );
+
+            // Reserve the address of the component, so that the pool can allow it to manage the
+            // liquidity
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(DexPool::blueprint_id());
+
+            // The pool checks that the resources are fungible and different
+            let pool_component = Blueprint::<TwoResourcePool>::instantiate(
+                OwnerRole::None,
+                rule!(require(global_caller(component_address))),
+                (resource_address1, resource_address2),
+                None,
+            );
+
+            Self {
+                pool_component,
+                fee,
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .with_address(address_reservation)
+            .globalize()
+        }
+
+        // Contributes liquidity to the pool, and returns the pool units along with the change of
+        // the resource contributed in excess of the pool's ratio, if any
+        pub fn add_liquidity(
+            &mut self,
+            resource1: FungibleBucket,
+            resource2: FungibleBucket,
+        ) -> (FungibleBucket, Option<FungibleBucket>) {
+            self.pool_component.contribute((resource1, resource2))
+        }
+
+        // Redeems pool units for their share of both resources. The holders of pool units could
+        // also redeem them from the pool directly.
+        pub fn remove_liquidity(
+            &mut self,
+            pool_units: FungibleBucket,
+        ) -> (FungibleBucket, FungibleBucket) {
+            self.pool_component.redeem(pool_units)
+        }
+
+        // Swaps one of the resources of the pool for the other, keeping the product of the
+        // reserves constant once the fee is taken out of the input
+        pub fn swap(&mut self, input_bucket: FungibleBucket) -> FungibleBucket {
+            let mut reserves = self.pool_component.get_vault_amounts();
+
+            let input_amount = input_bucket.amount();
+            let input_reserves = reserves
+                .swap_remove(&input_bucket.resource_address())
+                .expect("Resource does not belong to the pool");
+            let (output_resource_address, output_reserves) = reserves.into_iter().next().unwrap();
+
+            let input_amount_after_fee = input_amount
+                .checked_mul(Decimal::ONE.checked_sub(self.fee).unwrap())
+                .unwrap();
+            let output_amount = input_amount_after_fee
+                .checked_mul(output_reserves)
+                .unwrap()
+                .checked_div(input_reserves.checked_add(input_amount_after_fee).unwrap())
+                .unwrap();
+
+            Runtime::emit_event(SwapEvent {
+                input: (input_bucket.resource_address(), input_amount),
+                output: (output_resource_address, output_amount),
+            });
+
+            // The whole input is deposited, the fee being left in the pool for the liquidity
+            // providers
+            self.pool_component.protected_deposit(input_bucket);
+            self.pool_component.protected_withdraw(
+                output_resource_address,
+                output_amount,
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            )
+        }
+
+        pub fn get_reserves(&self) -> IndexMap<ResourceAddress, Decimal> {
+            self.pool_component.get_vault_amounts()
+        }
+    }
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SwapEvent {
+    pub input: (ResourceAddress, Decimal),
+    pub output: (ResourceAddress, Decimal),
+}
@@ -0,0 +1,112 @@
+use scrypto_test::prelude::*;
+
+use ${wasm_name}::dex_pool_test::*;
+
+#[test]
+fn test_dex_pool() {
+    // Setup the environment
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    // Create an account and two resources to trade
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
+    let resource1 = ledger.create_fungible_resource(dec!(10000), 18, account);
+    let resource2 = ledger.create_fungible_resource(dec!(10000), 18, account);
+
+    // Publish package
+    let package_address = ledger.compile_and_publish(this_package!());
+
+    // Test the `instantiate_dex_pool` function.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "DexPool",
+            "instantiate_dex_pool",
+            manifest_args!(resource1, resource2, dec!("0.003")),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    let commit = receipt.expect_commit(true);
+    let component: ComponentAddress = commit.output(1);
+    let pool_unit = commit.new_resource_addresses()[0];
+
+    // Test the `add_liquidity` method.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, resource1, dec!(1000))
+        .withdraw_from_account(account, resource2, dec!(1000))
+        .take_all_from_worktop(resource1, "resource1")
+        .take_all_from_worktop(resource2, "resource2")
+        .call_method_with_name_lookup(component, "add_liquidity", |lookup| {
+            (lookup.bucket("resource1"), lookup.bucket("resource2"))
+        })
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    assert!(ledger.get_component_balance(account, pool_unit) > Decimal::ZERO);
+
+    // Test the `swap` method.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, resource1, dec!(100))
+        .take_all_from_worktop(resource1, "input")
+        .call_method_with_name_lookup(component, "swap", |lookup| (lookup.bucket("input"),))
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    let output_amount = ledger.get_component_balance(account, resource2) - dec!(9000);
+    assert!(output_amount > Decimal::ZERO && output_amount < dec!(100));
+}
+
+#[test]
+fn test_dex_pool_with_test_environment() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address =
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let bucket1 = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(10000), &mut env)?;
+    let bucket2 = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(dec!(10000), &mut env)?;
+    let resource1 = bucket1.resource_address(&mut env)?;
+    let resource2 = bucket2.resource_address(&mut env)?;
+
+    let mut dex_pool =
+        DexPool::instantiate_dex_pool(resource1, resource2, dec!(0), package_address, &mut env)?;
+    let liquidity1 = bucket1.take(dec!(1000), &mut env)?;
+    let liquidity2 = bucket2.take(dec!(1000), &mut env)?;
+    let (pool_units, change) = dex_pool.add_liquidity(liquidity1, liquidity2, &mut env)?;
+    assert!(change.is_none());
+
+    // Act
+    let input = bucket1.take(dec!(1000), &mut env)?;
+    let output = dex_pool.swap(input, &mut env)?;
+
+    // Assert: without fee, swapping as much as the reserves halves the output reserves
+    assert_eq!(output.amount(&mut env)?, dec!(500));
+    let reserves = dex_pool.get_reserves(&mut env)?;
+    assert_eq!(reserves.get(&resource1), Some(&dec!(2000)));
+    assert_eq!(reserves.get(&resource2), Some(&dec!(500)));
+
+    // The pool units are redeemed for the whole reserves
+    let (redeemed1, redeemed2) = dex_pool.remove_liquidity(pool_units, &mut env)?;
+    assert_eq!(redeemed1.amount(&mut env)?, dec!(2000));
+    assert_eq!(redeemed2.amount(&mut env)?, dec!(500));
+
+    Ok(())
+}
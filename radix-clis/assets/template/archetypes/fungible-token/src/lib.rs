@@ -0,0 +1,95 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod fungible_token {
+    enable_method_auth! {
+        roles {
+            minter => updatable_by: [OWNER];
+        },
+        methods {
+            mint => restrict_to: [minter];
+            burn => PUBLIC;
+            total_supply => PUBLIC;
+        }
+    }
+
+    struct FungibleToken {
+        // The resource manager of the token, which only this component is allowed to mint with
+        token_manager: FungibleResourceManager,
+    }
+
+    impl FungibleToken {
+        // Creates a token with an initial supply, and returns the component, the initial supply
+        // and the owner badge, which is also allowed to call `mint` on the component
+        pub fn instantiate_fungible_token(
+            name: String,
+            symbol: String,
+            initial_supply: Decimal,
+        ) -> (Global<FungibleToken>, FungibleBucket, FungibleBucket) {
+            // Reserve the address of the component, so that the token can allow it to mint
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(FungibleToken::blueprint_id());
+
+            let owner_badge: FungibleBucket = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata! {
+                    init {
+                        "name" => format!("{} Owner Badge", name), locked;
+                    }
+                })
+                .mint_initial_supply(1);
+            let owner_rule = rule!(require(owner_badge.resource_address()));
+
+            let tokens: FungibleBucket =
+                ResourceBuilder::new_fungible(OwnerRole::Fixed(owner_rule.clone()))
+                    .divisibility(DIVISIBILITY_MAXIMUM)
+                    .metadata(metadata! {
+                        init {
+                            "name" => name, locked;
+                            "symbol" => symbol, locked;
+                        }
+                    })
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .burn_roles(burn_roles! {
+                        burner => rule!(allow_all);
+                        burner_updater => rule!(deny_all);
+                    })
+                    .mint_initial_supply(initial_supply);
+
+            let component = Self {
+                token_manager: tokens.resource_manager(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(owner_rule.clone()))
+            .roles(roles! {
+                minter => owner_rule;
+            })
+            .with_address(address_reservation)
+            .globalize();
+
+            (component, tokens, owner_badge)
+        }
+
+        // Mints new tokens, which requires the minter role
+        pub fn mint(&mut self, amount: Decimal) -> FungibleBucket {
+            self.token_manager.mint(amount)
+        }
+
+        // Burns tokens, which anyone holding them may do
+        pub fn burn(&mut self, tokens: FungibleBucket) {
+            assert_eq!(
+                tokens.resource_address(),
+                self.token_manager.address(),
+                "Only the tokens of this component can be burnt"
+            );
+            tokens.burn();
+        }
+
+        pub fn total_supply(&self) -> Decimal {
+            self.token_manager.total_supply().unwrap_or_default()
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use scrypto_test::prelude::*;
+
+use ${wasm_name}::fungible_token_test::*;
+
+#[test]
+fn test_fungible_token() {
+    // Setup the environment
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    // Create an account
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
+
+    // Publish package
+    let package_address = ledger.compile_and_publish(this_package!());
+
+    // Test the `instantiate_fungible_token` function.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "FungibleToken",
+            "instantiate_fungible_token",
+            manifest_args!("Token".to_string(), "TKN".to_string(), dec!(1000)),
+        )
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    let commit = receipt.expect_commit(true);
+    let component = commit.new_component_addresses()[0];
+    let owner_badge = commit.new_resource_addresses()[0];
+    let token = commit.new_resource_addresses()[1];
+    assert_eq!(ledger.get_component_balance(account, token), dec!(1000));
+
+    // Test the `mint` method, presenting the owner badge.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_amount(account, owner_badge, dec!(1))
+        .call_method(component, "mint", manifest_args!(dec!(500)))
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    assert_eq!(ledger.get_component_balance(account, token), dec!(1500));
+
+    // Test the `burn` method.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, token, dec!(100))
+        .take_all_from_worktop(token, "tokens")
+        .call_method_with_name_lookup(component, "burn", |lookup| (lookup.bucket("tokens"),))
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    assert_eq!(ledger.get_component_balance(account, token), dec!(1400));
+}
+
+#[test]
+fn test_mint_without_owner_badge_fails() {
+    // Setup the environment
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
+    let package_address = ledger.compile_and_publish(this_package!());
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "FungibleToken",
+            "instantiate_fungible_token",
+            manifest_args!("Token".to_string(), "TKN".to_string(), dec!(1000)),
+        )
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    let component = receipt.expect_commit(true).new_component_addresses()[0];
+
+    // Minting without presenting the owner badge is rejected by the minter role.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component, "mint", manifest_args!(dec!(500)))
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    receipt.expect_auth_failure();
+}
+
+#[test]
+fn test_fungible_token_with_test_environment() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address =
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let (mut token, tokens, _owner_badge) = FungibleToken::instantiate_fungible_token(
+        "Token".to_string(),
+        "TKN".to_string(),
+        dec!(1000),
+        package_address,
+        &mut env,
+    )?;
+
+    // Act
+    let minted = env.with_auth_module_disabled(|env| token.mint(dec!(500), env))?;
+    token.burn(tokens, &mut env)?;
+
+    // Assert
+    assert_eq!(minted.amount(&mut env)?, dec!(500));
+    assert_eq!(token.total_supply(&mut env)?, dec!(500));
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use scrypto::prelude::*;
+
+// The data of each NFT of the collection
+#[derive(ScryptoSbor, NonFungibleData)]
+pub struct Collectible {
+    pub name: String,
+    pub serial_number: u64,
+}
+
+#[blueprint]
+mod nft_collection {
+    enable_method_auth! {
+        methods {
+            mint => PUBLIC;
+            withdraw_proceeds => restrict_to: [OWNER];
+        }
+    }
+
+    struct NftCollection {
+        // The resource manager of the NFTs, which only this component is allowed to mint with
+        nft_manager: NonFungibleResourceManager,
+        // The price of an NFT, in XRD
+        price: Decimal,
+        // The maximum number of NFTs which can be minted
+        max_supply: u64,
+        // The number of NFTs minted so far, which is also the id of the next NFT
+        minted: u64,
+        // The XRD paid for the NFTs, which the owner can withdraw
+        proceeds: FungibleVault,
+    }
+
+    impl NftCollection {
+        // Creates a collection of at most `max_supply` NFTs sold at `price` XRD each, and returns
+        // the component and its owner badge
+        pub fn instantiate_nft_collection(
+            price: Decimal,
+            max_supply: u64,
+        ) -> (Global<NftCollection>, FungibleBucket) {
+            assert!(price >= Decimal::ZERO, "The price can't be negative");
+
+            // Reserve the address of the component, so that the NFTs can allow it to mint
+            let (address_reservation, component_address) =
+                Runtime::allocate_component_address(NftCollection::blueprint_id());
+
+            let owner_badge: FungibleBucket = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata(metadata! {
+                    init {
+                        "name" => "Collection Owner Badge", locked;
+                    }
+                })
+                .mint_initial_supply(1);
+            let owner_role = OwnerRole::Fixed(rule!(require(owner_badge.resource_address())));
+
+            let nft_manager =
+                ResourceBuilder::new_integer_non_fungible::<Collectible>(owner_role.clone())
+                    .metadata(metadata! {
+                        init {
+                            "name" => "My Collection", locked;
+                        }
+                    })
+                    .mint_roles(mint_roles! {
+                        minter => rule!(require(global_caller(component_address)));
+                        minter_updater => rule!(deny_all);
+                    })
+                    .create_with_no_initial_supply();
+
+            let component = Self {
+                nft_manager,
+                price,
+                max_supply,
+                minted: 0,
+                proceeds: FungibleVault::new(XRD),
+            }
+            .instantiate()
+            .prepare_to_globalize(owner_role)
+            .with_address(address_reservation)
+            .globalize();
+
+            (component, owner_badge)
+        }
+
+        // Mints the next NFT of the collection in exchange for its price, and returns the NFT
+        // along with the change
+        pub fn mint(&mut self, mut payment: FungibleBucket) -> (NonFungibleBucket, FungibleBucket) {
+            assert_eq!(
+                payment.resource_address(),
+                XRD,
+                "The payment must be in XRD"
+            );
+            assert!(self.minted < self.max_supply, "The collection is sold out");
+
+            self.proceeds.put(payment.take(self.price));
+            self.minted += 1;
+            let nft = self.nft_manager.mint_non_fungible(
+                &NonFungibleLocalId::integer(self.minted),
+                Collectible {
+                    name: format!("Collectible #{}", self.minted),
+                    serial_number: self.minted,
+                },
+            );
+
+            (nft, payment)
+        }
+
+        // Withdraws the XRD paid for the NFTs, which only the owner can do
+        pub fn withdraw_proceeds(&mut self) -> FungibleBucket {
+            self.proceeds.take_all()
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use scrypto_test::prelude::*;
+
+use ${wasm_name}::nft_collection_test::*;
+
+#[test]
+fn test_nft_collection() {
+    // Setup the environment
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+
+    // Create an account
+    let (public_key, _private_key, account) = ledger.new_allocated_account();
+
+    // Publish package
+    let package_address = ledger.compile_and_publish(this_package!());
+
+    // Test the `instantiate_nft_collection` function.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "NftCollection",
+            "instantiate_nft_collection",
+            manifest_args!(dec!(50), 10u64),
+        )
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    let commit = receipt.expect_commit(true);
+    let component = commit.new_component_addresses()[0];
+    let owner_badge = commit.new_resource_addresses()[0];
+    let nft = commit.new_resource_addresses()[1];
+
+    // Test the `mint` method, paying more than the price.
+    let xrd_balance = ledger.get_component_balance(account, XRD);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, XRD, dec!(80))
+        .take_from_worktop(XRD, dec!(80), "payment")
+        .call_method_with_name_lookup(component, "mint", |lookup| (lookup.bucket("payment"),))
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    assert_eq!(ledger.get_component_balance(account, nft), dec!(1));
+    assert_eq!(
+        ledger.get_component_balance(account, XRD),
+        xrd_balance - dec!(50)
+    );
+
+    // Test the `withdraw_proceeds` method, presenting the owner badge.
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_amount(account, owner_badge, dec!(1))
+        .call_method(component, "withdraw_proceeds", manifest_args!())
+        .deposit_entire_worktop(account)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(public_key)],
+    );
+    println!("{:?}\n", receipt);
+    receipt.expect_commit_success();
+    assert_eq!(ledger.get_component_balance(account, XRD), xrd_balance);
+}
+
+#[test]
+fn test_nft_collection_with_test_environment() -> Result<(), RuntimeError> {
+    // Arrange
+    let mut env = TestEnvironment::new();
+    let package_address =
+        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+
+    let (mut collection, _owner_badge) =
+        NftCollection::instantiate_nft_collection(dec!(50), 1, package_address, &mut env)?;
+
+    // Act
+    let payment = BucketFactory::create_fungible_bucket(
+        XRD,
+        dec!(80),
+        CreationStrategy::DisableAuthAndMint,
+        &mut env,
+    )?;
+    let (nft, change) = collection.mint(payment, &mut env)?;
+
+    // Assert
+    assert_eq!(
+        nft.non_fungible_local_ids(&mut env)?,
+        indexset!(NonFungibleLocalId::integer(1))
+    );
+    assert_eq!(change.amount(&mut env)?, dec!(30));
+
+    // The collection is sold out after its single NFT.
+    let payment = BucketFactory::create_fungible_bucket(
+        XRD,
+        dec!(50),
+        CreationStrategy::DisableAuthAndMint,
+        &mut env,
+    )?;
+    assert!(collection.mint(payment, &mut env).is_err());
+
+    Ok(())
+}
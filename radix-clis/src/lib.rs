@@ -4,6 +4,8 @@
 pub mod replay;
 /// Radix Engine Simulator CLI.
 pub mod resim;
+/// Radix transaction manifest tool CLI.
+pub mod rtm;
 /// Radix transaction manifest compiler CLI.
 pub mod rtmc;
 /// Radix transaction manifest decompiler CLI.
@@ -0,0 +1,121 @@
+use super::txn_reader::LedgerHashes;
+use super::Error;
+use radix_common::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A record of how far a replay got, written to the database folder every given number of
+/// versions. It is checked against the database when the replay resumes, so that a database which
+/// was modified or restored in the meantime is noticed.
+#[derive(Debug, Clone, Sbor)]
+pub struct Checkpoint {
+    pub state_version: u64,
+    pub state_root_hash: Hash,
+    /// The ledger hashes recorded by the source for this version, if it records them.
+    pub ledger_hashes: Option<LedgerHashes>,
+    /// The last version whose state root was verified against the source, if any.
+    pub last_verified_version: Option<u64>,
+}
+
+impl Checkpoint {
+    const FILE_NAME: &'static str = "replay_checkpoint";
+
+    fn path(database_dir: &Path) -> PathBuf {
+        database_dir.join(Self::FILE_NAME)
+    }
+
+    pub fn load(database_dir: &Path) -> Result<Option<Self>, Error> {
+        let path = Self::path(database_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = fs::read(&path).map_err(Error::IOError)?;
+        scrypto_decode(&bytes)
+            .map(Some)
+            .map_err(|e| Error::InvalidCheckpoint(format!("{:?}", e)))
+    }
+
+    /// Writes the checkpoint to a temporary file first, so that an interrupted write never leaves
+    /// a corrupted checkpoint behind.
+    pub fn save(&self, database_dir: &Path) -> Result<(), Error> {
+        let path = Self::path(database_dir);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, scrypto_encode(self).unwrap()).map_err(Error::IOError)?;
+        fs::rename(&temp_path, &path).map_err(Error::IOError)
+    }
+
+    /// Checks that a database at the given version and state root can resume from this checkpoint.
+    pub fn validate_resume(&self, state_version: u64, state_root_hash: Hash) -> Result<(), Error> {
+        if state_version < self.state_version {
+            return Err(Error::InvalidCheckpoint(format!(
+                "the database is at version {}, before the checkpoint at version {}",
+                state_version, self.state_version
+            )));
+        }
+        if state_version == self.state_version && state_root_hash != self.state_root_hash {
+            return Err(Error::InvalidCheckpoint(format!(
+                "the state root {} at version {} differs from the checkpoint's {}",
+                state_root_hash, state_version, self.state_root_hash
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(state_version: u64, state_root_hash: Hash) -> Checkpoint {
+        Checkpoint {
+            state_version,
+            state_root_hash,
+            ledger_hashes: None,
+            last_verified_version: Some(state_version / 2),
+        }
+    }
+
+    #[test]
+    fn saves_and_loads_a_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Checkpoint::load(dir.path()).unwrap().is_none());
+
+        checkpoint(10, Hash([1; 32])).save(dir.path()).unwrap();
+        checkpoint(20, Hash([2; 32])).save(dir.path()).unwrap();
+
+        let loaded = Checkpoint::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.state_version, 20);
+        assert_eq!(loaded.state_root_hash, Hash([2; 32]));
+        assert_eq!(loaded.ledger_hashes, None);
+        assert_eq!(loaded.last_verified_version, Some(10));
+        assert!(!Checkpoint::path(dir.path()).with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn rejects_a_corrupted_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(Checkpoint::path(dir.path()), [1, 2, 3]).unwrap();
+
+        assert!(matches!(
+            Checkpoint::load(dir.path()),
+            Err(Error::InvalidCheckpoint(_))
+        ));
+    }
+
+    #[test]
+    fn validates_the_database_it_resumes() {
+        let checkpoint = checkpoint(10, Hash([1; 32]));
+
+        assert!(checkpoint.validate_resume(10, Hash([1; 32])).is_ok());
+        // A database past the checkpoint was committed to after it got written.
+        assert!(checkpoint.validate_resume(15, Hash([2; 32])).is_ok());
+        assert!(matches!(
+            checkpoint.validate_resume(9, Hash([1; 32])),
+            Err(Error::InvalidCheckpoint(_))
+        ));
+        assert!(matches!(
+            checkpoint.validate_resume(10, Hash([2; 32])),
+            Err(Error::InvalidCheckpoint(_))
+        ));
+    }
+}
@@ -0,0 +1,659 @@
+use super::ledger_transaction_execution::*;
+use super::txn_reader::TxnReader;
+use super::Error;
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_engine::transaction::{
+    CostingParameters, ExecutionConfig, LimitParameters, TransactionOutcome, TransactionResult,
+};
+use radix_engine::vm::*;
+use radix_substate_store_impls::rocks_db_with_merkle_tree::RocksDBWithMerkleTreeSubstateStore;
+use radix_substate_store_impls::substate_database_overlay::SubstateDatabaseOverlay;
+use radix_substate_store_interface::interface::*;
+use serde::{de::Error as _, Deserialize, Deserializer};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+/// Run a range of transactions in archive under both the baseline and an alternative system
+/// configuration, and compare their receipts
+#[derive(Parser, Debug)]
+pub struct TxnCompare {
+    /// The transaction file, in `.tar.gz` format, with entries sorted
+    pub source: PathBuf,
+    /// Path to a folder for storing state
+    pub database_dir: PathBuf,
+
+    /// The first version to compare
+    #[clap(long)]
+    pub from: u64,
+    /// The last version to compare
+    #[clap(long)]
+    pub to: u64,
+    /// Path to the alternative configuration, in TOML format
+    #[clap(long)]
+    pub config: PathBuf,
+
+    /// The network to use, [mainnet | stokenet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+}
+
+/// An alternative configuration of the system, with the same layout as [`SystemOverrides`].
+/// Anything omitted is left as for the baseline execution.
+///
+/// [`SystemOverrides`]: radix_engine::transaction::SystemOverrides
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlternativeConfig {
+    pub disable_costing: Option<bool>,
+    pub disable_limits: Option<bool>,
+    pub disable_auth: Option<bool>,
+    pub costing_parameters: Option<CostingParametersConfig>,
+    pub limit_parameters: Option<LimitParametersConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CostingParametersConfig {
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub execution_cost_unit_price: Option<Decimal>,
+    pub execution_cost_unit_limit: Option<u32>,
+    pub execution_cost_unit_loan: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub finalization_cost_unit_price: Option<Decimal>,
+    pub finalization_cost_unit_limit: Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub usd_price: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub state_storage_price: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_decimal")]
+    pub archive_storage_price: Option<Decimal>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LimitParametersConfig {
+    pub max_call_depth: Option<usize>,
+    pub max_heap_substate_total_bytes: Option<usize>,
+    pub max_track_substate_total_bytes: Option<usize>,
+    pub max_substate_key_size: Option<usize>,
+    pub max_substate_value_size: Option<usize>,
+    pub max_invoke_input_size: Option<usize>,
+    pub max_event_size: Option<usize>,
+    pub max_log_size: Option<usize>,
+    pub max_panic_message_size: Option<usize>,
+    pub max_number_of_logs: Option<usize>,
+    pub max_number_of_events: Option<usize>,
+}
+
+/// Decimals are written as strings, to keep their full precision.
+fn deserialize_decimal<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Decimal>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| {
+            Decimal::from_str(&value)
+                .map_err(|e| D::Error::custom(format!("invalid decimal {}: {:?}", value, e)))
+        })
+        .transpose()
+}
+
+impl AlternativeConfig {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(Error::IOError)?;
+        toml::from_str(&content).map_err(|e| Error::InvalidConfig(e.to_string()))
+    }
+
+    /// Applies the alternative configuration on top of the one used for the baseline execution.
+    /// The costing parameters are based on the ones in effect for the baseline execution, as
+    /// given by its receipt.
+    pub fn apply(
+        &self,
+        config: ExecutionConfig,
+        baseline_costing_parameters: Option<CostingParameters>,
+    ) -> ExecutionConfig {
+        config.update_system_overrides(|mut overrides| {
+            if let Some(disable_costing) = self.disable_costing {
+                overrides.disable_costing = disable_costing;
+            }
+            if let Some(disable_limits) = self.disable_limits {
+                overrides.disable_limits = disable_limits;
+            }
+            if let Some(disable_auth) = self.disable_auth {
+                overrides.disable_auth = disable_auth;
+            }
+            if let Some(costing) = &self.costing_parameters {
+                let base = overrides
+                    .costing_parameters
+                    .or(baseline_costing_parameters)
+                    .unwrap_or_else(CostingParameters::latest);
+                overrides.costing_parameters = Some(costing.apply(base));
+            }
+            if let Some(limits) = &self.limit_parameters {
+                let base = overrides
+                    .limit_parameters
+                    .unwrap_or_else(LimitParameters::babylon_genesis);
+                overrides.limit_parameters = Some(limits.apply(base));
+            }
+            overrides
+        })
+    }
+}
+
+impl CostingParametersConfig {
+    fn apply(&self, base: CostingParameters) -> CostingParameters {
+        CostingParameters {
+            execution_cost_unit_price: self
+                .execution_cost_unit_price
+                .unwrap_or(base.execution_cost_unit_price),
+            execution_cost_unit_limit: self
+                .execution_cost_unit_limit
+                .unwrap_or(base.execution_cost_unit_limit),
+            execution_cost_unit_loan: self
+                .execution_cost_unit_loan
+                .unwrap_or(base.execution_cost_unit_loan),
+            finalization_cost_unit_price: self
+                .finalization_cost_unit_price
+                .unwrap_or(base.finalization_cost_unit_price),
+            finalization_cost_unit_limit: self
+                .finalization_cost_unit_limit
+                .unwrap_or(base.finalization_cost_unit_limit),
+            usd_price: self.usd_price.unwrap_or(base.usd_price),
+            state_storage_price: self.state_storage_price.unwrap_or(base.state_storage_price),
+            archive_storage_price: self
+                .archive_storage_price
+                .unwrap_or(base.archive_storage_price),
+        }
+    }
+}
+
+impl LimitParametersConfig {
+    fn apply(&self, base: LimitParameters) -> LimitParameters {
+        LimitParameters {
+            max_call_depth: self.max_call_depth.unwrap_or(base.max_call_depth),
+            max_heap_substate_total_bytes: self
+                .max_heap_substate_total_bytes
+                .unwrap_or(base.max_heap_substate_total_bytes),
+            max_track_substate_total_bytes: self
+                .max_track_substate_total_bytes
+                .unwrap_or(base.max_track_substate_total_bytes),
+            max_substate_key_size: self
+                .max_substate_key_size
+                .unwrap_or(base.max_substate_key_size),
+            max_substate_value_size: self
+                .max_substate_value_size
+                .unwrap_or(base.max_substate_value_size),
+            max_invoke_input_size: self
+                .max_invoke_input_size
+                .unwrap_or(base.max_invoke_input_size),
+            max_event_size: self.max_event_size.unwrap_or(base.max_event_size),
+            max_log_size: self.max_log_size.unwrap_or(base.max_log_size),
+            max_panic_message_size: self
+                .max_panic_message_size
+                .unwrap_or(base.max_panic_message_size),
+            max_number_of_logs: self.max_number_of_logs.unwrap_or(base.max_number_of_logs),
+            max_number_of_events: self
+                .max_number_of_events
+                .unwrap_or(base.max_number_of_events),
+        }
+    }
+}
+
+/// The totals over all the compared transactions.
+#[derive(Debug, Default)]
+struct ComparisonSummary {
+    transactions: usize,
+    differing_transactions: usize,
+    differing_outcomes: usize,
+    baseline_execution_cost_units: u64,
+    alternative_execution_cost_units: u64,
+    baseline_finalization_cost_units: u64,
+    alternative_finalization_cost_units: u64,
+    baseline_total_cost: Decimal,
+    alternative_total_cost: Decimal,
+}
+
+impl TxnCompare {
+    pub fn run(&self) -> Result<(), String> {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::mainnet(),
+        };
+        let alternative_config = AlternativeConfig::load(&self.config)?;
+
+        let mut database = RocksDBWithMerkleTreeSubstateStore::standard(self.database_dir.clone());
+        let cur_version = database.get_current_version();
+        if cur_version >= self.from || self.from > self.to {
+            return Err(Error::InvalidVersionRange(format!(
+                "cannot compare versions {} to {} from a database at version {}",
+                self.from, self.to, cur_version
+            ))
+            .into());
+        }
+        let from_version = self.from;
+        let to_version = self.to;
+
+        let start = std::time::Instant::now();
+        let (txn_read_thread_handle, rx) =
+            TxnReader::open(&self.source)?.spawn(cur_version, Some(to_version));
+
+        // txn executor
+        let txn_write_thread_handle = thread::spawn(move || {
+            let vm_modules = VmModules::default();
+            let mut iter = rx.iter();
+
+            // The transactions before the range are committed to the database as usual.
+            while database.get_current_version() + 1 < from_version {
+                let Some(tx_payload) = iter.next() else {
+                    break;
+                };
+                execute_and_commit_ledger_transaction(
+                    &mut database,
+                    &vm_modules,
+                    &network,
+                    &tx_payload,
+                    false,
+                    start,
+                    |_, _| Ok(()),
+                )?;
+            }
+
+            // The transactions within the range are committed to an overlay, so that the database
+            // stays right before the range, and the comparison can be repeated with other
+            // configurations.
+            let mut version = database.get_current_version();
+            let mut overlay = SubstateDatabaseOverlay::new_unmergeable(&database);
+            let mut summary = ComparisonSummary::default();
+            for tx_payload in iter {
+                version += 1;
+                let (kinded_hash, baseline) = execute_ledger_transaction_with_config(
+                    &overlay,
+                    &vm_modules,
+                    &network,
+                    &tx_payload,
+                    |config| config,
+                );
+                let baseline_costing_parameters = match &baseline {
+                    LedgerTransactionReceipt::Standard(receipt) => Some(receipt.costing_parameters),
+                    _ => None,
+                };
+                let (_, alternative) = execute_ledger_transaction_with_config(
+                    &overlay,
+                    &vm_modules,
+                    &network,
+                    &tx_payload,
+                    |config| alternative_config.apply(config, baseline_costing_parameters),
+                );
+
+                let differences =
+                    compare_receipts(baseline, alternative, &mut summary, |updates| {
+                        overlay.commit(updates)
+                    });
+                if !differences.is_empty() {
+                    println!(
+                        "Version {} ({}):",
+                        version,
+                        describe_kinded_hash(&kinded_hash)
+                    );
+                    for difference in differences {
+                        println!("  {}", difference);
+                    }
+                }
+            }
+
+            print_summary(&summary);
+            Ok::<(), Error>(())
+        });
+
+        txn_read_thread_handle.join().unwrap()?;
+        txn_write_thread_handle.join().unwrap()?;
+
+        Ok(())
+    }
+}
+
+/// Compares the outcome, fees, events and state updates of both receipts, and commits the
+/// baseline's state updates with the given function.
+fn compare_receipts(
+    baseline: LedgerTransactionReceipt,
+    alternative: LedgerTransactionReceipt,
+    summary: &mut ComparisonSummary,
+    commit_baseline: impl FnOnce(&DatabaseUpdates),
+) -> Vec<String> {
+    let mut differences = Vec::new();
+    summary.transactions += 1;
+
+    let baseline_outcome = describe_outcome(&baseline);
+    let alternative_outcome = describe_outcome(&alternative);
+    if baseline_outcome != alternative_outcome {
+        summary.differing_outcomes += 1;
+        differences.push(format!(
+            "Outcome: {} -> {}",
+            baseline_outcome, alternative_outcome
+        ));
+    }
+
+    if let (Some(baseline_fees), Some(alternative_fees)) =
+        (baseline.fee_summary(), alternative.fee_summary())
+    {
+        summary.baseline_execution_cost_units +=
+            baseline_fees.total_execution_cost_units_consumed as u64;
+        summary.alternative_execution_cost_units +=
+            alternative_fees.total_execution_cost_units_consumed as u64;
+        summary.baseline_finalization_cost_units +=
+            baseline_fees.total_finalization_cost_units_consumed as u64;
+        summary.alternative_finalization_cost_units +=
+            alternative_fees.total_finalization_cost_units_consumed as u64;
+        summary.baseline_total_cost += baseline_fees.total_cost();
+        summary.alternative_total_cost += alternative_fees.total_cost();
+
+        if baseline_fees.total_execution_cost_units_consumed
+            != alternative_fees.total_execution_cost_units_consumed
+        {
+            differences.push(format!(
+                "Execution cost units: {} -> {}",
+                baseline_fees.total_execution_cost_units_consumed,
+                alternative_fees.total_execution_cost_units_consumed
+            ));
+        }
+        if baseline_fees.total_finalization_cost_units_consumed
+            != alternative_fees.total_finalization_cost_units_consumed
+        {
+            differences.push(format!(
+                "Finalization cost units: {} -> {}",
+                baseline_fees.total_finalization_cost_units_consumed,
+                alternative_fees.total_finalization_cost_units_consumed
+            ));
+        }
+        if baseline_fees.total_cost() != alternative_fees.total_cost() {
+            differences.push(format!(
+                "Total cost: {} XRD -> {} XRD",
+                baseline_fees.total_cost(),
+                alternative_fees.total_cost()
+            ));
+        }
+    }
+
+    let baseline_events = baseline
+        .commit_result()
+        .map(|commit| commit.application_events.as_slice())
+        .unwrap_or_default();
+    let alternative_events = alternative
+        .commit_result()
+        .map(|commit| commit.application_events.as_slice())
+        .unwrap_or_default();
+    if baseline_events != alternative_events {
+        let differing_events = baseline_events
+            .iter()
+            .zip(alternative_events)
+            .filter(|(baseline_event, alternative_event)| baseline_event != alternative_event)
+            .count()
+            + baseline_events.len().abs_diff(alternative_events.len());
+        differences.push(format!(
+            "Events: {} -> {}, of which {} differ",
+            baseline_events.len(),
+            alternative_events.len(),
+            differing_events
+        ));
+    }
+
+    let baseline_updates = into_database_updates(baseline);
+    let alternative_updates = into_database_updates(alternative);
+    if baseline_updates != alternative_updates {
+        differences.push(format!(
+            "State updates: {} partition(s) differ",
+            count_differing_partitions(&baseline_updates, &alternative_updates)
+        ));
+    }
+    commit_baseline(&baseline_updates);
+
+    if !differences.is_empty() {
+        summary.differing_transactions += 1;
+    }
+    differences
+}
+
+fn describe_kinded_hash(kinded_hash: &LedgerTransactionKindedHash) -> String {
+    match kinded_hash {
+        LedgerTransactionKindedHash::Genesis(hash) => format!("genesis {}", hash.as_hash()),
+        LedgerTransactionKindedHash::User(hash) => format!("user {}", hash.as_hash()),
+        LedgerTransactionKindedHash::Validator(hash) => format!("validator {}", hash),
+        LedgerTransactionKindedHash::ProtocolUpdate(hash) => format!("protocol update {}", hash),
+    }
+}
+
+fn describe_outcome(receipt: &LedgerTransactionReceipt) -> String {
+    match receipt {
+        LedgerTransactionReceipt::Standard(receipt) => match &receipt.result {
+            TransactionResult::Commit(commit) => match &commit.outcome {
+                TransactionOutcome::Success(_) => "success".to_string(),
+                TransactionOutcome::Failure(error) => format!("failure ({:?})", error),
+            },
+            TransactionResult::Reject(reject) => format!("rejection ({:?})", reject.reason),
+            TransactionResult::Abort(abort) => format!("abortion ({:?})", abort.reason),
+        },
+        LedgerTransactionReceipt::Flash(_) | LedgerTransactionReceipt::ProtocolUpdateFlash(_) => {
+            "flash".to_string()
+        }
+    }
+}
+
+/// Returns the database updates of a receipt, which are empty unless it was committed.
+fn into_database_updates(receipt: LedgerTransactionReceipt) -> DatabaseUpdates {
+    match receipt {
+        LedgerTransactionReceipt::Standard(receipt)
+            if !matches!(receipt.result, TransactionResult::Commit(_)) =>
+        {
+            DatabaseUpdates::default()
+        }
+        receipt => receipt.into_state_updates().create_database_updates(),
+    }
+}
+
+fn count_differing_partitions(
+    baseline_updates: &DatabaseUpdates,
+    alternative_updates: &DatabaseUpdates,
+) -> usize {
+    fn partition_updates(
+        updates: &DatabaseUpdates,
+    ) -> IndexMap<(DbNodeKey, DbPartitionNum), &PartitionDatabaseUpdates> {
+        updates
+            .node_updates
+            .iter()
+            .flat_map(|(node_key, node_updates)| {
+                node_updates
+                    .partition_updates
+                    .iter()
+                    .map(move |(partition_num, updates)| {
+                        ((node_key.clone(), *partition_num), updates)
+                    })
+            })
+            .collect()
+    }
+    let baseline_partitions = partition_updates(baseline_updates);
+    let alternative_partitions = partition_updates(alternative_updates);
+    baseline_partitions
+        .keys()
+        .chain(alternative_partitions.keys())
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .filter(|key| baseline_partitions.get(*key) != alternative_partitions.get(*key))
+        .count()
+}
+
+fn print_summary(summary: &ComparisonSummary) {
+    println!("Transactions compared: {}", summary.transactions);
+    println!(
+        "Transactions with differences: {}",
+        summary.differing_transactions
+    );
+    println!(
+        "Transactions with different outcomes: {}",
+        summary.differing_outcomes
+    );
+    println!(
+        "Execution cost units: {} -> {}",
+        summary.baseline_execution_cost_units, summary.alternative_execution_cost_units
+    );
+    println!(
+        "Finalization cost units: {} -> {}",
+        summary.baseline_finalization_cost_units, summary.alternative_finalization_cost_units
+    );
+    println!(
+        "Total cost: {} XRD -> {} XRD",
+        summary.baseline_total_cost, summary.alternative_total_cost
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine::errors::{ApplicationError, RuntimeError};
+    use radix_engine::transaction::{CommitResult, TransactionReceipt};
+    use radix_engine_interface::prelude::dec;
+
+    fn load_config(content: &str) -> Result<AlternativeConfig, Error> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, content).unwrap();
+        AlternativeConfig::load(&path)
+    }
+
+    fn receipt(
+        outcome: TransactionOutcome,
+        state_updates: StateUpdates,
+    ) -> LedgerTransactionReceipt {
+        let mut commit = CommitResult::empty_with_outcome(outcome);
+        commit.state_updates = state_updates;
+        LedgerTransactionReceipt::Standard(Box::new(TransactionReceipt::empty_with_commit(commit)))
+    }
+
+    fn success() -> TransactionOutcome {
+        TransactionOutcome::Success(vec![])
+    }
+
+    fn failure() -> TransactionOutcome {
+        TransactionOutcome::Failure(RuntimeError::ApplicationError(
+            ApplicationError::PanicMessage("out of gas".to_owned()),
+        ))
+    }
+
+    fn updates(value: u8) -> StateUpdates {
+        StateUpdates::empty().set_substate(XRD, PartitionNumber(64), SubstateKey::Field(0), value)
+    }
+
+    #[test]
+    fn loads_a_config_with_decimal_strings() {
+        let config = load_config(
+            r#"
+            disable_auth = true
+
+            [costing_parameters]
+            usd_price = "16.666"
+            execution_cost_unit_limit = 200000000
+
+            [limit_parameters]
+            max_call_depth = 16
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.disable_auth, Some(true));
+        assert_eq!(config.disable_costing, None);
+        let costing = config.costing_parameters.unwrap();
+        assert_eq!(costing.usd_price, Some(dec!("16.666")));
+        assert_eq!(costing.execution_cost_unit_limit, Some(200000000));
+        assert_eq!(costing.execution_cost_unit_price, None);
+        assert_eq!(config.limit_parameters.unwrap().max_call_depth, Some(16));
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_invalid_decimals() {
+        assert!(matches!(
+            load_config("disable_royalties = true"),
+            Err(Error::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            load_config("[costing_parameters]\nusd_price = \"cheap\""),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn applies_only_the_configured_overrides() {
+        let config = AlternativeConfig {
+            disable_limits: Some(true),
+            costing_parameters: Some(CostingParametersConfig {
+                usd_price: Some(dec!("10")),
+                ..Default::default()
+            }),
+            limit_parameters: Some(LimitParametersConfig {
+                max_call_depth: Some(16),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut baseline_costing_parameters = CostingParameters::babylon_genesis();
+        baseline_costing_parameters.execution_cost_unit_limit = 1000;
+
+        let overrides = config
+            .apply(
+                ExecutionConfig::for_notarized_transaction(NetworkDefinition::mainnet()),
+                Some(baseline_costing_parameters),
+            )
+            .system_overrides
+            .unwrap();
+
+        assert!(overrides.disable_limits);
+        assert!(!overrides.disable_costing);
+        assert!(!overrides.disable_auth);
+        let costing_parameters = overrides.costing_parameters.unwrap();
+        assert_eq!(costing_parameters.usd_price, dec!("10"));
+        assert_eq!(costing_parameters.execution_cost_unit_limit, 1000);
+        let limit_parameters = overrides.limit_parameters.unwrap();
+        assert_eq!(limit_parameters.max_call_depth, 16);
+        assert_eq!(
+            limit_parameters.max_event_size,
+            LimitParameters::babylon_genesis().max_event_size
+        );
+    }
+
+    #[test]
+    fn equal_receipts_have_no_differences() {
+        let mut summary = ComparisonSummary::default();
+        let mut committed = None;
+
+        let differences = compare_receipts(
+            receipt(success(), updates(1)),
+            receipt(success(), updates(1)),
+            &mut summary,
+            |updates| committed = Some(updates.clone()),
+        );
+
+        assert!(differences.is_empty());
+        assert_eq!(summary.transactions, 1);
+        assert_eq!(summary.differing_transactions, 0);
+        assert_eq!(committed, Some(updates(1).create_database_updates()));
+    }
+
+    #[test]
+    fn reports_different_outcomes_and_commits_the_baseline() {
+        let mut summary = ComparisonSummary::default();
+        let mut committed = None;
+
+        let differences = compare_receipts(
+            receipt(success(), updates(1)),
+            receipt(failure(), updates(2)),
+            &mut summary,
+            |updates| committed = Some(updates.clone()),
+        );
+
+        assert_eq!(differences.len(), 2);
+        assert!(differences[0].starts_with("Outcome: success -> failure"));
+        assert_eq!(differences[1], "State updates: 1 partition(s) differ");
+        assert_eq!(summary.differing_transactions, 1);
+        assert_eq!(summary.differing_outcomes, 1);
+        assert_eq!(committed, Some(updates(1).create_database_updates()));
+    }
+}
@@ -0,0 +1,334 @@
+use super::Error;
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_substate_store_impls::rocks_db_with_merkle_tree::RocksDBWithMerkleTreeSubstateStore;
+use radix_substate_store_impls::state_tree::diff_substate_hashes_at_versions;
+use radix_substate_store_interface::db_key_mapper::{DatabaseKeyMapper, SpreadPrefixKeyMapper};
+use radix_substate_store_interface::interface::*;
+use radix_substate_store_queries::typed_substate_layout::*;
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+/// Compare the substates of two databases, e.g. after executing transactions with different versions
+#[derive(Parser, Debug)]
+pub struct TxnDiff {
+    /// Path to the folder of the first database
+    pub database_dir_a: PathBuf,
+    /// Path to the folder of the second database
+    pub database_dir_b: PathBuf,
+
+    /// The network to use for displaying addresses, [mainnet | stokenet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+
+    /// Only compare the entities, partitions and substates whose state tree hashes differ
+    #[clap(long)]
+    pub use_state_tree: bool,
+
+    /// The state version to compare the state trees of both databases at, rather than their
+    /// current versions. The values of the substates are only printed for a database at that
+    /// version, as older values aren't kept.
+    #[clap(long, requires = "use_state_tree")]
+    pub state_version: Option<u64>,
+
+    /// The max number of differences to print
+    #[clap(long)]
+    pub limit: Option<usize>,
+}
+
+/// A substate which is not the same in both databases.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SubstateDifference {
+    OnlyInA(ComparedValue),
+    OnlyInB(ComparedValue),
+    Changed(ComparedValue, ComparedValue),
+}
+
+/// The value of a substate, or only its hash when the value isn't kept at the compared version.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ComparedValue {
+    Value(DbSubstateValue),
+    Hash(Hash),
+}
+
+impl TxnDiff {
+    pub fn run(&self) -> Result<(), String> {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::mainnet(),
+        };
+        let address_encoder = AddressBech32Encoder::new(&network);
+
+        let database_a = RocksDBWithMerkleTreeSubstateStore::read_only(self.database_dir_a.clone())
+            .map_err(Error::DatabaseError)?;
+        let database_b = RocksDBWithMerkleTreeSubstateStore::read_only(self.database_dir_b.clone())
+            .map_err(Error::DatabaseError)?;
+        for (name, database) in [("A", &database_a), ("B", &database_b)] {
+            println!(
+                "Database {}: state version {}, state root {}",
+                name,
+                database.get_current_version(),
+                database.get_current_root_hash()
+            );
+        }
+
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let mut count = 0;
+        let mut report = |partition_key: &DbPartitionKey,
+                          sort_key: &DbSortKey,
+                          difference: &SubstateDifference| {
+            if count < limit {
+                print_difference(partition_key, sort_key, difference, &address_encoder);
+            }
+            count += 1;
+        };
+
+        if self.use_state_tree {
+            let version_a = self
+                .state_version
+                .unwrap_or_else(|| database_a.get_current_version());
+            let version_b = self
+                .state_version
+                .unwrap_or_else(|| database_b.get_current_version());
+            let differences =
+                diff_substate_hashes_at_versions(&database_a, version_a, &database_b, version_b);
+            // The values are only kept for the current version of a database.
+            let compared_value = |database: &RocksDBWithMerkleTreeSubstateStore,
+                                  version: u64,
+                                  partition_key: &DbPartitionKey,
+                                  sort_key: &DbSortKey,
+                                  hash: Hash| {
+                if version == database.get_current_version() {
+                    if let Some(value) =
+                        database.get_raw_substate_by_db_key(partition_key, sort_key)
+                    {
+                        return ComparedValue::Value(value);
+                    }
+                }
+                ComparedValue::Hash(hash)
+            };
+            for (partition_key, sort_key, hash_a, hash_b) in differences {
+                let value_a = hash_a.map(|hash| {
+                    compared_value(&database_a, version_a, &partition_key, &sort_key, hash)
+                });
+                let value_b = hash_b.map(|hash| {
+                    compared_value(&database_b, version_b, &partition_key, &sort_key, hash)
+                });
+                let difference = match (value_a, value_b) {
+                    (Some(value_a), Some(value_b)) => SubstateDifference::Changed(value_a, value_b),
+                    (Some(value_a), None) => SubstateDifference::OnlyInA(value_a),
+                    (None, Some(value_b)) => SubstateDifference::OnlyInB(value_b),
+                    (None, None) => continue,
+                };
+                report(&partition_key, &sort_key, &difference);
+            }
+        } else {
+            // Both databases list their partitions in the order of their keys, so they are merged
+            // as they are read.
+            let partition_keys = merge_sorted(
+                database_a.list_partition_keys().map(|key| (key, ())),
+                database_b.list_partition_keys().map(|key| (key, ())),
+            );
+            for (partition_key, _, _) in partition_keys {
+                let entries_a = database_a.list_raw_values_from_db_key(&partition_key, None);
+                let entries_b = database_b.list_raw_values_from_db_key(&partition_key, None);
+                for (sort_key, difference) in diff_entries(entries_a, entries_b) {
+                    report(&partition_key, &sort_key, &difference);
+                }
+            }
+        }
+
+        if count > limit {
+            println!("... and {} more", count - limit);
+        }
+        println!("{} substate(s) differ", count);
+        Ok(())
+    }
+}
+
+/// Merges two iterators sorted by key, pairing the items of equal keys.
+fn merge_sorted<'a, K: Ord + 'a, A: 'a, B: 'a>(
+    items_a: impl Iterator<Item = (K, A)> + 'a,
+    items_b: impl Iterator<Item = (K, B)> + 'a,
+) -> impl Iterator<Item = (K, Option<A>, Option<B>)> + 'a {
+    let mut items_a = items_a.peekable();
+    let mut items_b = items_b.peekable();
+    std::iter::from_fn(move || {
+        let ordering = match (items_a.peek(), items_b.peek()) {
+            (None, None) => return None,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some((key_a, _)), Some((key_b, _))) => key_a.cmp(key_b),
+        };
+        Some(match ordering {
+            Ordering::Less => {
+                let (key, item_a) = items_a.next().unwrap();
+                (key, Some(item_a), None)
+            }
+            Ordering::Greater => {
+                let (key, item_b) = items_b.next().unwrap();
+                (key, None, Some(item_b))
+            }
+            Ordering::Equal => {
+                let (key, item_a) = items_a.next().unwrap();
+                let (_, item_b) = items_b.next().unwrap();
+                (key, Some(item_a), Some(item_b))
+            }
+        })
+    })
+}
+
+/// Merges the sorted entries of the same partition from both databases, and returns the ones which
+/// differ.
+fn diff_entries<'a>(
+    entries_a: impl Iterator<Item = PartitionEntry> + 'a,
+    entries_b: impl Iterator<Item = PartitionEntry> + 'a,
+) -> impl Iterator<Item = (DbSortKey, SubstateDifference)> + 'a {
+    merge_sorted(entries_a, entries_b).filter_map(|(key, value_a, value_b)| {
+        let difference = match (value_a, value_b) {
+            (Some(value_a), Some(value_b)) if value_a == value_b => return None,
+            (Some(value_a), Some(value_b)) => SubstateDifference::Changed(
+                ComparedValue::Value(value_a),
+                ComparedValue::Value(value_b),
+            ),
+            (Some(value_a), None) => SubstateDifference::OnlyInA(ComparedValue::Value(value_a)),
+            (None, Some(value_b)) => SubstateDifference::OnlyInB(ComparedValue::Value(value_b)),
+            (None, None) => return None,
+        };
+        Some((key, difference))
+    })
+}
+
+fn print_difference(
+    partition_key: &DbPartitionKey,
+    sort_key: &DbSortKey,
+    difference: &SubstateDifference,
+    address_encoder: &AddressBech32Encoder,
+) {
+    let (node_id, partition_num) = SpreadPrefixKeyMapper::from_db_partition_key(partition_key);
+    let typed_key = to_substate_key(sort_key).and_then(|substate_key| {
+        let entity_type = node_id.entity_type()?;
+        to_typed_substate_key(entity_type, partition_num, &substate_key).ok()
+    });
+    let address = address_encoder
+        .encode(node_id.as_bytes())
+        .unwrap_or_else(|_| hex::encode(node_id.as_bytes()));
+    let key = match &typed_key {
+        Some(typed_key) => format!("{:?}", typed_key),
+        None => hex::encode(&sort_key.0),
+    };
+    let describe = |value: &ComparedValue| match (value, &typed_key) {
+        (ComparedValue::Hash(hash), _) => format!("value with hash {}", hash),
+        (ComparedValue::Value(value), Some(typed_key)) => to_typed_substate_value(typed_key, value)
+            .map(|typed_value| format!("{:?}", typed_value))
+            .unwrap_or_else(|_| hex::encode(value)),
+        (ComparedValue::Value(value), None) => hex::encode(value),
+    };
+
+    let change = match difference {
+        SubstateDifference::OnlyInA(_) => "Only in A",
+        SubstateDifference::OnlyInB(_) => "Only in B",
+        SubstateDifference::Changed(..) => "Changed",
+    };
+    println!(
+        "{}: {}, partition {}, {}",
+        change, address, partition_num.0, key
+    );
+    match difference {
+        SubstateDifference::OnlyInA(value_a) => println!("  A: {}", describe(value_a)),
+        SubstateDifference::OnlyInB(value_b) => println!("  B: {}", describe(value_b)),
+        SubstateDifference::Changed(value_a, value_b) => {
+            println!("  A: {}", describe(value_a));
+            println!("  B: {}", describe(value_b));
+        }
+    }
+}
+
+/// Recovers the substate key from a database sort key, which doesn't record its kind: fields are
+/// a single byte, and the other keys are prefixed with their hash, after a 2-byte sort prefix for
+/// sorted keys.
+fn to_substate_key(sort_key: &DbSortKey) -> Option<SubstateKey> {
+    if sort_key.0.len() == 1 {
+        return Some(SubstateKey::Field(
+            SpreadPrefixKeyMapper::field_from_db_sort_key(sort_key),
+        ));
+    }
+    if sort_key.0.len() >= 20 {
+        let map_key = SpreadPrefixKeyMapper::map_from_db_sort_key(sort_key);
+        if SpreadPrefixKeyMapper::map_to_db_sort_key(&map_key) == *sort_key {
+            return Some(SubstateKey::Map(map_key));
+        }
+    }
+    if sort_key.0.len() >= 22 {
+        let sorted_key = SpreadPrefixKeyMapper::sorted_from_db_sort_key(sort_key);
+        if SpreadPrefixKeyMapper::sorted_to_db_sort_key(&sorted_key) == *sort_key {
+            return Some(SubstateKey::Sorted(sorted_key));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(entries: &[(u8, u8)]) -> Vec<PartitionEntry> {
+        entries
+            .iter()
+            .map(|(key, value)| (DbSortKey(vec![*key]), vec![*value]))
+            .collect()
+    }
+
+    #[test]
+    fn diffs_the_entries_of_a_partition() {
+        let entries_a = entries(&[(1, 1), (2, 2), (4, 4), (5, 5)]);
+        let entries_b = entries(&[(2, 2), (3, 3), (4, 40), (6, 6)]);
+
+        let differences =
+            diff_entries(entries_a.into_iter(), entries_b.into_iter()).collect::<Vec<_>>();
+
+        let value = |value: u8| ComparedValue::Value(vec![value]);
+        assert_eq!(
+            differences,
+            vec![
+                (DbSortKey(vec![1]), SubstateDifference::OnlyInA(value(1))),
+                (DbSortKey(vec![3]), SubstateDifference::OnlyInB(value(3))),
+                (
+                    DbSortKey(vec![4]),
+                    SubstateDifference::Changed(value(4), value(40))
+                ),
+                (DbSortKey(vec![5]), SubstateDifference::OnlyInA(value(5))),
+                (DbSortKey(vec![6]), SubstateDifference::OnlyInB(value(6))),
+            ]
+        );
+    }
+
+    #[test]
+    fn equal_partitions_have_no_differences() {
+        let entries_a = entries(&[(1, 1), (2, 2)]);
+        let entries_b = entries_a.clone();
+
+        assert_eq!(
+            diff_entries(entries_a.into_iter(), entries_b.into_iter()).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn recovers_the_substate_keys_of_all_kinds() {
+        let field = SubstateKey::Field(3);
+        let map = SubstateKey::Map(scrypto_encode("key").unwrap());
+        let sorted = SubstateKey::Sorted(([0, 7], scrypto_encode("key").unwrap()));
+
+        for substate_key in [field, map, sorted] {
+            let sort_key = SpreadPrefixKeyMapper::to_db_sort_key(&substate_key);
+            assert_eq!(to_substate_key(&sort_key), Some(substate_key));
+        }
+    }
+
+    #[test]
+    fn unknown_sort_keys_have_no_substate_key() {
+        assert_eq!(to_substate_key(&DbSortKey(vec![])), None);
+        assert_eq!(to_substate_key(&DbSortKey(vec![1; 10])), None);
+    }
+}
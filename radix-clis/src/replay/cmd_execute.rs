@@ -1,18 +1,22 @@
-use super::ledger_transaction_execution::execute_ledger_transaction;
-use super::txn_reader::TxnReader;
+use super::checkpoint::Checkpoint;
+use super::ledger_transaction_execution::execute_and_commit_ledger_transaction;
+use super::txn_reader::{CommittedTxnReader, LedgerHashes, TxnReader};
 use super::Error;
 use clap::Parser;
-use flate2::read::GzDecoder;
 use flume;
 use radix_common::prelude::*;
 use radix_engine::vm::VmModules;
-use radix_substate_store_impls::rocks_db_with_merkle_tree::RocksDBWithMerkleTreeSubstateStore;
-use radix_substate_store_interface::interface::*;
-use std::fs::File;
+use radix_substate_store_impls::rocks_db_with_merkle_tree::{
+    compute_state_tree_update, RocksDBWithMerkleTreeSubstateStore,
+};
+use radix_transactions::prelude::*;
 use std::path::PathBuf;
 use std::thread;
-use std::time::Duration;
-use tar::Archive;
+
+/// The transactions to execute, along with the hashes the ledger recorded for them when the
+/// state root is verified.
+type LedgerTransactions =
+    Box<dyn Iterator<Item = (RawLedgerTransaction, Option<LedgerHashes>)> + Send>;
 
 /// Run transactions in archive, using RocksDB
 #[derive(Parser, Debug)]
@@ -32,6 +36,14 @@ pub struct TxnExecute {
     /// Trace transaction execution
     #[clap(long)]
     pub trace: bool,
+
+    /// The number of versions between checkpoints written to the database folder
+    #[clap(long, default_value = "10000", value_parser = clap::value_parser!(u64).range(1..))]
+    pub checkpoint_interval: u64,
+    /// Verify the state root against the one recorded by the source every given number of
+    /// versions, and stop at the first mismatch. The source must be a Node state manager database.
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub verify_state_root: Option<u64>,
 }
 
 impl TxnExecute {
@@ -41,9 +53,17 @@ impl TxnExecute {
             None => NetworkDefinition::mainnet(),
         };
 
+        let checkpoint = Checkpoint::load(&self.database_dir)?;
         let cur_version = {
             let database = RocksDBWithMerkleTreeSubstateStore::standard(self.database_dir.clone());
             let cur_version = database.get_current_version();
+            if let Some(checkpoint) = &checkpoint {
+                checkpoint.validate_resume(cur_version, database.get_current_root_hash())?;
+                println!(
+                    "Resuming from version {}, last checkpoint at version {}",
+                    cur_version, checkpoint.state_version
+                );
+            }
             if cur_version >= self.max_version.unwrap_or(u64::MAX) {
                 return Ok(());
             }
@@ -52,67 +72,134 @@ impl TxnExecute {
         let to_version = self.max_version;
 
         let start = std::time::Instant::now();
-        let (tx, rx) = flume::bounded(10);
 
         // txn reader
-        let mut txn_reader = if self.source.is_file() {
-            let tar_gz = File::open(&self.source).map_err(Error::IOError)?;
-            let tar = GzDecoder::new(tar_gz);
-            let archive = Archive::new(tar);
-            TxnReader::TransactionFile(Box::new(archive))
-        } else if self.source.is_dir() {
-            TxnReader::StateManagerDatabaseDir(self.source.clone())
-        } else {
-            return Err(Error::InvalidTransactionSource.into());
-        };
-        let txn_read_thread_handle =
-            thread::spawn(move || txn_reader.read(cur_version, to_version, tx));
+        let (txn_read_thread_handle, txns): (_, LedgerTransactions) =
+            if self.verify_state_root.is_some() {
+                if !self.source.is_dir() {
+                    return Err(Error::InvalidTransactionSource.into());
+                }
+                let (tx, rx) = flume::bounded(10);
+                let mut txn_reader =
+                    CommittedTxnReader::StateManagerDatabaseDir(self.source.clone());
+                let handle = thread::spawn(move || txn_reader.read(cur_version, to_version, tx));
+                (
+                    handle,
+                    Box::new(
+                        rx.into_iter()
+                            .map(|(tx_payload, ledger_hashes)| (tx_payload, Some(ledger_hashes))),
+                    ),
+                )
+            } else {
+                let (handle, rx) = TxnReader::open(&self.source)?.spawn(cur_version, to_version);
+                (
+                    handle,
+                    Box::new(rx.into_iter().map(|tx_payload| (tx_payload, None))),
+                )
+            };
 
         // txn executor
         let mut database = RocksDBWithMerkleTreeSubstateStore::standard(self.database_dir.clone());
+        let database_dir = self.database_dir.clone();
         let trace = self.trace;
+        let checkpoint_interval = self.checkpoint_interval;
+        let verify_state_root = self.verify_state_root;
         let txn_write_thread_handle = thread::spawn(move || {
             let vm_modules = VmModules::default();
-            let iter = rx.iter();
-            for tx_payload in iter {
-                let (_hash, receipt) = execute_ledger_transaction(
-                    &database,
+            let mut last_verified_version = checkpoint
+                .as_ref()
+                .and_then(|checkpoint| checkpoint.last_verified_version);
+            let mut last_ledger_hashes = checkpoint
+                .filter(|checkpoint| checkpoint.state_version == cur_version)
+                .and_then(|checkpoint| checkpoint.ledger_hashes);
+            for (tx_payload, ledger_hashes) in txns {
+                // The state root is verified before committing, so that the database stays at the
+                // last version before the mismatch.
+                let new_version = execute_and_commit_ledger_transaction(
+                    &mut database,
                     &vm_modules,
                     &network,
                     &tx_payload,
                     trace,
-                );
-                let state_updates = receipt.into_state_updates();
-                let database_updates = state_updates.create_database_updates();
-                database.commit(&database_updates);
+                    start,
+                    |database, executed| {
+                        let (Some(interval), Some(ledger_hashes)) =
+                            (verify_state_root, &ledger_hashes)
+                        else {
+                            return Ok(());
+                        };
+                        if !executed.state_version.is_multiple_of(interval) {
+                            return Ok(());
+                        }
+                        let (_, new_state_root_hash) = compute_state_tree_update(
+                            database,
+                            executed.state_version - 1,
+                            &executed.database_updates,
+                        );
+                        if new_state_root_hash != ledger_hashes.state_root.0 {
+                            return Err(Error::StateRootMismatch {
+                                version: executed.state_version,
+                                expected: ledger_hashes.state_root.0,
+                                actual: new_state_root_hash,
+                                last_verified_version,
+                            });
+                        }
+                        last_verified_version = Some(executed.state_version);
+                        Ok(())
+                    },
+                )?;
+                last_ledger_hashes = ledger_hashes;
 
-                let new_state_root_hash = database.get_current_root_hash();
-                let new_version = database.get_current_version();
-
-                if new_version < 1000 || new_version.is_multiple_of(1000) {
-                    print_progress(start.elapsed(), new_version, new_state_root_hash);
+                if new_version.is_multiple_of(checkpoint_interval) {
+                    Checkpoint {
+                        state_version: new_version,
+                        state_root_hash: database.get_current_root_hash(),
+                        ledger_hashes: last_ledger_hashes,
+                        last_verified_version,
+                    }
+                    .save(&database_dir)?;
                 }
             }
 
+            Checkpoint {
+                state_version: database.get_current_version(),
+                state_root_hash: database.get_current_root_hash(),
+                ledger_hashes: last_ledger_hashes,
+                last_verified_version,
+            }
+            .save(&database_dir)?;
+
             let duration = start.elapsed();
             println!("Time elapsed: {:?}", duration);
             println!("State version: {}", database.get_current_version());
             println!("State root hash: {}", database.get_current_root_hash());
+            if let Some(last_verified_version) = last_verified_version {
+                println!("Last verified version: {}", last_verified_version);
+            }
+            Ok::<(), Error>(())
         });
 
+        // The executor is joined first, as it may stop early (e.g. at a state root mismatch).
+        txn_write_thread_handle.join().unwrap()?;
         txn_read_thread_handle.join().unwrap()?;
-        txn_write_thread_handle.join().unwrap();
 
         Ok(())
     }
 }
 
-fn print_progress(duration: Duration, new_version: u64, new_root: Hash) {
-    let seconds = duration.as_secs() % 60;
-    let minutes = (duration.as_secs() / 60) % 60;
-    let hours = (duration.as_secs() / 60) / 60;
-    println!(
-        "New version: {}, {}, {:0>2}:{:0>2}:{:0>2}",
-        new_version, new_root, hours, minutes, seconds
-    );
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_of_zero_versions_are_rejected() {
+        let parse =
+            |args: &[&str]| TxnExecute::try_parse_from([&["execute", "txns", "db"], args].concat());
+
+        assert!(parse(&["--checkpoint-interval", "0"]).is_err());
+        assert!(parse(&["--verify-state-root", "0"]).is_err());
+        let command = parse(&["--checkpoint-interval", "1", "--verify-state-root", "1"]).unwrap();
+        assert_eq!(command.checkpoint_interval, 1);
+        assert_eq!(command.verify_state_root, Some(1));
+    }
 }
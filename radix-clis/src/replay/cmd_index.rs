@@ -0,0 +1,472 @@
+use super::ledger_transaction_execution::*;
+use super::txn_reader::TxnReader;
+use super::Error;
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_engine::transaction::{BalanceChange, TransactionOutcome, TransactionResult};
+use radix_engine::vm::*;
+use radix_substate_store_impls::rocks_db_with_merkle_tree::RocksDBWithMerkleTreeSubstateStore;
+use radix_substate_store_queries::typed_native_events::{
+    to_typed_native_event, typed_native_event_schema,
+};
+use radix_transactions::prelude::*;
+use rusqlite::{params, Connection};
+use sbor::representations::*;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+/// Run transactions in archive, using RocksDB, and index their receipts into a SQLite database
+#[derive(Parser, Debug)]
+pub struct TxnIndex {
+    /// The transaction file, in `.tar.gz` format, with entries sorted
+    pub source: PathBuf,
+    /// Path to a folder for storing state
+    pub database_dir: PathBuf,
+    /// Path to the SQLite database file for storing the index
+    pub index_file: PathBuf,
+
+    /// The network to use, [mainnet | stokenet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+    /// The max version to execute
+    #[clap(short, long)]
+    pub max_version: Option<u64>,
+
+    /// Trace transaction execution
+    #[clap(long)]
+    pub trace: bool,
+}
+
+const INDEX_SCHEMA: &str = "
+PRAGMA journal_mode = WAL;
+PRAGMA synchronous = NORMAL;
+
+CREATE TABLE IF NOT EXISTS transactions (
+    state_version INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    error TEXT,
+    execution_cost_units INTEGER,
+    finalization_cost_units INTEGER,
+    execution_cost_in_xrd TEXT,
+    finalization_cost_in_xrd TEXT,
+    tipping_cost_in_xrd TEXT,
+    storage_cost_in_xrd TEXT,
+    royalty_cost_in_xrd TEXT
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    state_version INTEGER NOT NULL,
+    event_index INTEGER NOT NULL,
+    emitter TEXT NOT NULL,
+    name TEXT NOT NULL,
+    data BLOB NOT NULL,
+    typed_data TEXT,
+    PRIMARY KEY (state_version, event_index)
+);
+CREATE INDEX IF NOT EXISTS events_by_emitter ON events (emitter);
+
+CREATE TABLE IF NOT EXISTS balance_changes (
+    state_version INTEGER NOT NULL,
+    vault TEXT NOT NULL,
+    resource TEXT NOT NULL,
+    fungible_amount TEXT,
+    non_fungible_added TEXT,
+    non_fungible_removed TEXT,
+    PRIMARY KEY (state_version, vault)
+);
+CREATE INDEX IF NOT EXISTS balance_changes_by_vault ON balance_changes (vault);
+CREATE INDEX IF NOT EXISTS balance_changes_by_resource ON balance_changes (resource);
+";
+
+impl TxnIndex {
+    pub fn run(&self) -> Result<(), String> {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::mainnet(),
+        };
+
+        let cur_version = {
+            let database = RocksDBWithMerkleTreeSubstateStore::standard(self.database_dir.clone());
+            let cur_version = database.get_current_version();
+            if cur_version >= self.max_version.unwrap_or(u64::MAX) {
+                return Ok(());
+            }
+            cur_version
+        };
+        let to_version = self.max_version;
+
+        let start = std::time::Instant::now();
+
+        // txn reader
+        let (txn_read_thread_handle, rx) =
+            TxnReader::open(&self.source)?.spawn(cur_version, to_version);
+
+        // txn executor
+        let mut database = RocksDBWithMerkleTreeSubstateStore::standard(self.database_dir.clone());
+        let mut index = TransactionIndex::open(&self.index_file, &network)?;
+        let trace = self.trace;
+        let txn_write_thread_handle = thread::spawn(move || {
+            let vm_modules = VmModules::default();
+            for tx_payload in rx.iter() {
+                // The receipt is indexed before its state updates get committed, so that resuming
+                // from the database's version never leaves a gap in the index.
+                execute_and_commit_ledger_transaction(
+                    &mut database,
+                    &vm_modules,
+                    &network,
+                    &tx_payload,
+                    trace,
+                    start,
+                    |_, executed| {
+                        index.insert(
+                            executed.state_version,
+                            &executed.kinded_hash,
+                            &executed.receipt,
+                        )
+                    },
+                )?;
+            }
+
+            let duration = start.elapsed();
+            println!("Time elapsed: {:?}", duration);
+            println!("State version: {}", database.get_current_version());
+            println!("State root hash: {}", database.get_current_root_hash());
+            Ok::<(), Error>(())
+        });
+
+        txn_read_thread_handle.join().unwrap()?;
+        txn_write_thread_handle.join().unwrap()?;
+
+        Ok(())
+    }
+}
+
+/// The SQLite database holding the outcome, fees, events and vault balance changes of each
+/// executed transaction, keyed by state version. Addresses are stored Bech32m-encoded, and
+/// decimals as strings, to keep their full precision. The events of native blueprints are also
+/// stored as JSON objects keyed by field name, to be queried with `json_extract`.
+pub struct TransactionIndex {
+    connection: Connection,
+    network: NetworkDefinition,
+}
+
+impl TransactionIndex {
+    pub fn open(path: &Path, network: &NetworkDefinition) -> Result<Self, Error> {
+        let connection = Connection::open(path).map_err(Error::IndexError)?;
+        Self::new(connection, network)
+    }
+
+    /// Creates the index tables in the given connection, unless they exist already.
+    pub fn new(connection: Connection, network: &NetworkDefinition) -> Result<Self, Error> {
+        connection
+            .execute_batch(INDEX_SCHEMA)
+            .map_err(Error::IndexError)?;
+        Ok(Self {
+            connection,
+            network: network.clone(),
+        })
+    }
+
+    pub fn insert(
+        &mut self,
+        state_version: u64,
+        kinded_hash: &LedgerTransactionKindedHash,
+        receipt: &LedgerTransactionReceipt,
+    ) -> Result<(), Error> {
+        let address_encoder = AddressBech32Encoder::new(&self.network);
+        let (kind, hash) = match kinded_hash {
+            LedgerTransactionKindedHash::Genesis(hash) => ("genesis", hash.as_hash().to_string()),
+            LedgerTransactionKindedHash::User(hash) => (
+                "user",
+                TransactionHashBech32Encoder::new(&self.network)
+                    .encode(hash)
+                    .unwrap(),
+            ),
+            LedgerTransactionKindedHash::Validator(hash) => ("validator", hash.to_string()),
+            LedgerTransactionKindedHash::ProtocolUpdate(hash) => {
+                ("protocol_update", hash.to_string())
+            }
+        };
+        let (outcome, error) = match receipt {
+            LedgerTransactionReceipt::Flash(_)
+            | LedgerTransactionReceipt::ProtocolUpdateFlash(_) => ("flash", None),
+            LedgerTransactionReceipt::Standard(receipt) => match &receipt.result {
+                TransactionResult::Commit(commit) => match &commit.outcome {
+                    TransactionOutcome::Success(_) => ("success", None),
+                    TransactionOutcome::Failure(error) => ("failure", Some(format!("{:?}", error))),
+                },
+                TransactionResult::Reject(reject) => {
+                    ("rejected", Some(format!("{:?}", reject.reason)))
+                }
+                TransactionResult::Abort(abort) => ("aborted", Some(format!("{:?}", abort.reason))),
+            },
+        };
+        let commit = receipt.commit_result();
+        let fee_summary = receipt.fee_summary();
+
+        let transaction = self.connection.transaction().map_err(Error::IndexError)?;
+        transaction
+            .execute(
+                "INSERT OR REPLACE INTO transactions VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    state_version,
+                    kind,
+                    hash,
+                    outcome,
+                    error,
+                    fee_summary.map(|fees| fees.total_execution_cost_units_consumed),
+                    fee_summary.map(|fees| fees.total_finalization_cost_units_consumed),
+                    fee_summary.map(|fees| fees.total_execution_cost_in_xrd.to_string()),
+                    fee_summary.map(|fees| fees.total_finalization_cost_in_xrd.to_string()),
+                    fee_summary.map(|fees| fees.total_tipping_cost_in_xrd.to_string()),
+                    fee_summary.map(|fees| fees.total_storage_cost_in_xrd.to_string()),
+                    fee_summary.map(|fees| fees.total_royalty_cost_in_xrd.to_string()),
+                ],
+            )
+            .map_err(Error::IndexError)?;
+
+        if let Some(commit) = commit {
+            for (event_index, (event_type_identifier, data)) in
+                commit.application_events.iter().enumerate()
+            {
+                let typed_data = to_typed_native_event(event_type_identifier, data)
+                    .and_then(|_| typed_native_event_schema(event_type_identifier))
+                    .ok()
+                    .and_then(|schema| {
+                        serde_json::to_string(
+                            &ScryptoRawPayload::new_from_valid_slice(data).serializable(
+                                SerializationParameters::WithSchema {
+                                    mode: SerializationMode::Natural,
+                                    custom_context:
+                                        ScryptoValueDisplayContext::with_optional_bech32(Some(
+                                            &address_encoder,
+                                        )),
+                                    schema: schema.schema.v1(),
+                                    type_id: schema.type_id,
+                                    depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+                                },
+                            ),
+                        )
+                        .ok()
+                    });
+                transaction
+                    .execute(
+                        "INSERT OR REPLACE INTO events VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            state_version,
+                            event_index,
+                            event_type_identifier
+                                .0
+                                .display(&address_encoder)
+                                .to_string(),
+                            event_type_identifier.1,
+                            data,
+                            typed_data,
+                        ],
+                    )
+                    .map_err(Error::IndexError)?;
+            }
+
+            for (vault_id, (resource_address, balance_change)) in commit.vault_balance_changes() {
+                let (fungible_amount, non_fungible_added, non_fungible_removed) =
+                    match balance_change {
+                        BalanceChange::Fungible(amount) => (Some(amount.to_string()), None, None),
+                        BalanceChange::NonFungible { added, removed } => (
+                            None,
+                            Some(
+                                added
+                                    .iter()
+                                    .map(|id| id.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(","),
+                            ),
+                            Some(
+                                removed
+                                    .iter()
+                                    .map(|id| id.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(","),
+                            ),
+                        ),
+                    };
+                transaction
+                    .execute(
+                        "INSERT OR REPLACE INTO balance_changes VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            state_version,
+                            vault_id.display(&address_encoder).to_string(),
+                            resource_address.display(&address_encoder).to_string(),
+                            fungible_amount,
+                            non_fungible_added,
+                            non_fungible_removed,
+                        ],
+                    )
+                    .map_err(Error::IndexError)?;
+            }
+        }
+
+        transaction.commit().map_err(Error::IndexError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine::blueprints::resource::MintFungibleResourceEvent;
+    use radix_engine::errors::RejectionReason;
+    use radix_engine::transaction::{CommitResult, RejectResult, TransactionReceipt};
+    use radix_engine_interface::prelude::*;
+
+    fn open_in_memory() -> TransactionIndex {
+        let connection = Connection::open_in_memory().unwrap();
+        TransactionIndex::new(connection, &NetworkDefinition::mainnet()).unwrap()
+    }
+
+    fn transaction_row(
+        index: &TransactionIndex,
+        state_version: u64,
+    ) -> (String, String, Option<String>) {
+        index
+            .connection
+            .query_row(
+                "SELECT kind, outcome, error FROM transactions WHERE state_version = ?1",
+                params![state_version],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap()
+    }
+
+    fn count_rows(index: &TransactionIndex, table: &str) -> u64 {
+        index
+            .connection
+            .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                row.get(0)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn indexes_a_committed_transaction_and_its_events() {
+        let mut index = open_in_memory();
+        let mut commit = CommitResult::empty_with_outcome(TransactionOutcome::Success(vec![]));
+        commit.application_events.push((
+            EventTypeIdentifier(
+                Emitter::Method(XRD.into_node_id(), ModuleId::Main),
+                "MintFungibleResourceEvent".to_owned(),
+            ),
+            scrypto_encode(&()).unwrap(),
+        ));
+        let receipt = LedgerTransactionReceipt::Standard(Box::new(
+            TransactionReceipt::empty_with_commit(commit),
+        ));
+
+        index
+            .insert(
+                5,
+                &LedgerTransactionKindedHash::Validator(Hash([1; 32])),
+                &receipt,
+            )
+            .unwrap();
+
+        assert_eq!(
+            transaction_row(&index, 5),
+            ("validator".to_owned(), "success".to_owned(), None)
+        );
+        let (emitter, name, typed_data): (String, String, Option<String>) = index
+            .connection
+            .query_row(
+                "SELECT emitter, name, typed_data FROM events WHERE state_version = 5 AND event_index = 0",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert!(emitter.contains("resource_rdx1"));
+        assert_eq!(name, "MintFungibleResourceEvent");
+        // The data isn't a valid native event, so only its raw bytes are kept.
+        assert_eq!(typed_data, None);
+    }
+
+    #[test]
+    fn indexes_native_events_as_json() {
+        let mut index = open_in_memory();
+        let mut commit = CommitResult::empty_with_outcome(TransactionOutcome::Success(vec![]));
+        commit.application_events.push((
+            EventTypeIdentifier(
+                Emitter::Method(XRD.into_node_id(), ModuleId::Main),
+                "MintFungibleResourceEvent".to_owned(),
+            ),
+            scrypto_encode(&MintFungibleResourceEvent { amount: dec!(12.5) }).unwrap(),
+        ));
+        let receipt = LedgerTransactionReceipt::Standard(Box::new(
+            TransactionReceipt::empty_with_commit(commit),
+        ));
+
+        index
+            .insert(
+                5,
+                &LedgerTransactionKindedHash::Validator(Hash([1; 32])),
+                &receipt,
+            )
+            .unwrap();
+
+        let amount: String = index
+            .connection
+            .query_row(
+                "SELECT json_extract(typed_data, '$.amount') FROM events WHERE state_version = 5",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(amount, "12.5");
+    }
+
+    #[test]
+    fn indexes_a_rejected_transaction_without_a_commit() {
+        let mut index = open_in_memory();
+        let mut receipt = TransactionReceipt::empty_commit_success();
+        receipt.result = TransactionResult::Reject(RejectResult {
+            reason: RejectionReason::TransactionEpochNotYetValid {
+                valid_from: Epoch::of(2),
+                current_epoch: Epoch::of(1),
+            },
+        });
+        let receipt = LedgerTransactionReceipt::Standard(Box::new(receipt));
+        assert!(receipt.commit_result().is_none());
+
+        index
+            .insert(
+                7,
+                &LedgerTransactionKindedHash::Validator(Hash([2; 32])),
+                &receipt,
+            )
+            .unwrap();
+
+        let (_, outcome, error) = transaction_row(&index, 7);
+        assert_eq!(outcome, "rejected");
+        assert!(error.unwrap().starts_with("TransactionEpochNotYetValid"));
+        assert_eq!(count_rows(&index, "events"), 0);
+        assert_eq!(count_rows(&index, "balance_changes"), 0);
+    }
+
+    #[test]
+    fn reindexing_a_version_replaces_it() {
+        let mut index = open_in_memory();
+        let receipt = LedgerTransactionReceipt::Standard(Box::new(
+            TransactionReceipt::empty_commit_success(),
+        ));
+
+        for _ in 0..2 {
+            index
+                .insert(
+                    3,
+                    &LedgerTransactionKindedHash::Validator(Hash([3; 32])),
+                    &receipt,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(count_rows(&index, "transactions"), 1);
+    }
+}
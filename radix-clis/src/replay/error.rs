@@ -1,4 +1,4 @@
-use radix_common::prelude::ParseNetworkError;
+use radix_common::prelude::{Hash, ParseNetworkError};
 use std::fmt;
 
 #[derive(Debug)]
@@ -6,9 +6,19 @@ pub enum Error {
     ParseNetworkError(ParseNetworkError),
     IOError(std::io::Error),
     DatabaseError(rocksdb::Error),
+    IndexError(rusqlite::Error),
     InvalidTransactionArchive,
     InvalidTransactionSource,
     InvalidBreakpoints(String),
+    InvalidConfig(String),
+    InvalidVersionRange(String),
+    InvalidCheckpoint(String),
+    StateRootMismatch {
+        version: u64,
+        expected: Hash,
+        actual: Hash,
+        last_verified_version: Option<u64>,
+    },
 }
 
 impl fmt::Display for Error {
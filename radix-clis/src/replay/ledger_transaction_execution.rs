@@ -1,13 +1,17 @@
+use super::Error;
 use radix_common::prelude::*;
 use radix_engine::system::bootstrap::*;
 use radix_engine::transaction::{
-    execute_transaction, ExecutionConfig, TransactionFeeSummary, TransactionReceipt,
+    execute_transaction, CommitResult, ExecutionConfig, TransactionFeeSummary, TransactionReceipt,
+    TransactionResult,
 };
 use radix_engine::vm::*;
 use radix_engine_interface::prelude::system_execution;
-use radix_substate_store_interface::interface::SubstateDatabase;
+use radix_substate_store_impls::rocks_db_with_merkle_tree::RocksDBWithMerkleTreeSubstateStore;
+use radix_substate_store_interface::interface::*;
 use radix_transactions::prelude::*;
 use radix_transactions::validation::*;
+use std::time::{Duration, Instant};
 
 pub enum LedgerTransactionReceipt {
     Flash(Box<FlashReceipt>),
@@ -26,6 +30,16 @@ impl LedgerTransactionReceipt {
         }
     }
 
+    pub fn state_updates(&self) -> &StateUpdates {
+        match self {
+            LedgerTransactionReceipt::Flash(receipt) => &receipt.state_updates,
+            LedgerTransactionReceipt::Standard(receipt) => {
+                &receipt.expect_commit_ignore_outcome().state_updates
+            }
+            LedgerTransactionReceipt::ProtocolUpdateFlash(state_updates) => state_updates,
+        }
+    }
+
     pub fn fee_summary(&self) -> Option<&TransactionFeeSummary> {
         match self {
             LedgerTransactionReceipt::Flash(_) => None,
@@ -33,6 +47,17 @@ impl LedgerTransactionReceipt {
             LedgerTransactionReceipt::ProtocolUpdateFlash(_) => None,
         }
     }
+
+    pub fn commit_result(&self) -> Option<&CommitResult> {
+        match self {
+            LedgerTransactionReceipt::Flash(_) => None,
+            LedgerTransactionReceipt::Standard(receipt) => match &receipt.result {
+                TransactionResult::Commit(commit) => Some(commit),
+                TransactionResult::Reject(_) | TransactionResult::Abort(_) => None,
+            },
+            LedgerTransactionReceipt::ProtocolUpdateFlash(_) => None,
+        }
+    }
 }
 
 pub enum LedgerTransactionKindedHash {
@@ -42,12 +67,82 @@ pub enum LedgerTransactionKindedHash {
     ProtocolUpdate(Hash),
 }
 
+/// A ledger transaction executed on top of a database, whose state updates are about to be
+/// committed at the given state version.
+pub struct ExecutedLedgerTransaction {
+    pub state_version: u64,
+    pub kinded_hash: LedgerTransactionKindedHash,
+    pub receipt: LedgerTransactionReceipt,
+    pub database_updates: DatabaseUpdates,
+}
+
+/// Executes a ledger transaction on top of the database and commits its state updates, printing
+/// the progress. The executed transaction is passed to `before_commit` first (e.g. to index its
+/// receipt, or to verify the resulting state root), which stops the replay by returning an error.
+/// Returns the new state version.
+pub fn execute_and_commit_ledger_transaction(
+    database: &mut RocksDBWithMerkleTreeSubstateStore,
+    vm_modules: &impl VmInitialize,
+    network: &NetworkDefinition,
+    raw: &RawLedgerTransaction,
+    trace: bool,
+    start: Instant,
+    before_commit: impl FnOnce(
+        &RocksDBWithMerkleTreeSubstateStore,
+        &ExecutedLedgerTransaction,
+    ) -> Result<(), Error>,
+) -> Result<u64, Error> {
+    let (kinded_hash, receipt) =
+        execute_ledger_transaction(database, vm_modules, network, raw, trace);
+    let database_updates = receipt.state_updates().create_database_updates();
+    let executed = ExecutedLedgerTransaction {
+        state_version: database.get_current_version() + 1,
+        kinded_hash,
+        receipt,
+        database_updates,
+    };
+    before_commit(database, &executed)?;
+    database.commit(&executed.database_updates);
+
+    let new_state_root_hash = database.get_current_root_hash();
+    let new_version = database.get_current_version();
+    if new_version < 1000 || new_version.is_multiple_of(1000) {
+        print_progress(start.elapsed(), new_version, new_state_root_hash);
+    }
+    Ok(new_version)
+}
+
+pub fn print_progress(duration: Duration, new_version: u64, new_root: Hash) {
+    let seconds = duration.as_secs() % 60;
+    let minutes = (duration.as_secs() / 60) % 60;
+    let hours = (duration.as_secs() / 60) / 60;
+    println!(
+        "New version: {}, {}, {:0>2}:{:0>2}:{:0>2}",
+        new_version, new_root, hours, minutes, seconds
+    );
+}
+
 pub fn execute_ledger_transaction<S: SubstateDatabase>(
     database: &S,
     vm_modules: &impl VmInitialize,
     network: &NetworkDefinition,
     raw: &RawLedgerTransaction,
     trace: bool,
+) -> (LedgerTransactionKindedHash, LedgerTransactionReceipt) {
+    execute_ledger_transaction_with_config(database, vm_modules, network, raw, |config| {
+        config.with_kernel_trace(trace).with_cost_breakdown(trace)
+    })
+}
+
+/// Executes a ledger transaction, with the [`ExecutionConfig`] of its kind adjusted by the given
+/// function (e.g. to override the system's costing parameters).
+/// Flash transactions aren't executed, and hence aren't affected.
+pub fn execute_ledger_transaction_with_config<S: SubstateDatabase>(
+    database: &S,
+    vm_modules: &impl VmInitialize,
+    network: &NetworkDefinition,
+    raw: &RawLedgerTransaction,
+    configure: impl FnOnce(ExecutionConfig) -> ExecutionConfig,
 ) -> (LedgerTransactionKindedHash, LedgerTransactionReceipt) {
     let validator = TransactionValidator::new(database, network);
     let validated = raw
@@ -80,9 +175,7 @@ pub fn execute_ledger_transaction<S: SubstateDatabase>(
                     let receipt = execute_transaction(
                         database,
                         vm_modules,
-                        &ExecutionConfig::for_genesis_transaction(network.clone())
-                            .with_kernel_trace(trace)
-                            .with_cost_breakdown(trace),
+                        &configure(ExecutionConfig::for_genesis_transaction(network.clone())),
                         tx.create_executable(btreeset!(system_execution(
                             SystemExecution::Protocol
                         ))),
@@ -95,9 +188,7 @@ pub fn execute_ledger_transaction<S: SubstateDatabase>(
             let receipt = execute_transaction(
                 database,
                 vm_modules,
-                &ExecutionConfig::for_notarized_transaction(network.clone())
-                    .with_kernel_trace(trace)
-                    .with_cost_breakdown(trace),
+                &configure(ExecutionConfig::for_notarized_transaction(network.clone())),
                 tx.create_executable(),
             );
             LedgerTransactionReceipt::Standard(receipt.into())
@@ -106,9 +197,7 @@ pub fn execute_ledger_transaction<S: SubstateDatabase>(
             let receipt = execute_transaction(
                 database,
                 vm_modules,
-                &ExecutionConfig::for_system_transaction(network.clone())
-                    .with_kernel_trace(trace)
-                    .with_cost_breakdown(trace),
+                &configure(ExecutionConfig::for_system_transaction(network.clone())),
                 tx.create_executable(),
             );
             LedgerTransactionReceipt::Standard(receipt.into())
@@ -1,17 +1,24 @@
+pub mod checkpoint;
 pub mod ledger_transaction_execution;
 pub mod txn_reader;
 
 mod cmd_alloc_dump;
+mod cmd_compare;
+mod cmd_diff;
 mod cmd_execute;
 mod cmd_execute_in_memory;
+mod cmd_index;
 mod cmd_measure;
 mod cmd_prepare;
 mod cmd_sync;
 mod error;
 
 pub use cmd_alloc_dump::*;
+pub use cmd_compare::*;
+pub use cmd_diff::*;
 pub use cmd_execute::*;
 pub use cmd_execute_in_memory::*;
+pub use cmd_index::*;
 pub use cmd_measure::*;
 pub use cmd_prepare::*;
 pub use cmd_sync::*;
@@ -35,6 +42,9 @@ pub enum Command {
     Sync(TxnSync),
     Measure(TxnMeasure),
     AllocDump(TxnAllocDump),
+    Index(TxnIndex),
+    Compare(TxnCompare),
+    Diff(TxnDiff),
 }
 
 pub fn run() -> Result<(), String> {
@@ -47,5 +57,8 @@ pub fn run() -> Result<(), String> {
         Command::Sync(cmd) => cmd.sync(),
         Command::Measure(cmd) => cmd.run(),
         Command::AllocDump(cmd) => cmd.run(),
+        Command::Index(cmd) => cmd.run(),
+        Command::Compare(cmd) => cmd.run(),
+        Command::Diff(cmd) => cmd.run(),
     }
 }
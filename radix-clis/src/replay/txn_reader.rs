@@ -1,14 +1,15 @@
 use super::Error;
 use flate2::read::GzDecoder;
 use flume;
-use flume::Sender;
+use flume::{Receiver, Sender};
 use radix_common::prelude::*;
-use radix_transactions::model::RawLedgerTransaction;
+use radix_transactions::prelude::*;
 use rocksdb::{Direction, IteratorMode, Options, DB};
 use std::fs::File;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::thread;
+use std::thread::JoinHandle;
 use std::time::Duration;
 use tar::Archive;
 
@@ -18,6 +19,35 @@ pub enum TxnReader {
 }
 
 impl TxnReader {
+    /// Opens a transaction file in `.tar.gz` format, or a Node state manager database folder.
+    pub fn open(source: &Path) -> Result<Self, Error> {
+        if source.is_file() {
+            let tar_gz = File::open(source).map_err(Error::IOError)?;
+            let tar = GzDecoder::new(tar_gz);
+            let archive = Archive::new(tar);
+            Ok(TxnReader::TransactionFile(Box::new(archive)))
+        } else if source.is_dir() {
+            Ok(TxnReader::StateManagerDatabaseDir(source.to_path_buf()))
+        } else {
+            Err(Error::InvalidTransactionSource)
+        }
+    }
+
+    /// Reads the transactions after `from_version` on a separate thread, and returns it along with
+    /// the receiving end of the transactions.
+    pub fn spawn(
+        mut self,
+        from_version: u64,
+        to_version: Option<u64>,
+    ) -> (
+        JoinHandle<Result<(), Error>>,
+        Receiver<RawLedgerTransaction>,
+    ) {
+        let (tx, rx) = flume::bounded(10);
+        let handle = thread::spawn(move || self.read(from_version, to_version, tx));
+        (handle, rx)
+    }
+
     pub fn read(
         &mut self,
         from_version: u64,
@@ -48,7 +78,11 @@ impl TxnReader {
                         }
                     }
 
-                    tx.send(RawLedgerTransaction::from_vec(tx_payload)).unwrap();
+                    // The executor drops the receiver when it stops early, e.g. at a state root
+                    // mismatch, which leaves nothing more to read.
+                    if tx.send(RawLedgerTransaction::from_vec(tx_payload)).is_err() {
+                        return Ok(());
+                    }
                 }
             }
             TxnReader::StateManagerDatabaseDir(db_dir) => {
@@ -79,8 +113,12 @@ impl TxnReader {
                     );
                     for next_txn in txn_iter {
                         let next_txn = next_txn.unwrap();
-                        tx.send(RawLedgerTransaction::from_vec(next_txn.1.to_vec()))
-                            .unwrap();
+                        if tx
+                            .send(RawLedgerTransaction::from_vec(next_txn.1.to_vec()))
+                            .is_err()
+                        {
+                            return Ok(());
+                        }
                     }
                     thread::sleep(Duration::from_secs(1));
                 }
@@ -90,3 +128,119 @@ impl TxnReader {
         Ok(())
     }
 }
+
+/// Reads committed transactions along with the ledger hashes resulting from them, which only a
+/// Node state manager database records.
+pub enum CommittedTxnReader {
+    StateManagerDatabaseDir(PathBuf),
+}
+
+impl CommittedTxnReader {
+    pub fn read(
+        &mut self,
+        from_version: u64,
+        to_version: Option<u64>,
+        tx: Sender<(RawLedgerTransaction, LedgerHashes)>,
+    ) -> Result<(), Error> {
+        match self {
+            CommittedTxnReader::StateManagerDatabaseDir(db_dir) => {
+                let temp_dir = tempfile::tempdir().map_err(Error::IOError)?;
+
+                let db = DB::open_cf_as_secondary(
+                    &Options::default(),
+                    db_dir.as_path(),
+                    temp_dir.as_ref(),
+                    vec![
+                        "raw_ledger_transactions",
+                        "committed_transaction_identifiers",
+                    ],
+                )
+                .unwrap();
+
+                let mut iter_start_state_version = from_version + 1;
+
+                loop {
+                    db.try_catch_up_with_primary()
+                        .expect("DB catch up with primary failed");
+                    let txn_iter = db.iterator_cf(
+                        &db.cf_handle("raw_ledger_transactions").unwrap(),
+                        IteratorMode::From(
+                            &iter_start_state_version.to_be_bytes(),
+                            Direction::Forward,
+                        ),
+                    );
+                    let mut identifiers_iter = db.iterator_cf(
+                        &db.cf_handle("committed_transaction_identifiers").unwrap(),
+                        IteratorMode::From(
+                            &iter_start_state_version.to_be_bytes(),
+                            Direction::Forward,
+                        ),
+                    );
+                    for next_txn in txn_iter {
+                        let next_txn = next_txn.unwrap();
+                        let next_state_version =
+                            u64::from_be_bytes(next_txn.0.as_ref().try_into().unwrap());
+
+                        let next_identifiers_bytes = identifiers_iter
+                            .next()
+                            .expect("Missing txn identifiers")
+                            .unwrap();
+
+                        let next_identifiers: VersionedCommittedTransactionIdentifiers =
+                            scrypto_decode(next_identifiers_bytes.1.as_ref()).unwrap();
+                        let ledger_hashes = next_identifiers
+                            .fully_update_and_into_latest_version()
+                            .resultant_ledger_hashes;
+
+                        // The executor drops the receiver when it stops early, e.g. at a state
+                        // root mismatch, which leaves nothing more to read.
+                        if tx
+                            .send((RawLedgerTransaction::from_slice(&next_txn.1), ledger_hashes))
+                            .is_err()
+                        {
+                            return Ok(());
+                        }
+                        if let Some(to_version) = to_version {
+                            if to_version == next_state_version {
+                                return Ok(());
+                            }
+                        }
+                        iter_start_state_version = next_state_version + 1;
+                    }
+                    thread::sleep(Duration::from_secs(1));
+                }
+            }
+        }
+    }
+}
+
+define_single_versioned! {
+    #[derive(Debug, Clone, Sbor)]
+    pub VersionedCommittedTransactionIdentifiers(CommittedTransactionIdentifiersVersions) => CommittedTransactionIdentifiers = CommittedTransactionIdentifiersV1
+}
+
+#[derive(Debug, Clone, Sbor)]
+pub struct CommittedTransactionIdentifiersV1 {
+    pub payload: LedgerTransactionHashes,
+    pub resultant_ledger_hashes: LedgerHashes,
+    pub proposer_timestamp_ms: i64,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, Debug, Sbor)]
+pub struct LedgerHashes {
+    pub state_root: StateHash,
+    pub transaction_root: TransactionTreeHash,
+    pub receipt_root: ReceiptTreeHash,
+}
+
+define_wrapped_hash! {
+    StateHash
+}
+
+define_wrapped_hash! {
+    TransactionTreeHash
+}
+
+define_wrapped_hash! {
+    ReceiptTreeHash
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use crate::resim::{resolve_alias, to_simulator_address};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AddressError {
@@ -13,6 +14,21 @@ impl fmt::Display for AddressError {
     }
 }
 
+/// Resolves an alias, or an address of the network the ledger is forked from, to the simulator
+/// address it stands for.
+pub fn resolve_address(address: &str) -> String {
+    to_simulator_address(&resolve_alias(address))
+}
+
+/// Resolves the address an argument such as `<resource_address>:<amount>` starts with, see
+/// [`resolve_address`].
+pub fn resolve_argument(argument: &str) -> String {
+    match argument.split_once(':') {
+        Some((address, rest)) => format!("{}:{}", resolve_address(address), rest),
+        None => resolve_address(argument),
+    }
+}
+
 #[derive(Clone)]
 pub struct SimulatorPackageAddress(pub PackageAddress);
 
@@ -32,10 +48,11 @@ impl FromStr for SimulatorPackageAddress {
     type Err = AddressError;
 
     fn from_str(address: &str) -> Result<Self, Self::Err> {
-        PackageAddress::try_from_hex(address)
+        let resolved = resolve_address(address);
+        PackageAddress::try_from_hex(&resolved)
             .or(PackageAddress::try_from_bech32(
                 &AddressBech32Decoder::for_simulator(),
-                address,
+                &resolved,
             ))
             .ok_or(AddressError::InvalidAddress(address.to_string()))
             .map(Self)
@@ -77,10 +94,11 @@ impl FromStr for SimulatorResourceAddress {
     type Err = AddressError;
 
     fn from_str(address: &str) -> Result<Self, Self::Err> {
-        ResourceAddress::try_from_hex(address)
+        let resolved = resolve_address(address);
+        ResourceAddress::try_from_hex(&resolved)
             .or(ResourceAddress::try_from_bech32(
                 &AddressBech32Decoder::for_simulator(),
-                address,
+                &resolved,
             ))
             .ok_or(AddressError::InvalidAddress(address.to_string()))
             .map(Self)
@@ -122,10 +140,11 @@ impl FromStr for SimulatorComponentAddress {
     type Err = AddressError;
 
     fn from_str(address: &str) -> Result<Self, Self::Err> {
-        ComponentAddress::try_from_hex(address)
+        let resolved = resolve_address(address);
+        ComponentAddress::try_from_hex(&resolved)
             .or(ComponentAddress::try_from_bech32(
                 &AddressBech32Decoder::for_simulator(),
-                address,
+                &resolved,
             ))
             .ok_or(AddressError::InvalidAddress(address.to_string()))
             .map(Self)
@@ -169,7 +188,7 @@ impl FromStr for SimulatorNonFungibleGlobalId {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let global_id = NonFungibleGlobalId::try_from_canonical_string(
             &AddressBech32Decoder::for_simulator(),
-            s,
+            &resolve_argument(s),
         )?;
         Ok(Self(global_id))
     }
@@ -0,0 +1,188 @@
+use crate::resim::*;
+use colored::*;
+use radix_common::prelude::*;
+use radix_engine::transaction::TransactionReceipt;
+use regex::{Captures, Regex};
+use std::fs;
+use std::path::PathBuf;
+
+/// The pattern of an alias at the start of an argument, such as `@alice` or `@gum:10`.
+const LEADING_ALIAS_PATTERN: &str = r"^@([A-Za-z0-9_\-]+)";
+
+pub fn get_aliases_path() -> Result<PathBuf, Error> {
+    let mut path = get_data_dir()?;
+    path.push("aliases");
+    Ok(path.with_extension("sbor"))
+}
+
+/// Returns the alias table, which maps alias names without the `@` to global addresses.
+pub fn get_aliases() -> Result<IndexMap<String, GlobalAddress>, Error> {
+    let path = get_aliases_path()?;
+    if path.exists() {
+        scrypto_decode(fs::read(path).map_err(Error::IOError)?.as_ref())
+            .map_err(Error::SborDecodeError)
+    } else {
+        Ok(IndexMap::default())
+    }
+}
+
+pub fn set_aliases(aliases: &IndexMap<String, GlobalAddress>) -> Result<(), Error> {
+    fs::write(get_aliases_path()?, scrypto_encode(aliases).unwrap()).map_err(Error::IOError)
+}
+
+/// Points the given alias at an address, replacing any address it pointed at.
+pub fn set_alias<A: Into<GlobalAddress>>(name: &str, address: A) -> Result<(), Error> {
+    let name = name.trim_start_matches('@');
+    if !is_valid_alias_name(name) {
+        return Err(Error::InvalidAliasName(name.to_owned()));
+    }
+    let mut aliases = get_aliases()?;
+    aliases.insert(name.to_owned(), address.into());
+    set_aliases(&aliases)
+}
+
+/// Picks the alias of an entity about to be created, so that a bad name is reported before the
+/// transaction runs. This is either the name chosen by the user, which must be valid and free,
+/// or a name derived from the entity, which is suffixed with a number if it is already taken.
+///
+/// Returns the alias name, without the `@`.
+pub fn choose_alias(chosen_name: &Option<String>, derived_name: &str) -> Result<String, Error> {
+    choose_alias_name(&get_aliases()?, chosen_name, derived_name)
+}
+
+fn choose_alias_name(
+    aliases: &IndexMap<String, GlobalAddress>,
+    chosen_name: &Option<String>,
+    derived_name: &str,
+) -> Result<String, Error> {
+    match chosen_name {
+        Some(name) => {
+            let name = name.trim_start_matches('@');
+            if !is_valid_alias_name(name) {
+                return Err(Error::InvalidAliasName(name.to_owned()));
+            }
+            if aliases.contains_key(name) {
+                return Err(Error::AliasAlreadyExists(name.to_owned()));
+            }
+            Ok(name.to_owned())
+        }
+        None => {
+            let base = sanitize_alias_name(derived_name);
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while aliases.contains_key(&name) {
+                name = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            Ok(name)
+        }
+    }
+}
+
+/// Picks the alias of the resource created by a `new-token-*` command, derived from its symbol
+/// or name, see [`choose_alias`].
+pub fn choose_token_alias(
+    chosen_name: &Option<String>,
+    symbol: &Option<String>,
+    name: &Option<String>,
+) -> Result<String, Error> {
+    let derived_name = symbol
+        .as_ref()
+        .or(name.as_ref())
+        .map_or("token", |name| name);
+    choose_alias(chosen_name, derived_name)
+}
+
+/// Prints the alias of the resource created by a `new-token-*` command, and then saves it.
+pub fn alias_new_token<O: std::io::Write>(
+    receipt: Option<TransactionReceipt>,
+    alias: &str,
+    out: &mut O,
+) -> Result<(), Error> {
+    if let Some(receipt) = receipt {
+        let resource_address = receipt.expect_commit(true).new_resource_addresses()[0];
+        record_json_value("alias", format!("@{}", alias));
+        writeln!(out, "Alias: {}", format!("@{}", alias).green()).map_err(Error::IOError)?;
+        set_alias(alias, resource_address)?;
+    }
+    Ok(())
+}
+
+/// Replaces the alias the given argument starts with, if any, with the bech32 address it points
+/// at, so that `@gum:10` becomes `<resource_address>:10`.
+///
+/// Unknown aliases are left untouched, so that they are reported by the address parsing.
+pub fn resolve_alias(text: &str) -> String {
+    if !text.starts_with('@') {
+        return text.to_owned();
+    }
+    let aliases = get_aliases().unwrap_or_default();
+    let encoder = AddressBech32Encoder::for_simulator();
+    let re = Regex::new(LEADING_ALIAS_PATTERN).unwrap();
+    re.replace(text, |caps: &Captures| match aliases.get(&caps[1]) {
+        Some(address) => address.display(&encoder).to_string(),
+        None => caps[0].to_owned(),
+    })
+    .into()
+}
+
+fn is_valid_alias_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn sanitize_alias_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.is_empty() {
+        "entity".to_owned()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_alias_names() {
+        assert_eq!(sanitize_alias_name("Gumball Token"), "gumball_token");
+        assert_eq!(sanitize_alias_name("hello_world_pkg"), "hello_world_pkg");
+        assert_eq!(sanitize_alias_name(" "), "entity");
+        assert!(is_valid_alias_name("alice-2"));
+        assert!(!is_valid_alias_name("alice smith"));
+        assert!(!is_valid_alias_name(""));
+    }
+
+    #[test]
+    fn chooses_free_alias_names() {
+        let aliases = indexmap!(
+            "account".to_owned() => GlobalAddress::from(XRD),
+            "account_2".to_owned() => GlobalAddress::from(XRD),
+        );
+
+        assert_eq!(
+            choose_alias_name(&aliases, &None, "account").unwrap(),
+            "account_3"
+        );
+        assert_eq!(
+            choose_alias_name(&aliases, &Some("@alice".to_owned()), "account").unwrap(),
+            "alice"
+        );
+        assert!(matches!(
+            choose_alias_name(&aliases, &Some("account".to_owned()), "account"),
+            Err(Error::AliasAlreadyExists(name)) if name == "account"
+        ));
+        assert!(matches!(
+            choose_alias_name(&aliases, &Some("alice smith".to_owned()), "account"),
+            Err(Error::InvalidAliasName(_))
+        ));
+    }
+}
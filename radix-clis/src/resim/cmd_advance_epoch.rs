@@ -0,0 +1,104 @@
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_engine::blueprints::consensus_manager::*;
+use radix_engine::system::system_db_reader::SystemDatabaseReader;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_interface::blueprints::consensus_manager::*;
+use radix_engine_interface::prelude::*;
+
+use crate::resim::*;
+
+/// Advance to the next epoch by running rounds until the epoch changes, which applies
+/// emissions, validator set updates and pending stake unlocks
+#[derive(Parser, Debug)]
+pub struct AdvanceEpoch {
+    /// The number of epochs to advance
+    #[clap(default_value = "1")]
+    pub epochs: u64,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    pub trace: bool,
+}
+
+impl AdvanceEpoch {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let (mut state, max_round_count, proposer_timestamp_ms) = Self::read_consensus_manager()?;
+        let target_epoch = state.epoch.after(self.epochs).ok_or(Error::EpochOverflow)?;
+
+        while state.epoch < target_epoch {
+            let epoch = state.epoch;
+            // The epoch changes at the latest after the maximum number of rounds
+            for _ in 0..max_round_count {
+                let manifest = ManifestBuilder::new_system_v1()
+                    .call_method(
+                        CONSENSUS_MANAGER,
+                        CONSENSUS_MANAGER_NEXT_ROUND_IDENT,
+                        ConsensusManagerNextRoundInput {
+                            round: Round::of(state.round.number() + 1),
+                            proposer_timestamp_ms,
+                            leader_proposal_history: LeaderProposalHistory {
+                                gap_round_leaders: vec![],
+                                current_leader: state.current_leader.unwrap_or(0),
+                                is_fallback: false,
+                            },
+                        },
+                    )
+                    .build();
+                let initial_proofs = btreeset![system_execution(SystemExecution::Validator)];
+                handle_system_transaction(manifest, initial_proofs, self.trace, false, out)?;
+
+                state = Self::read_consensus_manager()?.0;
+                if state.epoch > epoch {
+                    break;
+                }
+            }
+            if state.epoch == epoch {
+                return Err(Error::EpochNotAdvanced(epoch).into());
+            }
+        }
+
+        writeln!(out, "Epoch advanced to {}", state.epoch.number()).map_err(Error::IOError)?;
+        Ok(())
+    }
+
+    /// Reads the consensus manager state, the maximum number of rounds in an epoch and the
+    /// last proposer timestamp, which is kept so that only rounds, not time, advance.
+    fn read_consensus_manager() -> Result<(ConsensusManagerSubstate, u64, i64), Error> {
+        let SimulatorEnvironment { db, .. } = SimulatorEnvironment::new()?;
+        let reader = SystemDatabaseReader::new(&db);
+
+        let state = reader
+            .read_typed_object_field::<ConsensusManagerStateFieldPayload>(
+                CONSENSUS_MANAGER.as_node_id(),
+                ModuleId::Main,
+                ConsensusManagerField::State.field_index(),
+            )
+            .map_err(|_| Error::ConsensusManagerNotFound)?
+            .fully_update_and_into_latest_version();
+        let config = reader
+            .read_typed_object_field::<ConsensusManagerConfigurationFieldPayload>(
+                CONSENSUS_MANAGER.as_node_id(),
+                ModuleId::Main,
+                ConsensusManagerField::Configuration.field_index(),
+            )
+            .map_err(|_| Error::ConsensusManagerNotFound)?
+            .fully_update_and_into_latest_version()
+            .config;
+        let proposer_timestamp_ms = reader
+            .read_typed_object_field::<ConsensusManagerProposerMilliTimestampFieldPayload>(
+                CONSENSUS_MANAGER.as_node_id(),
+                ModuleId::Main,
+                ConsensusManagerField::ProposerMilliTimestamp.field_index(),
+            )
+            .map_err(|_| Error::ConsensusManagerNotFound)?
+            .fully_update_and_into_latest_version()
+            .epoch_milli;
+
+        Ok((
+            state,
+            config.epoch_change_condition.max_round_count,
+            proposer_timestamp_ms,
+        ))
+    }
+}
@@ -0,0 +1,97 @@
+use clap::{Parser, Subcommand};
+use colored::*;
+use radix_common::prelude::*;
+
+use crate::resim::*;
+
+/// List, set and remove the aliases which can be used in place of addresses, e.g. `@alice`
+#[derive(Parser, Debug)]
+pub struct Alias {
+    #[clap(subcommand)]
+    pub command: AliasCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AliasCommand {
+    /// List the aliases and the addresses they point at
+    List(AliasList),
+    /// Point an alias at an address
+    Set(AliasSet),
+    /// Remove an alias
+    Remove(AliasRemove),
+}
+
+#[derive(Parser, Debug)]
+pub struct AliasList {}
+
+#[derive(Parser, Debug)]
+pub struct AliasSet {
+    /// The name of the alias, with or without the leading `@`
+    pub name: String,
+
+    /// The address of a package, component or resource
+    pub address: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct AliasRemove {
+    /// The name of the alias, with or without the leading `@`
+    pub name: String,
+}
+
+impl Alias {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        match &self.command {
+            AliasCommand::List(cmd) => cmd.run(out),
+            AliasCommand::Set(cmd) => cmd.run(out),
+            AliasCommand::Remove(cmd) => cmd.run(out),
+        }
+    }
+}
+
+impl AliasList {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let encoder = AddressBech32Encoder::for_simulator();
+        for (name, address) in get_aliases()? {
+            let address = address.display(&encoder).to_string();
+            record_json_value(&name, address.clone());
+            writeln!(out, "{}: {}", format!("@{}", name).green(), address)
+                .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
+
+impl AliasSet {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let address = resolve_address(&self.address);
+        let global_address = GlobalAddress::try_from_hex(&address)
+            .or(GlobalAddress::try_from_bech32(
+                &AddressBech32Decoder::for_simulator(),
+                &address,
+            ))
+            .ok_or_else(|| Error::InvalidId(self.address.clone()))?;
+        set_alias(&self.name, global_address)?;
+        writeln!(
+            out,
+            "Alias @{} set to {}",
+            self.name.trim_start_matches('@'),
+            address.green()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+impl AliasRemove {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let name = self.name.trim_start_matches('@');
+        let mut aliases = get_aliases()?;
+        if aliases.shift_remove(name).is_none() {
+            return Err(Error::AliasNotFound(name.to_owned()).into());
+        }
+        set_aliases(&aliases)?;
+        writeln!(out, "Alias @{} removed", name).map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -39,6 +39,10 @@ pub struct CallFunction {
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
+
+    /// Preview the transaction without committing it
+    #[clap(long)]
+    pub preview: bool,
 }
 
 impl CallFunction {
@@ -55,7 +59,7 @@ impl CallFunction {
                 builder,
                 &address_bech32_decoder,
                 default_account,
-                resource_specifier,
+                resolve_argument(&resource_specifier),
             )
             .map_err(Error::FailedToBuildArguments)?;
         }
@@ -66,11 +70,17 @@ impl CallFunction {
                 self.package_address.0,
                 self.blueprint_name.clone(),
                 self.function_name.clone(),
-                self.arguments.clone(),
+                self.arguments
+                    .iter()
+                    .map(|argument| resolve_argument(argument))
+                    .collect(),
                 Some(default_account),
             )?
             .try_deposit_entire_worktop_or_refund(default_account, None)
             .build();
+        if self.preview {
+            return handle_preview(manifest.into(), &self.signing_keys, self.trace, out);
+        }
         handle_manifest(
             manifest.into(),
             &self.signing_keys,
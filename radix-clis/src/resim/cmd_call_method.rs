@@ -34,50 +34,116 @@ pub struct CallMethod {
     #[clap(short, long)]
     pub signing_keys: Option<String>,
 
+    /// A private key used for signing, can be repeated
+    #[clap(long)]
+    pub signer: Vec<String>,
+
+    /// The path to a subintent manifest to attach as a child of the transaction, can be repeated.
+    /// The subintents are yielded to in order before the method is called, and what they yield
+    /// back is deposited in the default account along with the rest of the worktop.
+    #[clap(long, conflicts_with = "manifest")]
+    pub subintent: Vec<PathBuf>,
+
+    /// The private keys used for signing the subintent at the same position, separated by comma
+    #[clap(long, conflicts_with = "manifest")]
+    pub subintent_signer: Vec<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
+
+    /// Preview the transaction without committing it
+    #[clap(long)]
+    pub preview: bool,
 }
 
 impl CallMethod {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
-        let address_bech32_decoder = AddressBech32Decoder::for_simulator();
+        if !self.subintent.is_empty() || !self.subintent_signer.is_empty() {
+            return self.run_with_subintents(out);
+        }
+        let manifest = self
+            .add_instructions(ManifestBuilder::new().lock_fee_from_faucet())?
+            .build();
+        if self.preview {
+            return handle_preview(
+                manifest.into(),
+                &merge_signing_keys(&self.signing_keys, &self.signer),
+                self.trace,
+                out,
+            );
+        }
+        handle_manifest(
+            manifest.into(),
+            &merge_signing_keys(&self.signing_keys, &self.signer),
+            &self.network,
+            &self.manifest,
+            self.trace,
+            true,
+            out,
+        )
+        .map(|_| ())
+    }
 
-        let default_account = get_default_account()?;
-        let proofs = self.proofs.clone().unwrap_or_default();
+    /// Builds a notarized V2 transaction which yields to the subintents before calling the method.
+    fn run_with_subintents<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::simulator(),
+        };
+        let subintents = Subintents::build(&self.subintent, &self.subintent_signer, &network)?;
 
-        let mut builder = ManifestBuilder::new().lock_fee_from_faucet();
-        for resource_specifier in proofs {
+        let mut builder = ManifestBuilder::new_v2().lock_fee_from_faucet();
+        for (name, hash) in subintents.children() {
+            builder = builder.use_child(name, hash);
+        }
+        for (name, _) in subintents.children() {
+            builder = builder.yield_to_child(name, ());
+        }
+        let manifest = self.add_instructions(builder)?.build();
+
+        subintents.run_transaction(
+            manifest,
+            &merge_signing_keys(&self.signing_keys, &self.signer),
+            self.trace,
+            self.preview,
+            out,
+        )
+    }
+
+    /// Adds the proofs, the method call and the deposit of the worktop to the default account.
+    fn add_instructions<M: BuildableManifest>(
+        &self,
+        mut builder: ManifestBuilder<M>,
+    ) -> Result<ManifestBuilder<M>, Error>
+    where
+        M::Instruction: From<InstructionV1>,
+    {
+        let address_bech32_decoder = AddressBech32Decoder::for_simulator();
+        let default_account = get_default_account()?;
+        for resource_specifier in self.proofs.clone().unwrap_or_default() {
             builder = create_proof_from_account(
                 builder,
                 &address_bech32_decoder,
                 default_account,
-                resource_specifier,
+                resolve_argument(&resource_specifier),
             )
             .map_err(Error::FailedToBuildArguments)?
         }
 
-        let manifest = self
+        Ok(self
             .add_call_method_instruction_with_schema(
                 builder,
                 &address_bech32_decoder,
                 self.component_address.0,
                 self.method_name.clone(),
-                self.arguments.clone(),
+                self.arguments
+                    .iter()
+                    .map(|argument| resolve_argument(argument))
+                    .collect(),
                 Some(default_account),
             )?
-            .try_deposit_entire_worktop_or_refund(default_account, None)
-            .build();
-        handle_manifest(
-            manifest.into(),
-            &self.signing_keys,
-            &self.network,
-            &self.manifest,
-            self.trace,
-            true,
-            out,
-        )
-        .map(|_| ())
+            .try_deposit_entire_worktop_or_refund(default_account, None))
     }
 
     /// Calls a method.
@@ -87,15 +153,18 @@ impl CallMethod {
     ///
     /// If an Account component address is provided, resources will be withdrawn from the given account;
     /// otherwise, they will be taken from transaction worktop.
-    pub fn add_call_method_instruction_with_schema(
+    pub fn add_call_method_instruction_with_schema<M: BuildableManifest>(
         &self,
-        builder: ManifestBuilder,
+        builder: ManifestBuilder<M>,
         address_bech32_decoder: &AddressBech32Decoder,
         component_address: ComponentAddress,
         method_name: String,
         args: Vec<String>,
         account: Option<ComponentAddress>,
-    ) -> Result<ManifestBuilder, Error> {
+    ) -> Result<ManifestBuilder<M>, Error>
+    where
+        M::Instruction: From<InstructionV1>,
+    {
         let object_info = export_object_info(component_address)?;
         let bp_info = object_info.blueprint_info;
         let bp_id = bp_info.blueprint_id;
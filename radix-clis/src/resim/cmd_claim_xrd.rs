@@ -0,0 +1,98 @@
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_engine::blueprints::consensus_manager::UnstakeData;
+use radix_engine::blueprints::resource::*;
+use radix_engine::system::system_db_reader::*;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_interface::types::CollectionDescriptor;
+use radix_substate_store_queries::query::ResourceAccounter;
+
+use crate::resim::*;
+
+/// Claim the XRD of all the claimable claim NFTs of a validator held by the default account
+#[derive(Parser, Debug)]
+pub struct ClaimXrd {
+    /// The validator address
+    pub validator_address: SimulatorComponentAddress,
+
+    /// The network to use when outputting manifest, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    pub manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    pub signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    pub trace: bool,
+}
+
+impl ClaimXrd {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let default_account = get_default_account()?;
+        let validator_address = self.validator_address.0;
+        let validator = get_validator_state(validator_address)?;
+
+        let claim_ids = {
+            let SimulatorEnvironment { db, .. } = SimulatorEnvironment::new()?;
+            let current_epoch = read_current_epoch(&db)?;
+            let mut accounter = ResourceAccounter::new(&db);
+            accounter.traverse(*default_account.as_node_id());
+            let held_ids = accounter
+                .close()
+                .non_fungibles
+                .remove(&validator.claim_nft)
+                .unwrap_or_default();
+
+            // Only the unstaked XRD whose claim epoch has been reached can be claimed
+            let reader = SystemDatabaseReader::new(&db);
+            let mut claim_ids = index_set_new();
+            for id in held_ids {
+                let unstake_data = reader
+                    .read_object_collection_entry::<_, NonFungibleResourceManagerDataEntryPayload>(
+                        validator.claim_nft.as_node_id(),
+                        ModuleId::Main,
+                        ObjectCollectionKey::KeyValue(
+                            NonFungibleResourceManagerCollection::DataKeyValue.collection_index(),
+                            &id,
+                        ),
+                    )
+                    .ok()
+                    .flatten()
+                    .and_then(|payload| {
+                        scrypto_decode::<UnstakeData>(&scrypto_encode(&payload).ok()?).ok()
+                    });
+                if matches!(unstake_data, Some(data) if data.claim_epoch <= current_epoch) {
+                    claim_ids.insert(id);
+                }
+            }
+            claim_ids
+        };
+        if claim_ids.is_empty() {
+            return Err(Error::NoClaimableStake(validator_address).into());
+        }
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(default_account, validator.claim_nft, claim_ids)
+            .take_all_from_worktop(validator.claim_nft, "claims")
+            .claim_xrd(validator_address, "claims")
+            .try_deposit_entire_worktop_or_refund(default_account, None)
+            .build();
+        handle_manifest(
+            manifest.into(),
+            &self.signing_keys,
+            &self.network,
+            &self.manifest,
+            self.trace,
+            true,
+            out,
+        )
+        .map(|_| ())
+    }
+}
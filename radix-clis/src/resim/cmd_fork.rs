@@ -0,0 +1,119 @@
+use clap::Parser;
+use colored::*;
+use radix_common::prelude::*;
+use radix_engine::blueprints::resource::*;
+use radix_engine::system::bootstrap::DEFAULT_TESTING_FAUCET_SUPPLY;
+use radix_engine::system::system_db_reader::*;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_interface::blueprints::resource::*;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Fork the simulator ledger from a database built by `replay execute`. The database is only
+/// read from, and the transactions run afterwards are committed on top of it in the data
+/// directory, until the next `reset`
+#[derive(Parser, Debug)]
+pub struct Fork {
+    /// The path to the replay database
+    pub source: PathBuf,
+
+    /// The network of the replay database, whose addresses are accepted alongside simulator ones
+    #[clap(short, long, default_value = "mainnet")]
+    pub network: String,
+}
+
+impl Fork {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        // Replays start from a fresh simulator ledger, not from the source database
+        ensure_not_recording("fork")?;
+        NetworkDefinition::from_str(&self.network).map_err(Error::ParseNetworkError)?;
+        let source = self
+            .source
+            .canonicalize()
+            .map_err(|err| Error::IOErrorAtPath(err, self.source.clone()))?;
+
+        clear_ledger_files(&get_data_dir()?)?;
+        set_fork_configs(&ForkConfigs {
+            source: source.to_string_lossy().into_owned(),
+            network: self.network.clone(),
+        })?;
+
+        // Opening the ledger brings it up to the latest protocol version, in the fork
+        let SimulatorEnvironment { mut db, .. } = SimulatorEnvironment::new()?;
+        let faucet_topped_up = top_up_faucet(&mut db)?;
+        let epoch = read_current_epoch(&db)?;
+
+        writeln!(
+            out,
+            "Forked the ledger from {} at epoch {}",
+            source.display().to_string().green(),
+            epoch.number()
+        )
+        .map_err(Error::IOError)?;
+        if faucet_topped_up {
+            writeln!(
+                out,
+                "The faucet was empty, and has been topped up in the fork."
+            )
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
+
+/// The faucet of a production network is empty, so it is given the testing supply in the fork,
+/// for fees to be locked from it as on a simulator ledger. The XRD total supply is not updated.
+fn top_up_faucet(db: &mut SimulatorDatabase) -> Result<bool, Error> {
+    let reader = SystemDatabaseReader::new(db);
+    let Ok(faucet_state) = reader.read_object_field(FAUCET.as_node_id(), ModuleId::Main, 0) else {
+        return Ok(false);
+    };
+    let Some(vault_id) = faucet_state.owned_nodes().first().cloned() else {
+        return Ok(false);
+    };
+    let balance = reader
+        .read_typed_object_field::<FungibleVaultBalanceFieldPayload>(
+            &vault_id,
+            ModuleId::Main,
+            FungibleVaultField::Balance.field_index(),
+        )
+        .map_err(|_| Error::ComponentNotFound(FAUCET))?
+        .fully_update_and_into_latest_version();
+    if !balance.is_empty() {
+        return Ok(false);
+    }
+
+    SystemDatabaseWriter::new(db)
+        .write_typed_object_field(
+            &vault_id,
+            ModuleId::Main,
+            FungibleVaultField::Balance.field_index(),
+            FungibleVaultBalanceFieldPayload::from_content_source(LiquidFungibleResource::new(
+                *DEFAULT_TESTING_FAUCET_SUPPLY,
+            )),
+        )
+        .map_err(|_| Error::ComponentNotFound(FAUCET))?;
+    Ok(true)
+}
+
+/// Converts an address of the network the ledger is forked from into a simulator address, and
+/// returns any other text as is.
+pub fn to_simulator_address(address: &str) -> String {
+    let Ok(Some(fork_configs)) = get_fork_configs() else {
+        return address.to_owned();
+    };
+    NetworkDefinition::from_str(&fork_configs.network)
+        .ok()
+        .and_then(|network| {
+            AddressBech32Decoder::new(&network)
+                .validate_and_decode(address)
+                .ok()
+        })
+        .and_then(|(_, full_data)| {
+            AddressBech32Encoder::for_simulator()
+                .encode(&full_data)
+                .ok()
+        })
+        .unwrap_or_else(|| address.to_owned())
+}
@@ -13,6 +13,8 @@ impl GenerateKeyPair {
         let secret = rand::thread_rng().gen::<[u8; 32]>();
         let private_key = Secp256k1PrivateKey::from_bytes(&secret).unwrap();
         let public_key = private_key.public_key();
+        record_json_value("public_key", public_key.to_string());
+        record_json_value("private_key", hex::encode(private_key.to_bytes()));
         writeln!(out, "Public key: {}", public_key.to_string().green()).map_err(Error::IOError)?;
         writeln!(
             out,
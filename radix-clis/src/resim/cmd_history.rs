@@ -0,0 +1,100 @@
+use clap::Parser;
+use colored::*;
+use radix_substate_store_queries::query::*;
+
+use crate::resim::*;
+
+/// List the committed transactions, optionally only those touching an entity
+#[derive(Parser, Debug)]
+pub struct History {
+    /// Only list the transactions which updated the given entity or any of its descendants
+    #[clap(short, long)]
+    pub address: Option<String>,
+}
+
+/// Collects every node owned by the traversed entity.
+#[derive(Default)]
+struct DescendantCollector {
+    node_ids: IndexSet<NodeId>,
+}
+
+impl StateTreeVisitor for DescendantCollector {
+    fn visit_node_id(
+        &mut self,
+        _parent_id: Option<&(NodeId, PartitionNumber, SubstateKey)>,
+        node_id: &NodeId,
+        _depth: u32,
+    ) {
+        self.node_ids.insert(*node_id);
+    }
+}
+
+impl History {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let history = get_transaction_history()?;
+        let filter = match &self.address {
+            Some(address) => {
+                let resolved = resolve_address(address);
+                let address = GlobalAddress::try_from_hex(&resolved)
+                    .or(GlobalAddress::try_from_bech32(
+                        &AddressBech32Decoder::for_simulator(),
+                        &resolved,
+                    ))
+                    .ok_or_else(|| Error::InvalidId(address.clone()))?;
+                let SimulatorEnvironment { db, .. } = SimulatorEnvironment::new()?;
+                let mut collector = DescendantCollector::default();
+                StateTreeTraverser::new(&db, &mut collector, 100)
+                    .traverse_subtree(None, address.into_node_id());
+                collector.node_ids.insert(address.into_node_id());
+                Some(collector.node_ids)
+            }
+            None => None,
+        };
+
+        let entries = history
+            .iter()
+            .filter(|entry| match &filter {
+                Some(node_ids) => entry
+                    .touched_nodes
+                    .iter()
+                    .any(|node_id| node_ids.contains(node_id)),
+                None => true,
+            })
+            .collect::<Vec<_>>();
+
+        record_json_value(
+            "transactions",
+            entries
+                .iter()
+                .map(|entry| {
+                    serde_json::json!({
+                        "id": transaction_id_to_json(&entry.id),
+                        "nonce": entry.nonce,
+                        "success": entry.success,
+                        "total_cost": entry.total_cost.to_string(),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        writeln!(out, "{}:", "Transactions".green().bold()).map_err(Error::IOError)?;
+        for (last, entry) in entries.iter().identify_last() {
+            writeln!(
+                out,
+                "{} {}: {} (nonce: {}, status: {}, cost: {} XRD)",
+                list_item_prefix(last),
+                entry.id.label(),
+                entry.id.to_display_string(),
+                entry.nonce,
+                if entry.success {
+                    "SUCCESS".green()
+                } else {
+                    "FAILURE".red()
+                },
+                entry.total_cost,
+            )
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
@@ -25,10 +25,15 @@ pub struct NewAccount {
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
+
+    /// The alias of the account, such as `alice`, defaults to `account` with a number if taken
+    #[clap(long)]
+    pub alias: Option<String>,
 }
 
 impl NewAccount {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let alias = choose_alias(&self.alias, "account")?;
         let secret = rand::thread_rng().gen::<[u8; 32]>();
         let private_key = Secp256k1PrivateKey::from_bytes(&secret).unwrap();
         let public_key = private_key.public_key();
@@ -91,6 +96,18 @@ impl NewAccount {
             let owner_badge =
                 NonFungibleGlobalId::new(resource_address, NonFungibleLocalId::integer(1));
 
+            record_json_value(
+                "account_address",
+                account.display(&address_bech32_encoder).to_string(),
+            );
+            record_json_value("public_key", public_key.to_string());
+            record_json_value("private_key", hex::encode(private_key.to_bytes()));
+            record_json_value(
+                "owner_badge",
+                owner_badge.to_canonical_string(&address_bech32_encoder),
+            );
+            record_json_value("alias", format!("@{}", alias));
+
             writeln!(out, "A new account has been created!").map_err(Error::IOError)?;
             writeln!(
                 out,
@@ -114,6 +131,7 @@ impl NewAccount {
                     .green()
             )
             .map_err(Error::IOError)?;
+            writeln!(out, "Alias: {}", format!("@{}", alias).green()).map_err(Error::IOError)?;
 
             let mut configs = get_configs()?;
             if configs.default_account.is_none()
@@ -131,7 +149,11 @@ impl NewAccount {
                 )
                 .map_err(Error::IOError)?;
             }
+            set_alias(&alias, account)?;
         } else {
+            record_json_value("public_key", public_key.to_string());
+            record_json_value("private_key", hex::encode(private_key.to_bytes()));
+
             writeln!(out, "A manifest has been produced for the following key pair. To complete account creation, you will need to run the manifest!").map_err(Error::IOError)?;
             writeln!(out, "Public key: {}", public_key.to_string().green())
                 .map_err(Error::IOError)?;
@@ -47,11 +47,16 @@ pub struct NewTokenFixed {
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
+
+    /// The alias of the token, defaults to its symbol or name
+    #[clap(long)]
+    pub alias: Option<String>,
 }
 
 impl NewTokenFixed {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
         let default_account = get_default_account()?;
+        let alias = choose_token_alias(&self.alias, &self.symbol, &self.name)?;
         let mut metadata = BTreeMap::new();
         if let Some(symbol) = self.symbol.clone() {
             metadata.insert("symbol".to_string(), MetadataValue::String(symbol));
@@ -88,7 +93,7 @@ impl NewTokenFixed {
             .new_token_fixed(OwnerRole::None, metadata, self.total_supply)
             .try_deposit_entire_worktop_or_refund(default_account, None)
             .build();
-        handle_manifest(
+        let receipt = handle_manifest(
             manifest.into(),
             &self.signing_keys,
             &self.network,
@@ -96,7 +101,8 @@ impl NewTokenFixed {
             self.trace,
             true,
             out,
-        )
-        .map(|_| ())
+        )?;
+        alias_new_token(receipt, &alias, out)?;
+        Ok(())
     }
 }
@@ -46,10 +46,15 @@ pub struct NewTokenMutable {
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
+
+    /// The alias of the token, defaults to its symbol or name
+    #[clap(long)]
+    pub alias: Option<String>,
 }
 
 impl NewTokenMutable {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let alias = choose_token_alias(&self.alias, &self.symbol, &self.name)?;
         let mut metadata = BTreeMap::new();
         if let Some(symbol) = self.symbol.clone() {
             metadata.insert("symbol".to_string(), MetadataValue::String(symbol));
@@ -85,7 +90,7 @@ impl NewTokenMutable {
             .lock_fee_from_faucet()
             .new_token_mutable(metadata, self.minter_badge.clone().into())
             .build();
-        handle_manifest(
+        let receipt = handle_manifest(
             manifest.into(),
             &self.signing_keys,
             &self.network,
@@ -93,7 +98,8 @@ impl NewTokenMutable {
             self.trace,
             true,
             out,
-        )
-        .map(|_| ())
+        )?;
+        alias_new_token(receipt, &alias, out)?;
+        Ok(())
     }
 }
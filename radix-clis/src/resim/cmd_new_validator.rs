@@ -0,0 +1,140 @@
+use clap::Parser;
+use colored::*;
+use radix_common::prelude::*;
+use radix_engine_interface::blueprints::consensus_manager::*;
+
+use crate::resim::Error::TransactionFailed;
+use crate::resim::*;
+
+/// Create a validator owned by the default account
+#[derive(Parser, Debug)]
+pub struct NewValidator {
+    /// The public key of the validator, defaults to the public key of the default account
+    pub public_key: Option<Secp256k1PublicKey>,
+
+    /// The share of the emissions kept by the validator, between 0 and 1
+    #[clap(long, default_value = "0.01")]
+    pub fee_factor: Decimal,
+
+    /// Allow anyone to stake to the validator, otherwise only the owner can
+    #[clap(long)]
+    pub accept_delegated_stake: bool,
+
+    /// The network to use when outputting manifest, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    pub manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    pub signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    pub trace: bool,
+}
+
+impl NewValidator {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let default_account = get_default_account()?;
+        let public_key = match self.public_key {
+            Some(public_key) => public_key,
+            None => get_default_private_key()?.public_key(),
+        };
+
+        // The creation fee is paid from the faucet, and the change is deposited with the owner badge
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .get_free_xrd_from_faucet()
+            .take_all_from_worktop(XRD, "xrd_creation_fee")
+            .create_validator(public_key, self.fee_factor, "xrd_creation_fee")
+            .try_deposit_entire_worktop_or_refund(default_account, None)
+            .build();
+        let receipt = handle_manifest(
+            manifest.into(),
+            &self.signing_keys,
+            &self.network,
+            &self.manifest,
+            self.trace,
+            false,
+            out,
+        )?;
+
+        if let Some(receipt) = receipt {
+            let commit_result = receipt.expect_commit(true);
+            commit_result
+                .outcome
+                .success_or_else(|err| TransactionFailed(err.clone()))?;
+            let validator_address = commit_result.new_component_addresses()[0];
+
+            if self.accept_delegated_stake {
+                let manifest = ManifestBuilder::new()
+                    .lock_fee_from_faucet()
+                    .create_proof_from_account_of_non_fungibles(
+                        default_account,
+                        VALIDATOR_OWNER_BADGE,
+                        [validator_owner_badge_id(validator_address)],
+                    )
+                    .call_method(
+                        validator_address,
+                        VALIDATOR_UPDATE_ACCEPT_DELEGATED_STAKE_IDENT,
+                        ValidatorUpdateAcceptDelegatedStakeInput {
+                            accept_delegated_stake: true,
+                        },
+                    )
+                    .build();
+                handle_manifest(
+                    manifest.into(),
+                    &self.signing_keys,
+                    &self.network,
+                    &None,
+                    self.trace,
+                    false,
+                    out,
+                )?;
+            }
+
+            let address_bech32_encoder = AddressBech32Encoder::for_simulator();
+            let owner_badge = NonFungibleGlobalId::new(
+                VALIDATOR_OWNER_BADGE,
+                validator_owner_badge_id(validator_address),
+            );
+            record_json_value(
+                "validator_address",
+                validator_address
+                    .display(&address_bech32_encoder)
+                    .to_string(),
+            );
+            record_json_value(
+                "owner_badge",
+                owner_badge.to_canonical_string(&address_bech32_encoder),
+            );
+
+            writeln!(out, "A new validator has been created!").map_err(Error::IOError)?;
+            writeln!(
+                out,
+                "Validator address: {}",
+                validator_address
+                    .display(&address_bech32_encoder)
+                    .to_string()
+                    .green()
+            )
+            .map_err(Error::IOError)?;
+            writeln!(out, "Public key: {}", public_key.to_string().green())
+                .map_err(Error::IOError)?;
+            writeln!(
+                out,
+                "Owner badge: {}",
+                owner_badge
+                    .to_canonical_string(&address_bech32_encoder)
+                    .green()
+            )
+            .map_err(Error::IOError)?;
+        }
+
+        Ok(())
+    }
+}
@@ -34,6 +34,10 @@ pub struct Publish {
     #[clap(long)]
     pub package_address: Option<SimulatorPackageAddress>,
 
+    /// The alias of the package, defaults to the name of the package followed by `_pkg`
+    #[clap(long)]
+    pub alias: Option<String>,
+
     /// The network to use when outputting manifest, [simulator | adapanet | nebunet | mainnet]
     #[clap(short, long)]
     pub network: Option<String>,
@@ -82,6 +86,10 @@ impl Publish {
             (code_path, schema_path)
         };
 
+        let package_name = code_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
         let code = fs::read(code_path).map_err(Error::IOError)?;
         let package_definition = manifest_decode::<ManifestPackageDefinition>(
             &fs::read(&definition_path)
@@ -322,6 +330,7 @@ impl Publish {
                 .clone()
                 .map(|owner_badge| owner_badge.0)
                 .unwrap_or(get_default_owner_badge()?);
+            let alias = choose_alias(&self.alias, &format!("{}_pkg", package_name))?;
 
             let manifest = ManifestBuilder::new()
                 .lock_fee_from_faucet()
@@ -342,15 +351,20 @@ impl Publish {
                 out,
             )?;
             if let Some(receipt) = receipt {
+                let package_address = receipt.expect_commit(true).new_package_addresses()[0];
                 writeln!(
                     out,
                     "Success! New Package: {}",
-                    receipt.expect_commit(true).new_package_addresses()[0]
+                    package_address
                         .display(&AddressBech32Encoder::for_simulator())
                         .to_string()
                         .green()
                 )
                 .map_err(Error::IOError)?;
+                record_json_value("alias", format!("@{}", alias));
+                writeln!(out, "Alias: {}", format!("@{}", alias).green())
+                    .map_err(Error::IOError)?;
+                set_alias(&alias, package_address)?;
             }
         }
 
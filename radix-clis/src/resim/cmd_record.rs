@@ -0,0 +1,203 @@
+use clap::{Parser, Subcommand};
+use colored::*;
+use radix_engine::transaction::*;
+
+use crate::resim::*;
+
+/// Record the transactions committed by resim commands into a replayable file
+#[derive(Parser, Debug)]
+pub struct Record {
+    #[clap(subcommand)]
+    pub command: RecordCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RecordCommand {
+    /// Start recording committed transactions into the given file
+    Start(RecordStart),
+    /// Stop the active recording
+    Stop(RecordStop),
+}
+
+#[derive(Parser, Debug)]
+pub struct RecordStart {
+    /// The path of the recording file to create
+    pub path: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+pub struct RecordStop {}
+
+/// A step of a session recorded by `resim record`, which the recording file is a log of.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub enum RecordedStep {
+    /// A test transaction, as committed by most commands.
+    Transaction(RecordedTransaction),
+    /// A system transaction, e.g. as committed by `resim advance-epoch`.
+    SystemTransaction(RecordedTransaction),
+    /// A notarized V2 transaction, e.g. as committed by `resim run --subintent`.
+    NotarizedTransactionV2(RecordedNotarizedTransaction),
+    SetCurrentEpoch(Epoch),
+    SetCurrentTime {
+        epoch_milli: i64,
+        epoch_minute: i32,
+    },
+    /// The ledger was reset with `resim reset`.
+    Reset,
+}
+
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct RecordedTransaction {
+    /// The manifest and its blobs, encoded with [`AnyManifest::to_raw`].
+    pub raw_manifest: Vec<u8>,
+    pub nonce: u32,
+    pub initial_proofs: BTreeSet<NonFungibleGlobalId>,
+    pub outcome: RecordedOutcome,
+}
+
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct RecordedNotarizedTransaction {
+    /// The transaction, encoded as a [`RawNotarizedTransaction`].
+    pub raw_transaction: Vec<u8>,
+    pub outcome: RecordedOutcome,
+}
+
+/// The parts of a receipt which are compared when a recording is replayed.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct RecordedOutcome {
+    pub success: bool,
+    pub new_entities: Vec<GlobalAddress>,
+    pub event_count: u32,
+    pub total_cost: Decimal,
+}
+
+impl RecordedOutcome {
+    pub fn from_commit(fee_summary: &TransactionFeeSummary, commit: &CommitResult) -> Self {
+        let new_entities = commit
+            .new_package_addresses()
+            .iter()
+            .map(|address| GlobalAddress::from(*address))
+            .chain(
+                commit
+                    .new_component_addresses()
+                    .iter()
+                    .map(|address| GlobalAddress::from(*address)),
+            )
+            .chain(
+                commit
+                    .new_resource_addresses()
+                    .iter()
+                    .map(|address| GlobalAddress::from(*address)),
+            )
+            .collect();
+        Self {
+            success: commit.outcome.is_success(),
+            new_entities,
+            event_count: commit.application_events.len() as u32,
+            total_cost: fee_summary.total_cost(),
+        }
+    }
+
+    /// The outcome of a receipt, if it was committed, as only committed transactions are recorded.
+    pub fn from_receipt(receipt: &TransactionReceipt) -> Option<Self> {
+        match &receipt.result {
+            TransactionResult::Commit(commit) => {
+                Some(Self::from_commit(&receipt.fee_summary, commit))
+            }
+            TransactionResult::Reject(_) | TransactionResult::Abort(_) => None,
+        }
+    }
+}
+
+impl Record {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        match &self.command {
+            RecordCommand::Start(cmd) => cmd.run(out),
+            RecordCommand::Stop(cmd) => cmd.run(out),
+        }
+    }
+}
+
+impl RecordStart {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        if let Some(path) = get_active_recording()? {
+            return Err(Error::RecordingAlreadyActive(path).into());
+        }
+
+        write_ensuring_folder_exists(&self.path, [])
+            .map_err(|err| Error::IOErrorAtPath(err, self.path.clone()))?;
+        let path = fs::canonicalize(&self.path)
+            .map_err(|err| Error::IOErrorAtPath(err, self.path.clone()))?;
+        fs::write(
+            get_recording_marker_path()?,
+            path.to_string_lossy().as_bytes(),
+        )
+        .map_err(Error::IOError)?;
+
+        writeln!(
+            out,
+            "Recording committed transactions to {}.",
+            path.display().to_string().green()
+        )
+        .map_err(Error::IOError)?;
+        if get_nonce()? != 0 {
+            writeln!(
+                out,
+                "{}",
+                "Note: the ledger is not fresh, but `resim replay` starts from a fresh ledger; consider running `resim reset` first."
+                    .yellow()
+            )
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
+
+impl RecordStop {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let path = get_active_recording()?.ok_or(Error::NoActiveRecording)?;
+        fs::remove_file(get_recording_marker_path()?).map_err(Error::IOError)?;
+
+        let recording = read_recording(&path)?;
+        writeln!(
+            out,
+            "Recorded {} step(s) to {}.",
+            recording.len(),
+            path.display().to_string().green()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+fn get_recording_marker_path() -> Result<PathBuf, Error> {
+    Ok(get_data_dir()?.join(RECORDING_MARKER_FILE_UNDER_DATA_DIR))
+}
+
+/// Returns the path of the file being recorded into, if a recording has been started.
+pub fn get_active_recording() -> Result<Option<PathBuf>, Error> {
+    let marker_path = get_recording_marker_path()?;
+    if marker_path.exists() {
+        let path = fs::read_to_string(&marker_path).map_err(Error::IOError)?;
+        Ok(Some(PathBuf::from(path)))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn read_recording(path: &Path) -> Result<Vec<RecordedStep>, Error> {
+    read_sbor_records(path)
+}
+
+/// Appends a step to the recording at the given path, see [`get_active_recording`].
+pub fn record_step(path: &Path, step: &RecordedStep) -> Result<(), Error> {
+    append_sbor_record(path, step)
+}
+
+/// Fails if a recording has been started, for commands whose effects can't be replayed.
+pub fn ensure_not_recording(command: &str) -> Result<(), Error> {
+    match get_active_recording()? {
+        Some(_) => Err(Error::UnsupportedWhileRecording(command.to_owned())),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,62 @@
+use clap::Parser;
+use radix_common::prelude::*;
+
+use crate::resim::*;
+
+/// Register a validator owned by the default account, so that it can join the active set
+#[derive(Parser, Debug)]
+pub struct RegisterValidator {
+    /// The validator address
+    pub validator_address: SimulatorComponentAddress,
+
+    /// Unregister the validator instead
+    #[clap(long)]
+    pub unregister: bool,
+
+    /// The network to use when outputting manifest, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    pub manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    pub signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    pub trace: bool,
+}
+
+impl RegisterValidator {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let default_account = get_default_account()?;
+        let validator_address = self.validator_address.0;
+
+        let builder = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                default_account,
+                VALIDATOR_OWNER_BADGE,
+                [validator_owner_badge_id(validator_address)],
+            );
+        let manifest = if self.unregister {
+            builder.unregister_validator(validator_address)
+        } else {
+            builder.register_validator(validator_address)
+        }
+        .build();
+        handle_manifest(
+            manifest.into(),
+            &self.signing_keys,
+            &self.network,
+            &self.manifest,
+            self.trace,
+            true,
+            out,
+        )
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,221 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::blueprints::consensus_manager::{
+    ProposerMilliTimestampSubstate, ProposerMinuteTimestampSubstate,
+};
+use radix_engine::transaction::*;
+use radix_engine::updates::*;
+use radix_engine::vm::*;
+use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
+use radix_transactions::validation::TransactionValidator;
+
+use crate::resim::*;
+
+/// Replay a recorded session against a fresh ledger and report divergent receipts
+#[derive(Parser, Debug)]
+pub struct Replay {
+    /// The path to a recording file created by `resim record`
+    pub path: PathBuf,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    pub trace: bool,
+}
+
+impl Replay {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let steps = read_recording(&self.path)?;
+
+        // The replay happens in memory, so the simulator ledger is left untouched
+        let network = NetworkDefinition::simulator();
+        let mut db = fresh_ledger(&network);
+        let vm_modules = VmModules::default();
+        let address_bech32_encoder = AddressBech32Encoder::new(&network);
+
+        let mut divergences = 0;
+        for (index, step) in steps.into_iter().enumerate() {
+            let validator = TransactionValidator::new(&db, &network);
+            let (executable, execution_config, expected_outcome) = match step {
+                RecordedStep::Transaction(recorded) => {
+                    let manifest =
+                        AnyManifest::from_raw(&RawManifest::from_vec(recorded.raw_manifest))
+                            .map_err(Error::SborDecodeError)?;
+                    let executable = TestTransaction::new_from_any_manifest(
+                        manifest,
+                        recorded.nonce,
+                        recorded.initial_proofs,
+                    )?
+                    .into_executable(&validator)
+                    .map_err(Error::TransactionPrepareError)?;
+                    (
+                        executable,
+                        ExecutionConfig::for_test_transaction(),
+                        recorded.outcome,
+                    )
+                }
+                RecordedStep::SystemTransaction(recorded) => {
+                    let manifest = SystemTransactionManifestV1::from_raw(&RawManifest::from_vec(
+                        recorded.raw_manifest,
+                    ))?;
+                    let executable = manifest
+                        .into_transaction(system_transaction_hash(recorded.nonce))
+                        .with_proofs(recorded.initial_proofs)
+                        .into_executable(&validator)
+                        .map_err(Error::TransactionPrepareError)?;
+                    (
+                        executable,
+                        ExecutionConfig::for_system_transaction(network.clone()),
+                        recorded.outcome,
+                    )
+                }
+                RecordedStep::NotarizedTransactionV2(recorded) => {
+                    let executable = RawNotarizedTransaction::from_vec(recorded.raw_transaction)
+                        .into_executable(&validator)
+                        .map_err(Error::TransactionValidationError)?;
+                    (
+                        executable,
+                        ExecutionConfig::for_notarized_transaction(network.clone()),
+                        recorded.outcome,
+                    )
+                }
+                RecordedStep::SetCurrentEpoch(epoch) => {
+                    write_epoch(&mut db, epoch);
+                    continue;
+                }
+                RecordedStep::SetCurrentTime {
+                    epoch_milli,
+                    epoch_minute,
+                } => {
+                    write_timestamps(
+                        &mut db,
+                        ProposerMilliTimestampSubstate { epoch_milli },
+                        ProposerMinuteTimestampSubstate { epoch_minute },
+                    );
+                    continue;
+                }
+                RecordedStep::Reset => {
+                    db = fresh_ledger(&network);
+                    continue;
+                }
+            };
+            let receipt = execute_and_commit_transaction(
+                &mut db,
+                &vm_modules,
+                &execution_config.with_kernel_trace(self.trace),
+                executable,
+            );
+
+            let differences = match &receipt.result {
+                TransactionResult::Commit(commit) => describe_differences(
+                    &expected_outcome,
+                    &RecordedOutcome::from_commit(&receipt.fee_summary, commit),
+                    &address_bech32_encoder,
+                ),
+                TransactionResult::Reject(rejection) => vec![format!(
+                    "rejected: {}",
+                    rejection.reason.to_string(&address_bech32_encoder)
+                )],
+                TransactionResult::Abort(abort) => vec![format!("aborted: {:?}", abort.reason)],
+            };
+
+            if differences.is_empty() {
+                writeln!(out, "Step #{}: {}", index, "OK".green()).map_err(Error::IOError)?;
+            } else {
+                divergences += 1;
+                writeln!(out, "Step #{}: {}", index, "DIVERGED".red()).map_err(Error::IOError)?;
+                for (last, difference) in differences.iter().identify_last() {
+                    writeln!(out, "{} {}", list_item_prefix(last), difference)
+                        .map_err(Error::IOError)?;
+                }
+            }
+        }
+
+        if divergences > 0 {
+            return Err(Error::ReplayDiverged(divergences).into());
+        }
+        Ok(())
+    }
+}
+
+/// A bootstrapped in-memory ledger, as a recording starts from a fresh ledger.
+fn fresh_ledger(network: &NetworkDefinition) -> InMemorySubstateDatabase {
+    let mut db = InMemorySubstateDatabase::standard();
+    ProtocolBuilder::for_network(network)
+        .from_bootstrap_to_latest()
+        .commit_each_protocol_update(&mut db);
+    db
+}
+
+fn describe_differences(
+    expected: &RecordedOutcome,
+    actual: &RecordedOutcome,
+    address_bech32_encoder: &AddressBech32Encoder,
+) -> Vec<String> {
+    let mut differences = vec![];
+    let outcome = |success: bool| if success { "success" } else { "failure" };
+    if expected.success != actual.success {
+        differences.push(format!(
+            "outcome: expected {}, got {}",
+            outcome(expected.success),
+            outcome(actual.success)
+        ));
+    }
+    if expected.new_entities != actual.new_entities {
+        let display = |entities: &Vec<GlobalAddress>| {
+            entities
+                .iter()
+                .map(|address| address.display(address_bech32_encoder).to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        differences.push(format!(
+            "new entities: expected [{}], got [{}]",
+            display(&expected.new_entities),
+            display(&actual.new_entities)
+        ));
+    }
+    if expected.event_count != actual.event_count {
+        differences.push(format!(
+            "events: expected {}, got {}",
+            expected.event_count, actual.event_count
+        ));
+    }
+    if expected.total_cost != actual.total_cost {
+        differences.push(format!(
+            "total cost: expected {} XRD, got {} XRD",
+            expected.total_cost, actual.total_cost
+        ));
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_outcomes_have_no_differences() {
+        let outcome = RecordedOutcome {
+            success: true,
+            new_entities: vec![XRD.into()],
+            event_count: 3,
+            total_cost: dec!("0.5"),
+        };
+        let encoder = AddressBech32Encoder::for_simulator();
+
+        assert!(describe_differences(&outcome, &outcome.clone(), &encoder).is_empty());
+        assert_eq!(
+            describe_differences(
+                &outcome,
+                &RecordedOutcome {
+                    success: false,
+                    new_entities: vec![],
+                    ..outcome.clone()
+                },
+                &encoder
+            )
+            .len(),
+            2
+        );
+    }
+}
@@ -24,10 +24,27 @@ pub struct Run {
     #[clap(short, long)]
     pub signing_keys: Option<String>,
 
+    /// A private key used for signing, can be repeated
+    #[clap(long)]
+    pub signer: Vec<String>,
+
+    /// The path to a subintent manifest to attach as a child of the transaction, can be repeated.
+    /// Its hash is available as `${<file stem>}` in the transaction manifest.
+    #[clap(long)]
+    pub subintent: Vec<PathBuf>,
+
+    /// The private keys used for signing the subintent at the same position, separated by comma
+    #[clap(long)]
+    pub subintent_signer: Vec<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
 
+    /// Preview the transaction without committing it
+    #[clap(long)]
+    pub preview: bool,
+
     /// The manifest type [V1 | SystemV1 | V2 | SubintentV2], defaults to V2
     #[clap(short, long)]
     kind: Option<String>,
@@ -35,20 +52,43 @@ pub struct Run {
 
 impl Run {
     pub fn pre_process_manifest(manifest: &str) -> String {
+        Self::pre_process_manifest_with_variables(manifest, &IndexMap::default())
+    }
+
+    /// Replaces `${@ALIAS}` with the address of the alias, and `${NAME}` with the given variable,
+    /// or else with the environment variable.
+    pub fn pre_process_manifest_with_variables(
+        manifest: &str,
+        variables: &IndexMap<String, String>,
+    ) -> String {
         let re = Regex::new(r"\$\{(.+?)\}").unwrap();
         re.replace_all(manifest, |caps: &Captures| {
-            env::var(caps[1].trim()).unwrap_or_default()
+            let name = caps[1].trim();
+            if name.starts_with('@') {
+                return resolve_alias(name);
+            }
+            variables
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| env::var(name).unwrap_or_default())
         })
         .into()
     }
 
+    fn network_definition(&self) -> Result<NetworkDefinition, Error> {
+        match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError),
+            None => Ok(NetworkDefinition::simulator()),
+        }
+    }
+
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
         let manifest = std::fs::read_to_string(&self.path).map_err(Error::IOError)?;
+        if !self.subintent.is_empty() || !self.subintent_signer.is_empty() {
+            return self.run_with_subintents(&manifest, out);
+        }
         let pre_processed_manifest = Self::pre_process_manifest(&manifest);
-        let network = match &self.network {
-            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
-            None => NetworkDefinition::simulator(),
-        };
+        let network = self.network_definition()?;
         let mut blobs = Vec::new();
         if let Some(paths) = &self.blobs {
             for path in paths {
@@ -68,9 +108,17 @@ impl Run {
         validate_call_arguments_to_native_components(&manifest)
             .map_err(Error::InstructionSchemaValidationError)?;
 
+        if self.preview {
+            return handle_preview(
+                manifest,
+                &merge_signing_keys(&self.signing_keys, &self.signer),
+                self.trace,
+                out,
+            );
+        }
         handle_manifest(
             manifest,
-            &self.signing_keys,
+            &merge_signing_keys(&self.signing_keys, &self.signer),
             &self.network,
             &None,
             self.trace,
@@ -79,4 +127,41 @@ impl Run {
         )
         .map(|_| ())
     }
+
+    /// Builds a notarized V2 transaction with the subintents as children of the transaction intent.
+    fn run_with_subintents<O: std::io::Write>(
+        &self,
+        manifest: &str,
+        out: &mut O,
+    ) -> Result<(), String> {
+        if !matches!(
+            ManifestKind::parse_or_latest(self.kind.as_deref())?,
+            ManifestKind::V2
+        ) {
+            return Err(Error::SubintentsRequireV2Manifest.into());
+        }
+        let network = self.network_definition()?;
+        let subintents = Subintents::build(&self.subintent, &self.subintent_signer, &network)?;
+
+        let mut blobs = Vec::new();
+        if let Some(paths) = &self.blobs {
+            for path in paths {
+                blobs.push(std::fs::read(path).map_err(Error::IOError)?);
+            }
+        }
+        let manifest = compile_manifest_with_pretty_error::<TransactionManifestV2>(
+            &Self::pre_process_manifest_with_variables(manifest, &subintents.variables),
+            &network,
+            BlobProvider::new_with_blobs(blobs),
+            CompileErrorDiagnosticsStyle::TextTerminalColors,
+        )?;
+
+        subintents.run_transaction(
+            manifest,
+            &merge_signing_keys(&self.signing_keys, &self.signer),
+            self.trace,
+            self.preview,
+            out,
+        )
+    }
 }
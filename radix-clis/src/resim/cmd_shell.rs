@@ -0,0 +1,152 @@
+use clap::{CommandFactory, Parser};
+use colored::*;
+use radix_engine::utils::*;
+use radix_transactions::manifest::*;
+use rustyline::error::ReadlineError;
+use rustyline::Editor;
+
+use crate::resim::*;
+
+const SHELL_HISTORY_FILE_UNDER_DATA_DIR: &str = "shell_history.txt";
+
+const SHELL_HELP: &str =
+    "Any resim command can be run without the `resim` prefix, e.g. `show-ledger`.
+Lines ending with `;` are added to the manifest being built, which can be managed with:
+  manifest    Print the manifest being built
+  commit      Sign with the default account and run the manifest, remember to lock a fee
+  preview     Preview the manifest without committing it
+  discard     Discard the manifest
+  help        Print this help
+  exit        Leave the shell";
+
+/// Start an interactive shell which keeps the ledger open between commands
+#[derive(Parser, Debug)]
+pub struct Shell {}
+
+impl Shell {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        keep_database_open(true);
+        let result = self.run_shell(out);
+        keep_database_open(false);
+        result
+    }
+
+    fn run_shell<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let commands = ResimCli::command()
+            .get_subcommands()
+            .map(|command| command.get_name().to_owned())
+            .chain(
+                ["manifest", "commit", "preview", "discard", "help", "exit"]
+                    .iter()
+                    .map(|command| command.to_string()),
+            )
+            .collect();
+        let helper = ShellHelper::new(commands);
+
+        let mut editor = Editor::new().map_err(|err| Error::ShellError(format!("{:?}", err)))?;
+        editor.set_helper(Some(helper));
+        let history_path = get_data_dir()?.join(SHELL_HISTORY_FILE_UNDER_DATA_DIR);
+        // There is no history the first time the shell is started
+        let _ = editor.load_history(&history_path);
+
+        writeln!(
+            out,
+            "Welcome to the resim shell! Type `help` for help, or `exit` to leave."
+        )
+        .map_err(Error::IOError)?;
+
+        let mut instructions: Vec<String> = vec![];
+        loop {
+            let line = match editor.readline("resim> ") {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(err) => return Err(Error::ShellError(format!("{:?}", err)).into()),
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let _ = editor.add_history_entry(line);
+
+            let result = if line.ends_with(';') {
+                instructions.push(line.to_owned());
+                let result = Self::compile(&instructions).map(|_| ());
+                if result.is_err() {
+                    instructions.pop();
+                }
+                result
+            } else {
+                let Some(args) = shlex::split(line) else {
+                    writeln!(out, "{}", "Error: unbalanced quotes".red())
+                        .map_err(Error::IOError)?;
+                    continue;
+                };
+                match args[0].as_str() {
+                    "exit" | "quit" => break,
+                    "help" => {
+                        writeln!(out, "{}", SHELL_HELP).map_err(|err| Error::IOError(err).into())
+                    }
+                    "manifest" => writeln!(out, "{}", instructions.join("\n"))
+                        .map_err(|err| Error::IOError(err).into()),
+                    "discard" => {
+                        instructions.clear();
+                        Ok(())
+                    }
+                    "commit" => Self::compile(&instructions).and_then(|manifest| {
+                        handle_manifest(manifest, &None, &None, &None, false, true, out)?;
+                        instructions.clear();
+                        Ok(())
+                    }),
+                    "preview" => Self::compile(&instructions)
+                        .and_then(|manifest| handle_preview(manifest, &None, false, out)),
+                    _ => Self::run_command(args, out),
+                }
+            };
+            if let Err(err) = result {
+                writeln!(out, "{}", format!("Error: {}", err).red()).map_err(Error::IOError)?;
+            }
+
+            let helper = editor.helper_mut().expect("The helper was set");
+            helper.invalidate_addresses();
+            helper.refresh_bucket_names(&instructions.join("\n"));
+        }
+
+        let _ = editor.save_history(&history_path);
+        Ok(())
+    }
+
+    fn run_command<O: std::io::Write>(args: Vec<String>, out: &mut O) -> Result<(), String> {
+        let matches = match ResimCli::command()
+            .try_get_matches_from(std::iter::once("resim".to_owned()).chain(args))
+        {
+            Ok(matches) => matches,
+            Err(err) => {
+                // Help and version requests are reported as errors by clap
+                return write!(out, "{}", err).map_err(|err| Error::IOError(err).into());
+            }
+        };
+        let cli = ResimCli::from_arg_matches(&matches).map_err(|err| err.to_string())?;
+        if let Command::Shell(_) = cli.command {
+            return Err(Error::ShellError("Already in the shell".to_owned()).into());
+        }
+        let result = run_cli(cli, &matches, out);
+        // The JSON output turns off colors for the whole process
+        colored::control::unset_override();
+        result
+    }
+
+    fn compile(instructions: &[String]) -> Result<AnyManifest, String> {
+        let network = NetworkDefinition::simulator();
+        let manifest = compile_any_manifest_with_pretty_error(
+            &Run::pre_process_manifest(&instructions.join("\n")),
+            ManifestKind::parse_or_latest(None)?,
+            &network,
+            BlobProvider::new(),
+            CompileErrorDiagnosticsStyle::TextTerminalColors,
+        )?;
+        validate_call_arguments_to_native_components(&manifest)
+            .map_err(Error::InstructionSchemaValidationError)?;
+        Ok(manifest)
+    }
+}
@@ -10,40 +10,40 @@ pub struct ShowConfigs {}
 impl ShowConfigs {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
         let configs = get_configs()?;
+        let encoder = AddressBech32Encoder::for_simulator();
+        let default_account = configs
+            .default_account
+            .map(|component| component.display(&encoder).to_string());
+        let default_owner_badge = configs
+            .default_owner_badge
+            .map(|owner_badge| owner_badge.display(&encoder).to_string());
+
+        record_json_value("default_account", default_account.clone());
+        record_json_value("default_private_key", configs.default_private_key.clone());
+        record_json_value("default_owner_badge", default_owner_badge.clone());
+        record_json_value("nonce", configs.nonce);
+
         writeln!(
             out,
             "{}: {}",
             "Account Address".green().bold(),
-            match configs.default_account {
-                Some(component) => format!(
-                    "{}",
-                    component.display(&AddressBech32Encoder::for_simulator()),
-                ),
-                None => "None".to_owned(),
-            }
+            default_account.unwrap_or_else(|| "None".to_owned())
         )
         .map_err(Error::IOError)?;
         writeln!(
             out,
             "{}: {}",
             "Account Private Key".green().bold(),
-            match configs.default_private_key {
-                Some(private_key) => private_key,
-                None => "None".to_owned(),
-            }
+            configs
+                .default_private_key
+                .unwrap_or_else(|| "None".to_owned())
         )
         .map_err(Error::IOError)?;
         writeln!(
             out,
             "{}: {}",
             "Account Owner Badge".green().bold(),
-            match configs.default_owner_badge {
-                Some(owner_badge) => format!(
-                    "{}",
-                    owner_badge.display(&AddressBech32Encoder::for_simulator())
-                ),
-                None => "None".to_owned(),
-            }
+            default_owner_badge.unwrap_or_else(|| "None".to_owned())
         )
         .map_err(Error::IOError)?;
         writeln!(
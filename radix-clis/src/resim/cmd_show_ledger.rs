@@ -4,7 +4,6 @@ use colored::*;
 use radix_common::time::Instant;
 use radix_common::time::UtcDateTime;
 use radix_engine_interface::blueprints::consensus_manager::*;
-use radix_substate_store_impls::rocks_db::RocksdbSubstateStore;
 use radix_substate_store_interface::interface::*;
 
 use crate::resim::*;
@@ -21,6 +20,7 @@ impl ShowLedger {
         }
 
         let current_epoch = Self::get_current_epoch(out)?;
+        record_json_value("current_epoch", current_epoch.number());
         writeln!(
             out,
             "{}: {}",
@@ -31,6 +31,7 @@ impl ShowLedger {
 
         let instant = Self::get_current_time(out, TimePrecisionV1::Minute)?;
         let date_time = UtcDateTime::from_instant(&instant).unwrap();
+        record_json_value("current_time", date_time.to_string());
         writeln!(out, "{}: {}", "Current Time".green().bold(), date_time)
             .map_err(Error::IOError)?;
 
@@ -39,27 +40,29 @@ impl ShowLedger {
 
     pub fn list_entries<O: std::io::Write>(
         out: &mut O,
-        substate_db: &RocksdbSubstateStore,
+        substate_db: &SimulatorDatabase,
     ) -> Result<(), Error> {
         let address_bech32_encoder = AddressBech32Encoder::new(&NetworkDefinition::simulator());
-        let mut packages: Vec<PackageAddress> = vec![];
-        let mut components: Vec<ComponentAddress> = vec![];
-        let mut resources: Vec<ResourceAddress> = vec![];
-
-        for (node_id, _) in substate_db.read_partition_keys() {
-            if let Ok(address) = PackageAddress::try_from(node_id.as_ref()) {
-                if !packages.contains(&address) {
-                    packages.push(address);
-                }
-            } else if let Ok(address) = ComponentAddress::try_from(node_id.as_ref()) {
-                if !components.contains(&address) {
-                    components.push(address);
-                }
-            } else if let Ok(address) = ResourceAddress::try_from(node_id.as_ref()) {
-                if !resources.contains(&address) {
-                    resources.push(address);
-                }
-            }
+        let (packages, components, resources) = Self::list_entities(substate_db);
+        if is_json_output() {
+            let addresses = |addresses: Vec<GlobalAddress>| {
+                addresses
+                    .iter()
+                    .map(|address| address.display(&address_bech32_encoder).to_string())
+                    .collect::<Vec<_>>()
+            };
+            record_json_value(
+                "packages",
+                addresses(packages.iter().map(|a| (*a).into()).collect()),
+            );
+            record_json_value(
+                "components",
+                addresses(components.iter().map(|a| (*a).into()).collect()),
+            );
+            record_json_value(
+                "resources",
+                addresses(resources.iter().map(|a| (*a).into()).collect()),
+            );
         }
         writeln!(out, "{}:", "Packages".green().bold()).map_err(Error::IOError)?;
         for (last, address) in packages.iter().identify_last() {
@@ -95,6 +98,36 @@ impl ShowLedger {
         Ok(())
     }
 
+    /// Lists the packages, components and resources in the ledger, in the order they are stored.
+    pub fn list_entities(
+        substate_db: &SimulatorDatabase,
+    ) -> (
+        Vec<PackageAddress>,
+        Vec<ComponentAddress>,
+        Vec<ResourceAddress>,
+    ) {
+        let mut packages: Vec<PackageAddress> = vec![];
+        let mut components: Vec<ComponentAddress> = vec![];
+        let mut resources: Vec<ResourceAddress> = vec![];
+
+        for (node_id, _) in substate_db.read_partition_keys() {
+            if let Ok(address) = PackageAddress::try_from(node_id.as_ref()) {
+                if !packages.contains(&address) {
+                    packages.push(address);
+                }
+            } else if let Ok(address) = ComponentAddress::try_from(node_id.as_ref()) {
+                if !components.contains(&address) {
+                    components.push(address);
+                }
+            } else if let Ok(address) = ResourceAddress::try_from(node_id.as_ref()) {
+                if !resources.contains(&address) {
+                    resources.push(address);
+                }
+            }
+        }
+        (packages, components, resources)
+    }
+
     pub fn get_current_epoch<O: std::io::Write>(out: &mut O) -> Result<Epoch, Error> {
         let manifest = ManifestBuilder::new_system_v1()
             .call_method(
@@ -0,0 +1,48 @@
+use clap::Parser;
+use colored::*;
+
+use crate::resim::*;
+
+/// Show the receipt of a previously committed transaction
+#[derive(Parser, Debug)]
+pub struct ShowTx {
+    /// The id of the transaction, either a Bech32m encoded intent hash or a hash in hex
+    pub id: String,
+}
+
+impl ShowTx {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let hash = parse_transaction_hash(&self.id)?;
+        let receipt = get_stored_receipt(&hash)?;
+        let encoder = AddressBech32Encoder::for_simulator();
+        let addresses = |addresses: Vec<GlobalAddress>| {
+            addresses
+                .iter()
+                .map(|address| address.display(&encoder).to_string())
+                .collect::<Vec<_>>()
+        };
+        let summary = &receipt.state_update_summary;
+        record_json_value("id", transaction_id_to_json(&receipt.id));
+        record_json_value("nonce", receipt.nonce);
+        record_json_value("total_cost", receipt.fee_summary.total_cost().to_string());
+        record_json_value(
+            "new_entities",
+            serde_json::json!({
+                "packages": addresses(summary.new_packages.iter().map(|a| (*a).into()).collect()),
+                "components": addresses(summary.new_components.iter().map(|a| (*a).into()).collect()),
+                "resources": addresses(summary.new_resources.iter().map(|a| (*a).into()).collect()),
+            }),
+        );
+
+        writeln!(
+            out,
+            "{}: {}",
+            receipt.id.label().green().bold(),
+            receipt.id.to_display_string()
+        )
+        .map_err(Error::IOError)?;
+        writeln!(out, "{}: {}", "Nonce".green().bold(), receipt.nonce).map_err(Error::IOError)?;
+        writeln!(out, "{}", receipt.rendered).map_err(Error::IOError)?;
+        Ok(())
+    }
+}
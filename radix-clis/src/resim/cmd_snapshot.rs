@@ -0,0 +1,355 @@
+use clap::{Parser, Subcommand};
+use colored::*;
+use radix_substate_store_interface::db_key_mapper::*;
+use radix_substate_store_interface::interface::*;
+
+use crate::resim::*;
+
+/// Save, restore, list and compare snapshots of the simulator ledger
+#[derive(Parser, Debug)]
+pub struct Snapshot {
+    #[clap(subcommand)]
+    pub command: SnapshotCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotCommand {
+    /// Save the current ledger state and configs under the given name
+    Save(SnapshotSave),
+    /// Replace the current ledger state and configs with a saved snapshot
+    Restore(SnapshotRestore),
+    /// List the saved snapshots
+    List(SnapshotList),
+    /// Show the entities whose substates differ between two snapshots
+    Diff(SnapshotDiff),
+}
+
+#[derive(Parser, Debug)]
+pub struct SnapshotSave {
+    /// The name of the snapshot
+    pub name: String,
+
+    /// Overwrite the snapshot if it already exists
+    #[clap(short, long)]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct SnapshotRestore {
+    /// The name of the snapshot
+    pub name: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct SnapshotList {}
+
+#[derive(Parser, Debug)]
+pub struct SnapshotDiff {
+    /// The name of the base snapshot
+    pub from: String,
+
+    /// The name of the snapshot to compare against the base
+    pub to: String,
+}
+
+/// The number of substates of an entity which differ between two snapshots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubstateChanges {
+    pub added: usize,
+    pub removed: usize,
+    pub updated: usize,
+}
+
+impl Snapshot {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        match &self.command {
+            SnapshotCommand::Save(cmd) => cmd.run(out),
+            SnapshotCommand::Restore(cmd) => cmd.run(out),
+            SnapshotCommand::List(cmd) => cmd.run(out),
+            SnapshotCommand::Diff(cmd) => cmd.run(out),
+        }
+    }
+}
+
+impl SnapshotSave {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let snapshot_dir = get_snapshot_dir(&self.name)?;
+        if snapshot_dir.exists() {
+            if !self.force {
+                return Err(Error::SnapshotAlreadyExists(self.name.clone()).into());
+            }
+            fs::remove_dir_all(&snapshot_dir)
+                .map_err(|err| Error::IOErrorAtPath(err, snapshot_dir.clone()))?;
+        }
+
+        // Make sure the ledger is bootstrapped, and closed before its files are copied
+        drop(SimulatorEnvironment::new()?);
+        close_database();
+
+        let data_dir = get_data_dir()?;
+        fs::create_dir_all(&snapshot_dir)
+            .map_err(|err| Error::IOErrorAtPath(err, snapshot_dir.clone()))?;
+        for entry in fs::read_dir(&data_dir).map_err(Error::IOError)? {
+            let entry = entry.map_err(Error::IOError)?;
+            if entry.file_name() == SNAPSHOTS_DIR_UNDER_DATA_DIR
+                || entry.file_name() == RECORDING_MARKER_FILE_UNDER_DATA_DIR
+            {
+                continue;
+            }
+            let target = snapshot_dir.join(entry.file_name());
+            if entry.path().is_dir() {
+                copy_dir_all(entry.path(), &target)
+            } else {
+                fs::copy(entry.path(), &target).map(|_| ())
+            }
+            .map_err(|err| Error::IOErrorAtPath(err, entry.path()))?;
+        }
+
+        writeln!(out, "Snapshot {} saved.", self.name.green()).map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+impl SnapshotRestore {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        // A recording can't capture the restored ledger state, so it could no longer be replayed
+        ensure_not_recording("snapshot restore")?;
+        let snapshot_dir = get_existing_snapshot_dir(&self.name)?;
+        let data_dir = get_data_dir()?;
+
+        clear_ledger_files(&data_dir)?;
+        copy_dir_all(&snapshot_dir, &data_dir)
+            .map_err(|err| Error::IOErrorAtPath(err, snapshot_dir))?;
+
+        writeln!(out, "Snapshot {} restored.", self.name.green()).map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+impl SnapshotList {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let snapshots_dir = get_snapshots_dir()?;
+        let mut snapshots = vec![];
+        if snapshots_dir.exists() {
+            for entry in fs::read_dir(&snapshots_dir).map_err(Error::IOError)? {
+                let entry = entry.map_err(Error::IOError)?;
+                if entry.path().is_dir() {
+                    let configs = read_configs(&entry.path().join("config.sbor"))?;
+                    snapshots.push((entry.file_name().to_string_lossy().to_string(), configs));
+                }
+            }
+        }
+        snapshots.sort_by(|(a, _), (b, _)| a.cmp(b));
+        record_json_value(
+            "snapshots",
+            snapshots
+                .iter()
+                .map(|(name, configs)| serde_json::json!({ "name": name, "nonce": configs.nonce }))
+                .collect::<Vec<_>>(),
+        );
+
+        writeln!(out, "{}:", "Snapshots".green().bold()).map_err(Error::IOError)?;
+        for (last, (name, configs)) in snapshots.iter().identify_last() {
+            writeln!(
+                out,
+                "{} {} (next nonce: {})",
+                list_item_prefix(last),
+                name,
+                configs.nonce
+            )
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
+
+impl SnapshotDiff {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let from_dir = get_existing_snapshot_dir(&self.from)?;
+        let to_dir = get_existing_snapshot_dir(&self.to)?;
+
+        let changes = {
+            let from_db = open_snapshot_database(&self.from, &from_dir)?;
+            let to_db = open_snapshot_database(&self.to, &to_dir)?;
+            match (from_db.fork_changes(), to_db.fork_changes()) {
+                // Forks of the same database can only differ where they were changed, which
+                // saves listing the whole source database
+                (Some((from_source, from_keys)), Some((to_source, to_keys)))
+                    if from_source == to_source =>
+                {
+                    diff_partitions(
+                        &from_db,
+                        &to_db,
+                        from_keys.into_iter().chain(to_keys).collect(),
+                    )
+                }
+                _ => diff_substate_databases(&from_db, &to_db),
+            }
+        };
+        let from_configs = read_configs(&from_dir.join("config.sbor"))?;
+        let to_configs = read_configs(&to_dir.join("config.sbor"))?;
+
+        let address_bech32_encoder = AddressBech32Encoder::for_simulator();
+        writeln!(out, "{}:", "Changed Entities".green().bold()).map_err(Error::IOError)?;
+        for (last, (node_id, changes)) in changes.iter().identify_last() {
+            writeln!(
+                out,
+                "{} {}: {} added, {} removed, {} updated",
+                list_item_prefix(last),
+                node_id.display(&address_bech32_encoder),
+                changes.added,
+                changes.removed,
+                changes.updated,
+            )
+            .map_err(Error::IOError)?;
+        }
+
+        writeln!(out, "{}:", "Configs".green().bold()).map_err(Error::IOError)?;
+        let default_account = |configs: &Configs| match configs.default_account {
+            Some(account) => account.display(&address_bech32_encoder).to_string(),
+            None => "None".to_owned(),
+        };
+        let config_rows = [
+            (
+                "Account Address",
+                default_account(&from_configs),
+                default_account(&to_configs),
+            ),
+            (
+                "Next Transaction Nonce",
+                from_configs.nonce.to_string(),
+                to_configs.nonce.to_string(),
+            ),
+        ];
+        for (last, (label, from, to)) in config_rows.iter().identify_last() {
+            if from == to {
+                writeln!(out, "{} {}: {}", list_item_prefix(last), label, from)
+            } else {
+                writeln!(
+                    out,
+                    "{} {}: {} -> {}",
+                    list_item_prefix(last),
+                    label,
+                    from.red(),
+                    to.green()
+                )
+            }
+            .map_err(Error::IOError)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two substate databases, partition by partition, and returns the number of
+/// differing substates of each entity which is not identical in both.
+pub fn diff_substate_databases(
+    from: &(impl SubstateDatabase + ListableSubstateDatabase),
+    to: &(impl SubstateDatabase + ListableSubstateDatabase),
+) -> IndexMap<NodeId, SubstateChanges> {
+    let partition_keys = from
+        .list_partition_keys()
+        .chain(to.list_partition_keys())
+        .collect::<BTreeSet<_>>();
+    diff_partitions(from, to, partition_keys)
+}
+
+/// Compares the given partitions of two substate databases, see [`diff_substate_databases`].
+pub fn diff_partitions(
+    from: &impl SubstateDatabase,
+    to: &impl SubstateDatabase,
+    partition_keys: BTreeSet<DbPartitionKey>,
+) -> IndexMap<NodeId, SubstateChanges> {
+    let mut changes = index_map_new::<NodeId, SubstateChanges>();
+    for partition_key in partition_keys {
+        let from_entries = from
+            .list_raw_values_from_db_key(&partition_key, None)
+            .collect::<BTreeMap<_, _>>();
+        let to_entries = to
+            .list_raw_values_from_db_key(&partition_key, None)
+            .collect::<BTreeMap<_, _>>();
+        if from_entries == to_entries {
+            continue;
+        }
+
+        let (node_id, _) = SpreadPrefixKeyMapper::from_db_partition_key(&partition_key);
+        let entity_changes = changes.entry(node_id).or_default();
+        for (sort_key, from_value) in &from_entries {
+            match to_entries.get(sort_key) {
+                None => entity_changes.removed += 1,
+                Some(to_value) if to_value != from_value => entity_changes.updated += 1,
+                Some(_) => {}
+            }
+        }
+        entity_changes.added += to_entries
+            .keys()
+            .filter(|sort_key| !from_entries.contains_key(*sort_key))
+            .count();
+    }
+    changes
+}
+
+fn get_snapshot_dir(name: &str) -> Result<PathBuf, Error> {
+    let is_valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if !is_valid {
+        return Err(Error::InvalidSnapshotName(name.to_owned()));
+    }
+    Ok(get_snapshots_dir()?.join(name))
+}
+
+fn get_existing_snapshot_dir(name: &str) -> Result<PathBuf, Error> {
+    let snapshot_dir = get_snapshot_dir(name)?;
+    if !snapshot_dir.is_dir() {
+        return Err(Error::SnapshotNotFound(name.to_owned()));
+    }
+    Ok(snapshot_dir)
+}
+
+fn open_snapshot_database(name: &str, snapshot_dir: &Path) -> Result<SimulatorStore, Error> {
+    open_ledger_database(snapshot_dir, true).map_err(|err| match err {
+        Error::LedgerDatabaseError(_, err) => Error::SnapshotDatabaseError(name.to_owned(), err),
+        err => err,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
+
+    #[test]
+    fn diff_substate_databases_counts_changes_per_entity() {
+        let node_id = XRD.into_node_id();
+        let mut from = InMemorySubstateDatabase::standard();
+        let mut to = InMemorySubstateDatabase::standard();
+        from.update_substate_raw(node_id, PartitionNumber(0), SubstateKey::Field(0), vec![1]);
+        from.update_substate_raw(node_id, PartitionNumber(0), SubstateKey::Field(1), vec![1]);
+        from.update_substate_raw(node_id, PartitionNumber(1), SubstateKey::Field(0), vec![1]);
+        to.update_substate_raw(node_id, PartitionNumber(0), SubstateKey::Field(0), vec![2]);
+        to.update_substate_raw(node_id, PartitionNumber(0), SubstateKey::Field(2), vec![1]);
+        to.update_substate_raw(node_id, PartitionNumber(1), SubstateKey::Field(0), vec![1]);
+
+        let changes = diff_substate_databases(&from, &to);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            changes.get(&node_id),
+            Some(&SubstateChanges {
+                added: 1,
+                removed: 1,
+                updated: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn snapshot_names_cannot_escape_the_snapshots_directory() {
+        assert!(get_snapshot_dir("before-upgrade_2.0").is_ok());
+        assert!(get_snapshot_dir("").is_err());
+        assert!(get_snapshot_dir("..").is_err());
+        assert!(get_snapshot_dir("a/b").is_err());
+    }
+}
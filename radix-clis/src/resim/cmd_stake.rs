@@ -0,0 +1,71 @@
+use clap::Parser;
+use radix_common::prelude::*;
+
+use crate::resim::*;
+
+/// Stake XRD from the default account to a validator
+#[derive(Parser, Debug)]
+pub struct Stake {
+    /// The validator address
+    pub validator_address: SimulatorComponentAddress,
+
+    /// The amount of XRD to stake
+    pub amount: Decimal,
+
+    /// Stake as the validator owner, which is allowed even if the validator does not accept delegated stake
+    #[clap(long)]
+    pub as_owner: bool,
+
+    /// The network to use when outputting manifest, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    pub manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    pub signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    pub trace: bool,
+}
+
+impl Stake {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let default_account = get_default_account()?;
+        let validator_address = self.validator_address.0;
+
+        let mut builder = ManifestBuilder::new().lock_fee_from_faucet();
+        if self.as_owner {
+            builder = builder.create_proof_from_account_of_non_fungibles(
+                default_account,
+                VALIDATOR_OWNER_BADGE,
+                [validator_owner_badge_id(validator_address)],
+            );
+        }
+        builder = builder
+            .withdraw_from_account(default_account, XRD, self.amount)
+            .take_all_from_worktop(XRD, "stake");
+        builder = if self.as_owner {
+            builder.stake_validator_as_owner(validator_address, "stake")
+        } else {
+            builder.stake_validator(validator_address, "stake")
+        };
+        let manifest = builder
+            .try_deposit_entire_worktop_or_refund(default_account, None)
+            .build();
+        handle_manifest(
+            manifest.into(),
+            &self.signing_keys,
+            &self.network,
+            &self.manifest,
+            self.trace,
+            true,
+            out,
+        )
+        .map(|_| ())
+    }
+}
@@ -32,6 +32,10 @@ pub struct Transfer {
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
+
+    /// Preview the transaction without committing it
+    #[clap(long)]
+    pub preview: bool,
 }
 
 impl Transfer {
@@ -47,14 +51,16 @@ impl Transfer {
                 builder,
                 &address_bech32_decoder,
                 default_account,
-                resource_specifier,
+                resolve_argument(&resource_specifier),
             )
             .map_err(Error::FailedToBuildArguments)?
         }
 
-        let resource_specifier =
-            parse_resource_specifier(&self.resource_specifier, &address_bech32_decoder)
-                .map_err(|_| Error::InvalidResourceSpecifier(self.resource_specifier.clone()))?;
+        let resource_specifier = parse_resource_specifier(
+            &resolve_argument(&self.resource_specifier),
+            &address_bech32_decoder,
+        )
+        .map_err(|_| Error::InvalidResourceSpecifier(self.resource_specifier.clone()))?;
 
         builder = match resource_specifier {
             crate::utils::ResourceSpecifier::Amount(amount, resource_address) => {
@@ -67,6 +73,9 @@ impl Transfer {
         let manifest = builder
             .try_deposit_entire_worktop_or_refund(self.recipient.0, None)
             .build();
+        if self.preview {
+            return handle_preview(manifest.into(), &self.signing_keys, self.trace, out);
+        }
         handle_manifest(
             manifest.into(),
             &self.signing_keys,
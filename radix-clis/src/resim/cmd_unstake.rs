@@ -0,0 +1,56 @@
+use clap::Parser;
+use radix_common::prelude::*;
+
+use crate::resim::*;
+
+/// Unstake from a validator, exchanging stake units for a claim NFT
+#[derive(Parser, Debug)]
+pub struct Unstake {
+    /// The validator address
+    pub validator_address: SimulatorComponentAddress,
+
+    /// The amount of stake units to redeem
+    pub amount: Decimal,
+
+    /// The network to use when outputting manifest, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    pub manifest: Option<PathBuf>,
+
+    /// The private keys used for signing, separated by comma
+    #[clap(short, long)]
+    pub signing_keys: Option<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    pub trace: bool,
+}
+
+impl Unstake {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let default_account = get_default_account()?;
+        let validator_address = self.validator_address.0;
+        let validator = get_validator_state(validator_address)?;
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(default_account, validator.stake_unit_resource, self.amount)
+            .take_all_from_worktop(validator.stake_unit_resource, "stake_units")
+            .unstake_validator(validator_address, "stake_units")
+            .try_deposit_entire_worktop_or_refund(default_account, None)
+            .build();
+        handle_manifest(
+            manifest.into(),
+            &self.signing_keys,
+            &self.network,
+            &self.manifest,
+            self.trace,
+            true,
+            out,
+        )
+        .map(|_| ())
+    }
+}
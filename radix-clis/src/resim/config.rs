@@ -2,14 +2,240 @@ use crate::resim::*;
 use radix_common::prelude::*;
 use radix_engine::updates::*;
 use radix_engine::vm::*;
+use radix_substate_store_impls::rocks_db_with_merkle_tree::RocksDBWithMerkleTreeSubstateStore;
+use radix_substate_store_impls::substate_database_overlay::*;
+use radix_substate_store_interface::interface::*;
+use std::cell::{Cell, RefCell};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    /// The ledger database kept open between commands, see [`keep_database_open`].
+    static OPEN_DATABASE: RefCell<Option<SimulatorStore>> = const { RefCell::new(None) };
+    static KEEP_DATABASE_OPEN: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Keeps the ledger database open once a command has opened it, so that the following commands
+/// reuse it instead of reopening it. This is used by long-lived processes such as `resim shell`.
+pub fn keep_database_open(keep: bool) {
+    KEEP_DATABASE_OPEN.with(|keep_open| keep_open.set(keep));
+    if !keep {
+        close_database();
+    }
+}
+
+/// Closes the ledger database if it is being kept open, e.g. before its files are touched.
+pub fn close_database() {
+    OPEN_DATABASE.with(|database| database.borrow_mut().take());
+}
+
+/// The ledger database in a data directory, see [`open_ledger_database`].
+pub enum SimulatorStore {
+    /// A ledger of its own, stored in the data directory.
+    Local(RocksdbSubstateStore),
+    /// A ledger forked from a replay database, see [`Fork`]. The source database is only read
+    /// from, and the changes made on top of it are appended to the data directory on every commit.
+    Forked {
+        source: String,
+        overlay: OwnedSubstateDatabaseOverlay<RocksDBWithMerkleTreeSubstateStore>,
+        overlay_path: PathBuf,
+    },
+}
+
+/// Opens the ledger database stored in a data directory, such as the one of a snapshot.
+///
+/// A local ledger is opened read-only if requested, while a forked ledger never writes to its
+/// source database.
+pub fn open_ledger_database(data_dir: &Path, read_only: bool) -> Result<SimulatorStore, Error> {
+    let Some(fork_configs) = read_fork_configs(&data_dir.join(FORK_CONFIGS_FILE_UNDER_DATA_DIR))?
+    else {
+        return Ok(SimulatorStore::Local(if read_only {
+            RocksdbSubstateStore::read_only(data_dir.to_path_buf())
+                .map_err(|err| Error::LedgerDatabaseError(data_dir.to_path_buf(), err))?
+        } else {
+            RocksdbSubstateStore::standard(data_dir.to_path_buf())
+        }));
+    };
+
+    let source = RocksDBWithMerkleTreeSubstateStore::read_only(fork_configs.source.clone().into())
+        .map_err(|err| Error::LedgerDatabaseError(fork_configs.source.clone().into(), err))?;
+    let mut overlay = SubstateDatabaseOverlay::new_owned(source);
+    let overlay_path = data_dir.join(FORK_OVERLAY_FILE_UNDER_DATA_DIR);
+    for database_updates in read_sbor_records::<DatabaseUpdates>(&overlay_path)? {
+        overlay.commit(&database_updates);
+    }
+    Ok(SimulatorStore::Forked {
+        source: fork_configs.source,
+        overlay,
+        overlay_path,
+    })
+}
+
+impl SimulatorStore {
+    /// Returns the source of a forked ledger, and the partitions changed on top of it.
+    pub fn fork_changes(&self) -> Option<(&str, Vec<DbPartitionKey>)> {
+        match self {
+            SimulatorStore::Local(_) => None,
+            SimulatorStore::Forked {
+                source, overlay, ..
+            } => {
+                let partition_keys = overlay
+                    .database_updates()
+                    .node_updates
+                    .iter()
+                    .flat_map(|(node_key, node_updates)| {
+                        node_updates
+                            .partition_updates
+                            .keys()
+                            .map(|partition_num| DbPartitionKey {
+                                node_key: node_key.clone(),
+                                partition_num: *partition_num,
+                            })
+                    })
+                    .collect();
+                Some((source, partition_keys))
+            }
+        }
+    }
+}
+
+impl SubstateDatabase for SimulatorStore {
+    fn get_raw_substate_by_db_key(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue> {
+        match self {
+            SimulatorStore::Local(store) => {
+                store.get_raw_substate_by_db_key(partition_key, sort_key)
+            }
+            SimulatorStore::Forked { overlay, .. } => {
+                overlay.get_raw_substate_by_db_key(partition_key, sort_key)
+            }
+        }
+    }
+
+    fn list_raw_values_from_db_key(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        match self {
+            SimulatorStore::Local(store) => {
+                store.list_raw_values_from_db_key(partition_key, from_sort_key)
+            }
+            SimulatorStore::Forked { overlay, .. } => {
+                overlay.list_raw_values_from_db_key(partition_key, from_sort_key)
+            }
+        }
+    }
+}
+
+impl CommittableSubstateDatabase for SimulatorStore {
+    fn commit(&mut self, database_updates: &DatabaseUpdates) {
+        match self {
+            SimulatorStore::Local(store) => store.commit(database_updates),
+            SimulatorStore::Forked {
+                overlay,
+                overlay_path,
+                ..
+            } => {
+                // The commit interface can't return an error, and carrying on would lose the
+                // changes made to the forked ledger from the next command on
+                if let Err(err) = append_sbor_record(overlay_path, database_updates) {
+                    panic!(
+                        "Failed to save the changes made to the forked ledger: {:?}",
+                        err
+                    );
+                }
+                overlay.commit(database_updates);
+            }
+        }
+    }
+}
+
+impl ListableSubstateDatabase for SimulatorStore {
+    fn list_partition_keys(&self) -> Box<dyn Iterator<Item = DbPartitionKey> + '_> {
+        match self {
+            SimulatorStore::Local(store) => store.list_partition_keys(),
+            SimulatorStore::Forked { overlay, .. } => overlay.list_partition_keys(),
+        }
+    }
+}
+
+/// The simulator ledger database, which is closed when dropped unless it is kept open.
+pub struct SimulatorDatabase {
+    store: Option<SimulatorStore>,
+}
+
+impl SimulatorDatabase {
+    fn open(data_dir: PathBuf) -> Result<Self, Error> {
+        if let Some(store) = OPEN_DATABASE.with(|database| database.borrow_mut().take()) {
+            return Ok(Self { store: Some(store) });
+        }
+        Ok(Self {
+            store: Some(open_ledger_database(&data_dir, false)?),
+        })
+    }
+
+    fn store(&self) -> &SimulatorStore {
+        self.store
+            .as_ref()
+            .expect("The store is only taken when dropped")
+    }
+
+    fn store_mut(&mut self) -> &mut SimulatorStore {
+        self.store
+            .as_mut()
+            .expect("The store is only taken when dropped")
+    }
+}
+
+impl Drop for SimulatorDatabase {
+    fn drop(&mut self) {
+        if KEEP_DATABASE_OPEN.with(|keep_open| keep_open.get()) {
+            OPEN_DATABASE.with(|database| *database.borrow_mut() = self.store.take());
+        }
+    }
+}
+
+impl SubstateDatabase for SimulatorDatabase {
+    fn get_raw_substate_by_db_key(
+        &self,
+        partition_key: &DbPartitionKey,
+        sort_key: &DbSortKey,
+    ) -> Option<DbSubstateValue> {
+        self.store()
+            .get_raw_substate_by_db_key(partition_key, sort_key)
+    }
+
+    fn list_raw_values_from_db_key(
+        &self,
+        partition_key: &DbPartitionKey,
+        from_sort_key: Option<&DbSortKey>,
+    ) -> Box<dyn Iterator<Item = PartitionEntry> + '_> {
+        self.store()
+            .list_raw_values_from_db_key(partition_key, from_sort_key)
+    }
+}
+
+impl CommittableSubstateDatabase for SimulatorDatabase {
+    fn commit(&mut self, database_updates: &DatabaseUpdates) {
+        self.store_mut().commit(database_updates)
+    }
+}
+
+impl ListableSubstateDatabase for SimulatorDatabase {
+    fn list_partition_keys(&self) -> Box<dyn Iterator<Item = DbPartitionKey> + '_> {
+        self.store().list_partition_keys()
+    }
+}
 
 /// The environment that the simulator runs in.
 pub struct SimulatorEnvironment {
     // Db
-    pub db: RocksdbSubstateStore,
+    pub db: SimulatorDatabase,
     // VMs
     pub vm_modules: DefaultVmModules,
     pub network_definition: NetworkDefinition,
@@ -17,15 +243,18 @@ pub struct SimulatorEnvironment {
 
 impl SimulatorEnvironment {
     pub fn new_reset() -> Result<Self, Error> {
-        let dir = get_data_dir()?;
-        std::fs::remove_dir_all(dir).map_err(Error::IOError)?;
+        // Snapshots are kept across resets, so that a known-good state can be restored later
+        clear_ledger_files(&get_data_dir()?)?;
+        if let Some(path) = get_active_recording()? {
+            record_step(&path, &RecordedStep::Reset)?;
+        }
 
         Self::new()
     }
 
     pub fn new() -> Result<Self, Error> {
         // Create the database
-        let db = RocksdbSubstateStore::standard(get_data_dir()?);
+        let db = SimulatorDatabase::open(get_data_dir()?)?;
 
         // Create the VMs
         let vm_modules = VmModules::default();
@@ -58,7 +287,7 @@ pub struct Configs {
     pub nonce: u32,
 }
 
-fn get_data_dir() -> Result<PathBuf, Error> {
+pub fn get_data_dir() -> Result<PathBuf, Error> {
     let path = match env::var(ENV_DATA_DIR) {
         Ok(value) => std::path::PathBuf::from(value),
         Err(..) => {
@@ -73,6 +302,33 @@ fn get_data_dir() -> Result<PathBuf, Error> {
     Ok(path)
 }
 
+pub fn get_snapshots_dir() -> Result<PathBuf, Error> {
+    let mut path = get_data_dir()?;
+    path.push(SNAPSHOTS_DIR_UNDER_DATA_DIR);
+    Ok(path)
+}
+
+/// Removes everything in the data directory apart from the saved snapshots and the marker of
+/// the active recording, if any.
+pub fn clear_ledger_files(data_dir: &Path) -> Result<(), Error> {
+    close_database();
+    for entry in fs::read_dir(data_dir).map_err(Error::IOError)? {
+        let path = entry.map_err(Error::IOError)?.path();
+        if path.file_name() == Some(SNAPSHOTS_DIR_UNDER_DATA_DIR.as_ref())
+            || path.file_name() == Some(RECORDING_MARKER_FILE_UNDER_DATA_DIR.as_ref())
+        {
+            continue;
+        }
+        if path.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        }
+        .map_err(|err| Error::IOErrorAtPath(err, path))?;
+    }
+    Ok(())
+}
+
 pub fn get_configs_path() -> Result<PathBuf, Error> {
     let mut path = get_data_dir()?;
     path.push("config");
@@ -80,7 +336,10 @@ pub fn get_configs_path() -> Result<PathBuf, Error> {
 }
 
 pub fn get_configs() -> Result<Configs, Error> {
-    let path = get_configs_path()?;
+    read_configs(&get_configs_path()?)
+}
+
+pub fn read_configs(path: &Path) -> Result<Configs, Error> {
     if path.exists() {
         scrypto_decode(fs::read(path).map_err(Error::IOError)?.as_ref())
             .map_err(Error::SborDecodeError)
@@ -93,6 +352,42 @@ pub fn set_configs(configs: &Configs) -> Result<(), Error> {
     fs::write(get_configs_path()?, scrypto_encode(configs).unwrap()).map_err(Error::IOError)
 }
 
+/// The replay database a simulator ledger is forked from, see [`Fork`].
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct ForkConfigs {
+    /// The path to the replay database
+    pub source: String,
+    /// The network of the replay database, whose addresses are accepted alongside simulator ones
+    pub network: String,
+}
+
+pub fn get_fork_configs_path() -> Result<PathBuf, Error> {
+    Ok(get_data_dir()?.join(FORK_CONFIGS_FILE_UNDER_DATA_DIR))
+}
+
+/// Returns the fork configs if the simulator ledger is forked from a replay database.
+pub fn get_fork_configs() -> Result<Option<ForkConfigs>, Error> {
+    read_fork_configs(&get_fork_configs_path()?)
+}
+
+pub fn read_fork_configs(path: &Path) -> Result<Option<ForkConfigs>, Error> {
+    if path.exists() {
+        scrypto_decode(fs::read(path).map_err(Error::IOError)?.as_ref())
+            .map(Some)
+            .map_err(Error::SborDecodeError)
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn set_fork_configs(fork_configs: &ForkConfigs) -> Result<(), Error> {
+    fs::write(
+        get_fork_configs_path()?,
+        scrypto_encode(fork_configs).map_err(Error::SborEncodeError)?,
+    )
+    .map_err(Error::IOError)
+}
+
 pub fn get_default_account() -> Result<ComponentAddress, Error> {
     get_configs()?
         .default_account
@@ -115,3 +410,97 @@ pub fn get_default_owner_badge() -> Result<NonFungibleGlobalId, Error> {
 pub fn get_nonce() -> Result<u32, Error> {
     Ok(get_configs()?.nonce)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_substate(db: &mut impl CommittableSubstateDatabase, value: u8) {
+        db.update_substate_raw(
+            XRD.into_node_id(),
+            PartitionNumber(0),
+            SubstateKey::Field(0),
+            vec![value],
+        );
+    }
+
+    fn read_substate(db: &impl SubstateDatabase) -> Option<Vec<u8>> {
+        db.get_raw_substate(
+            XRD.into_node_id(),
+            PartitionNumber(0),
+            SubstateKey::Field(0),
+        )
+    }
+
+    fn fork_data_dir(source: &Path) -> tempfile::TempDir {
+        let data_dir = tempfile::tempdir().unwrap();
+        let fork_configs = ForkConfigs {
+            source: source.to_string_lossy().into_owned(),
+            network: "mainnet".to_owned(),
+        };
+        fs::write(
+            data_dir.path().join(FORK_CONFIGS_FILE_UNDER_DATA_DIR),
+            scrypto_encode(&fork_configs).unwrap(),
+        )
+        .unwrap();
+        data_dir
+    }
+
+    #[test]
+    fn forked_ledger_appends_its_changes_and_leaves_the_source_untouched() {
+        let source_dir = tempfile::tempdir().unwrap();
+        write_substate(
+            &mut RocksDBWithMerkleTreeSubstateStore::standard(source_dir.path().to_path_buf()),
+            1,
+        );
+        let data_dir = fork_data_dir(source_dir.path());
+
+        {
+            let mut store = open_ledger_database(data_dir.path(), false).unwrap();
+            assert_eq!(read_substate(&store), Some(vec![1]));
+            write_substate(&mut store, 2);
+            write_substate(&mut store, 3);
+        }
+        let overlay_path = data_dir.path().join(FORK_OVERLAY_FILE_UNDER_DATA_DIR);
+        assert_eq!(
+            read_sbor_records::<DatabaseUpdates>(&overlay_path)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let store = open_ledger_database(data_dir.path(), true).unwrap();
+        assert_eq!(read_substate(&store), Some(vec![3]));
+        let (source, partition_keys) = store.fork_changes().unwrap();
+        assert_eq!(source, source_dir.path().to_string_lossy());
+        assert_eq!(partition_keys.len(), 1);
+        drop(store);
+
+        let source =
+            RocksDBWithMerkleTreeSubstateStore::read_only(source_dir.path().to_path_buf()).unwrap();
+        assert_eq!(read_substate(&source), Some(vec![1]));
+    }
+
+    #[test]
+    fn forked_ledger_without_source_is_an_error() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let data_dir = fork_data_dir(&source_dir.path().join("missing"));
+
+        assert!(matches!(
+            open_ledger_database(data_dir.path(), false),
+            Err(Error::LedgerDatabaseError(..))
+        ));
+    }
+
+    #[test]
+    fn local_ledger_is_only_opened_read_only_if_it_exists() {
+        let data_dir = tempfile::tempdir().unwrap();
+
+        assert!(matches!(
+            open_ledger_database(data_dir.path(), true),
+            Err(Error::LedgerDatabaseError(..))
+        ));
+        let store = open_ledger_database(data_dir.path(), false).unwrap();
+        assert!(store.fork_changes().is_none());
+    }
+}
@@ -15,6 +15,9 @@ use radix_rust::ContextualDisplay;
 use radix_substate_store_interface::interface::*;
 use radix_substate_store_queries::query::ResourceAccounter;
 use radix_substate_store_queries::typed_substate_layout::*;
+use serde_json::{json, Value};
+
+use crate::resim::record_json_value;
 
 /// Represents an error when displaying an entity.
 #[derive(Debug, Clone)]
@@ -49,19 +52,26 @@ pub fn dump_package<T: SubstateDatabase, O: std::io::Write>(
         "Package Address".green().bold(),
         package_address.display(&address_bech32_encoder)
     );
+    let code_size = substate
+        .into_value()
+        .unwrap()
+        .fully_update_and_into_latest_version()
+        .code
+        .len();
     writeln!(
         output,
         "{}: {} bytes",
         "Code size".green().bold(),
-        substate
-            .into_value()
-            .unwrap()
-            .fully_update_and_into_latest_version()
-            .code
-            .len()
+        code_size
     );
 
     let metadata = get_entity_metadata(package_address.as_node_id(), substate_db);
+    record_json_value(
+        "package_address",
+        package_address.display(&address_bech32_encoder).to_string(),
+    );
+    record_json_value("code_size", code_size);
+    record_json_value("metadata", metadata_to_json(&metadata));
     writeln!(output, "{}: {}", "Metadata".green().bold(), metadata.len());
     for (last, (key, value)) in metadata.iter().identify_last() {
         writeln!(output, "{} {}: {:?}", list_item_prefix(last), key, value);
@@ -103,6 +113,19 @@ pub fn dump_component<T: SubstateDatabase, O: std::io::Write>(
         "Component Address".green().bold(),
         component_address.display(&address_bech32_encoder),
     );
+    record_json_value(
+        "component_address",
+        component_address
+            .display(&address_bech32_encoder)
+            .to_string(),
+    );
+    record_json_value(
+        "blueprint_id",
+        json!({
+            "package_address": package_address.display(&address_bech32_encoder).to_string(),
+            "blueprint_name": blueprint_name,
+        }),
+    );
 
     writeln!(
         output,
@@ -118,6 +141,7 @@ pub fn dump_component<T: SubstateDatabase, O: std::io::Write>(
         "Owned Fungible Resources".green().bold(),
         resources.balances.len()
     );
+    let mut fungible_resources = vec![];
     for (last, (resource_address, amount)) in resources.balances.iter().identify_last() {
         let metadata = get_entity_metadata(resource_address.as_node_id(), substate_db);
         let name = if let Some(MetadataValue::String(name)) = metadata.get("name") {
@@ -139,7 +163,14 @@ pub fn dump_component<T: SubstateDatabase, O: std::io::Write>(
             name,
             symbol_text,
         );
+        fungible_resources.push(json!({
+            "resource_address": resource_address.display(&address_bech32_encoder).to_string(),
+            "amount": amount.to_string(),
+            "name": metadata_string(&metadata, "name"),
+            "symbol": metadata_string(&metadata, "symbol"),
+        }));
     }
+    record_json_value("fungible_resources", fungible_resources);
 
     writeln!(
         output,
@@ -147,6 +178,7 @@ pub fn dump_component<T: SubstateDatabase, O: std::io::Write>(
         "Owned Non-fungibles Resources".green().bold(),
         resources.non_fungibles.len()
     );
+    let mut non_fungible_resources = vec![];
     for (last, (resource_address, ids)) in resources.non_fungibles.iter().identify_last() {
         let metadata = get_entity_metadata(resource_address.as_node_id(), substate_db);
         let name = if let Some(MetadataValue::String(name)) = metadata.get("name") {
@@ -171,9 +203,17 @@ pub fn dump_component<T: SubstateDatabase, O: std::io::Write>(
         for (last, id) in ids.iter().identify_last() {
             writeln!(output, "   {} {}", list_item_prefix(last), id);
         }
+        non_fungible_resources.push(json!({
+            "resource_address": resource_address.display(&address_bech32_encoder).to_string(),
+            "ids": ids.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+            "name": metadata_string(&metadata, "name"),
+            "symbol": metadata_string(&metadata, "symbol"),
+        }));
     }
+    record_json_value("non_fungible_resources", non_fungible_resources);
 
     let metadata = get_entity_metadata(component_address.as_node_id(), substate_db);
+    record_json_value("metadata", metadata_to_json(&metadata));
     writeln!(output, "{}: {}", "Metadata".green().bold(), metadata.len());
     for (last, (key, value)) in metadata.iter().identify_last() {
         writeln!(output, "{} {}: {:?}", list_item_prefix(last), key, value);
@@ -196,6 +236,12 @@ pub fn dump_resource_manager<T: SubstateDatabase, O: std::io::Write>(
         "Resource Address".green().bold(),
         resource_address.display(&address_bech32_encoder)
     );
+    record_json_value(
+        "resource_address",
+        resource_address
+            .display(&address_bech32_encoder)
+            .to_string(),
+    );
 
     let reader = SystemDatabaseReader::new(substate_db);
     let info = reader
@@ -218,6 +264,8 @@ pub fn dump_resource_manager<T: SubstateDatabase, O: std::io::Write>(
 
         writeln!(output, "{}: Non-fungible", "Resource Type".green().bold());
         writeln!(output, "{}: {:?}", "ID Type".green().bold(), id_type);
+        record_json_value("resource_type", "non_fungible");
+        record_json_value("id_type", format!("{:?}", id_type));
 
         if info
             .get_features()
@@ -238,6 +286,7 @@ pub fn dump_resource_manager<T: SubstateDatabase, O: std::io::Write>(
                 "Total Supply".green().bold(),
                 total_supply
             );
+            record_json_value("total_supply", total_supply.to_string());
         }
     } else {
         let divisibility = reader
@@ -256,6 +305,8 @@ pub fn dump_resource_manager<T: SubstateDatabase, O: std::io::Write>(
             "Divisibility".green().bold(),
             divisibility
         );
+        record_json_value("resource_type", "fungible");
+        record_json_value("divisibility", divisibility);
 
         if info
             .get_features()
@@ -276,10 +327,12 @@ pub fn dump_resource_manager<T: SubstateDatabase, O: std::io::Write>(
                 "Total Supply".green().bold(),
                 total_supply
             );
+            record_json_value("total_supply", total_supply.to_string());
         }
     }
 
     let metadata = get_entity_metadata(resource_address.as_node_id(), substate_db);
+    record_json_value("metadata", metadata_to_json(&metadata));
     writeln!(output, "{}: {}", "Metadata".green().bold(), metadata.len());
     for (last, (key, value)) in metadata.iter().identify_last() {
         writeln!(output, "{} {}: {:?}", list_item_prefix(last), key, value);
@@ -288,6 +341,24 @@ pub fn dump_resource_manager<T: SubstateDatabase, O: std::io::Write>(
     Ok(())
 }
 
+fn metadata_string<'a>(
+    metadata: &'a IndexMap<String, MetadataValue>,
+    key: &str,
+) -> Option<&'a str> {
+    match metadata.get(key) {
+        Some(MetadataValue::String(value)) => Some(value.as_str()),
+        _ => None,
+    }
+}
+
+fn metadata_to_json(metadata: &IndexMap<String, MetadataValue>) -> Value {
+    metadata
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::String(format!("{:?}", value))))
+        .collect::<serde_json::Map<String, Value>>()
+        .into()
+}
+
 fn get_entity_metadata<T: SubstateDatabase>(
     entity_node_id: &NodeId,
     substate_db: &T,
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use crate::resim::EntityDumpError;
 use radix_engine::errors::*;
-use radix_engine::transaction::AbortReason;
+use radix_engine::transaction::{AbortReason, PreviewError};
 use radix_engine::vm::wasm::PrepareError as WasmPrepareError;
 use radix_transactions::errors::*;
 use radix_transactions::manifest::DecompileError;
@@ -74,6 +74,49 @@ pub enum Error {
     RemoteGenericSubstitutionNotSupported,
 
     PackageDefinitionConversionError(ConversionError),
+
+    SnapshotNotFound(String),
+
+    SnapshotAlreadyExists(String),
+
+    InvalidSnapshotName(String),
+
+    SnapshotDatabaseError(String, rocksdb::Error),
+
+    LedgerDatabaseError(PathBuf, rocksdb::Error),
+
+    RecordingAlreadyActive(PathBuf),
+
+    NoActiveRecording,
+
+    UnsupportedWhileRecording(String),
+
+    ReplayDiverged(usize),
+
+    TransactionNotFound(Hash),
+
+    InvalidTransactionHash(String),
+
+    SubintentsRequireV2Manifest,
+    DuplicateSubintentName(String),
+    TooManySubintentSigners(usize),
+    SubintentChildrenMismatch,
+
+    PreviewError(PreviewError),
+    PreviewNotSupported,
+    SubintentsNotSupportedInPreview,
+
+    ValidatorNotFound(ComponentAddress),
+    NoClaimableStake(ComponentAddress),
+    ConsensusManagerNotFound,
+    EpochOverflow,
+    EpochNotAdvanced(Epoch),
+
+    ShellError(String),
+
+    InvalidAliasName(String),
+    AliasNotFound(String),
+    AliasAlreadyExists(String),
 }
 
 impl fmt::Display for Error {
@@ -186,6 +229,69 @@ impl Debug for Error {
             Self::RemoteGenericSubstitutionNotSupported => {
                 write!(f, "RemoteGenericSubstitutionNotSupported")
             }
+            Self::SnapshotNotFound(name) => f.debug_tuple("SnapshotNotFound").field(name).finish(),
+            Self::SnapshotAlreadyExists(name) => {
+                f.debug_tuple("SnapshotAlreadyExists").field(name).finish()
+            }
+            Self::InvalidSnapshotName(name) => {
+                f.debug_tuple("InvalidSnapshotName").field(name).finish()
+            }
+            Self::SnapshotDatabaseError(name, err) => f
+                .debug_tuple("SnapshotDatabaseError")
+                .field(name)
+                .field(err)
+                .finish(),
+            Self::LedgerDatabaseError(path, err) => f
+                .debug_tuple("LedgerDatabaseError")
+                .field(path)
+                .field(err)
+                .finish(),
+            Self::RecordingAlreadyActive(path) => {
+                f.debug_tuple("RecordingAlreadyActive").field(path).finish()
+            }
+            Self::NoActiveRecording => write!(f, "NoActiveRecording"),
+            Self::UnsupportedWhileRecording(command) => f
+                .debug_tuple("UnsupportedWhileRecording")
+                .field(command)
+                .finish(),
+            Self::ReplayDiverged(count) => f.debug_tuple("ReplayDiverged").field(count).finish(),
+            Self::TransactionNotFound(hash) => {
+                f.debug_tuple("TransactionNotFound").field(hash).finish()
+            }
+            Self::InvalidTransactionHash(s) => {
+                f.debug_tuple("InvalidTransactionHash").field(s).finish()
+            }
+            Self::SubintentsRequireV2Manifest => write!(f, "SubintentsRequireV2Manifest"),
+            Self::DuplicateSubintentName(name) => {
+                f.debug_tuple("DuplicateSubintentName").field(name).finish()
+            }
+            Self::TooManySubintentSigners(count) => f
+                .debug_tuple("TooManySubintentSigners")
+                .field(count)
+                .finish(),
+            Self::SubintentChildrenMismatch => write!(f, "SubintentChildrenMismatch"),
+            Self::PreviewError(err) => f.debug_tuple("PreviewError").field(err).finish(),
+            Self::PreviewNotSupported => write!(f, "PreviewNotSupported"),
+            Self::SubintentsNotSupportedInPreview => write!(f, "SubintentsNotSupportedInPreview"),
+            Self::ValidatorNotFound(validator_address) => f
+                .debug_tuple("ValidatorNotFound")
+                .field(&validator_address.to_string(address_encoder))
+                .finish(),
+            Self::NoClaimableStake(validator_address) => f
+                .debug_tuple("NoClaimableStake")
+                .field(&validator_address.to_string(address_encoder))
+                .finish(),
+            Self::ConsensusManagerNotFound => write!(f, "ConsensusManagerNotFound"),
+            Self::EpochOverflow => write!(f, "EpochOverflow"),
+            Self::EpochNotAdvanced(epoch) => {
+                f.debug_tuple("EpochNotAdvanced").field(epoch).finish()
+            }
+            Self::ShellError(err) => f.debug_tuple("ShellError").field(err).finish(),
+            Self::InvalidAliasName(name) => f.debug_tuple("InvalidAliasName").field(name).finish(),
+            Self::AliasNotFound(name) => f.debug_tuple("AliasNotFound").field(name).finish(),
+            Self::AliasAlreadyExists(name) => {
+                f.debug_tuple("AliasAlreadyExists").field(name).finish()
+            }
         }
     }
 }
@@ -0,0 +1,361 @@
+use crate::prelude::*;
+use crate::resim::TransactionId;
+use radix_engine::transaction::*;
+use sbor::representations::*;
+use serde_json::{json, Map, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The version of the JSON document emitted with `--output json`.
+///
+/// It must be bumped whenever a field is removed or its meaning changes; adding fields is fine.
+pub const JSON_OUTPUT_VERSION: u32 = 1;
+
+/// The results collected while a command runs in JSON output mode.
+#[derive(Default)]
+struct JsonOutput {
+    transactions: Vec<Value>,
+    values: Map<String, Value>,
+}
+
+thread_local! {
+    static JSON_OUTPUT: RefCell<Option<JsonOutput>> = const { RefCell::new(None) };
+}
+
+pub fn is_json_output() -> bool {
+    JSON_OUTPUT.with(|output| output.borrow().is_some())
+}
+
+/// Runs a command, collecting its transactions and values, and writes the JSON document
+/// describing the result to `out`. The human-readable output is captured into the `text` field.
+pub fn run_with_json_output<O, F>(command: &str, out: &mut O, run: F) -> Result<(), String>
+where
+    O: std::io::Write,
+    F: FnOnce(&mut Vec<u8>) -> Result<(), String>,
+{
+    colored::control::set_override(false);
+    JSON_OUTPUT.with(|output| *output.borrow_mut() = Some(JsonOutput::default()));
+
+    let mut text = Vec::new();
+    let result = run(&mut text);
+
+    let output = JSON_OUTPUT
+        .with(|output| output.borrow_mut().take())
+        .unwrap_or_default();
+    let document = json_document(command, &result, output, &text);
+    writeln!(
+        out,
+        "{}",
+        serde_json::to_string_pretty(&document).expect("JSON values are always serializable")
+    )
+    .map_err(|err| err.to_string())?;
+
+    result
+}
+
+fn json_document(
+    command: &str,
+    result: &Result<(), String>,
+    output: JsonOutput,
+    text: &[u8],
+) -> Value {
+    json!({
+        "version": JSON_OUTPUT_VERSION,
+        "command": command,
+        "success": result.is_ok(),
+        "error": result.as_ref().err(),
+        "transactions": output.transactions,
+        "values": output.values,
+        "text": String::from_utf8_lossy(text),
+    })
+}
+
+/// Adds a named value, such as a generated key, to the JSON document.
+pub fn record_json_value(key: &str, value: impl Into<Value>) {
+    JSON_OUTPUT.with(|output| {
+        if let Some(output) = output.borrow_mut().as_mut() {
+            output.values.insert(key.to_owned(), value.into());
+        }
+    });
+}
+
+/// Adds the receipt of an executed transaction to the JSON document.
+pub fn record_json_transaction(
+    id: &TransactionId,
+    receipt: &TransactionReceipt,
+    display_context: &TransactionReceiptDisplayContext,
+) {
+    JSON_OUTPUT.with(|output| {
+        if let Some(output) = output.borrow_mut().as_mut() {
+            output
+                .transactions
+                .push(transaction_to_json(id, receipt, display_context));
+        }
+    });
+}
+
+fn transaction_to_json(
+    id: &TransactionId,
+    receipt: &TransactionReceipt,
+    display_context: &TransactionReceiptDisplayContext,
+) -> Value {
+    let mut transaction = receipt_to_json(receipt, display_context);
+    transaction["id"] = transaction_id_to_json(id);
+    transaction
+}
+
+pub fn transaction_id_to_json(id: &TransactionId) -> Value {
+    json!({
+        "kind": match id {
+            TransactionId::TestTransaction(_) => "test_transaction_hash",
+            TransactionId::Intent(_) => "intent_hash",
+        },
+        "value": id.to_display_string(),
+    })
+}
+
+/// The status, fees, new entities and events of a receipt, e.g. of a previewed transaction.
+pub fn receipt_to_json(
+    receipt: &TransactionReceipt,
+    display_context: &TransactionReceiptDisplayContext,
+) -> Value {
+    let address_display_context = display_context.address_display_context();
+    let addresses = |addresses: Vec<GlobalAddress>| {
+        addresses
+            .iter()
+            .map(|address| address.display(address_display_context).to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let (status, error, new_entities, events) = match &receipt.result {
+        TransactionResult::Commit(commit) => {
+            let (status, error) = match &commit.outcome {
+                TransactionOutcome::Success(_) => ("committed_success", None),
+                TransactionOutcome::Failure(error) => {
+                    ("committed_failure", Some(format!("{:?}", error)))
+                }
+            };
+            let new_entities = json!({
+                "packages": addresses(
+                    commit.new_package_addresses().iter().map(|a| (*a).into()).collect()
+                ),
+                "components": addresses(
+                    commit.new_component_addresses().iter().map(|a| (*a).into()).collect()
+                ),
+                "resources": addresses(
+                    commit.new_resource_addresses().iter().map(|a| (*a).into()).collect()
+                ),
+            });
+            let events = commit
+                .application_events
+                .iter()
+                .map(|(event_type_identifier, event_data)| {
+                    json!({
+                        "emitter": emitter_to_json(&event_type_identifier.0, address_display_context),
+                        "name": event_type_identifier.1,
+                        "data": EventPayload::new(
+                            event_type_identifier,
+                            event_data,
+                            &commit.system_structure,
+                            display_context,
+                        )
+                        .to_json(display_context),
+                    })
+                })
+                .collect::<Vec<_>>();
+            (status, error, new_entities, events)
+        }
+        TransactionResult::Reject(rejection) => (
+            "rejected",
+            Some(format!("{:?}", rejection.reason)),
+            Value::Null,
+            vec![],
+        ),
+        TransactionResult::Abort(abort) => (
+            "aborted",
+            Some(format!("{:?}", abort.reason)),
+            Value::Null,
+            vec![],
+        ),
+    };
+
+    let fee_summary = &receipt.fee_summary;
+    json!({
+        "status": status,
+        "error": error,
+        "fee_summary": {
+            "total_cost": fee_summary.total_cost().to_string(),
+            "execution_cost": fee_summary.total_execution_cost_in_xrd.to_string(),
+            "finalization_cost": fee_summary.total_finalization_cost_in_xrd.to_string(),
+            "storage_cost": fee_summary.total_storage_cost_in_xrd.to_string(),
+            "royalty_cost": fee_summary.total_royalty_cost_in_xrd.to_string(),
+            "tipping_cost": fee_summary.total_tipping_cost_in_xrd.to_string(),
+            "execution_cost_units_consumed": fee_summary.total_execution_cost_units_consumed,
+            "finalization_cost_units_consumed": fee_summary.total_finalization_cost_units_consumed,
+        },
+        "new_entities": new_entities,
+        "events": events,
+    })
+}
+
+fn emitter_to_json(emitter: &Emitter, address_display_context: AddressDisplayContext) -> Value {
+    match emitter {
+        Emitter::Function(blueprint_id) => json!({
+            "type": "function",
+            "package_address": blueprint_id
+                .package_address
+                .display(address_display_context)
+                .to_string(),
+            "blueprint_name": blueprint_id.blueprint_name,
+        }),
+        Emitter::Method(node_id, module_id) => json!({
+            "type": "method",
+            "entity": node_id.display(address_display_context).to_string(),
+            "module": format!("{:?}", module_id),
+        }),
+    }
+}
+
+/// An event payload along with its schema, when it can be found, so that the text and the JSON
+/// output decode events the same way.
+pub struct EventPayload<'a> {
+    payload: ScryptoRawPayload<'a>,
+    schema: Option<(LocalTypeId, Rc<VersionedScryptoSchema>)>,
+}
+
+impl<'a> EventPayload<'a> {
+    pub fn new(
+        event_type_identifier: &EventTypeIdentifier,
+        event_data: &'a [u8],
+        system_structure: &SystemStructure,
+        display_context: &TransactionReceiptDisplayContext,
+    ) -> Self {
+        let schema = system_structure
+            .event_system_structures
+            .get(event_type_identifier)
+            .and_then(|event_system_structure| {
+                display_context
+                    .lookup_schema(&event_system_structure.package_type_reference.full_type_id)
+            });
+        Self {
+            payload: ScryptoRawPayload::new_from_valid_slice(event_data),
+            schema,
+        }
+    }
+
+    /// The payload on a single line, annotated with field and variant names if possible.
+    pub fn to_display_string(&self, display_context: &TransactionReceiptDisplayContext) -> String {
+        match &self.schema {
+            Some((type_id, schema)) => self.payload.to_string(ValueDisplayParameters::Annotated {
+                display_mode: DisplayMode::RustLike(RustLikeOptions::full()),
+                print_mode: PrintMode::SingleLine,
+                custom_context: display_context.display_context(),
+                schema: schema.v1(),
+                type_id: *type_id,
+                depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+            }),
+            None => self.payload.to_string(ValueDisplayParameters::Schemaless {
+                display_mode: DisplayMode::RustLike(RustLikeOptions::full()),
+                print_mode: PrintMode::SingleLine,
+                custom_context: display_context.display_context(),
+                depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+            }),
+        }
+    }
+
+    /// The payload in the programmatic JSON representation, annotated with field and variant
+    /// names if possible.
+    pub fn to_json(&self, display_context: &TransactionReceiptDisplayContext) -> Value {
+        let serialized = match &self.schema {
+            Some((type_id, schema)) => serde_json::to_value(self.payload.serializable(
+                SerializationParameters::WithSchema {
+                    mode: SerializationMode::Programmatic,
+                    custom_context: display_context.display_context(),
+                    schema: schema.v1(),
+                    type_id: *type_id,
+                    depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+                },
+            )),
+            None => serde_json::to_value(self.payload.serializable(
+                SerializationParameters::Schemaless {
+                    mode: SerializationMode::Programmatic,
+                    custom_context: display_context.display_context(),
+                    depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+                },
+            )),
+        };
+        serialized
+            .unwrap_or_else(|err| Value::String(format!("Failed to serialize event: {}", err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine_interface::api::ModuleId;
+
+    #[test]
+    fn json_document_is_versioned_and_reports_errors() {
+        let mut output = JsonOutput::default();
+        output.values.insert("key".to_owned(), "value".into());
+
+        let document = json_document(
+            "new-account",
+            &Err("TransactionFailed".to_owned()),
+            output,
+            b"some text",
+        );
+
+        assert_eq!(
+            document,
+            json!({
+                "version": JSON_OUTPUT_VERSION,
+                "command": "new-account",
+                "success": false,
+                "error": "TransactionFailed",
+                "transactions": [],
+                "values": { "key": "value" },
+                "text": "some text",
+            })
+        );
+    }
+
+    #[test]
+    fn event_payload_without_schema_is_decoded_schemaless() {
+        let event_type_identifier = EventTypeIdentifier(
+            Emitter::Method(XRD.into_node_id(), ModuleId::Main),
+            "TestEvent".to_owned(),
+        );
+        let event_data = scrypto_encode(&(1u32, "a".to_owned())).unwrap();
+        let display_context = TransactionReceiptDisplayContextBuilder::new().build();
+
+        let payload = EventPayload::new(
+            &event_type_identifier,
+            &event_data,
+            &SystemStructure::default(),
+            &display_context,
+        );
+
+        assert_eq!(
+            payload.to_display_string(&display_context),
+            r#"Tuple(1u32, "a")"#
+        );
+        assert_eq!(
+            payload.to_json(&display_context),
+            json!({
+                "kind": "Tuple",
+                "fields": [
+                    { "kind": "U32", "value": "1" },
+                    { "kind": "String", "value": "a" },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn values_are_only_collected_in_json_output_mode() {
+        record_json_value("key", "value");
+        assert!(!is_json_output());
+        assert!(JSON_OUTPUT.with(|output| output.borrow().is_none()));
+    }
+}
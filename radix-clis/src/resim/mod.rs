@@ -1,8 +1,14 @@
 mod addressing;
+mod alias;
+mod cmd_advance_epoch;
+mod cmd_alias;
 mod cmd_call_function;
 mod cmd_call_method;
+mod cmd_claim_xrd;
 mod cmd_export_package_definition;
+mod cmd_fork;
 mod cmd_generate_key_pair;
+mod cmd_history;
 mod cmd_mint;
 mod cmd_new_account;
 mod cmd_new_badge_fixed;
@@ -10,51 +16,91 @@ mod cmd_new_badge_mutable;
 mod cmd_new_simple_badge;
 mod cmd_new_token_fixed;
 mod cmd_new_token_mutable;
+mod cmd_new_validator;
 mod cmd_publish;
+mod cmd_record;
+mod cmd_register_validator;
+mod cmd_replay;
 mod cmd_reset;
 mod cmd_run;
 mod cmd_set_current_epoch;
 mod cmd_set_current_time;
 mod cmd_set_default_account;
+mod cmd_shell;
 mod cmd_show;
 mod cmd_show_configs;
 mod cmd_show_ledger;
+mod cmd_show_tx;
+mod cmd_snapshot;
+mod cmd_stake;
 mod cmd_transfer;
+mod cmd_unstake;
 mod config;
 mod dumper;
 mod error;
+mod json_output;
+mod preview;
+mod receipts;
+mod record_log;
+mod shell_completion;
+mod subintents;
 
 pub use addressing::*;
+pub use alias::*;
+pub use cmd_advance_epoch::*;
+pub use cmd_alias::*;
 pub use cmd_call_function::CallFunction;
 pub use cmd_call_method::CallMethod;
+pub use cmd_claim_xrd::*;
 pub use cmd_export_package_definition::*;
+pub use cmd_fork::*;
 pub use cmd_generate_key_pair::*;
+pub use cmd_history::*;
 pub use cmd_new_account::*;
 pub use cmd_new_badge_fixed::*;
 pub use cmd_new_badge_mutable::*;
 pub use cmd_new_simple_badge::*;
 pub use cmd_new_token_fixed::*;
 pub use cmd_new_token_mutable::*;
+pub use cmd_new_validator::*;
 pub use cmd_publish::*;
+pub use cmd_record::*;
+pub use cmd_register_validator::*;
+pub use cmd_replay::*;
 pub use cmd_reset::*;
 pub use cmd_run::*;
 pub use cmd_set_current_epoch::*;
 pub use cmd_set_current_time::*;
 pub use cmd_set_default_account::*;
+pub use cmd_shell::*;
 pub use cmd_show::*;
 pub use cmd_show_configs::*;
 pub use cmd_show_ledger::*;
+pub use cmd_show_tx::*;
+pub use cmd_snapshot::*;
+pub use cmd_stake::*;
 pub use cmd_transfer::*;
+pub use cmd_unstake::*;
 pub use config::*;
 pub use dumper::*;
 pub use error::*;
+pub use json_output::*;
+pub use preview::*;
+pub use receipts::*;
+pub use record_log::*;
+pub use shell_completion::*;
+pub use subintents::*;
 
 pub const DEFAULT_SCRYPTO_DIR_UNDER_HOME: &str = ".scrypto";
 pub const ENV_DATA_DIR: &str = "DATA_DIR";
 pub const ENV_DISABLE_MANIFEST_OUTPUT: &str = "DISABLE_MANIFEST_OUTPUT";
+pub const SNAPSHOTS_DIR_UNDER_DATA_DIR: &str = "snapshots";
+pub const FORK_CONFIGS_FILE_UNDER_DATA_DIR: &str = "fork.sbor";
+pub const FORK_OVERLAY_FILE_UNDER_DATA_DIR: &str = "fork_overlay.sbor";
+pub const RECORDING_MARKER_FILE_UNDER_DATA_DIR: &str = "recording";
 
 use crate::prelude::*;
-use clap::{Parser, Subcommand};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use radix_engine::blueprints::consensus_manager::*;
 use radix_engine::blueprints::models::FieldPayload;
 use radix_engine::system::system_db_reader::*;
@@ -62,6 +108,7 @@ use radix_engine::transaction::*;
 use radix_engine_interface::api::ModuleId;
 use radix_engine_interface::blueprints::package::*;
 use radix_substate_store_impls::rocks_db::RocksdbSubstateStore;
+use radix_substate_store_interface::interface::*;
 use radix_transactions::validation::TransactionValidator;
 
 /// Build fast, reward everyone, and scale without friction
@@ -70,6 +117,10 @@ use radix_transactions::validation::TransactionValidator;
 pub struct ResimCli {
     #[clap(subcommand)]
     pub(crate) command: Command,
+
+    /// The output format, [text | json]
+    #[clap(long, global = true, default_value = "text")]
+    pub(crate) output: OutputFormat,
 }
 
 impl ResimCli {
@@ -80,10 +131,15 @@ impl ResimCli {
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    AdvanceEpoch(AdvanceEpoch),
+    Alias(Alias),
     CallFunction(CallFunction),
     CallMethod(CallMethod),
+    ClaimXrd(ClaimXrd),
     ExportPackageDefinition(ExportPackageDefinition),
+    Fork(Fork),
     GenerateKeyPair(GenerateKeyPair),
+    History(History),
     Mint(crate::resim::cmd_mint::Mint),
     NewAccount(NewAccount),
     NewSimpleBadge(NewSimpleBadge),
@@ -91,45 +147,87 @@ pub enum Command {
     NewBadgeMutable(NewBadgeMutable),
     NewTokenFixed(NewTokenFixed),
     NewTokenMutable(NewTokenMutable),
+    NewValidator(NewValidator),
     Publish(Publish),
+    Record(Record),
+    RegisterValidator(RegisterValidator),
+    Replay(Replay),
     Reset(Reset),
     Run(Run),
     SetCurrentEpoch(SetCurrentEpoch),
     SetCurrentTime(SetCurrentTime),
     SetDefaultAccount(SetDefaultAccount),
+    Shell(Shell),
     ShowConfigs(ShowConfigs),
     ShowLedger(ShowLedger),
+    ShowTx(ShowTx),
     Show(Show),
+    Snapshot(Snapshot),
+    Stake(Stake),
     Transfer(Transfer),
+    Unstake(Unstake),
 }
 
 pub fn run() -> Result<(), String> {
-    let cli = ResimCli::parse();
-
-    let mut out = std::io::stdout();
-
-    match cli.command {
-        Command::CallFunction(cmd) => cmd.run(&mut out),
-        Command::CallMethod(cmd) => cmd.run(&mut out),
-        Command::ExportPackageDefinition(cmd) => cmd.run(&mut out),
-        Command::GenerateKeyPair(cmd) => cmd.run(&mut out),
-        Command::Mint(cmd) => cmd.run(&mut out),
-        Command::NewAccount(cmd) => cmd.run(&mut out),
-        Command::NewSimpleBadge(cmd) => cmd.run(&mut out).map(|_| ()),
-        Command::NewBadgeFixed(cmd) => cmd.run(&mut out),
-        Command::NewBadgeMutable(cmd) => cmd.run(&mut out),
-        Command::NewTokenFixed(cmd) => cmd.run(&mut out),
-        Command::NewTokenMutable(cmd) => cmd.run(&mut out),
-        Command::Publish(cmd) => cmd.run(&mut out),
-        Command::Reset(cmd) => cmd.run(&mut out),
-        Command::Run(cmd) => cmd.run(&mut out),
-        Command::SetCurrentEpoch(cmd) => cmd.run(&mut out),
-        Command::SetCurrentTime(cmd) => cmd.run(&mut out),
-        Command::SetDefaultAccount(cmd) => cmd.run(&mut out),
-        Command::ShowConfigs(cmd) => cmd.run(&mut out),
-        Command::ShowLedger(cmd) => cmd.run(&mut out),
-        Command::Show(cmd) => cmd.run(&mut out),
-        Command::Transfer(cmd) => cmd.run(&mut out),
+    let matches = ResimCli::command().get_matches();
+    let cli = ResimCli::from_arg_matches(&matches).map_err(|err| err.to_string())?;
+
+    run_cli(cli, &matches, &mut std::io::stdout())
+}
+
+/// Runs a parsed command line, in the requested output format.
+pub fn run_cli<O: std::io::Write>(
+    cli: ResimCli,
+    matches: &ArgMatches,
+    out: &mut O,
+) -> Result<(), String> {
+    match cli.output {
+        OutputFormat::Text => run_command(cli.command, out),
+        OutputFormat::Json => {
+            run_with_json_output(matches.subcommand_name().unwrap_or_default(), out, |text| {
+                run_command(cli.command, text)
+            })
+        }
+    }
+}
+
+fn run_command<O: std::io::Write>(command: Command, out: &mut O) -> Result<(), String> {
+    match command {
+        Command::AdvanceEpoch(cmd) => cmd.run(out),
+        Command::Alias(cmd) => cmd.run(out),
+        Command::CallFunction(cmd) => cmd.run(out),
+        Command::CallMethod(cmd) => cmd.run(out),
+        Command::ClaimXrd(cmd) => cmd.run(out),
+        Command::ExportPackageDefinition(cmd) => cmd.run(out),
+        Command::Fork(cmd) => cmd.run(out),
+        Command::GenerateKeyPair(cmd) => cmd.run(out),
+        Command::History(cmd) => cmd.run(out),
+        Command::Mint(cmd) => cmd.run(out),
+        Command::NewAccount(cmd) => cmd.run(out),
+        Command::NewSimpleBadge(cmd) => cmd.run(out).map(|_| ()),
+        Command::NewBadgeFixed(cmd) => cmd.run(out),
+        Command::NewBadgeMutable(cmd) => cmd.run(out),
+        Command::NewTokenFixed(cmd) => cmd.run(out),
+        Command::NewTokenMutable(cmd) => cmd.run(out),
+        Command::NewValidator(cmd) => cmd.run(out),
+        Command::Publish(cmd) => cmd.run(out),
+        Command::Record(cmd) => cmd.run(out),
+        Command::RegisterValidator(cmd) => cmd.run(out),
+        Command::Replay(cmd) => cmd.run(out),
+        Command::Reset(cmd) => cmd.run(out),
+        Command::Run(cmd) => cmd.run(out),
+        Command::SetCurrentEpoch(cmd) => cmd.run(out),
+        Command::SetCurrentTime(cmd) => cmd.run(out),
+        Command::SetDefaultAccount(cmd) => cmd.run(out),
+        Command::Shell(cmd) => cmd.run(out),
+        Command::ShowConfigs(cmd) => cmd.run(out),
+        Command::ShowLedger(cmd) => cmd.run(out),
+        Command::ShowTx(cmd) => cmd.run(out),
+        Command::Show(cmd) => cmd.run(out),
+        Command::Snapshot(cmd) => cmd.run(out),
+        Command::Stake(cmd) => cmd.run(out),
+        Command::Transfer(cmd) => cmd.run(out),
+        Command::Unstake(cmd) => cmd.run(out),
     }
 }
 
@@ -145,7 +243,19 @@ pub fn handle_system_transaction<O: std::io::Write>(
     } = SimulatorEnvironment::new()?;
 
     let nonce = get_nonce()?;
-    let unique_hash = hash(format!("Simulator system transaction: {}", nonce));
+    let recording = match get_active_recording()? {
+        Some(path) => Some((
+            path,
+            manifest
+                .clone()
+                .to_raw()
+                .map_err(Error::SborEncodeError)?
+                .to_vec(),
+            initial_proofs.clone(),
+        )),
+        None => None,
+    };
+    let unique_hash = system_transaction_hash(nonce);
     let transaction = manifest.into_transaction(unique_hash);
     let validator = TransactionValidator::new(&db, &NetworkDefinition::simulator());
 
@@ -160,14 +270,28 @@ pub fn handle_system_transaction<O: std::io::Write>(
             .map_err(Error::TransactionPrepareError)?,
     );
 
-    if print_receipt {
-        let encoder = AddressBech32Encoder::for_simulator();
-        let display_context = TransactionReceiptDisplayContextBuilder::new()
-            .encoder(&encoder)
-            .schema_lookup_from_db(&db)
-            .build();
-        writeln!(out, "{}", receipt.display(display_context)).map_err(Error::IOError)?;
+    if let (Some((path, raw_manifest, initial_proofs)), Some(outcome)) =
+        (recording, RecordedOutcome::from_receipt(&receipt))
+    {
+        record_step(
+            &path,
+            &RecordedStep::SystemTransaction(RecordedTransaction {
+                raw_manifest,
+                nonce,
+                initial_proofs,
+                outcome,
+            }),
+        )?;
     }
+
+    output_receipt(
+        &db,
+        TransactionId::TestTransaction(unique_hash),
+        nonce,
+        &receipt,
+        print_receipt,
+        out,
+    )?;
     drop(db);
 
     process_receipt(receipt)
@@ -219,27 +343,44 @@ pub fn handle_manifest<O: std::io::Write>(
                 .map(|e| NonFungibleGlobalId::from_public_key(e.public_key()))
                 .collect::<BTreeSet<NonFungibleGlobalId>>();
             let nonce = get_nonce()?;
+            let recording = match get_active_recording()? {
+                Some(path) => Some((
+                    path,
+                    manifest.to_raw().map_err(Error::SborEncodeError)?.to_vec(),
+                    initial_proofs.clone(),
+                )),
+                None => None,
+            };
             let validator = TransactionValidator::new(&db, &NetworkDefinition::simulator());
             let transaction =
                 TestTransaction::new_from_any_manifest(manifest, nonce, initial_proofs)?;
 
+            let executable = transaction
+                .into_executable(&validator)
+                .map_err(Error::TransactionPrepareError)?;
+            let id = TransactionId::TestTransaction(*executable.unique_hash());
             let receipt = execute_and_commit_transaction(
                 &mut db,
                 &vm_modules,
                 &ExecutionConfig::for_test_transaction().with_kernel_trace(trace),
-                transaction
-                    .into_executable(&validator)
-                    .map_err(Error::TransactionPrepareError)?,
+                executable,
             );
 
-            if print_receipt {
-                let encoder = AddressBech32Encoder::for_simulator();
-                let display_context = TransactionReceiptDisplayContextBuilder::new()
-                    .encoder(&encoder)
-                    .schema_lookup_from_db(&db)
-                    .build();
-                writeln!(out, "{}", receipt.display(display_context)).map_err(Error::IOError)?;
+            if let (Some((path, raw_manifest, initial_proofs)), Some(outcome)) =
+                (recording, RecordedOutcome::from_receipt(&receipt))
+            {
+                record_step(
+                    &path,
+                    &RecordedStep::Transaction(RecordedTransaction {
+                        raw_manifest,
+                        nonce,
+                        initial_proofs,
+                        outcome,
+                    }),
+                )?;
             }
+
+            output_receipt(&db, id, nonce, &receipt, print_receipt, out)?;
             drop(db);
 
             process_receipt(receipt)
@@ -249,6 +390,108 @@ pub fn handle_manifest<O: std::io::Write>(
     }
 }
 
+/// Executes a notarized V2 transaction, which may include subintents, against the simulator ledger.
+pub fn handle_notarized_transaction_v2<O: std::io::Write>(
+    transaction: DetailedNotarizedTransactionV2,
+    trace: bool,
+    print_receipt: bool,
+    out: &mut O,
+) -> Result<TransactionReceipt, String> {
+    let SimulatorEnvironment {
+        mut db,
+        vm_modules,
+        network_definition,
+    } = SimulatorEnvironment::new()?;
+
+    let nonce = get_nonce()?;
+    let recording = get_active_recording()?.map(|path| (path, transaction.raw.as_slice().to_vec()));
+    let id = TransactionId::Intent(transaction.transaction_hashes.transaction_intent_hash);
+    let validator = TransactionValidator::new(&db, &network_definition);
+    let executable = transaction
+        .into_executable(&validator)
+        .map_err(Error::TransactionValidationError)?;
+    let receipt = execute_and_commit_transaction(
+        &mut db,
+        &vm_modules,
+        &ExecutionConfig::for_notarized_transaction(network_definition).with_kernel_trace(trace),
+        executable,
+    );
+
+    if let (Some((path, raw_transaction)), Some(outcome)) =
+        (recording, RecordedOutcome::from_receipt(&receipt))
+    {
+        record_step(
+            &path,
+            &RecordedStep::NotarizedTransactionV2(RecordedNotarizedTransaction {
+                raw_transaction,
+                outcome,
+            }),
+        )?;
+    }
+
+    output_receipt(&db, id, nonce, &receipt, print_receipt, out)?;
+    drop(db);
+
+    process_receipt(receipt).map_err(|err| err.into())
+}
+
+/// Stores the receipt of a transaction, adds it to the JSON output and prints it if requested.
+fn output_receipt<O: std::io::Write>(
+    db: &SimulatorDatabase,
+    id: TransactionId,
+    nonce: u32,
+    receipt: &TransactionReceipt,
+    print_receipt: bool,
+    out: &mut O,
+) -> Result<(), Error> {
+    let encoder = AddressBech32Encoder::for_simulator();
+    if is_json_output() {
+        record_json_transaction(
+            &id,
+            receipt,
+            &TransactionReceiptDisplayContextBuilder::new()
+                .encoder(&encoder)
+                .schema_lookup_from_db(db)
+                .build(),
+        );
+    }
+    store_receipt(
+        id,
+        nonce,
+        receipt,
+        TransactionReceiptDisplayContextBuilder::new()
+            .encoder(&encoder)
+            .schema_lookup_from_db(db)
+            .use_ansi_colors(false)
+            .build(),
+    )?;
+
+    if print_receipt {
+        writeln!(out, "{}: {}", id.label(), id.to_display_string()).map_err(Error::IOError)?;
+        let display_context = TransactionReceiptDisplayContextBuilder::new()
+            .encoder(&encoder)
+            .schema_lookup_from_db(db)
+            .build();
+        writeln!(out, "{}", receipt.display(display_context)).map_err(Error::IOError)?;
+    }
+    Ok(())
+}
+
+/// Combines comma separated signing keys with the keys passed one by one, e.g. with `--signer`.
+pub fn merge_signing_keys(signing_keys: &Option<String>, signers: &[String]) -> Option<String> {
+    if signers.is_empty() {
+        return signing_keys.clone();
+    }
+    Some(
+        signing_keys
+            .iter()
+            .chain(signers.iter())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
 pub fn process_receipt(receipt: TransactionReceipt) -> Result<TransactionReceipt, Error> {
     match &receipt.result {
         TransactionResult::Commit(commit) => {
@@ -350,13 +593,54 @@ pub fn get_blueprint_id(component_address: ComponentAddress) -> Result<Blueprint
     Ok(object_info.blueprint_info.blueprint_id)
 }
 
+pub fn get_validator_state(
+    validator_address: ComponentAddress,
+) -> Result<ValidatorSubstate, Error> {
+    let SimulatorEnvironment { db, .. } = SimulatorEnvironment::new()?;
+
+    let system_reader = SystemDatabaseReader::new(&db);
+    system_reader
+        .read_typed_object_field::<ValidatorStateFieldPayload>(
+            validator_address.as_node_id(),
+            ModuleId::Main,
+            ValidatorField::State.field_index(),
+        )
+        .map(|state| state.fully_update_and_into_latest_version())
+        .map_err(|_| Error::ValidatorNotFound(validator_address))
+}
+
+/// The local id of the owner badge of a validator, which is minted to its creator.
+pub fn validator_owner_badge_id(validator_address: ComponentAddress) -> NonFungibleLocalId {
+    NonFungibleLocalId::bytes(validator_address.as_node_id().0).unwrap()
+}
+
 pub fn db_upsert_timestamps(
     milli_timestamp: ProposerMilliTimestampSubstate,
     minute_timestamp: ProposerMinuteTimestampSubstate,
 ) -> Result<(), Error> {
     let SimulatorEnvironment { mut db, .. } = SimulatorEnvironment::new()?;
 
-    let mut writer = SystemDatabaseWriter::new(&mut db);
+    if let Some(path) = get_active_recording()? {
+        record_step(
+            &path,
+            &RecordedStep::SetCurrentTime {
+                epoch_milli: milli_timestamp.epoch_milli,
+                epoch_minute: minute_timestamp.epoch_minute,
+            },
+        )?;
+    }
+    write_timestamps(&mut db, milli_timestamp, minute_timestamp);
+
+    Ok(())
+}
+
+/// Overwrites the proposer timestamps of the consensus manager, see [`db_upsert_timestamps`].
+pub fn write_timestamps<S: SubstateDatabase + CommittableSubstateDatabase>(
+    db: &mut S,
+    milli_timestamp: ProposerMilliTimestampSubstate,
+    minute_timestamp: ProposerMinuteTimestampSubstate,
+) {
+    let mut writer = SystemDatabaseWriter::new(db);
 
     writer
         .write_typed_object_field(
@@ -379,14 +663,34 @@ pub fn db_upsert_timestamps(
             ),
         )
         .unwrap();
+}
 
-    Ok(())
+/// Reads the current epoch from the consensus manager, without committing a transaction.
+pub fn read_current_epoch(db: &SimulatorDatabase) -> Result<Epoch, Error> {
+    SystemDatabaseReader::new(db)
+        .read_typed_object_field::<ConsensusManagerStateFieldPayload>(
+            CONSENSUS_MANAGER.as_node_id(),
+            ModuleId::Main,
+            ConsensusManagerField::State.field_index(),
+        )
+        .map(|state| state.fully_update_and_into_latest_version().epoch)
+        .map_err(|_| Error::ConsensusManagerNotFound)
 }
 
 pub fn db_upsert_epoch(epoch: Epoch) -> Result<(), Error> {
     let SimulatorEnvironment { mut db, .. } = SimulatorEnvironment::new()?;
 
-    let reader = SystemDatabaseReader::new(&db);
+    if let Some(path) = get_active_recording()? {
+        record_step(&path, &RecordedStep::SetCurrentEpoch(epoch))?;
+    }
+    write_epoch(&mut db, epoch);
+
+    Ok(())
+}
+
+/// Overwrites the epoch of the consensus manager, see [`db_upsert_epoch`].
+pub fn write_epoch<S: SubstateDatabase + CommittableSubstateDatabase>(db: &mut S, epoch: Epoch) {
+    let reader = SystemDatabaseReader::new(db);
 
     let mut consensus_mgr_state = reader
         .read_typed_object_field::<ConsensusManagerStateFieldPayload>(
@@ -408,7 +712,7 @@ pub fn db_upsert_epoch(epoch: Epoch) -> Result<(), Error> {
 
     consensus_mgr_state.epoch = epoch;
 
-    let mut writer = SystemDatabaseWriter::new(&mut db);
+    let mut writer = SystemDatabaseWriter::new(db);
 
     writer
         .write_typed_object_field(
@@ -418,8 +722,11 @@ pub fn db_upsert_epoch(epoch: Epoch) -> Result<(), Error> {
             ConsensusManagerStateFieldPayload::from_content_source(consensus_mgr_state),
         )
         .unwrap();
+}
 
-    Ok(())
+/// The unique hash of the simulator system transaction with the given nonce.
+pub fn system_transaction_hash(nonce: u32) -> Hash {
+    hash(format!("Simulator system transaction: {}", nonce))
 }
 
 #[cfg(test)]
@@ -434,6 +741,7 @@ mod tests {
             network: None,
             manifest: None,
             trace: false,
+            alias: None,
         };
         assert!(new_account.run(&mut out).is_ok());
         let cmd = Show { address: None };
@@ -456,6 +764,12 @@ mod tests {
                 let manifest = r#"CALL_METHOD ComponentAddress("${  faucet  }") "free";\nTAKE_ALL_FROM_WORKTOP ResourceAddress("${xrd}") Bucket("bucket1");\n"#;
                 let after = r#"CALL_METHOD ComponentAddress("system_sim1qsqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqpql4sktx") "free";\nTAKE_ALL_FROM_WORKTOP ResourceAddress("resource_sim1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqzqu57yag") Bucket("bucket1");\n"#;
                 assert_eq!(Run::pre_process_manifest(manifest), after);
+
+                let variables = indexmap!("xrd".to_owned() => "overridden".to_owned());
+                assert!(
+                    Run::pre_process_manifest_with_variables(manifest, &variables)
+                        .contains(r#"ResourceAddress("overridden")"#)
+                );
             },
         );
     }
@@ -484,6 +798,19 @@ mod tests {
         assert!(make_cmd(public_key.to_string()).run(&mut out).is_err());
     }
 
+    #[test]
+    fn merge_signing_keys_appends_signers() {
+        assert_eq!(merge_signing_keys(&None, &[]), None);
+        assert_eq!(
+            merge_signing_keys(&Some("a".to_owned()), &["b".to_owned(), "c".to_owned()]),
+            Some("a,b,c".to_owned())
+        );
+        assert_eq!(
+            merge_signing_keys(&None, &["b".to_owned()]),
+            Some("b".to_owned())
+        );
+    }
+
     #[test]
     fn serial_resim_command_tests() {
         test_no_value();
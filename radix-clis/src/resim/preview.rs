@@ -0,0 +1,431 @@
+use crate::resim::*;
+use colored::*;
+use radix_engine::system::system_modules::execution_trace::{
+    ResourceSpecifier as TracedResourceSpecifier, WorktopChange,
+};
+use radix_engine::transaction::*;
+use radix_engine::vm::DefaultVmModules;
+use radix_substate_store_impls::substate_database_overlay::SubstateDatabaseOverlay;
+use radix_substate_store_interface::interface::*;
+use radix_substate_store_queries::query::*;
+use radix_transactions::validation::TransactionValidator;
+
+/// The number of epochs for which the previewed intents are valid.
+const PREVIEW_EPOCH_WINDOW: u64 = 100;
+
+/// The flags used for every preview: the epoch is not checked, as the manifest could be previewed
+/// at any point, but auth is, so that the preview fails like the real transaction would.
+fn preview_flags() -> PreviewFlags {
+    PreviewFlags {
+        use_free_credit: false,
+        assume_all_signature_proofs: false,
+        skip_epoch_check: true,
+        disable_auth: false,
+    }
+}
+
+/// Executes a manifest with the preview executor and prints what it would do.
+/// Nothing is committed to the simulator ledger.
+pub fn handle_preview<O: std::io::Write>(
+    manifest: AnyManifest,
+    signing_keys: &Option<String>,
+    trace: bool,
+    out: &mut O,
+) -> Result<(), String> {
+    manifest
+        .validate(ValidationRuleset::all())
+        .map_err(|err| format!("{err:?}"))?;
+    let signer_public_keys = get_signing_keys(signing_keys)?
+        .iter()
+        .map(|key| key.public_key().into())
+        .collect::<Vec<PublicKey>>();
+
+    let SimulatorEnvironment {
+        db,
+        vm_modules,
+        network_definition,
+    } = SimulatorEnvironment::new()?;
+    let current_epoch = read_current_epoch(&db)?;
+    let end_epoch = current_epoch
+        .after(PREVIEW_EPOCH_WINDOW)
+        .unwrap_or(current_epoch);
+    // The notary is not a signatory, so any key will do
+    let notary_public_key = Secp256k1PrivateKey::from_u64(1)
+        .unwrap()
+        .public_key()
+        .into();
+
+    let receipt = match manifest {
+        AnyManifest::V1(manifest) => {
+            let (instructions, blobs) = manifest.for_intent();
+            let preview_intent = PreviewIntentV1 {
+                intent: IntentV1 {
+                    header: TransactionHeaderV1 {
+                        network_id: network_definition.id,
+                        start_epoch_inclusive: current_epoch,
+                        end_epoch_exclusive: end_epoch,
+                        nonce: get_nonce()?,
+                        notary_public_key,
+                        notary_is_signatory: false,
+                        tip_percentage: 0,
+                    },
+                    instructions,
+                    blobs,
+                    message: MessageV1::None,
+                },
+                signer_public_keys,
+                flags: preview_flags(),
+            };
+            execute_preview(&db, &vm_modules, &network_definition, preview_intent, trace)
+                .map_err(Error::PreviewError)?
+        }
+        AnyManifest::V2(manifest) => {
+            if !manifest.children.is_empty() {
+                return Err(Error::SubintentsNotSupportedInPreview.into());
+            }
+            let preview_transaction = TransactionBuilder::new_v2()
+                .intent_header(IntentHeaderV2 {
+                    network_id: network_definition.id,
+                    start_epoch_inclusive: current_epoch,
+                    end_epoch_exclusive: end_epoch,
+                    min_proposer_timestamp_inclusive: None,
+                    max_proposer_timestamp_exclusive: None,
+                    intent_discriminator: u64::from(get_nonce()?),
+                })
+                .manifest(manifest)
+                .transaction_header(TransactionHeaderV2 {
+                    notary_public_key,
+                    notary_is_signatory: false,
+                    tip_basis_points: 0,
+                })
+                .build_preview_transaction_no_validate(signer_public_keys);
+            execute_preview_transaction_v2(&db, &vm_modules, preview_transaction, trace)?
+        }
+        AnyManifest::SystemV1(_) | AnyManifest::SubintentV2(_) => {
+            return Err(Error::PreviewNotSupported.into());
+        }
+    };
+
+    print_preview_report(&db, &receipt, out)?;
+    Ok(())
+}
+
+/// Previews a V2 transaction, which may include subintents, and prints what it would do.
+pub fn handle_preview_transaction_v2<O: std::io::Write>(
+    preview_transaction: PreviewTransactionV2,
+    trace: bool,
+    out: &mut O,
+) -> Result<(), String> {
+    let SimulatorEnvironment { db, vm_modules, .. } = SimulatorEnvironment::new()?;
+    let receipt = execute_preview_transaction_v2(&db, &vm_modules, preview_transaction, trace)?;
+    print_preview_report(&db, &receipt, out)?;
+    Ok(())
+}
+
+fn execute_preview_transaction_v2(
+    db: &SimulatorDatabase,
+    vm_modules: &DefaultVmModules,
+    preview_transaction: PreviewTransactionV2,
+    trace: bool,
+) -> Result<TransactionReceipt, Error> {
+    let network_definition = NetworkDefinition::simulator();
+    let validator = TransactionValidator::new(db, &network_definition);
+    let executable = preview_transaction
+        .prepare_and_validate(&validator)
+        .map_err(Error::TransactionValidationError)?
+        .create_executable(preview_flags());
+    Ok(execute_transaction(
+        db,
+        vm_modules,
+        &ExecutionConfig::for_preview(network_definition).with_kernel_trace(trace),
+        executable,
+    ))
+}
+
+fn print_preview_report<O: std::io::Write>(
+    db: &SimulatorDatabase,
+    receipt: &TransactionReceipt,
+    out: &mut O,
+) -> Result<(), Error> {
+    let encoder = AddressBech32Encoder::for_simulator();
+    let display_context = TransactionReceiptDisplayContextBuilder::new()
+        .encoder(&encoder)
+        .schema_lookup_from_db(db)
+        .build();
+    record_json_value("receipt", receipt_to_json(receipt, &display_context));
+
+    writeln!(
+        out,
+        "{}",
+        "Preview only, nothing has been committed.".yellow()
+    )
+    .map_err(Error::IOError)?;
+    let commit = match &receipt.result {
+        TransactionResult::Commit(commit) => {
+            match &commit.outcome {
+                TransactionOutcome::Success(_) => {
+                    writeln!(out, "{} {}", "Status:".green().bold(), "SUCCESS".green())
+                }
+                TransactionOutcome::Failure(error) => writeln!(
+                    out,
+                    "{} {}",
+                    "Status:".green().bold(),
+                    format!("FAILURE: {:?}", error).red()
+                ),
+            }
+            .map_err(Error::IOError)?;
+            commit
+        }
+        TransactionResult::Reject(rejection) => {
+            writeln!(
+                out,
+                "{} {}",
+                "Status:".green().bold(),
+                format!("REJECTED: {}", rejection.reason.to_string(&encoder)).red()
+            )
+            .map_err(Error::IOError)?;
+            return Ok(());
+        }
+        TransactionResult::Abort(abort) => {
+            writeln!(
+                out,
+                "{} {}",
+                "Status:".green().bold(),
+                format!("ABORTED: {:?}", abort.reason).red()
+            )
+            .map_err(Error::IOError)?;
+            return Ok(());
+        }
+    };
+
+    let fee_summary = &receipt.fee_summary;
+    writeln!(
+        out,
+        "{} {} XRD",
+        "Fees:".green().bold(),
+        fee_summary.total_cost()
+    )
+    .map_err(Error::IOError)?;
+    let fee_rows = [
+        format!(
+            "Network execution: {} XRD, {} execution cost units",
+            fee_summary.total_execution_cost_in_xrd,
+            fee_summary.total_execution_cost_units_consumed
+        ),
+        format!(
+            "Network finalization: {} XRD, {} finalization cost units",
+            fee_summary.total_finalization_cost_in_xrd,
+            fee_summary.total_finalization_cost_units_consumed
+        ),
+        format!("Tip: {} XRD", fee_summary.total_tipping_cost_in_xrd),
+        format!(
+            "Network Storage: {} XRD",
+            fee_summary.total_storage_cost_in_xrd
+        ),
+        format!("Royalties: {} XRD", fee_summary.total_royalty_cost_in_xrd),
+    ];
+    for (last, row) in fee_rows.iter().identify_last() {
+        writeln!(out, "{} {}", list_item_prefix(last), row).map_err(Error::IOError)?;
+    }
+
+    writeln!(out, "{}", "Worktop Changes:".green().bold()).map_err(Error::IOError)?;
+    let worktop_changes = commit
+        .execution_trace
+        .as_ref()
+        .map(|execution_trace| execution_trace.worktop_changes())
+        .unwrap_or_default();
+    for (last, (instruction_index, changes)) in worktop_changes.iter().identify_last() {
+        writeln!(
+            out,
+            "{} Instruction #{}",
+            list_item_prefix(last),
+            instruction_index
+        )
+        .map_err(Error::IOError)?;
+        for (last_change, change) in changes.iter().identify_last() {
+            let (sign, specifier) = match change {
+                WorktopChange::Put(specifier) => ("+", specifier),
+                WorktopChange::Take(specifier) => ("-", specifier),
+            };
+            let (resource_address, amount) = match specifier {
+                TracedResourceSpecifier::Amount(resource_address, amount) => {
+                    (resource_address, amount.to_string())
+                }
+                TracedResourceSpecifier::Ids(resource_address, ids) => (
+                    resource_address,
+                    ids.iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            };
+            writeln!(
+                out,
+                "{}  {} {}{} {}",
+                if last { " " } else { "│" },
+                list_item_prefix(last_change),
+                sign,
+                amount,
+                resource_address.display(&encoder)
+            )
+            .map_err(Error::IOError)?;
+        }
+    }
+
+    writeln!(out, "{}", "Balance Changes:".green().bold()).map_err(Error::IOError)?;
+    let balance_changes = sum_balance_changes_by_entity(db, commit);
+    record_json_value(
+        "balance_changes",
+        balance_changes_to_json(&balance_changes, &encoder),
+    );
+    for (last, (node_id, changes)) in balance_changes.iter().identify_last() {
+        writeln!(
+            out,
+            "{} {}",
+            list_item_prefix(last),
+            node_id.display(&encoder)
+        )
+        .map_err(Error::IOError)?;
+        for (last_change, (resource_address, change)) in changes.iter().identify_last() {
+            let change = match change {
+                BalanceChange::Fungible(amount) => amount.to_string(),
+                BalanceChange::NonFungible { added, removed } => format!(
+                    "+{{{}}} -{{{}}}",
+                    added
+                        .iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    removed
+                        .iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            writeln!(
+                out,
+                "{}  {} {}: {}",
+                if last { " " } else { "│" },
+                list_item_prefix(last_change),
+                resource_address.display(&encoder),
+                change
+            )
+            .map_err(Error::IOError)?;
+        }
+    }
+
+    writeln!(out, "{}", "Events:".green().bold()).map_err(Error::IOError)?;
+    for (last, (event_type_identifier, event_data)) in
+        commit.application_events.iter().identify_last()
+    {
+        writeln!(
+            out,
+            "{} {} from {}: {}",
+            list_item_prefix(last),
+            event_type_identifier.1,
+            match &event_type_identifier.0 {
+                Emitter::Function(blueprint_id) => format!(
+                    "{}:{}",
+                    blueprint_id.package_address.display(&encoder),
+                    blueprint_id.blueprint_name
+                ),
+                Emitter::Method(node_id, module_id) => {
+                    format!("{} ({:?})", node_id.display(&encoder), module_id)
+                }
+            },
+            EventPayload::new(
+                event_type_identifier,
+                event_data,
+                &commit.system_structure,
+                &display_context
+            )
+            .to_display_string(&display_context)
+        )
+        .map_err(Error::IOError)?;
+    }
+    Ok(())
+}
+
+fn balance_changes_to_json(
+    balance_changes: &IndexMap<NodeId, IndexMap<ResourceAddress, BalanceChange>>,
+    encoder: &AddressBech32Encoder,
+) -> serde_json::Value {
+    balance_changes
+        .iter()
+        .map(|(node_id, changes)| {
+            let changes = changes
+                .iter()
+                .map(|(resource_address, change)| {
+                    let change = match change {
+                        BalanceChange::Fungible(amount) => {
+                            serde_json::Value::from(amount.to_string())
+                        }
+                        BalanceChange::NonFungible { added, removed } => serde_json::json!({
+                            "added": added.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+                            "removed": removed.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+                        }),
+                    };
+                    (resource_address.display(encoder).to_string(), change)
+                })
+                .collect();
+            (
+                node_id.display(encoder).to_string(),
+                serde_json::Value::Object(changes),
+            )
+        })
+        .collect::<serde_json::Map<String, serde_json::Value>>()
+        .into()
+}
+
+/// Sums the balance changes of the vaults owned by each entity whose balances changed, by
+/// traversing the entity subtrees in the ledger state the previewed transaction would produce.
+fn sum_balance_changes_by_entity(
+    db: &SimulatorDatabase,
+    commit: &CommitResult,
+) -> IndexMap<NodeId, IndexMap<ResourceAddress, BalanceChange>> {
+    let mut overlay = SubstateDatabaseOverlay::new_unmergeable(db);
+    overlay.commit(&commit.state_updates.create_database_updates());
+
+    let traced_entities = commit
+        .execution_trace
+        .iter()
+        .flat_map(|execution_trace| execution_trace.resource_changes.values().flatten())
+        .map(|resource_change| resource_change.node_id);
+    let updated_entities = commit
+        .state_updates
+        .by_node
+        .keys()
+        .filter(|node_id| node_id.is_global_component())
+        .cloned();
+    let entities = traced_entities
+        .chain(updated_entities)
+        .filter(|node_id| node_id.is_global())
+        .collect::<IndexSet<_>>();
+
+    let vault_balance_changes = commit.vault_balance_changes();
+    let mut balance_changes = index_map_new();
+    for node_id in entities {
+        let mut vault_finder = VaultFinder::new();
+        StateTreeTraverser::new(&overlay, &mut vault_finder, 100).traverse_subtree(None, node_id);
+        let changes = vault_finder
+            .to_vaults()
+            .into_iter()
+            .filter_map(|(resource_address, vault_ids)| {
+                vault_ids
+                    .into_iter()
+                    .filter_map(|vault_id| vault_balance_changes.get(&vault_id))
+                    .map(|(_, change)| change.clone())
+                    .reduce(|mut left, right| {
+                        left += right;
+                        left
+                    })
+                    .map(|change| (resource_address, change))
+            })
+            .collect::<IndexMap<_, _>>();
+        if !changes.is_empty() {
+            balance_changes.insert(node_id, changes);
+        }
+    }
+    balance_changes
+}
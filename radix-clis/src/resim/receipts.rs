@@ -0,0 +1,161 @@
+use crate::resim::*;
+use radix_engine::transaction::*;
+
+pub const RECEIPTS_DIR_UNDER_DATA_DIR: &str = "receipts";
+
+/// The identifier of a committed transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScryptoSbor)]
+pub enum TransactionId {
+    /// A test transaction, as committed by most commands. It has no intent, so it is identified by
+    /// the pseudo intent hash `hash("Test transaction: <nonce>")`, which is only unique per ledger.
+    TestTransaction(Hash),
+    /// A notarized transaction, identified by its transaction intent hash.
+    Intent(TransactionIntentHash),
+}
+
+impl TransactionId {
+    pub fn as_hash(&self) -> &Hash {
+        match self {
+            Self::TestTransaction(hash) => hash,
+            Self::Intent(intent_hash) => intent_hash.as_hash(),
+        }
+    }
+
+    /// The label the id is displayed with, which tells pseudo hashes apart from intent hashes.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::TestTransaction(_) => "Test Transaction Hash",
+            Self::Intent(_) => "Transaction ID",
+        }
+    }
+
+    /// The hash in hex for a test transaction, or the Bech32m encoded intent hash otherwise.
+    pub fn to_display_string(&self) -> String {
+        match self {
+            Self::TestTransaction(hash) => hash.to_string(),
+            Self::Intent(intent_hash) => intent_hash
+                .display(&TransactionHashBech32Encoder::for_simulator())
+                .to_string(),
+        }
+    }
+}
+
+/// An entry of the transaction history, in the order the transactions were committed.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct TransactionHistoryEntry {
+    pub id: TransactionId,
+    pub nonce: u32,
+    pub success: bool,
+    pub total_cost: Decimal,
+    /// All nodes with substate updates, used to find the transactions touching an entity.
+    pub touched_nodes: IndexSet<NodeId>,
+}
+
+/// A committed receipt, persisted so it can be looked up by its id.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct StoredReceipt {
+    pub id: TransactionId,
+    pub nonce: u32,
+    pub fee_summary: TransactionFeeSummary,
+    pub state_update_summary: StateUpdateSummary,
+    /// The receipt as displayed by resim, including the decoded events and outputs.
+    pub rendered: String,
+}
+
+fn get_receipts_dir() -> Result<PathBuf, Error> {
+    let mut path = get_data_dir()?;
+    path.push(RECEIPTS_DIR_UNDER_DATA_DIR);
+    Ok(path)
+}
+
+fn get_history_path() -> Result<PathBuf, Error> {
+    Ok(get_receipts_dir()?.join("history.sbor"))
+}
+
+fn get_receipt_path(hash: &Hash) -> Result<PathBuf, Error> {
+    Ok(get_receipts_dir()?.join(format!("{}.sbor", hash)))
+}
+
+pub fn get_transaction_history() -> Result<Vec<TransactionHistoryEntry>, Error> {
+    read_sbor_records(&get_history_path()?)
+}
+
+/// Looks up a stored receipt by the hash of its [`TransactionId`].
+pub fn get_stored_receipt(hash: &Hash) -> Result<StoredReceipt, Error> {
+    let path = get_receipt_path(hash)?;
+    if !path.exists() {
+        return Err(Error::TransactionNotFound(*hash));
+    }
+    scrypto_decode(&fs::read(&path).map_err(|err| Error::IOErrorAtPath(err, path))?)
+        .map_err(Error::SborDecodeError)
+}
+
+/// Persists a receipt and appends it to the transaction history, if it was committed.
+pub fn store_receipt(
+    id: TransactionId,
+    nonce: u32,
+    receipt: &TransactionReceipt,
+    display_context: TransactionReceiptDisplayContext,
+) -> Result<(), Error> {
+    let TransactionResult::Commit(commit) = &receipt.result else {
+        return Ok(());
+    };
+
+    let stored_receipt = StoredReceipt {
+        id,
+        nonce,
+        fee_summary: receipt.fee_summary.clone(),
+        state_update_summary: commit.state_update_summary.clone(),
+        rendered: receipt.display(display_context).to_string(),
+    };
+    let path = get_receipt_path(id.as_hash())?;
+    write_ensuring_folder_exists(
+        &path,
+        scrypto_encode(&stored_receipt).map_err(Error::SborEncodeError)?,
+    )
+    .map_err(|err| Error::IOErrorAtPath(err, path))?;
+
+    append_sbor_record(
+        &get_history_path()?,
+        &TransactionHistoryEntry {
+            id,
+            nonce,
+            success: commit.outcome.is_success(),
+            total_cost: receipt.fee_summary.total_cost(),
+            touched_nodes: commit.state_updates.by_node.keys().cloned().collect(),
+        },
+    )
+}
+
+/// Parses the hash of a [`TransactionId`], either a Bech32m encoded intent hash or a hash in hex.
+pub fn parse_transaction_hash(s: &str) -> Result<Hash, Error> {
+    TransactionHashBech32Decoder::for_simulator()
+        .validate_and_decode::<TransactionIntentHash>(s)
+        .ok()
+        .map(|intent_hash| *intent_hash.as_hash())
+        .or_else(|| Hash::from_str(s).ok())
+        .ok_or_else(|| Error::InvalidTransactionHash(s.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transaction_ids_are_labelled_by_kind() {
+        let hash = hash("Test transaction: 3");
+        let test_transaction = TransactionId::TestTransaction(hash);
+        let intent = TransactionId::Intent(TransactionIntentHash::from_hash(hash));
+
+        assert_eq!(test_transaction.label(), "Test Transaction Hash");
+        assert_eq!(test_transaction.to_display_string(), hash.to_string());
+        assert_eq!(intent.label(), "Transaction ID");
+        assert!(intent.to_display_string().starts_with("txid_sim1"));
+        assert_eq!(
+            parse_transaction_hash(&intent.to_display_string()).unwrap(),
+            hash
+        );
+        assert_eq!(parse_transaction_hash(&hash.to_string()).unwrap(), hash);
+        assert!(parse_transaction_hash("not a hash").is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::resim::*;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+/// Appends a record to a file of length-prefixed SBOR records, creating the file if needed.
+///
+/// Appending keeps the cost of saving a record independent of how many records came before it.
+pub fn append_sbor_record<T: ScryptoEncode + ?Sized>(path: &Path, record: &T) -> Result<(), Error> {
+    let bytes = scrypto_encode(record).map_err(Error::SborEncodeError)?;
+    let length = u32::try_from(bytes.len()).expect("SBOR payloads are limited in size");
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| Error::IOErrorAtPath(err, path.to_owned()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| Error::IOErrorAtPath(err, path.to_owned()))?;
+    let mut entry = Vec::with_capacity(4 + bytes.len());
+    entry.extend_from_slice(&length.to_le_bytes());
+    entry.extend_from_slice(&bytes);
+    file.write_all(&entry)
+        .map_err(|err| Error::IOErrorAtPath(err, path.to_owned()))
+}
+
+/// Reads all records of a file written with [`append_sbor_record`], or none if there is no file.
+pub fn read_sbor_records<T: ScryptoDecode>(path: &Path) -> Result<Vec<T>, Error> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let bytes = fs::read(path).map_err(|err| Error::IOErrorAtPath(err, path.to_owned()))?;
+
+    let mut records = vec![];
+    let mut remaining = bytes.as_slice();
+    while !remaining.is_empty() {
+        let Some((length, rest)) = remaining.split_first_chunk::<4>() else {
+            return Err(Error::SborDecodeError(DecodeError::BufferUnderflow {
+                required: 4,
+                remaining: remaining.len(),
+            }));
+        };
+        let length = u32::from_le_bytes(*length) as usize;
+        if rest.len() < length {
+            return Err(Error::SborDecodeError(DecodeError::BufferUnderflow {
+                required: length,
+                remaining: rest.len(),
+            }));
+        }
+        let (record, rest) = rest.split_at(length);
+        records.push(scrypto_decode(record).map_err(Error::SborDecodeError)?);
+        remaining = rest;
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appended_records_are_read_back_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.sbor");
+
+        assert_eq!(
+            read_sbor_records::<String>(&path).unwrap(),
+            Vec::<String>::new()
+        );
+        append_sbor_record(&path, "first").unwrap();
+        append_sbor_record(&path, "second").unwrap();
+
+        assert_eq!(
+            read_sbor_records::<String>(&path).unwrap(),
+            vec!["first".to_owned(), "second".to_owned()]
+        );
+    }
+
+    #[test]
+    fn truncated_record_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.sbor");
+        append_sbor_record(&path, "first").unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+
+        assert!(matches!(
+            read_sbor_records::<String>(&path),
+            Err(Error::SborDecodeError(DecodeError::BufferUnderflow { .. }))
+        ));
+    }
+}
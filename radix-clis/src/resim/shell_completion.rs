@@ -0,0 +1,259 @@
+use crate::resim::*;
+use regex::Regex;
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+use std::cell::RefCell;
+
+/// The tab-completion of `resim shell`.
+///
+/// It completes command names, aliases, the addresses of the entities in the ledger, the
+/// blueprints, functions and methods which can be called on them, and the buckets named by the
+/// manifest being built.
+#[derive(Default)]
+pub struct ShellHelper {
+    commands: Vec<String>,
+    /// The aliases and entity addresses, listed from the ledger when first needed after a change
+    addresses: RefCell<Option<Vec<String>>>,
+    bucket_names: Vec<String>,
+    /// The callable names looked up from the blueprint schemas, which never change once published
+    callables: RefCell<IndexMap<String, Vec<String>>>,
+}
+
+impl ShellHelper {
+    pub fn new(commands: Vec<String>) -> Self {
+        Self {
+            commands,
+            ..Default::default()
+        }
+    }
+
+    /// Forgets the listed addresses, as a command may have created entities or aliases.
+    ///
+    /// Listing the entities scans the whole ledger, so it is only done again once an address
+    /// needs to be completed.
+    pub fn invalidate_addresses(&mut self) {
+        self.addresses.replace(None);
+    }
+
+    fn addresses(&self) -> Vec<String> {
+        if let Some(addresses) = self.addresses.borrow().as_ref() {
+            return addresses.clone();
+        }
+        // Failed lookups are not cached, as with the callables
+        let Ok(SimulatorEnvironment { db, .. }) = SimulatorEnvironment::new() else {
+            return vec![];
+        };
+        let addresses = Self::list_addresses(&db);
+        self.addresses.replace(Some(addresses.clone()));
+        addresses
+    }
+
+    fn list_addresses(substate_db: &SimulatorDatabase) -> Vec<String> {
+        let encoder = AddressBech32Encoder::for_simulator();
+        let (packages, components, resources) = ShowLedger::list_entities(substate_db);
+        get_aliases()
+            .unwrap_or_default()
+            .keys()
+            .map(|name| format!("@{}", name))
+            .chain(
+                packages
+                    .iter()
+                    .map(|address| address.display(&encoder).to_string()),
+            )
+            .chain(
+                components
+                    .iter()
+                    .map(|address| address.display(&encoder).to_string()),
+            )
+            .chain(
+                resources
+                    .iter()
+                    .map(|address| address.display(&encoder).to_string()),
+            )
+            .collect()
+    }
+
+    pub fn refresh_bucket_names(&mut self, manifest: &str) {
+        let re = Regex::new(r#"Bucket\("([^"]+)"\)"#).unwrap();
+        self.bucket_names = re
+            .captures_iter(manifest)
+            .map(|caps| caps[1].to_owned())
+            .collect::<IndexSet<_>>()
+            .into_iter()
+            .collect();
+    }
+
+    /// Returns the start of the word under the cursor and the candidates to complete it.
+    fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .rfind(|c: char| c.is_whitespace() || c == '"' || c == '(' || c == ',')
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        let word = &before[start..];
+        let previous = before[..start].split_whitespace().collect::<Vec<_>>();
+
+        let options = if before[..start].ends_with("Bucket(\"") {
+            self.bucket_names.clone()
+        } else {
+            match previous.as_slice() {
+                [] => self.commands.clone(),
+                ["call-function", package] => self.blueprints(package),
+                ["call-function", package, blueprint] => self.functions(package, blueprint),
+                ["call-method", component] => self.methods(component),
+                ["CALL_FUNCTION", package, "\""] => self.blueprints(unwrap_address(package)),
+                ["CALL_FUNCTION", package, blueprint, "\""] => {
+                    self.functions(unwrap_address(package), blueprint.trim_matches('"'))
+                }
+                ["CALL_METHOD", component, "\""] => self.methods(unwrap_address(component)),
+                _ => self.addresses(),
+            }
+        };
+
+        let candidates = options
+            .into_iter()
+            .filter(|option| option.starts_with(word))
+            .collect();
+        (start, candidates)
+    }
+
+    fn blueprints(&self, package: &str) -> Vec<String> {
+        self.cached(format!("blueprints {}", package), || {
+            let package_address = SimulatorPackageAddress::from_str(package).ok()?.0;
+            let definition = export_package_schema(package_address).ok()?;
+            Some(definition.keys().map(|key| key.blueprint.clone()).collect())
+        })
+    }
+
+    fn functions(&self, package: &str, blueprint: &str) -> Vec<String> {
+        self.cached(format!("functions {} {}", package, blueprint), || {
+            let package_address = SimulatorPackageAddress::from_str(package).ok()?.0;
+            let interface = export_blueprint_interface(package_address, blueprint).ok()?;
+            Some(
+                interface
+                    .functions
+                    .iter()
+                    .filter(|(_, schema)| schema.receiver.is_none())
+                    .map(|(name, _)| name.clone())
+                    .collect(),
+            )
+        })
+    }
+
+    fn methods(&self, component: &str) -> Vec<String> {
+        self.cached(format!("methods {}", component), || {
+            let component_address = SimulatorComponentAddress::from_str(component).ok()?.0;
+            let blueprint_id = export_object_info(component_address)
+                .ok()?
+                .blueprint_info
+                .blueprint_id;
+            let interface = export_blueprint_interface(
+                blueprint_id.package_address,
+                &blueprint_id.blueprint_name,
+            )
+            .ok()?;
+            Some(
+                interface
+                    .functions
+                    .iter()
+                    .filter(|(_, schema)| schema.receiver.is_some())
+                    .map(|(name, _)| name.clone())
+                    .collect(),
+            )
+        })
+    }
+
+    fn cached<F>(&self, key: String, lookup: F) -> Vec<String>
+    where
+        F: FnOnce() -> Option<Vec<String>>,
+    {
+        if let Some(names) = self.callables.borrow().get(&key) {
+            return names.clone();
+        }
+        // Failed lookups are not cached, as the entity may not have been created yet
+        let names = lookup().unwrap_or_default();
+        if !names.is_empty() {
+            self.callables.borrow_mut().insert(key, names.clone());
+        }
+        names
+    }
+}
+
+/// Extracts the address from a manifest value such as `Address("...")`.
+fn unwrap_address(value: &str) -> &str {
+    value
+        .trim_start_matches("Address(")
+        .trim_end_matches(')')
+        .trim_matches('"')
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.candidates(line, pos))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completes_commands_addresses_and_buckets() {
+        let mut helper = ShellHelper::new(vec!["call-method".to_owned(), "commit".to_owned()]);
+        helper.addresses.replace(Some(vec![
+            "account_sim1abc".to_owned(),
+            "resource_sim1xyz".to_owned(),
+        ]));
+        helper.refresh_bucket_names(
+            r#"TAKE_ALL_FROM_WORKTOP Address("resource_sim1xyz") Bucket("xrd");"#,
+        );
+
+        assert_eq!(
+            helper.candidates("c", 1),
+            (0, vec!["call-method".to_owned(), "commit".to_owned()])
+        );
+        assert_eq!(
+            helper.candidates("show acc", 8),
+            (5, vec!["account_sim1abc".to_owned()])
+        );
+        let line = r#"CALL_METHOD Address("res"#;
+        assert_eq!(
+            helper.candidates(line, line.len()),
+            (21, vec!["resource_sim1xyz".to_owned()])
+        );
+        let line = r#"DEPOSIT Bucket(""#;
+        assert_eq!(
+            helper.candidates(line, line.len()),
+            (line.len(), vec!["xrd".to_owned()])
+        );
+    }
+
+    #[test]
+    fn extracts_addresses_from_manifest_values() {
+        assert_eq!(
+            unwrap_address(r#"Address("component_sim1abc")"#),
+            "component_sim1abc"
+        );
+        assert_eq!(unwrap_address("component_sim1abc"), "component_sim1abc");
+    }
+}
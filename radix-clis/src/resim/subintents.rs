@@ -0,0 +1,156 @@
+use crate::resim::*;
+use radix_transactions::manifest::*;
+use std::path::PathBuf;
+
+/// The number of epochs for which the intents built by resim are valid.
+const INTENT_EPOCH_WINDOW: u64 = 100;
+
+/// The signed subintents given with `--subintent` and `--subintent-signer`, to be attached as
+/// children of a V2 transaction.
+pub struct Subintents {
+    network_id: u8,
+    current_epoch: Epoch,
+    /// The hash of each subintent by the file stem of its manifest, which is both its name in the
+    /// transaction and the variable its hash is available as in the manifests which follow.
+    pub variables: IndexMap<String, String>,
+    children: Vec<(String, DetailedSignedPartialTransactionV2)>,
+}
+
+impl Subintents {
+    /// Compiles the subintent manifests for the given network and signs each of them with the
+    /// keys at the same position in `signers`, which are separated by comma.
+    ///
+    /// A subintent manifest can use the hashes of the subintents before it as `${<file stem>}`.
+    pub fn build(
+        paths: &[PathBuf],
+        signers: &[String],
+        network: &NetworkDefinition,
+    ) -> Result<Self, String> {
+        if signers.len() > paths.len() {
+            return Err(Error::TooManySubintentSigners(signers.len()).into());
+        }
+        // The manifests are compiled for the given network, but the intents are executed against
+        // the simulator ledger
+        let SimulatorEnvironment {
+            db,
+            network_definition,
+            ..
+        } = SimulatorEnvironment::new()?;
+        let current_epoch = read_current_epoch(&db)?;
+        drop(db);
+        let mut subintents = Self {
+            network_id: network_definition.id,
+            current_epoch,
+            variables: index_map_new(),
+            children: vec![],
+        };
+
+        let hash_encoder = TransactionHashBech32Encoder::new(network);
+        for (index, path) in paths.iter().enumerate() {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if subintents.variables.contains_key(&name) {
+                return Err(Error::DuplicateSubintentName(name).into());
+            }
+            let manifest =
+                fs::read_to_string(path).map_err(|err| Error::IOErrorAtPath(err, path.clone()))?;
+            let manifest = compile_manifest_with_pretty_error::<SubintentManifestV2>(
+                &Run::pre_process_manifest_with_variables(&manifest, &subintents.variables),
+                network,
+                BlobProvider::new(),
+                CompileErrorDiagnosticsStyle::TextTerminalColors,
+            )?;
+            let signers = match signers.get(index) {
+                Some(keys) => get_signing_keys(&Some(keys.clone()))?,
+                None => vec![],
+            };
+            let subintent = TransactionBuilder::new_partial_v2()
+                .intent_header(subintents.intent_header())
+                .manifest(manifest)
+                .multi_sign(&signers)
+                .build();
+            subintents.variables.insert(
+                name.clone(),
+                subintent
+                    .root_subintent_hash
+                    .display(&hash_encoder)
+                    .to_string(),
+            );
+            subintents.children.push((name, subintent));
+        }
+        Ok(subintents)
+    }
+
+    /// The subintents by their name, in the order they were given.
+    pub fn children(&self) -> impl Iterator<Item = (&str, SubintentHash)> {
+        self.children
+            .iter()
+            .map(|(name, subintent)| (name.as_str(), subintent.root_subintent_hash))
+    }
+
+    fn intent_header(&self) -> IntentHeaderV2 {
+        IntentHeaderV2 {
+            network_id: self.network_id,
+            start_epoch_inclusive: self.current_epoch,
+            end_epoch_exclusive: self
+                .current_epoch
+                .after(INTENT_EPOCH_WINDOW)
+                .unwrap_or(self.current_epoch),
+            min_proposer_timestamp_inclusive: None,
+            max_proposer_timestamp_exclusive: None,
+            intent_discriminator: rand::random(),
+        }
+    }
+
+    /// Signs the transaction manifest, which must use exactly the subintents as its children, and
+    /// runs it with the subintents, or previews it.
+    pub fn run_transaction<O: std::io::Write>(
+        self,
+        manifest: TransactionManifestV2,
+        signing_keys: &Option<String>,
+        trace: bool,
+        preview: bool,
+        out: &mut O,
+    ) -> Result<(), String> {
+        let child_hashes = self
+            .children()
+            .map(|(_, hash)| ChildSubintentSpecifier { hash })
+            .collect::<IndexSet<_>>();
+        if manifest.children != child_hashes {
+            return Err(Error::SubintentChildrenMismatch.into());
+        }
+
+        let signers = get_signing_keys(signing_keys)?;
+        // The notary is not a signatory, so any key will do
+        let notary = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let intent_header = self.intent_header();
+        let mut builder = self
+            .children
+            .into_iter()
+            .fold(
+                TransactionBuilder::new_v2(),
+                |builder, (name, subintent)| builder.add_signed_child(name, subintent),
+            )
+            .intent_header(intent_header)
+            .manifest(manifest)
+            .transaction_header(TransactionHeaderV2 {
+                notary_public_key: notary.public_key().into(),
+                notary_is_signatory: false,
+                tip_basis_points: 0,
+            });
+        if preview {
+            let preview_transaction = builder.build_preview_transaction_no_validate(
+                signers.iter().map(|signer| signer.public_key().into()),
+            );
+            return handle_preview_transaction_v2(preview_transaction, trace, out);
+        }
+        let transaction = builder
+            .multi_sign(&signers)
+            .notarize(&notary)
+            .build_no_validate();
+
+        handle_notarized_transaction_v2(transaction, trace, true, out).map(|_| ())
+    }
+}
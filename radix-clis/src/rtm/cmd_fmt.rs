@@ -0,0 +1,104 @@
+use crate::prelude::*;
+use std::io::Read;
+
+/// Format transaction manifests in the canonical style
+#[derive(Parser, Debug)]
+pub struct Fmt {
+    /// The manifest files to format in place. If none are given, the manifest is read from stdin
+    /// and written to stdout
+    inputs: Vec<PathBuf>,
+
+    /// Check that the manifests are formatted, without changing them
+    #[clap(short, long)]
+    check: bool,
+
+    /// The line width beyond which instructions and values are split over several lines
+    #[clap(long, default_value = "100")]
+    max_width: usize,
+
+    /// Rename the buckets, proofs, address reservations, named addresses and named intents to
+    /// the names given by the decompiler, e.g. `bucket1`
+    #[clap(long)]
+    normalize_names: bool,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IoError(std::io::Error),
+    FormatError { path: String, diagnostics: String },
+    NotFormatted(Vec<String>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::IoError(err) => write!(f, "{}", err),
+            Error::FormatError { path, diagnostics } => {
+                write!(f, "Failed to parse {}\n{}", path, diagnostics)
+            }
+            Error::NotFormatted(paths) => {
+                write!(f, "Not formatted: {}", paths.join(", "))
+            }
+        }
+    }
+}
+
+impl From<Error> for String {
+    fn from(err: Error) -> String {
+        err.to_string()
+    }
+}
+
+impl Fmt {
+    pub fn run(&self) -> Result<(), String> {
+        let options = ManifestFormatterOptions {
+            max_width: self.max_width,
+            normalize_names: self.normalize_names,
+        };
+
+        if self.inputs.is_empty() {
+            let path = "<stdin>".to_string();
+            let mut content = String::new();
+            std::io::stdin()
+                .read_to_string(&mut content)
+                .map_err(Error::IoError)?;
+            let formatted = format(&path, &content, &options)?;
+            if !self.check {
+                print!("{}", formatted);
+            } else if formatted != content {
+                return Err(Error::NotFormatted(vec![path]).into());
+            }
+            return Ok(());
+        }
+
+        let mut not_formatted = Vec::new();
+        for input in &self.inputs {
+            let path = input.display().to_string();
+            let content = fs::read_to_string(input).map_err(Error::IoError)?;
+            let formatted = format(&path, &content, &options)?;
+            if formatted == content {
+                continue;
+            }
+            if self.check {
+                not_formatted.push(path);
+            } else {
+                fs::write(input, formatted).map_err(Error::IoError)?;
+            }
+        }
+        if !not_formatted.is_empty() {
+            return Err(Error::NotFormatted(not_formatted).into());
+        }
+        Ok(())
+    }
+}
+
+fn format(path: &str, content: &str, options: &ManifestFormatterOptions) -> Result<String, Error> {
+    format_manifest(content, options).map_err(|err| Error::FormatError {
+        path: path.to_string(),
+        diagnostics: compile_error_diagnostics(
+            content,
+            err,
+            CompileErrorDiagnosticsStyle::TextTerminalColors,
+        ),
+    })
+}
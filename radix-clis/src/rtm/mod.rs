@@ -0,0 +1,26 @@
+mod cmd_fmt;
+
+pub use cmd_fmt::*;
+
+use clap::{Parser, Subcommand};
+
+/// Work with transaction manifest files
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None, name = "rtm")]
+pub struct RtmCli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    Fmt(Fmt),
+}
+
+pub fn run() -> Result<(), String> {
+    let cli = RtmCli::parse();
+
+    match cli.command {
+        Command::Fmt(cmd) => cmd.run(),
+    }
+}
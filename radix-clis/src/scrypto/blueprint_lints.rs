@@ -0,0 +1,776 @@
+//! Static analysis of the `#[blueprint]` modules of a package, flagging common mistakes which
+//! compile fine but fail or misbehave once the blueprint is published.
+//!
+//! The blueprints are looked at the way `#[blueprint]` expands them: the state is the struct of
+//! the module and the functions and methods are those of its `impl` block which the generated
+//! schema exports. Which methods anyone can call and which roles are declared is read from the
+//! package definition generated by the build, rather than from the `enable_method_auth!` tokens.
+//!
+//! The bodies of the methods are analysed on the syntax tree of the sources, so types are only
+//! known where they're spelled out: the fields of the blueprint state, the parameters of the
+//! methods, annotated `let` bindings and the expressions which obviously make a `Decimal`, such as
+//! `dec!` or `amount()`.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use radix_engine_interface::blueprints::package::{
+    BlueprintDefinitionInit, MethodAuthTemplate, PackageDefinition, RoleSpecification,
+    StaticRoleDefinition,
+};
+use radix_engine_interface::blueprints::resource::MethodAccessibility;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{
+    parse_file, BinOp, Expr, ExprAssignOp, ExprBinary, FnArg, ImplItem, ImplItemMethod, Item,
+    Local, Member, Pat, Stmt, Type,
+};
+
+/// The methods of vaults, buckets, resource managers and accounts which return a bucket, along
+/// with their number of arguments, which tells them apart from the likes of `Option::take`.
+const BUCKET_METHODS: [(&str, usize); 9] = [
+    ("take", 1),
+    ("take_all", 0),
+    ("take_advanced", 2),
+    ("take_non_fungible", 1),
+    ("take_non_fungibles", 1),
+    ("mint", 1),
+    ("mint_non_fungible", 2),
+    ("mint_ruid_non_fungible", 1),
+    ("withdraw", 2),
+];
+
+/// The methods of vaults, buckets and accounts which create a proof.
+const PROOF_METHODS: [&str; 5] = [
+    "create_proof",
+    "create_proof_of_all",
+    "create_proof_of_amount",
+    "create_proof_of_non_fungibles",
+    "create_proof_of_non_fungible",
+];
+
+/// The roles which every component has, without declaring them.
+const BUILTIN_ROLES: [&str; 2] = ["OWNER", "SELF"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lint {
+    /// A public method of a blueprint which doesn't use `enable_method_auth!`, so that anyone can
+    /// call it.
+    MissingMethodAuth,
+    /// A bucket returned by a method and dropped, which fails the transaction unless it's empty.
+    DroppedBucket,
+    /// An arithmetic operator applied to a `Decimal`, which panics on overflow.
+    UncheckedDecimalArithmetic,
+    /// A proof created and dropped right away, which doesn't authorize anything.
+    DroppedProof,
+    /// A role declared by `enable_method_auth!` which no method nor role refers to.
+    UnusedRole,
+}
+
+impl Lint {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MissingMethodAuth => "missing_method_auth",
+            Self::DroppedBucket => "dropped_bucket",
+            Self::UncheckedDecimalArithmetic => "unchecked_decimal_arithmetic",
+            Self::DroppedProof => "dropped_proof",
+            Self::UnusedRole => "unused_role",
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "missing_method_auth" => Ok(Self::MissingMethodAuth),
+            "dropped_bucket" => Ok(Self::DroppedBucket),
+            "unchecked_decimal_arithmetic" => Ok(Self::UncheckedDecimalArithmetic),
+            "dropped_proof" => Ok(Self::DroppedProof),
+            "unused_role" => Ok(Self::UnusedRole),
+            _ => Err(format!(
+                "Invalid lint `{}`, expected missing_method_auth, dropped_bucket, \
+                unchecked_decimal_arithmetic, dropped_proof or unused_role",
+                s
+            )),
+        }
+    }
+}
+
+/// A mistake found in a blueprint, located by the line and column (both 1-based) where it starts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub lint: Lint,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub blueprint: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "warning[{}]: {}", self.lint, self.message)?;
+        writeln!(
+            f,
+            "  --> {}:{}:{}",
+            self.file.display(),
+            self.line,
+            self.column
+        )?;
+        write!(f, "   = in blueprint `{}`", self.blueprint)
+    }
+}
+
+/// Lints the blueprints of a source file, `file` being the path the findings are reported with and
+/// `definition` the definition of the package generated by building it.
+pub fn lint_blueprints(
+    file: &Path,
+    source: &str,
+    definition: &PackageDefinition,
+) -> syn::Result<Vec<Finding>> {
+    let syntax = parse_file(source)?;
+    let mut findings = Vec::new();
+    lint_items(file, &syntax.items, definition, &mut findings);
+    Ok(findings)
+}
+
+fn lint_items(
+    file: &Path,
+    items: &[Item],
+    definition: &PackageDefinition,
+    findings: &mut Vec<Finding>,
+) {
+    for item in items {
+        if let Item::Mod(module) = item {
+            if let Some((_, content)) = &module.content {
+                let is_blueprint = module.attrs.iter().any(|attribute| {
+                    attribute
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "blueprint")
+                });
+                if is_blueprint {
+                    lint_blueprint(file, content, definition, findings);
+                } else {
+                    lint_items(file, content, definition, findings);
+                }
+            }
+        }
+    }
+}
+
+fn lint_blueprint(
+    file: &Path,
+    items: &[Item],
+    definition: &PackageDefinition,
+    findings: &mut Vec<Finding>,
+) {
+    let Some(structure) = items.iter().find_map(|item| match item {
+        Item::Struct(structure) => Some(structure),
+        _ => None,
+    }) else {
+        return;
+    };
+    let blueprint = structure.ident.to_string();
+    // A blueprint which isn't part of the built package, such as one behind a disabled feature,
+    // isn't published either.
+    let Some(blueprint_definition) = definition.blueprints.get(&blueprint) else {
+        return;
+    };
+    let decimal_fields: BTreeSet<String> = structure
+        .fields
+        .iter()
+        .filter(|field| is_decimal_type(&field.ty))
+        .filter_map(|field| field.ident.as_ref().map(|ident| ident.to_string()))
+        .collect();
+    let method_auth_tokens = items.iter().find_map(|item| match item {
+        Item::Macro(item) if item.mac.path.is_ident("enable_method_auth") => Some(&item.mac.tokens),
+        _ => None,
+    });
+
+    let mut report = |lint: Lint, span: Span, message: String| {
+        let start = span.start();
+        findings.push(Finding {
+            lint,
+            file: file.to_path_buf(),
+            line: start.line,
+            column: start.column + 1,
+            blueprint: blueprint.clone(),
+            message,
+        })
+    };
+
+    // Only the inherent `impl` of the blueprint is expanded into functions and methods.
+    let methods = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Impl(implementation)
+                if implementation.trait_.is_none()
+                    && matches!(&*implementation.self_ty, Type::Path(ty) if ty.path.is_ident(&blueprint)) =>
+            {
+                Some(implementation)
+            }
+            _ => None,
+        })
+        .flat_map(|implementation| implementation.items.iter())
+        .filter_map(|item| match item {
+            ImplItem::Method(method) => Some(method),
+            _ => None,
+        });
+    for method in methods {
+        let is_exported_method = blueprint_definition
+            .schema
+            .functions
+            .functions
+            .get(&method.sig.ident.to_string())
+            .is_some_and(|function| function.receiver.is_some());
+        if is_exported_method
+            && matches!(
+                blueprint_definition.auth_config.method_auth,
+                MethodAuthTemplate::AllowAll
+            )
+        {
+            report(
+                Lint::MissingMethodAuth,
+                method.sig.ident.span(),
+                format!(
+                    "method `{}` can be called by anyone, as the blueprint doesn't declare the \
+                    access rules of its methods with `enable_method_auth!`",
+                    method.sig.ident
+                ),
+            );
+        }
+
+        let mut linter = MethodLinter {
+            decimal_fields: &decimal_fields,
+            decimal_locals: decimal_locals(method, &decimal_fields),
+            findings: Vec::new(),
+        };
+        linter.visit_block(&method.block);
+        for (lint, span, message) in linter.findings {
+            report(lint, span, message);
+        }
+    }
+
+    for role in unused_roles(blueprint_definition) {
+        // The role is reported where `enable_method_auth!` declares it, the roles coming first.
+        let span = method_auth_tokens
+            .and_then(|tokens| find_ident(tokens, &role))
+            .unwrap_or_else(|| structure.ident.span());
+        report(
+            Lint::UnusedRole,
+            span,
+            format!(
+                "role `{}` is declared but no method is restricted to it and it doesn't update \
+                any other role",
+                role
+            ),
+        );
+    }
+}
+
+/// The roles of a blueprint which no method is restricted to and which don't update any other
+/// role, in the order they're declared.
+fn unused_roles(blueprint_definition: &BlueprintDefinitionInit) -> Vec<String> {
+    let MethodAuthTemplate::StaticRoleDefinition(StaticRoleDefinition {
+        roles: RoleSpecification::Normal(roles),
+        methods,
+    }) = &blueprint_definition.auth_config.method_auth
+    else {
+        return Vec::new();
+    };
+
+    let mut used_roles = BTreeSet::new();
+    for accessibility in methods.values() {
+        if let MethodAccessibility::RoleProtected(role_list) = accessibility {
+            used_roles.extend(role_list.list.iter().map(|role| role.key.clone()));
+        }
+    }
+    for (role, updater_roles) in roles {
+        // A role which can only update itself isn't of any use either.
+        used_roles.extend(
+            updater_roles
+                .list
+                .iter()
+                .filter(|updater_role| *updater_role != role)
+                .map(|updater_role| updater_role.key.clone()),
+        );
+    }
+
+    roles
+        .keys()
+        .map(|role| role.key.clone())
+        .filter(|role| !used_roles.contains(role) && !BUILTIN_ROLES.contains(&role.as_str()))
+        .collect()
+}
+
+/// The span of the first identifier of the given name in a token stream, looking into groups.
+fn find_ident(tokens: &TokenStream, name: &str) -> Option<Span> {
+    tokens.clone().into_iter().find_map(|tree| match tree {
+        TokenTree::Ident(ident) if ident == name => Some(ident.span()),
+        TokenTree::Group(group) => find_ident(&group.stream(), name),
+        _ => None,
+    })
+}
+
+fn is_decimal_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => {
+            path.path.segments.last().is_some_and(|segment| {
+                segment.ident == "Decimal" || segment.ident == "PreciseDecimal"
+            })
+        }
+        Type::Reference(reference) => is_decimal_type(&reference.elem),
+        Type::Paren(paren) => is_decimal_type(&paren.elem),
+        _ => false,
+    }
+}
+
+/// The names of the parameters and local variables of a method which hold a `Decimal`.
+fn decimal_locals(method: &ImplItemMethod, decimal_fields: &BTreeSet<String>) -> BTreeSet<String> {
+    let mut collector = DecimalLocalsCollector {
+        decimal_fields,
+        decimal_locals: BTreeSet::new(),
+    };
+    for input in method.sig.inputs.iter() {
+        if let FnArg::Typed(input) = input {
+            if let (Pat::Ident(pat), true) = (&*input.pat, is_decimal_type(&input.ty)) {
+                collector.decimal_locals.insert(pat.ident.to_string());
+            }
+        }
+    }
+    // Variables defined from other variables are only found once those are, so this goes on until
+    // no new variable is found.
+    loop {
+        let count = collector.decimal_locals.len();
+        collector.visit_block(&method.block);
+        if collector.decimal_locals.len() == count {
+            return collector.decimal_locals;
+        }
+    }
+}
+
+struct DecimalLocalsCollector<'a> {
+    decimal_fields: &'a BTreeSet<String>,
+    decimal_locals: BTreeSet<String>,
+}
+
+impl<'ast> Visit<'ast> for DecimalLocalsCollector<'_> {
+    fn visit_local(&mut self, local: &'ast Local) {
+        let ident = match &local.pat {
+            Pat::Type(pat) if is_decimal_type(&pat.ty) => match &*pat.pat {
+                Pat::Ident(pat) => Some(&pat.ident),
+                _ => None,
+            },
+            Pat::Ident(pat)
+                if local.init.as_ref().is_some_and(|(_, init)| {
+                    is_decimal_expr(init, self.decimal_fields, &self.decimal_locals)
+                }) =>
+            {
+                Some(&pat.ident)
+            }
+            _ => None,
+        };
+        if let Some(ident) = ident {
+            self.decimal_locals.insert(ident.to_string());
+        }
+        visit::visit_local(self, local);
+    }
+}
+
+fn is_decimal_expr(
+    expr: &Expr,
+    decimal_fields: &BTreeSet<String>,
+    decimal_locals: &BTreeSet<String>,
+) -> bool {
+    let is_decimal_path = |path: &syn::Path| {
+        path.segments
+            .first()
+            .is_some_and(|segment| segment.ident == "Decimal" || segment.ident == "PreciseDecimal")
+    };
+    match expr {
+        Expr::Macro(expr) => expr.mac.path.is_ident("dec") || expr.mac.path.is_ident("pdec"),
+        Expr::Path(expr) => {
+            (expr.path.segments.len() > 1 && is_decimal_path(&expr.path))
+                || expr
+                    .path
+                    .get_ident()
+                    .is_some_and(|ident| decimal_locals.contains(&ident.to_string()))
+        }
+        Expr::Call(expr) => match &*expr.func {
+            Expr::Path(func) => {
+                is_decimal_path(&func.path)
+                    && func
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "from")
+            }
+            _ => false,
+        },
+        Expr::Field(expr) => match (&*expr.base, &expr.member) {
+            (Expr::Path(base), Member::Named(member)) => {
+                base.path.is_ident("self") && decimal_fields.contains(&member.to_string())
+            }
+            _ => false,
+        },
+        Expr::MethodCall(expr) => expr.method == "amount" && expr.args.is_empty(),
+        Expr::Paren(expr) => is_decimal_expr(&expr.expr, decimal_fields, decimal_locals),
+        Expr::Unary(expr) => is_decimal_expr(&expr.expr, decimal_fields, decimal_locals),
+        Expr::Binary(expr) => {
+            checked_method(&expr.op).is_some()
+                && (is_decimal_expr(&expr.left, decimal_fields, decimal_locals)
+                    || is_decimal_expr(&expr.right, decimal_fields, decimal_locals))
+        }
+        _ => false,
+    }
+}
+
+/// The checked counterpart of an arithmetic operator of `Decimal`.
+fn checked_method(op: &BinOp) -> Option<&'static str> {
+    match op {
+        BinOp::Add(_) | BinOp::AddEq(_) => Some("checked_add"),
+        BinOp::Sub(_) | BinOp::SubEq(_) => Some("checked_sub"),
+        BinOp::Mul(_) | BinOp::MulEq(_) => Some("checked_mul"),
+        BinOp::Div(_) | BinOp::DivEq(_) => Some("checked_div"),
+        _ => None,
+    }
+}
+
+struct MethodLinter<'a> {
+    decimal_fields: &'a BTreeSet<String>,
+    decimal_locals: BTreeSet<String>,
+    findings: Vec<(Lint, Span, String)>,
+}
+
+impl MethodLinter<'_> {
+    fn lint_arithmetic(&mut self, left: &Expr, op: &BinOp, right: &Expr) {
+        if let Some(checked_method) = checked_method(op) {
+            if is_decimal_expr(left, self.decimal_fields, &self.decimal_locals)
+                || is_decimal_expr(right, self.decimal_fields, &self.decimal_locals)
+            {
+                self.findings.push((
+                    Lint::UncheckedDecimalArithmetic,
+                    op.span(),
+                    format!(
+                        "`{}` on a `Decimal` panics on overflow, use `{}` and handle the `None` \
+                        case instead",
+                        op.to_token_stream(),
+                        checked_method
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn lint_discarded(&mut self, expr: &Expr) {
+        if let Expr::MethodCall(call) = expr {
+            let method = call.method.to_string();
+            if BUCKET_METHODS.contains(&(method.as_str(), call.args.len())) {
+                self.findings.push((
+                    Lint::DroppedBucket,
+                    call.method.span(),
+                    format!(
+                        "the bucket returned by `{}` is dropped, which fails the transaction \
+                        unless it's empty; put it in a vault or return it",
+                        method
+                    ),
+                ));
+            } else if PROOF_METHODS.contains(&method.as_str()) {
+                self.findings.push((
+                    Lint::DroppedProof,
+                    call.method.span(),
+                    format!(
+                        "the proof created by `{}` is dropped right away, so it doesn't \
+                        authorize anything",
+                        method
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for MethodLinter<'_> {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        match stmt {
+            Stmt::Semi(expr, _) => self.lint_discarded(expr),
+            Stmt::Local(Local {
+                pat,
+                init: Some((_, init)),
+                ..
+            }) if is_discarding_pattern(pat) => self.lint_discarded(init),
+            _ => {}
+        }
+        visit::visit_stmt(self, stmt);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast ExprBinary) {
+        self.lint_arithmetic(&expr.left, &expr.op, &expr.right);
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_expr_assign_op(&mut self, expr: &'ast ExprAssignOp) {
+        self.lint_arithmetic(&expr.left, &expr.op, &expr.right);
+        visit::visit_expr_assign_op(self, expr);
+    }
+}
+
+/// Whether a `let` pattern drops the value right away, or lets it go unused without a warning.
+fn is_discarding_pattern(pat: &Pat) -> bool {
+    match pat {
+        Pat::Wild(_) => true,
+        Pat::Ident(pat) => pat.ident.to_string().starts_with('_'),
+        Pat::Type(pat) => is_discarding_pattern(&pat.pat),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_engine_interface::blueprints::resource::{MethodKey, RoleKey, RoleList};
+    use radix_rust::indexmap;
+
+    /// A blueprint as its name, its functions with whether they're methods, and its method auth.
+    type TestBlueprint<'a> = (&'a str, Vec<(&'a str, bool)>, MethodAuthTemplate);
+
+    /// A package definition with the given blueprints.
+    fn definition(blueprints: Vec<TestBlueprint>) -> PackageDefinition {
+        let mut definition = PackageDefinition::default();
+        for (blueprint, functions, method_auth) in blueprints {
+            let functions = functions
+                .into_iter()
+                .map(|(function, is_method)| (function, function, is_method))
+                .collect();
+            let mut blueprint_definition =
+                PackageDefinition::new_functions_only_test_definition(blueprint, functions)
+                    .blueprints
+                    .swap_remove(blueprint)
+                    .unwrap();
+            blueprint_definition.auth_config.method_auth = method_auth;
+            definition
+                .blueprints
+                .insert(blueprint.to_owned(), blueprint_definition);
+        }
+        definition
+    }
+
+    fn role_list(roles: &[&str]) -> RoleList {
+        RoleList {
+            list: roles.iter().map(|role| RoleKey::new(*role)).collect(),
+        }
+    }
+
+    fn lints(source: &str, definition: &PackageDefinition) -> Vec<(Lint, usize)> {
+        lint_blueprints(Path::new("src/lib.rs"), source, definition)
+            .unwrap()
+            .into_iter()
+            .map(|finding| (finding.lint, finding.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_method_auth_lints() {
+        let source = r#"
+use scrypto::prelude::*;
+
+#[blueprint]
+mod vault {
+    enable_method_auth! {
+        roles {
+            withdrawer => updatable_by: [OWNER];
+            auditor => updatable_by: [auditor];
+        },
+        methods {
+            withdraw => restrict_to: [withdrawer];
+        }
+    }
+
+    struct Vault {
+        vault: FungibleVault,
+    }
+
+    impl Vault {
+        pub fn withdraw(&mut self, amount: Decimal) -> FungibleBucket {
+            self.vault.take(amount)
+        }
+    }
+}
+
+#[blueprint]
+mod hello {
+    struct Hello {
+        vault: FungibleVault,
+    }
+
+    impl Hello {
+        pub fn new() -> Global<Hello> {
+            Self { vault: FungibleVault::new(XRD) }.instantiate().prepare_to_globalize(OwnerRole::None).globalize()
+        }
+
+        pub fn free_token(&mut self) -> FungibleBucket {
+            self.vault.take(1)
+        }
+
+        fn helper(&self) {}
+    }
+}
+"#;
+        let definition = definition(vec![
+            (
+                "Vault",
+                vec![("withdraw", true)],
+                MethodAuthTemplate::StaticRoleDefinition(StaticRoleDefinition {
+                    roles: RoleSpecification::Normal(indexmap!(
+                        RoleKey::new("withdrawer") => role_list(&["OWNER"]),
+                        RoleKey::new("auditor") => role_list(&["auditor"])
+                    )),
+                    methods: indexmap!(
+                        MethodKey::new("withdraw") =>
+                            MethodAccessibility::RoleProtected(role_list(&["withdrawer"]))
+                    ),
+                }),
+            ),
+            (
+                "Hello",
+                vec![("new", false), ("free_token", true)],
+                MethodAuthTemplate::AllowAll,
+            ),
+        ]);
+        assert_eq!(
+            lints(source, &definition),
+            vec![(Lint::UnusedRole, 9), (Lint::MissingMethodAuth, 38)]
+        );
+    }
+
+    #[test]
+    fn test_dropped_resources_and_decimal_arithmetic_lints() {
+        let source = r#"
+#[blueprint]
+mod pool {
+    enable_method_auth! {
+        methods {
+            swap => PUBLIC;
+        }
+    }
+
+    struct Pool {
+        vault: FungibleVault,
+        fee: Decimal,
+        swaps: u64,
+    }
+
+    impl Pool {
+        pub fn swap(&mut self, input: FungibleBucket) -> FungibleBucket {
+            let amount = input.amount();
+            let fee = amount * self.fee;
+            let output_amount: Decimal = amount.checked_sub(fee).unwrap();
+            self.swaps += 1;
+            self.vault.put(input);
+            self.vault.take(dec!(1));
+            let _ = self.vault.create_proof_of_amount(dec!(1));
+            let _unused = self.vault.take_all();
+            self.value.take();
+            let count = self.swaps * 2;
+            self.vault.take(output_amount / 2)
+        }
+    }
+}
+"#;
+        let definition = definition(vec![(
+            "Pool",
+            vec![("swap", true)],
+            MethodAuthTemplate::StaticRoleDefinition(StaticRoleDefinition {
+                roles: RoleSpecification::Normal(indexmap!()),
+                methods: indexmap!(MethodKey::new("swap") => MethodAccessibility::Public),
+            }),
+        )]);
+        assert_eq!(
+            lints(source, &definition),
+            vec![
+                (Lint::UncheckedDecimalArithmetic, 19),
+                (Lint::DroppedBucket, 23),
+                (Lint::DroppedProof, 24),
+                (Lint::DroppedBucket, 25),
+                (Lint::UncheckedDecimalArithmetic, 28),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_blueprint_modules_are_not_linted() {
+        let source = r#"
+mod helpers {
+    pub struct Helper;
+
+    impl Helper {
+        pub fn drop_it(&self, vault: &mut Vault) {
+            vault.take_all();
+        }
+    }
+}
+"#;
+        assert!(lints(source, &PackageDefinition::default()).is_empty());
+    }
+
+    #[test]
+    fn test_only_the_expanded_blueprint_is_linted() {
+        let source = r#"
+#[blueprint]
+mod hello {
+    struct Hello {
+        vault: FungibleVault,
+    }
+
+    impl Hello {
+        pub fn free_token(&mut self) -> FungibleBucket {
+            self.vault.take(1)
+        }
+    }
+
+    impl Drop for Hello {
+        fn drop(&mut self) {
+            self.vault.take_all();
+        }
+    }
+}
+
+#[blueprint]
+mod unpublished {
+    struct Unpublished {
+        vault: FungibleVault,
+    }
+
+    impl Unpublished {
+        pub fn drop_it(&mut self) {
+            self.vault.take_all();
+        }
+    }
+}
+"#;
+        let definition = definition(vec![(
+            "Hello",
+            vec![("free_token", true)],
+            MethodAuthTemplate::AllowAll,
+        )]);
+        assert_eq!(
+            lints(source, &definition),
+            vec![(Lint::MissingMethodAuth, 9)]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! The report printed by `scrypto build --report`: how a package measures up against the limits
+//! of the WASM validator, and what it costs to publish and to call.
+
+use radix_common::prelude::*;
+use radix_engine::system::system_modules::costing::FeeTable;
+use radix_engine::transaction::CostingParameters;
+use radix_engine::vm::wasm::{ScryptoV1WasmValidator, WasmModule, WasmModuleStatistics};
+use radix_engine::vm::ScryptoVmVersion;
+use radix_engine_interface::blueprints::package::{
+    PackageDefinition, PACKAGE_PUBLISH_WASM_ADVANCED_IDENT,
+};
+use radix_transactions::model::PreparationSettings;
+use std::fmt;
+
+use crate::utils::BuildError;
+
+/// The exported functions of a blueprint, along with the size in bytes of their body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlueprintExports {
+    pub blueprint_name: String,
+    pub function_exports: Vec<(String, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildReport {
+    pub package_name: String,
+    pub code_size: usize,
+    pub instrumented_code_size: usize,
+    pub definition_size: usize,
+    pub statistics: WasmModuleStatistics,
+    pub blueprints: Vec<BlueprintExports>,
+    /// The cost units charged for instantiating the code, on every call to the package.
+    pub instantiate_cost: u32,
+    /// The cost units charged for the publishing transaction to validate and store the package.
+    pub publish_execution_cost: u32,
+    /// The fee for publishing, in XRD, at the current cost unit and storage prices.
+    pub publish_fee: Decimal,
+}
+
+impl BuildReport {
+    /// Validates the code of a package as the engine does when it's published, and measures it.
+    pub fn new(
+        package_name: &str,
+        code: &[u8],
+        definition: &PackageDefinition,
+    ) -> Result<Self, BuildError> {
+        let (instrumented_code, _) = ScryptoV1WasmValidator::new(ScryptoVmVersion::latest())
+            .validate(code, definition.blueprints.values())
+            .map_err(BuildError::WasmValidationError)?;
+        let statistics = WasmModule::init(code)
+            .and_then(|module| module.statistics())
+            .map_err(BuildError::WasmValidationError)?;
+
+        let function_sizes: IndexMap<&str, usize> = statistics
+            .function_exports
+            .iter()
+            .map(|(name, size)| (name.as_str(), *size))
+            .collect();
+        let blueprints = definition
+            .blueprints
+            .iter()
+            .map(|(blueprint_name, blueprint)| BlueprintExports {
+                blueprint_name: blueprint_name.clone(),
+                function_exports: blueprint
+                    .schema
+                    .exports()
+                    .into_iter()
+                    .map(|export| {
+                        let size = function_sizes.get(export.as_str()).copied().unwrap_or(0);
+                        (export, size)
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        // The package is published with a transaction which holds both its code and definition,
+        // and the engine stores its original and instrumented code along with the definition.
+        let definition_size = scrypto_encode(definition)
+            .map_err(BuildError::SchemaEncodeError)?
+            .len();
+        let payload_size = code.len() + definition_size;
+        let fee_table = FeeTable::latest();
+        let costing_parameters = CostingParameters::latest();
+        let publish_execution_cost = fee_table
+            .run_native_code_cost(
+                &PACKAGE_PACKAGE,
+                PACKAGE_PUBLISH_WASM_ADVANCED_IDENT,
+                &payload_size,
+            )
+            .saturating_add(fee_table.validate_tx_payload_cost(payload_size));
+        let stored_size = payload_size + instrumented_code.len();
+        let publish_fee = costing_parameters.execution_cost_unit_price
+            * Decimal::from(publish_execution_cost)
+            + costing_parameters.state_storage_price * Decimal::from(stored_size)
+            + costing_parameters.archive_storage_price * Decimal::from(payload_size);
+
+        Ok(Self {
+            package_name: package_name.to_string(),
+            code_size: code.len(),
+            instrumented_code_size: instrumented_code.len(),
+            definition_size,
+            blueprints,
+            instantiate_cost: fee_table.instantiate_wasm_code_cost(code.len()),
+            publish_execution_cost,
+            publish_fee,
+            statistics,
+        })
+    }
+}
+
+impl fmt::Display for BuildReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let statistics = &self.statistics;
+        let max_payload_size = PreparationSettings::latest().max_user_payload_length;
+        let payload_size = self.code_size + self.definition_size;
+
+        writeln!(f, "Package {}", self.package_name)?;
+        writeln!(
+            f,
+            "  Code size:          {} bytes, {} bytes once instrumented",
+            self.code_size, self.instrumented_code_size
+        )?;
+        writeln!(f, "  Definition size:    {} bytes", self.definition_size)?;
+        writeln!(
+            f,
+            "  Publish payload:    {} of {} bytes ({:.1}%)",
+            payload_size,
+            max_payload_size,
+            payload_size as f64 * 100.0 / max_payload_size as f64
+        )?;
+        writeln!(
+            f,
+            "  Functions:          {} of {}",
+            statistics.function_count, MAX_NUMBER_OF_FUNCTIONS
+        )?;
+        writeln!(
+            f,
+            "  Globals:            {} of {}",
+            statistics.global_count, MAX_NUMBER_OF_GLOBALS
+        )?;
+        writeln!(
+            f,
+            "  Tables:             {}, with {} of {} initial entries",
+            statistics.table_count,
+            statistics.initial_table_size.unwrap_or(0),
+            MAX_INITIAL_TABLE_SIZE
+        )?;
+        writeln!(
+            f,
+            "  Memory:             {} of {} pages",
+            statistics.initial_memory_pages.unwrap_or(0),
+            MAX_MEMORY_SIZE_IN_PAGES
+        )?;
+        writeln!(f, "  Imports:")?;
+        for (module, name) in &statistics.function_imports {
+            writeln!(f, "    {}::{}", module, name)?;
+        }
+        for blueprint in &self.blueprints {
+            writeln!(f, "  Blueprint {}:", blueprint.blueprint_name)?;
+            for (export, size) in &blueprint.function_exports {
+                writeln!(f, "    {:<40} {:>8} bytes", export, size)?;
+            }
+        }
+        writeln!(
+            f,
+            "  Instantiation cost: {} execution cost units, on every call to the package",
+            self.instantiate_cost
+        )?;
+        write!(
+            f,
+            "  Publish cost:       about {} execution cost units and {} XRD",
+            self.publish_execution_cost, self.publish_fee
+        )
+    }
+}
@@ -69,6 +69,12 @@ pub struct Build {
     #[clap(long)]
     locked: bool,
 
+    /// Builds the package reproducibly, so that anyone can check with `scrypto verify` that the
+    /// WASM was built from its sources. This requires a Cargo.lock file and a toolchain pinned by a
+    /// `rust-toolchain.toml` file, and writes a build info file next to the WASM file.
+    #[clap(long, conflicts_with = "disable-wasm-opt")]
+    reproducible: bool,
+
     /// Pass any additional option to `cargo build` call.
     #[clap(long)]
     custom_option: Option<Vec<String>>,
@@ -76,6 +82,11 @@ pub struct Build {
     /// Prints compilation steps.
     #[clap(short, long)]
     verbose: bool,
+
+    /// Validates the WASM as the engine does when publishing it, and prints its size, shape and
+    /// imports along with an estimate of what it costs to publish and call.
+    #[clap(long)]
+    report: bool,
 }
 
 impl Build {
@@ -109,6 +120,9 @@ impl Build {
         if self.locked {
             compiler_builder.locked();
         }
+        if self.reproducible {
+            compiler_builder.reproducible();
+        }
         if let Some(features) = &self.features {
             features.split(',').for_each(|f| {
                 compiler_builder.feature(f);
@@ -159,9 +173,34 @@ impl Build {
             );
         }
 
-        compiler_builder
+        let artifacts = compiler_builder
             .compile()
-            .map(|_| ())
-            .map_err(|e| Error::BuildError(BuildError::ScryptoCompilerError(e)).into())
+            .map_err(|e| Error::BuildError(BuildError::ScryptoCompilerError(e)))?;
+
+        for build_info in artifacts.iter().filter_map(|a| a.build_info.as_ref()) {
+            println!(
+                "Build info written to {}, code hash: {}",
+                build_info.path.display(),
+                build_info.content.code_hash
+            );
+        }
+        if self.report {
+            for artifact in artifacts.iter() {
+                let package_name = artifact
+                    .wasm
+                    .path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let report = BuildReport::new(
+                    &package_name,
+                    &artifact.wasm.content,
+                    &artifact.package_definition.content,
+                )
+                .map_err(Error::BuildError)?;
+                println!("{}", report);
+            }
+        }
+        Ok(())
     }
 }
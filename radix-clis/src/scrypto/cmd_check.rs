@@ -0,0 +1,113 @@
+use crate::scrypto::*;
+use crate::utils::*;
+use clap::Parser;
+use radix_common::prelude::manifest_decode;
+use radix_engine_interface::blueprints::package::{ManifestPackageDefinition, PackageDefinition};
+use radix_engine_interface::prelude::Level;
+use std::env::current_dir;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Check the blueprints of a Scrypto package for common mistakes
+///
+/// The package is built first, as the lints read which methods are exported and how they're
+/// protected from the schema the build generates.
+#[derive(Parser, Debug)]
+pub struct Check {
+    /// The package directory. If not specified current directory will be used.
+    #[clap(long)]
+    path: Option<PathBuf>,
+
+    /// The format of the findings: `text` or `json`.
+    #[clap(long, default_value = "text")]
+    format: OutputFormat,
+
+    /// Lints not to report, such as `unused_role`. Can be specified multiple times.
+    #[clap(long)]
+    allow: Option<Vec<Lint>>,
+
+    /// Fails if any lint reports a finding.
+    #[clap(long)]
+    deny_warnings: bool,
+}
+
+#[derive(Debug)]
+pub enum CheckError {
+    ParseError { path: PathBuf, message: String },
+
+    DefinitionDecodeError { path: PathBuf, message: String },
+
+    FindingsDenied(usize),
+}
+
+impl Check {
+    pub fn run(&self) -> Result<(), String> {
+        let package_dir = self.path.clone().unwrap_or(current_dir().unwrap());
+        let allowed_lints = self.allow.clone().unwrap_or_default();
+        let definition = build_package_definition(&package_dir)?;
+
+        let mut findings = Vec::new();
+        for entry in WalkDir::new(package_dir.join("src")).sort_by_file_name() {
+            let entry = entry.map_err(|e| Error::IOError(io::Error::from(e)))?;
+            if entry.path().extension() != Some(OsStr::new("rs")) {
+                continue;
+            }
+            let source = fs::read_to_string(entry.path()).map_err(Error::IOError)?;
+            let path = entry
+                .path()
+                .strip_prefix(&package_dir)
+                .unwrap_or(entry.path());
+            findings.extend(lint_blueprints(path, &source, &definition).map_err(|e| {
+                Error::CheckError(CheckError::ParseError {
+                    path: path.to_path_buf(),
+                    message: e.to_string(),
+                })
+            })?);
+        }
+        findings.retain(|finding| !allowed_lints.contains(&finding.lint));
+
+        match self.format {
+            OutputFormat::Text => {
+                for finding in &findings {
+                    println!("{}\n", finding);
+                }
+                println!("{} warning(s) found", findings.len());
+            }
+            OutputFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&findings).unwrap());
+            }
+        }
+
+        if self.deny_warnings && !findings.is_empty() {
+            return Err(Error::CheckError(CheckError::FindingsDenied(findings.len())).into());
+        }
+        Ok(())
+    }
+}
+
+/// Builds the package and merges the definitions of the blueprints of its crates.
+fn build_package_definition(package_dir: &Path) -> Result<PackageDefinition, Error> {
+    let build_artifacts = build_package(package_dir, false, Level::default(), false, [])
+        .map_err(Error::BuildError)?;
+
+    let mut definition = PackageDefinition::default();
+    for (_, definition_path) in build_artifacts {
+        let decode_error = |message: String| {
+            Error::CheckError(CheckError::DefinitionDecodeError {
+                path: definition_path.clone(),
+                message,
+            })
+        };
+        let package_definition = manifest_decode::<ManifestPackageDefinition>(
+            &fs::read(&definition_path).map_err(Error::IOError)?,
+        )
+        .map_err(|err| decode_error(format!("{:?}", err)))?
+        .try_into_typed()
+        .map_err(|err| decode_error(format!("{:?}", err)))?;
+        definition.blueprints.extend(package_definition.blueprints);
+    }
+    Ok(definition)
+}
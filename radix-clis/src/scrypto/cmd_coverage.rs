@@ -7,6 +7,8 @@
 //! 4. That we're always using the `release` profile for all of the coverage builds.
 //! 5. That the user already has `clang`, `llvm-cov`, and `llvm-profdata` installed on their local
 //!    machine and available in the path.
+//! 6. That the symbols of the coverage builds are mangled with the legacy mangling scheme, which
+//!    the summaries rely on to find the functions of the blueprints.
 
 use cargo_metadata::MetadataCommand;
 use cargo_metadata::Package;
@@ -20,14 +22,17 @@ use scrypto_compiler::ScryptoCompiler;
 use scrypto_compiler::DEFAULT_ENVIRONMENT_VARIABLES;
 use std::env::current_dir;
 use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
 use std::process::Stdio;
 use std::string::FromUtf8Error;
 use std::sync::LazyLock;
+use std::time::SystemTime;
 use walkdir::WalkDir;
 
+use crate::scrypto::{BlueprintFunctions, CoverageData, ReportFormat};
 use crate::utils::*;
 
 /// Run Scrypto tests and generate code coverage report
@@ -45,9 +50,19 @@ pub struct Coverage {
     #[clap(long)]
     locked: bool,
 
-    /// The package directory
+    /// The package directory. For a workspace, the coverage of all of its Scrypto packages is
+    /// reported together.
     #[clap(long)]
     path: Option<PathBuf>,
+
+    /// The format of the report: `html`, `lcov` or `cobertura`. It is written to the
+    /// `target/coverage/report` directory.
+    #[clap(long, default_value = "html")]
+    format: ReportFormat,
+
+    /// Fails if the percentage of lines covered is below this threshold.
+    #[clap(long)]
+    fail_under: Option<f64>,
 }
 
 impl Coverage {
@@ -62,67 +77,86 @@ impl Coverage {
         // the nightly compiler.
         let llvm_toolchain = LLVMToolchain::new()?;
 
-        // Building the package with WASM instrumentation.
+        // Building the packages with WASM instrumentation, and collecting the functions of their
+        // blueprints for the summaries.
         let build_environment_variables = construct_build_environment_variables();
-        ScryptoCompiler::builder()
-            .manifest_path(paths.manifest_path.as_path())
-            .log_level(Level::Trace)
-            .optimize_with_wasm_opt(None)
-            .target_directory(paths.coverage_dir_path.as_path())
-            .envs(build_environment_variables)
-            .coverage()
-            .compile()
-            .map_err(BuildError::ScryptoCompilerError)
-            .map_err(CoverageError::BuildError)?;
+        let mut blueprints = Vec::new();
+        for package_paths in paths.packages.iter() {
+            let build_artifacts = ScryptoCompiler::builder()
+                .manifest_path(package_paths.manifest_path.as_path())
+                .log_level(Level::Trace)
+                .optimize_with_wasm_opt(None)
+                .target_directory(package_paths.coverage_dir_path.as_path())
+                .envs(build_environment_variables.clone())
+                .coverage()
+                .compile()
+                .map_err(BuildError::ScryptoCompilerError)
+                .map_err(CoverageError::BuildError)?;
+            blueprints.extend(build_artifacts.into_iter().flat_map(|artifacts| {
+                artifacts
+                    .package_definition
+                    .content
+                    .blueprints
+                    .into_iter()
+                    .map(|(blueprint_name, definition)| BlueprintFunctions {
+                        blueprint_name,
+                        function_names: definition.schema.functions.functions.into_keys().collect(),
+                    })
+            }));
+        }
 
         // Reinitializing the directories that require reinitialization.
         paths.reinitialize_required_directories()?;
 
-        // Running the tests on the package, this will generate the profraw files for us.
-        test_package(
-            paths.package_directory_path.as_path(),
-            self.arguments.clone(),
-            true,
-            is_scrypto_cargo_locked_env_var_active() || self.locked,
-            indexmap! {
-                "COVERAGE_DIRECTORY" => paths.coverage_data_dir_path.as_path()
-            },
-        )
-        .map_err(CoverageError::TestError)?;
-
-        // Reading the LLVM-IR file of the compiled package and applying the necessary corrections
-        // to it.
-        let llvm_ir_file_pre_correction_contents =
-            std::fs::read_to_string(paths.llvm_ir_pre_corrections_file_path.as_path())?;
-        let llvm_ir_file_post_correction_contents = LLVM_IR_CORRECTIONS_REGEX
-            .replace_all(
-                llvm_ir_file_pre_correction_contents.as_str(),
-                "${1}start:\n  unreachable\n}\n",
+        // Running the tests on the packages, this will generate the profraw files for us.
+        for package_paths in paths.packages.iter() {
+            test_package(
+                package_paths.package_directory_path.as_path(),
+                self.arguments.clone(),
+                true,
+                is_scrypto_cargo_locked_env_var_active() || self.locked,
+                indexmap! {
+                    "COVERAGE_DIRECTORY" => paths.coverage_data_dir_path.as_path()
+                },
             )
-            .to_string();
-        std::fs::write(
-            paths.llvm_ir_post_corrections_file_path.as_path(),
-            llvm_ir_file_post_correction_contents,
-        )?;
-
-        // Converting the corrected LLVM-IR into an object file through clang.
-        let object_file_conversion_output = llvm_toolchain
-            .new_clang_command()
-            .arg(paths.llvm_ir_post_corrections_file_path.as_path())
-            .arg("-Wno-override-module")
-            .arg("-c")
-            .arg("-o")
-            .arg(paths.object_file_path.as_path())
-            .arg("--target=aarch64-unknown-linux-gnu")
-            .output()
-            .map_err(CoverageError::CommandFailedToRun)?;
-        if !object_file_conversion_output.status.success() {
-            let error = String::from_utf8_lossy(&object_file_conversion_output.stderr);
-            eprintln!("clang failed: {}", error);
-            return Err(CoverageError::ClangFailed(error.to_string()));
+            .map_err(CoverageError::TestError)?;
+        }
+
+        for package_paths in paths.packages.iter() {
+            // Reading the LLVM-IR file of the compiled package and applying the necessary
+            // corrections to it.
+            let llvm_ir_file_pre_correction_contents =
+                std::fs::read_to_string(package_paths.llvm_ir_pre_corrections_file_path.as_path())?;
+            let llvm_ir_file_post_correction_contents = LLVM_IR_CORRECTIONS_REGEX
+                .replace_all(
+                    llvm_ir_file_pre_correction_contents.as_str(),
+                    "${1}start:\n  unreachable\n}\n",
+                )
+                .to_string();
+            std::fs::write(
+                package_paths.llvm_ir_post_corrections_file_path.as_path(),
+                llvm_ir_file_post_correction_contents,
+            )?;
+
+            // Converting the corrected LLVM-IR into an object file through clang.
+            let object_file_conversion_output = llvm_toolchain
+                .new_clang_command()
+                .arg(package_paths.llvm_ir_post_corrections_file_path.as_path())
+                .arg("-Wno-override-module")
+                .arg("-c")
+                .arg("-o")
+                .arg(package_paths.object_file_path.as_path())
+                .arg("--target=aarch64-unknown-linux-gnu")
+                .output()
+                .map_err(CoverageError::CommandFailedToRun)?;
+            if !object_file_conversion_output.status.success() {
+                let error = String::from_utf8_lossy(&object_file_conversion_output.stderr);
+                eprintln!("clang failed: {}", error);
+                return Err(CoverageError::ClangFailed(error.to_string()));
+            }
         }
 
-        // Merging all of the profraw files into a profdata file.
+        // Merging all of the profraw files of all of the packages into a profdata file.
         let profraw_files_iterator = WalkDir::new(paths.coverage_data_dir_path.as_path())
             .into_iter()
             .filter_map(|entry| entry.ok())
@@ -146,32 +180,89 @@ impl Coverage {
             return Err(CoverageError::LlvmProfdataFailed(error.to_string()));
         }
 
-        // Generating the final report based on the object file and the profdata that was generated.
-        let report_generation_output = llvm_toolchain
-            .new_llvm_cov_command()
-            .arg("show")
-            .arg("--instr-profile")
-            .arg(paths.profdata_file_path.as_path())
-            .arg(paths.object_file_path.as_path())
-            .arg("--show-instantiations=false")
-            .arg("--format=html")
-            .arg("--output-dir")
-            .arg(paths.report_dir_path.as_path())
-            .arg("-sources")
-            .arg(paths.package_directory_path.as_path())
-            .output()
-            .map_err(CoverageError::CommandFailedToRun)?;
-        if !report_generation_output.status.success() {
-            let error = String::from_utf8_lossy(&report_generation_output.stderr);
-            eprintln!("clang failed: {}", error);
-            return Err(CoverageError::LlvmCovFailed(error.to_string()));
+        // Generating the final report based on the object files and the profdata that was
+        // generated.
+        std::fs::create_dir_all(paths.report_dir_path.as_path())?;
+        match self.format {
+            ReportFormat::Html => {
+                paths.llvm_cov(&llvm_toolchain, "show", |command| {
+                    command
+                        .arg("--show-instantiations=false")
+                        .arg("--format=html")
+                        .arg("--output-dir")
+                        .arg(paths.report_dir_path.as_path());
+                })?;
+            }
+            ReportFormat::Lcov => {
+                let lcov = paths.llvm_cov(&llvm_toolchain, "export", |command| {
+                    command.arg("--format=lcov");
+                })?;
+                std::fs::write(paths.report_dir_path.join("lcov.info"), lcov)?;
+            }
+            ReportFormat::Cobertura => {}
         }
 
-        Ok(())
+        // Computing the line coverage for the summaries, the Cobertura report and the threshold.
+        let export = paths.llvm_cov(&llvm_toolchain, "export", |command| {
+            command.arg("--format=text");
+        })?;
+        let export = serde_json::from_slice::<serde_json::Value>(&export)
+            .map_err(|error| CoverageError::InvalidCoverageExport(error.to_string()))?;
+        let source_directories = paths.source_directories();
+        let coverage_data = CoverageData::from_llvm_cov_export(&export, &source_directories)?;
+
+        if self.format == ReportFormat::Cobertura {
+            let timestamp = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default();
+            let packages = paths
+                .packages
+                .iter()
+                .map(|package_paths| {
+                    (
+                        package_paths.package_name.clone(),
+                        package_paths.package_directory_path.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            std::fs::write(
+                paths.report_dir_path.join("cobertura.xml"),
+                coverage_data.to_cobertura(
+                    paths.package_directory_path.as_path(),
+                    &packages,
+                    &blueprints,
+                    timestamp,
+                ),
+            )?;
+        }
+
+        let total = coverage_data.total();
+        for summary in coverage_data.blueprint_summaries(&blueprints) {
+            println!("{:<48} {}", summary.blueprint_name, summary.lines);
+            for (function_name, lines) in summary.functions {
+                match lines {
+                    Some(lines) => println!("  {:<46} {}", function_name, lines),
+                    None => println!("  {:<46} -", function_name),
+                }
+            }
+        }
+        println!("{:<48} {}", "Total", total);
+
+        match self.fail_under {
+            Some(required) if total.percent() < required => {
+                Err(CoverageError::CoverageBelowThreshold {
+                    required,
+                    actual: total.percent(),
+                })
+            }
+            _ => Ok(()),
+        }
     }
 }
 
-/// A struct that contains all of the paths, filenames, and information on the build artifacts. Note
+/// A struct that contains all of the paths and information shared by the packages that we're doing
+/// a coverage report for, which are a single package or the Scrypto packages of a workspace. Note
 /// that all of the paths contained in this struct are canonicalized and do not require the user of
 /// the struct to do it again.
 ///
@@ -179,32 +270,166 @@ impl Coverage {
 ///
 /// You should never construct this struct yourself. You should always construct it through the
 /// [`Paths::new`] function which performs the required checks to ensure that all paths are correct.
-///
-/// This struct makes the assumption (when appropriate) that the nightly compiler is used since we
-/// always make use of it for coverage and it also assumes a release profile. There is no reason for
-/// us to turn those into arguments since this struct is EXCLUSIVELY used in this coverage module
-/// and we can safely make assumptions about how other parts of the code will act.
 #[allow(dead_code)]
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Paths {
-    /// The path of the package that we're doing a coverage report for.
+    /// The path of the package or workspace that we're doing a coverage report for.
     pub package_directory_path: PathBuf,
-    /// The path of the `Cargo.toml` manifest of package that we're doing a coverage report for.
+    /// The path of the `Cargo.toml` manifest of the package or workspace that we're doing a
+    /// coverage report for.
     pub manifest_path: PathBuf,
-    /// The name of the package that we're doing a coverage report for. We're disallowing workspaces
-    /// at this moment of time and assuming that there will only be a single compiled WASM.
-    pub package_name: String,
 
-    /// The path of the target directory used for the compilations of all of the artifacts.
+    /// The path of the target directory of the package or workspace.
     pub target_dir_path: PathBuf,
     /// The path of the coverage directory within the target directory that contains everything
     /// related to coverage.
     pub coverage_dir_path: PathBuf,
-    /// The path of the coverage data directory within the target directory that contains everything
-    /// related to coverage.
+    /// The path of the coverage data directory within the target directory that contains the
+    /// coverage data of all of the packages.
     pub coverage_data_dir_path: PathBuf,
     /// The path of the directory that contains the final generated coverage report.
     pub report_dir_path: PathBuf,
+
+    /// The file name of the profdata file, merged from the profraw files of all of the packages.
+    pub profdata_file_name: String,
+    /// The file path of the profdata file.
+    pub profdata_file_path: PathBuf,
+
+    /// The paths of each of the packages that we're doing a coverage report for.
+    pub packages: Vec<PackagePaths>,
+}
+
+impl Paths {
+    pub fn new(user_provided_path: Option<PathBuf>) -> Result<Self, CoverageError> {
+        // We either use the user provided path or the current directory path. Error out of neither
+        // path exists.
+        let package_directory_path = user_provided_path
+            .or(current_dir().ok())
+            .ok_or(CoverageError::FailedToResolvePackagePath)?
+            .canonicalize()
+            .map_err(|_| CoverageError::FailedToResolvePackagePath)?;
+        let manifest_path = assert_path_exists(package_directory_path.join("Cargo.toml"))?;
+
+        // Getting the packages through the `cargo metadata` command. In a workspace, only the
+        // packages with Scrypto metadata are Scrypto packages.
+        let metadata = MetadataCommand::new()
+            .manifest_path(manifest_path.as_path())
+            .no_deps()
+            .exec()
+            .map_err(CoverageError::CargoMetadataError)?;
+        let packages = match metadata.packages.as_slice() {
+            [] => Err(CoverageError::NoPackagesFound),
+            [package] => Ok(vec![package]),
+            packages => {
+                let packages = packages
+                    .iter()
+                    .filter(|package| package.metadata.get("scrypto").is_some())
+                    .collect::<Vec<_>>();
+                if packages.is_empty() {
+                    Err(CoverageError::NoPackagesFound)
+                } else {
+                    Ok(packages)
+                }
+            }
+        }?
+        .into_iter()
+        .map(PackagePaths::new)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        // Creating the paths of the target directory
+        let target_dir_path = package_directory_path.join("target");
+        let coverage_dir_path = target_dir_path.join("coverage");
+        let report_dir_path = coverage_dir_path.join("report");
+        let coverage_data_dir_path = coverage_dir_path.join("data");
+
+        let profdata_file_name = "coverage.profdata".to_owned();
+        let profdata_file_path = coverage_data_dir_path.join(profdata_file_name.clone());
+
+        Ok(Self {
+            package_directory_path,
+            manifest_path,
+            target_dir_path,
+            coverage_dir_path,
+            coverage_data_dir_path,
+            report_dir_path,
+            profdata_file_name,
+            profdata_file_path,
+            packages,
+        })
+    }
+
+    /// Reinitializes any directory that requires re-initialization.
+    pub fn reinitialize_required_directories(&self) -> Result<(), CoverageError> {
+        let directory_path = self.coverage_data_dir_path.as_path();
+        let _ = std::fs::remove_dir_all(directory_path);
+        std::fs::create_dir_all(directory_path)?;
+        Ok(())
+    }
+
+    /// The directories of the sources of the packages, which the reports are restricted to.
+    pub fn source_directories(&self) -> Vec<PathBuf> {
+        self.packages
+            .iter()
+            .map(|package_paths| package_paths.package_directory_path.clone())
+            .collect()
+    }
+
+    /// Runs an `llvm-cov` subcommand over the object files of all of the packages and the merged
+    /// profdata, and returns its output.
+    pub fn llvm_cov(
+        &self,
+        llvm_toolchain: &LLVMToolchain,
+        subcommand: &str,
+        configure: impl FnOnce(&mut Command),
+    ) -> Result<Vec<u8>, CoverageError> {
+        let mut command = llvm_toolchain.new_llvm_cov_command();
+        command
+            .arg(subcommand)
+            .arg("--instr-profile")
+            .arg(self.profdata_file_path.as_path());
+        for (index, package_paths) in self.packages.iter().enumerate() {
+            if index > 0 {
+                command.arg("-object");
+            }
+            command.arg(package_paths.object_file_path.as_path());
+        }
+        configure(&mut command);
+        command
+            .arg("-sources")
+            .args(self.source_directories().into_iter().map(OsString::from));
+
+        let output = command
+            .output()
+            .map_err(CoverageError::CommandFailedToRun)?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            eprintln!("clang failed: {}", error);
+            return Err(CoverageError::LlvmCovFailed(error.to_string()));
+        }
+        Ok(output.stdout)
+    }
+}
+
+/// A struct that contains all of the paths, filenames, and information on the build artifacts of a
+/// package.
+///
+/// This struct makes the assumption (when appropriate) that the nightly compiler is used since we
+/// always make use of it for coverage and it also assumes a release profile. There is no reason for
+/// us to turn those into arguments since this struct is EXCLUSIVELY used in this coverage module
+/// and we can safely make assumptions about how other parts of the code will act.
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct PackagePaths {
+    /// The path of the package.
+    pub package_directory_path: PathBuf,
+    /// The path of the `Cargo.toml` manifest of the package.
+    pub manifest_path: PathBuf,
+    /// The name of the package.
+    pub package_name: String,
+
+    /// The path of the coverage directory of the package, which the tests of the package look the
+    /// build artifacts up in.
+    pub coverage_dir_path: PathBuf,
     /// The path of the output directory that contains all of the build artifacts.
     pub build_artifacts_dir_path: PathBuf,
 
@@ -238,43 +463,21 @@ struct Paths {
     pub object_file_name: String,
     /// The file path of the object file.
     pub object_file_path: PathBuf,
-
-    /// The file name of the profdata file.
-    pub profdata_file_name: String,
-    /// The file path of the profdata file.
-    pub profdata_file_path: PathBuf,
 }
 
-impl Paths {
-    pub fn new(user_provided_path: Option<PathBuf>) -> Result<Self, CoverageError> {
-        // We either use the user provided path or the current directory path. Error out of neither
-        // path exists.
-        let package_directory_path = user_provided_path
-            .or(current_dir().ok())
+impl PackagePaths {
+    pub fn new(package: &Package) -> Result<Self, CoverageError> {
+        let manifest_path = assert_path_exists(package.manifest_path.clone().into_std_path_buf())?;
+        let package_directory_path = manifest_path
+            .parent()
             .ok_or(CoverageError::FailedToResolvePackagePath)?
-            .canonicalize()
-            .map_err(|_| CoverageError::FailedToResolvePackagePath)?;
-        let manifest_path = assert_path_exists(package_directory_path.join("Cargo.toml"))?;
-
-        // Getting the package name through the `cargo metadata` command.
-        let metadata = MetadataCommand::new()
-            .manifest_path(manifest_path.as_path())
-            .no_deps()
-            .exec()
-            .map_err(CoverageError::CargoMetadataError)?;
-        let package_name = match metadata.packages.as_slice() {
-            [Package { name, .. }] => Ok(name.as_str()),
-            [] => Err(CoverageError::NoPackagesFound),
-            [..] => Err(CoverageError::WorkspacesNotPermitted),
-        }?
-        .to_owned();
+            .to_path_buf();
+        let package_name = package.name.to_string();
         let file_name = package_name.replace('-', "_");
 
-        // Creating the paths of the target directory
-        let target_dir_path = package_directory_path.join("target");
-        let coverage_dir_path = target_dir_path.join("coverage");
-        let report_dir_path = coverage_dir_path.join("report");
-        let coverage_data_dir_path = coverage_dir_path.join("data");
+        // Creating the paths of the target directory, which is the one of the package even within
+        // a workspace as this is where the tests look the coverage build artifacts up.
+        let coverage_dir_path = package_directory_path.join("target").join("coverage");
         let build_artifacts_dir_path = coverage_dir_path
             .join("wasm32-unknown-unknown")
             .join("release");
@@ -299,17 +502,11 @@ impl Paths {
         let object_file_name = format!("{file_name}.o");
         let object_file_path = build_artifacts_dir_path.join(object_file_name.clone());
 
-        let profdata_file_name = format!("{file_name}.profdata");
-        let profdata_file_path = coverage_data_dir_path.join(profdata_file_name.clone());
-
         Ok(Self {
             package_directory_path,
             manifest_path,
             package_name,
-            target_dir_path,
             coverage_dir_path,
-            coverage_data_dir_path,
-            report_dir_path,
             build_artifacts_dir_path,
             file_name,
             wasm_file_name,
@@ -323,18 +520,8 @@ impl Paths {
             llvm_ir_post_corrections_file_path,
             object_file_name,
             object_file_path,
-            profdata_file_name,
-            profdata_file_path,
         })
     }
-
-    /// Reinitializes any directory that requires re-initialization.
-    pub fn reinitialize_required_directories(&self) -> Result<(), CoverageError> {
-        let directory_path = self.coverage_data_dir_path.as_path();
-        let _ = std::fs::remove_dir_all(directory_path);
-        std::fs::create_dir(directory_path)?;
-        Ok(())
-    }
 }
 
 /// A struct that contains the paths and helper methods for the tools from the LLVM Toolchain that
@@ -449,14 +636,9 @@ pub enum CoverageError {
     #[error("Encountered an error when trying to get the cargo metadata for the package: {0}")]
     CargoMetadataError(#[from] cargo_metadata::Error),
 
-    /// Found multiple packages when we got the metadata leading us to know that this is a workspace
-    /// which is currently not permitted.
-    #[error("The provided package is a workspace which we don't currently support")]
-    WorkspacesNotPermitted,
-
-    /// Could not find any packages when we got the `cargo metadata` and therefore there is nothing
-    /// that we can perform.
-    #[error("The provided directory doesn't contain any packages")]
+    /// Could not find any packages when we got the `cargo metadata`, or any package with Scrypto
+    /// metadata in a workspace, and therefore there is nothing that we can perform.
+    #[error("The provided directory doesn't contain any Scrypto packages")]
     NoPackagesFound,
 
     /// We ran a command but it failed to run or failed during waiting.
@@ -496,6 +678,14 @@ pub enum CoverageError {
     /// An error was encountered when running the llvm-cov command
     #[error("An error was encountered when running the llvm-cov command: {0:?}")]
     LlvmCovFailed(String),
+
+    /// The coverage data exported by the llvm-cov command couldn't be interpreted.
+    #[error("The coverage data exported by llvm-cov is invalid: {0}")]
+    InvalidCoverageExport(String),
+
+    /// The percentage of lines covered is below the threshold provided by the user.
+    #[error("The line coverage of {actual:.2}% is below the required {required:.2}%")]
+    CoverageBelowThreshold { required: f64, actual: f64 },
 }
 
 /// Check if a path exists or not. If it does then it's returned, otherwise, an error is returned.
@@ -566,7 +756,9 @@ fn construct_build_environment_variables() -> IndexMap<String, String> {
         .with_flag("-Cinstrument-coverage")
         .with_flag("-Zno-profiler-runtime")
         .with_flag("--emit=llvm-ir")
-        .with_flag("-Zlocation-detail=none");
+        .with_flag("-Zlocation-detail=none")
+        .with_flag("-Zunstable-options")
+        .with_flag("-Csymbol-mangling-version=legacy");
     for (env_var, cargo_encoding) in [
         ("RUSTFLAGS", false),
         ("CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUSTFLAGS", false),
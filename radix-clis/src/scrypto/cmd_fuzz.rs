@@ -0,0 +1,58 @@
+use clap::Parser;
+use scrypto_compiler::is_scrypto_cargo_locked_env_var_active;
+use std::env::current_dir;
+use std::path::PathBuf;
+
+use crate::scrypto::*;
+use crate::utils::*;
+
+/// Run a Scrypto fuzz test
+///
+/// The test is a `Fuzzer` based test of the package, which replays the inputs of its regression
+/// file and then runs with random inputs. Failing inputs are minimized and saved to
+/// `fuzz/regressions/<test name>.rs`.
+#[derive(Parser, Debug)]
+pub struct Fuzz {
+    /// The name of the fuzz test
+    test: String,
+
+    /// The package directory
+    #[clap(long)]
+    path: Option<PathBuf>,
+
+    /// The number of random inputs to run the test with
+    #[clap(short, long, default_value = "1000")]
+    iterations: u64,
+
+    /// The seed of the random inputs. A random seed is used if not specified.
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// Ensures the Cargo.lock file is used as-is. Equivalent to `cargo test --locked`.
+    /// Alternatively, the `SCRYPTO_CARGO_LOCKED` environment variable can be used,
+    /// which makes it easy to set universally in CI.
+    #[clap(long)]
+    locked: bool,
+}
+
+impl Fuzz {
+    pub fn run(&self) -> Result<(), String> {
+        let seed = self.seed.unwrap_or_else(rand::random);
+        println!(
+            "Fuzzing {} with {} inputs, seed {}",
+            self.test, self.iterations, seed
+        );
+
+        test_package(
+            self.path.clone().unwrap_or(current_dir().unwrap()),
+            [self.test.as_str(), "--nocapture"],
+            false,
+            is_scrypto_cargo_locked_env_var_active() || self.locked,
+            [
+                ("SCRYPTO_FUZZ_ITERATIONS", self.iterations.to_string()),
+                ("SCRYPTO_FUZZ_SEED", seed.to_string()),
+            ],
+        )
+        .map_err(|err| Error::TestError(err).into())
+    }
+}
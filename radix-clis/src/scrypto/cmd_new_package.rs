@@ -2,9 +2,123 @@ use clap::Parser;
 use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use crate::scrypto::*;
 
+/// The blueprint a new package starts from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PackageTemplate {
+    /// A component giving away tokens from its vault.
+    #[default]
+    HelloWorld,
+    /// A token whose supply the owner can mint and anyone holding it can burn.
+    FungibleToken,
+    /// A collection of NFTs sold for XRD.
+    NftCollection,
+    /// A constant product exchange wrapping the native two-resource pool.
+    DexPool,
+    /// An airdrop through the native account locker, with claims for the accounts which don't
+    /// accept deposits.
+    AccountLockerAirdrop,
+    /// A vault which only the holders of a badge can withdraw from.
+    AccessControlledVault,
+}
+
+impl FromStr for PackageTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hello-world" => Ok(Self::HelloWorld),
+            "fungible-token" => Ok(Self::FungibleToken),
+            "nft-collection" => Ok(Self::NftCollection),
+            "dex-pool" => Ok(Self::DexPool),
+            "account-locker-airdrop" => Ok(Self::AccountLockerAirdrop),
+            "access-controlled-vault" => Ok(Self::AccessControlledVault),
+            _ => Err(format!(
+                "Invalid template `{}`, expected hello-world, fungible-token, nft-collection, \
+                 dex-pool, account-locker-airdrop or access-controlled-vault",
+                s
+            )),
+        }
+    }
+}
+
+impl PackageTemplate {
+    /// The files of the template, as paths relative to the package directory with their content.
+    fn files(&self) -> Vec<(&'static str, &'static str)> {
+        macro_rules! archetype {
+            ($name:literal, [$($manifest:literal),*]) => {
+                vec![
+                    (
+                        "src/lib.rs",
+                        include_str!(concat!(
+                            "../../assets/template/archetypes/",
+                            $name,
+                            "/src/lib.rs"
+                        )),
+                    ),
+                    (
+                        "tests/lib.rs",
+                        include_str!(concat!(
+                            "../../assets/template/archetypes/",
+                            $name,
+                            "/tests/lib.rs"
+                        )),
+                    ),
+                    $((
+                        concat!("manifests/", $manifest),
+                        include_str!(concat!(
+                            "../../assets/template/archetypes/",
+                            $name,
+                            "/manifests/",
+                            $manifest
+                        )),
+                    ),)*
+                ]
+            };
+        }
+
+        match self {
+            Self::HelloWorld => vec![
+                (
+                    "src/lib.rs",
+                    include_str!("../../assets/template/src/lib.rs"),
+                ),
+                (
+                    "tests/lib.rs",
+                    include_str!("../../assets/template/tests/lib.rs"),
+                ),
+            ],
+            Self::FungibleToken => archetype!(
+                "fungible-token",
+                ["instantiate.rtm", "mint.rtm", "burn.rtm"]
+            ),
+            Self::NftCollection => archetype!(
+                "nft-collection",
+                ["instantiate.rtm", "mint.rtm", "withdraw_proceeds.rtm"]
+            ),
+            Self::DexPool => archetype!(
+                "dex-pool",
+                ["instantiate.rtm", "add_liquidity.rtm", "swap.rtm"]
+            ),
+            Self::AccountLockerAirdrop => archetype!(
+                "account-locker-airdrop",
+                ["instantiate.rtm", "airdrop.rtm", "claim.rtm"]
+            ),
+            Self::AccessControlledVault => archetype!(
+                "access-controlled-vault",
+                [
+                    "instantiate.rtm",
+                    "withdraw.rtm",
+                    "set_withdrawal_limit.rtm"
+                ]
+            ),
+        }
+    }
+}
+
 /// Create a Scrypto package
 #[derive(Parser, Debug)]
 pub struct NewPackage {
@@ -18,6 +132,11 @@ pub struct NewPackage {
     /// Use local Scrypto as dependency
     #[clap(short, long)]
     local: bool,
+
+    /// The blueprint to start from: hello-world, fungible-token, nft-collection, dex-pool,
+    /// account-locker-airdrop or access-controlled-vault
+    #[clap(long, default_value = "hello-world")]
+    template: PackageTemplate,
 }
 
 impl NewPackage {
@@ -47,8 +166,7 @@ impl NewPackage {
         if path.exists() {
             Err(Error::PackageAlreadyExists.into())
         } else {
-            fs::create_dir_all(child_of(&path, "src")).map_err(Error::IOError)?;
-            fs::create_dir_all(child_of(&path, "tests")).map_err(Error::IOError)?;
+            fs::create_dir_all(&path).map_err(Error::IOError)?;
 
             fs::write(
                 child_of(&path, "Cargo.toml"),
@@ -74,18 +192,14 @@ impl NewPackage {
             )
             .map_err(Error::IOError)?;
 
-            fs::write(
-                child_of(&child_of(&path, "src"), "lib.rs"),
-                include_str!("../../assets/template/src/lib.rs"),
-            )
-            .map_err(Error::IOError)?;
-
-            fs::write(
-                child_of(&child_of(&path, "tests"), "lib.rs"),
-                include_str!("../../assets/template/tests/lib.rs")
-                    .replace("${wasm_name}", &wasm_name),
-            )
-            .map_err(Error::IOError)?;
+            for (file_path, content) in self.template.files() {
+                let file_path = child_of(&path, file_path);
+                if let Some(parent) = file_path.parent() {
+                    fs::create_dir_all(parent).map_err(Error::IOError)?;
+                }
+                fs::write(file_path, content.replace("${wasm_name}", &wasm_name))
+                    .map_err(Error::IOError)?;
+            }
 
             fs::write(
                 child_of(&path, "rust-toolchain.toml"),
@@ -143,3 +257,67 @@ dependencies = [
 fn child_of(path: &Path, name: &str) -> PathBuf {
     path.join(name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_common::prelude::*;
+    use radix_transactions::manifest::*;
+
+    #[test]
+    fn template_manifests_compile() {
+        let network = NetworkDefinition::simulator();
+        let encoder = AddressBech32Encoder::new(&network);
+        let account = |seed: u64| {
+            let public_key = Secp256k1PrivateKey::from_u64(seed).unwrap().public_key();
+            ComponentAddress::preallocated_account_from_public_key(&public_key).to_string(&encoder)
+        };
+        let variables: IndexMap<&str, String> = indexmap!(
+            "account" => account(1),
+            "claimant" => account(2),
+            "claimant1" => account(3),
+            "claimant2" => account(4),
+            "component" => FAUCET.to_string(&encoder),
+            "locker" => FAUCET.to_string(&encoder),
+            "package" => FAUCET_PACKAGE.to_string(&encoder),
+            "resource1" => XRD.to_string(&encoder),
+            "resource2" => ACCOUNT_OWNER_BADGE.to_string(&encoder),
+            "token" => XRD.to_string(&encoder),
+            "owner_badge" => ACCOUNT_OWNER_BADGE.to_string(&encoder),
+            "withdrawer_badge" => ACCOUNT_OWNER_BADGE.to_string(&encoder),
+            "xrd" => XRD.to_string(&encoder)
+        );
+        let placeholder = Regex::new(r"\$\{(.+?)\}").unwrap();
+
+        for template in [
+            PackageTemplate::HelloWorld,
+            PackageTemplate::FungibleToken,
+            PackageTemplate::NftCollection,
+            PackageTemplate::DexPool,
+            PackageTemplate::AccountLockerAirdrop,
+            PackageTemplate::AccessControlledVault,
+        ] {
+            for (path, content) in template.files() {
+                if !path.ends_with(".rtm") {
+                    continue;
+                }
+                let manifest = placeholder.replace_all(content, |caps: &regex::Captures| {
+                    variables
+                        .get(&caps[1])
+                        .unwrap_or_else(|| panic!("Unknown variable {} in {}", &caps[1], path))
+                        .clone()
+                });
+                if let Err(error) = compile_any_manifest_with_pretty_error(
+                    &manifest,
+                    // The kind `resim run` compiles manifests as by default
+                    ManifestKind::parse_or_latest(None).unwrap(),
+                    &network,
+                    BlobProvider::new(),
+                    CompileErrorDiagnosticsStyle::PlainText,
+                ) {
+                    panic!("{} of {:?} doesn't compile:\n{}", path, template, error);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::resim::{SimulatorEnvironment, SimulatorPackageAddress};
+use crate::scrypto::*;
+use crate::utils::*;
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_engine_interface::prelude::Level;
+use radix_engine_interface::types::{BlueprintPartitionOffset, PackagePartitionOffset};
+use radix_substate_store_interface::interface::*;
+use radix_substate_store_queries::typed_substate_layout::*;
+use scrypto_compiler::*;
+use std::path::{Path, PathBuf};
+
+/// Rebuild a Scrypto package reproducibly and check that it matches a published package or a
+/// WASM file
+#[derive(Parser, Debug)]
+pub struct Verify {
+    /// The address of a package published on the simulator ledger, or the path to a WASM file.
+    /// If a build info file lies next to the WASM file, the package is rebuilt with its settings.
+    target: String,
+
+    /// The directory of the package sources.
+    source_dir: PathBuf,
+
+    /// The target directory. If not specified default target directory for project will be used.
+    #[clap(long)]
+    target_dir: Option<PathBuf>,
+
+    /// The max log level, such as ERROR, WARN, INFO, DEBUG and TRACE.
+    /// The default is INFO.
+    #[clap(long)]
+    log_level: Option<Level>,
+
+    /// Comma separated list of features to activate.
+    #[clap(short = 'F', long)]
+    features: Option<String>,
+
+    /// Project profile to use. The default is Release.
+    #[clap(long)]
+    profile: Option<Profile>,
+
+    /// Do not activate the `default` feature.
+    #[clap(long)]
+    no_default_features: bool,
+
+    /// Activate all available features.
+    #[clap(long)]
+    all_features: bool,
+
+    /// Prints compilation steps.
+    #[clap(short, long)]
+    verbose: bool,
+}
+
+#[derive(Debug)]
+pub enum VerifyError {
+    InvalidTarget(String),
+
+    PackageNotFound(PackageAddress),
+
+    ResimError(crate::resim::Error),
+
+    BuildError(BuildError),
+
+    CodeHashMismatch { expected: Hash, actual: Hash },
+}
+
+impl Verify {
+    pub fn run(&self) -> Result<(), String> {
+        let target_path = Path::new(&self.target);
+        let (expected_code_hash, build_info) = if target_path.is_file() {
+            let code = std::fs::read(target_path).map_err(Error::IOError)?;
+            let build_info_path = BuildInfo::path_for_wasm(target_path);
+            let build_info = if build_info_path.exists() {
+                Some(BuildInfo::read(&build_info_path).map_err(|e| {
+                    Error::VerifyError(VerifyError::BuildError(BuildError::ScryptoCompilerError(e)))
+                })?)
+            } else {
+                None
+            };
+            (hash(code), build_info)
+        } else {
+            (
+                published_code_hash(&self.target).map_err(Error::VerifyError)?,
+                None,
+            )
+        };
+
+        let mut compiler_builder = ScryptoCompiler::builder();
+        compiler_builder
+            .manifest_path(&self.source_dir)
+            .reproducible()
+            .debug(self.verbose);
+        if let Some(target_dir) = &self.target_dir {
+            compiler_builder.target_directory(target_dir);
+        }
+        match &build_info {
+            // The build info records the complete list of features, log level ones included
+            Some(build_info) => {
+                compiler_builder
+                    .profile(build_info.profile.clone())
+                    .disable_logs();
+                build_info.features.iter().for_each(|f| {
+                    compiler_builder.feature(f);
+                });
+                if build_info.no_default_features {
+                    compiler_builder.no_default_features();
+                }
+                if build_info.all_features {
+                    compiler_builder.all_features();
+                }
+            }
+            None => {
+                if let Some(log_level) = self.log_level {
+                    compiler_builder.log_level(log_level);
+                }
+                if let Some(profile) = &self.profile {
+                    compiler_builder.profile(profile.clone());
+                }
+                if let Some(features) = &self.features {
+                    features.split(',').for_each(|f| {
+                        compiler_builder.feature(f);
+                    });
+                }
+                if self.no_default_features {
+                    compiler_builder.no_default_features();
+                }
+                if self.all_features {
+                    compiler_builder.all_features();
+                }
+            }
+        }
+
+        let artifacts = compiler_builder
+            .compile()
+            .map_err(BuildError::ScryptoCompilerError)
+            .and_then(|mut artifacts| artifacts.pop().ok_or(BuildError::BuildArtifactsEmpty))
+            .map_err(|e| Error::VerifyError(VerifyError::BuildError(e)))?;
+        let actual_code_hash = hash(&artifacts.wasm.content);
+
+        if actual_code_hash != expected_code_hash {
+            return Err(Error::VerifyError(VerifyError::CodeHashMismatch {
+                expected: expected_code_hash,
+                actual: actual_code_hash,
+            })
+            .into());
+        }
+
+        println!("Verified, code hash: {}", actual_code_hash);
+        Ok(())
+    }
+}
+
+fn published_code_hash(package_address: &str) -> Result<Hash, VerifyError> {
+    let package_address = SimulatorPackageAddress::from_str(package_address)
+        .map_err(|_| VerifyError::InvalidTarget(package_address.to_string()))?
+        .0;
+
+    let SimulatorEnvironment { db, .. } =
+        SimulatorEnvironment::new().map_err(VerifyError::ResimError)?;
+    let (_, substate) = db
+        .list_map_values::<PackageCodeOriginalCodeEntrySubstate>(
+            package_address,
+            PackagePartitionOffset::CodeOriginalCodeKeyValue.as_main_partition(),
+            None::<SubstateKey>,
+        )
+        .next()
+        .ok_or(VerifyError::PackageNotFound(package_address))?;
+    let code = substate
+        .into_value()
+        .ok_or(VerifyError::PackageNotFound(package_address))?
+        .fully_update_and_into_latest_version()
+        .code;
+
+    Ok(hash(code))
+}
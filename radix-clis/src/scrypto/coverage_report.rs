@@ -0,0 +1,550 @@
+//! Processing of the coverage data exported by `llvm-cov export` into line coverage: summaries per
+//! blueprint and method, the threshold check and the Cobertura report.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::scrypto::CoverageError;
+
+/// The format of the report generated by `scrypto coverage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// A browsable HTML report, written to `report/index.html`.
+    #[default]
+    Html,
+    /// An LCOV tracefile, written to `report/lcov.info`.
+    Lcov,
+    /// A Cobertura XML report, written to `report/cobertura.xml`.
+    Cobertura,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "html" => Ok(Self::Html),
+            "lcov" => Ok(Self::Lcov),
+            "cobertura" => Ok(Self::Cobertura),
+            _ => Err(format!(
+                "Invalid format `{}`, expected html, lcov or cobertura",
+                s
+            )),
+        }
+    }
+}
+
+/// The number of lines which were executed out of the lines which map to code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LinesSummary {
+    pub covered: usize,
+    pub total: usize,
+}
+
+impl LinesSummary {
+    fn of<'a>(hits: impl IntoIterator<Item = &'a u64>) -> Self {
+        hits.into_iter()
+            .fold(Self::default(), |summary, hits| Self {
+                covered: summary.covered + usize::from(*hits > 0),
+                total: summary.total + 1,
+            })
+    }
+
+    /// The percentage of lines covered, a package without any code being fully covered.
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.covered as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    fn rate(&self) -> f64 {
+        self.percent() / 100.0
+    }
+}
+
+impl fmt::Display for LinesSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.2}% ({}/{})",
+            self.percent(),
+            self.covered,
+            self.total
+        )
+    }
+}
+
+/// A blueprint of a package along with the names of its functions and methods.
+#[derive(Debug, Clone)]
+pub struct BlueprintFunctions {
+    pub blueprint_name: String,
+    pub function_names: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlueprintSummary {
+    pub blueprint_name: String,
+    pub lines: LinesSummary,
+    /// The summary of each function and method, which is `None` if no code of the function was
+    /// found, as it was inlined for instance.
+    pub functions: Vec<(String, Option<LinesSummary>)>,
+}
+
+/// The number of times each line was executed, for a file or a function.
+type LineHits = BTreeMap<(PathBuf, u64), u64>;
+
+#[derive(Debug, Clone)]
+struct FunctionCoverage {
+    /// The identifiers of the path of the function, e.g. `["hello", "hello", "Hello", "new"]`.
+    path: Vec<String>,
+    lines: LineHits,
+}
+
+/// The line coverage of the sources of the packages.
+#[derive(Debug, Clone, Default)]
+pub struct CoverageData {
+    lines: LineHits,
+    functions: Vec<FunctionCoverage>,
+}
+
+impl CoverageData {
+    /// Computes the line coverage from the JSON output of `llvm-cov export`, keeping the files
+    /// which are within one of the source directories only.
+    pub fn from_llvm_cov_export(
+        export: &serde_json::Value,
+        source_directories: &[PathBuf],
+    ) -> Result<Self, CoverageError> {
+        let functions = export
+            .pointer("/data/0/functions")
+            .and_then(|functions| functions.as_array())
+            .ok_or_else(|| CoverageError::InvalidCoverageExport("missing functions".to_owned()))?;
+
+        let mut coverage = Self::default();
+        for function in functions {
+            let (Some(name), Some(regions), Some(filenames)) = (
+                function.get("name").and_then(|name| name.as_str()),
+                function
+                    .get("regions")
+                    .and_then(|regions| regions.as_array()),
+                function
+                    .get("filenames")
+                    .and_then(|filenames| filenames.as_array()),
+            ) else {
+                return Err(CoverageError::InvalidCoverageExport(format!(
+                    "invalid function {}",
+                    function
+                )));
+            };
+
+            let mut regions_per_file = BTreeMap::<PathBuf, Vec<Region>>::new();
+            for region in regions {
+                let Some(region) = Region::from_json(region) else {
+                    return Err(CoverageError::InvalidCoverageExport(format!(
+                        "invalid region {}",
+                        region
+                    )));
+                };
+                let Some(filename) = filenames.get(region.file_id).and_then(|f| f.as_str()) else {
+                    continue;
+                };
+                let filename = PathBuf::from(filename);
+                if region.kind == CODE_REGION
+                    && source_directories
+                        .iter()
+                        .any(|directory| filename.starts_with(directory))
+                {
+                    regions_per_file.entry(filename).or_default().push(region);
+                }
+            }
+            if regions_per_file.is_empty() {
+                continue;
+            }
+
+            let mut lines = LineHits::new();
+            for (filename, regions) in regions_per_file {
+                for (line, hits) in line_hits(&regions) {
+                    lines.insert((filename.clone(), line), hits);
+                }
+            }
+            merge_line_hits(&mut coverage.lines, &lines);
+            coverage.functions.push(FunctionCoverage {
+                path: demangle_path(name),
+                lines,
+            });
+        }
+
+        Ok(coverage)
+    }
+
+    pub fn total(&self) -> LinesSummary {
+        LinesSummary::of(self.lines.values())
+    }
+
+    /// Summarizes the coverage of each of the blueprints, and of each of their functions and
+    /// methods along with the closures defined within them.
+    pub fn blueprint_summaries(&self, blueprints: &[BlueprintFunctions]) -> Vec<BlueprintSummary> {
+        blueprints
+            .iter()
+            .map(|blueprint| {
+                let mut blueprint_lines = LineHits::new();
+                let functions = blueprint
+                    .function_names
+                    .iter()
+                    .map(|function_name| {
+                        let lines = self.function_lines(&blueprint.blueprint_name, function_name);
+                        merge_line_hits(&mut blueprint_lines, &lines);
+                        let summary = (!lines.is_empty()).then(|| LinesSummary::of(lines.values()));
+                        (function_name.clone(), summary)
+                    })
+                    .collect();
+                BlueprintSummary {
+                    blueprint_name: blueprint.blueprint_name.clone(),
+                    lines: LinesSummary::of(blueprint_lines.values()),
+                    functions,
+                }
+            })
+            .collect()
+    }
+
+    /// Renders the coverage as a Cobertura XML report, with one package per Scrypto package, one
+    /// class per source file and the blueprint functions and methods of the file as its methods.
+    pub fn to_cobertura(
+        &self,
+        root_directory: &Path,
+        packages: &[(String, PathBuf)],
+        blueprints: &[BlueprintFunctions],
+        timestamp: u64,
+    ) -> String {
+        let mut xml = String::new();
+        let total = self.total();
+        xml.push_str("<?xml version=\"1.0\" ?>\n");
+        xml.push_str(
+            "<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">\n",
+        );
+        xml.push_str(&format!(
+            "<coverage line-rate=\"{:.4}\" branch-rate=\"0\" lines-covered=\"{}\" lines-valid=\"{}\" branches-covered=\"0\" branches-valid=\"0\" complexity=\"0\" version=\"scrypto {}\" timestamp=\"{}\">\n",
+            total.rate(),
+            total.covered,
+            total.total,
+            env!("CARGO_PKG_VERSION"),
+            timestamp
+        ));
+        xml.push_str(&format!(
+            "  <sources>\n    <source>{}</source>\n  </sources>\n  <packages>\n",
+            escape_xml(&root_directory.display().to_string())
+        ));
+
+        let mut files = BTreeMap::<&Path, BTreeMap<u64, u64>>::new();
+        for ((filename, line), hits) in &self.lines {
+            files.entry(filename).or_default().insert(*line, *hits);
+        }
+
+        for (package_name, package_directory) in packages {
+            let package_files = files
+                .iter()
+                .filter(|(filename, _)| filename.starts_with(package_directory))
+                .collect::<Vec<_>>();
+            let package_lines =
+                LinesSummary::of(package_files.iter().flat_map(|(_, l)| l.values()));
+            xml.push_str(&format!(
+                "    <package name=\"{}\" line-rate=\"{:.4}\" branch-rate=\"0\" complexity=\"0\">\n      <classes>\n",
+                escape_xml(package_name),
+                package_lines.rate()
+            ));
+            for (filename, lines) in package_files {
+                let relative_path = filename
+                    .strip_prefix(root_directory)
+                    .unwrap_or(filename)
+                    .display()
+                    .to_string();
+                xml.push_str(&format!(
+                    "        <class name=\"{0}\" filename=\"{0}\" line-rate=\"{1:.4}\" branch-rate=\"0\" complexity=\"0\">\n          <methods>\n",
+                    escape_xml(&relative_path),
+                    LinesSummary::of(lines.values()).rate()
+                ));
+                for blueprint in blueprints {
+                    for function_name in &blueprint.function_names {
+                        let function_lines = self
+                            .function_lines(&blueprint.blueprint_name, function_name)
+                            .into_iter()
+                            .filter(|((function_file, _), _)| function_file.as_path() == *filename)
+                            .map(|((_, line), hits)| (line, hits))
+                            .collect::<BTreeMap<_, _>>();
+                        if function_lines.is_empty() {
+                            continue;
+                        }
+                        xml.push_str(&format!(
+                            "            <method name=\"{}::{}\" signature=\"\" line-rate=\"{:.4}\" branch-rate=\"0\" complexity=\"0\">\n              <lines>\n",
+                            escape_xml(&blueprint.blueprint_name),
+                            escape_xml(function_name),
+                            LinesSummary::of(function_lines.values()).rate()
+                        ));
+                        push_cobertura_lines(&mut xml, &function_lines, "                ");
+                        xml.push_str("              </lines>\n            </method>\n");
+                    }
+                }
+                xml.push_str("          </methods>\n          <lines>\n");
+                push_cobertura_lines(&mut xml, lines, "            ");
+                xml.push_str("          </lines>\n        </class>\n");
+            }
+            xml.push_str("      </classes>\n    </package>\n");
+        }
+
+        xml.push_str("  </packages>\n</coverage>\n");
+        xml
+    }
+
+    fn function_lines(&self, blueprint_name: &str, function_name: &str) -> LineHits {
+        let mut lines = LineHits::new();
+        self.functions
+            .iter()
+            .filter(|function| {
+                function
+                    .path
+                    .windows(2)
+                    .any(|pair| pair[0] == blueprint_name && pair[1] == function_name)
+            })
+            .for_each(|function| merge_line_hits(&mut lines, &function.lines));
+        lines
+    }
+}
+
+const CODE_REGION: u64 = 0;
+
+/// A region of the coverage mapping, exported as
+/// `[line_start, column_start, line_end, column_end, execution_count, file_id, expanded_file_id,
+/// kind]`.
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    line_start: u64,
+    column_start: u64,
+    line_end: u64,
+    execution_count: u64,
+    file_id: usize,
+    kind: u64,
+}
+
+impl Region {
+    fn from_json(region: &serde_json::Value) -> Option<Self> {
+        let fields = region
+            .as_array()?
+            .iter()
+            .map(|field| field.as_u64())
+            .collect::<Option<Vec<_>>>()?;
+        match fields.as_slice() {
+            [line_start, column_start, line_end, _, execution_count, file_id, _, kind, ..] => {
+                Some(Self {
+                    line_start: *line_start,
+                    column_start: *column_start,
+                    line_end: *line_end,
+                    execution_count: *execution_count,
+                    file_id: *file_id as usize,
+                    kind: *kind,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Computes the number of times each line of a function was executed. A line counts the
+/// executions of the regions starting on it, or otherwise of the innermost region spanning it,
+/// which is the way `llvm-cov` counts them as well.
+fn line_hits(regions: &[Region]) -> BTreeMap<u64, u64> {
+    let mut lines = BTreeMap::new();
+    let first_line = regions.iter().map(|r| r.line_start).min().unwrap_or(0);
+    let last_line = regions.iter().map(|r| r.line_end).max().unwrap_or(0);
+    for line in first_line..=last_line {
+        let starting = regions
+            .iter()
+            .filter(|region| region.line_start == line)
+            .map(|region| region.execution_count)
+            .max();
+        let hits = starting.or_else(|| {
+            regions
+                .iter()
+                .filter(|region| region.line_start < line && line <= region.line_end)
+                .max_by_key(|region| (region.line_start, region.column_start))
+                .map(|region| region.execution_count)
+        });
+        if let Some(hits) = hits {
+            lines.insert(line, hits);
+        }
+    }
+    lines
+}
+
+fn merge_line_hits(lines: &mut LineHits, other: &LineHits) {
+    for (line, hits) in other {
+        let entry = lines.entry(line.clone()).or_default();
+        *entry = (*entry).max(*hits);
+    }
+}
+
+fn push_cobertura_lines(xml: &mut String, lines: &BTreeMap<u64, u64>, indentation: &str) {
+    for (line, hits) in lines {
+        xml.push_str(&format!(
+            "{}<line number=\"{}\" hits=\"{}\" branch=\"false\"/>\n",
+            indentation, line, hits
+        ));
+    }
+}
+
+/// Extracts the identifiers of the path of a symbol mangled with the legacy Rust mangling scheme,
+/// e.g. `_ZN5hello5hello5Hello3new17h0123456789abcdefE`. The coverage build enforces this scheme,
+/// the paths of symbols which are not mangled being empty.
+fn demangle_path(symbol: &str) -> Vec<String> {
+    let Some(mut rest) = symbol
+        .strip_prefix("_ZN")
+        .or_else(|| symbol.strip_prefix("__ZN"))
+    else {
+        return Vec::new();
+    };
+
+    let mut path = Vec::new();
+    loop {
+        let length_digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let Ok(length) = rest[..length_digits].parse::<usize>() else {
+            break;
+        };
+        let Some(identifier) = rest.get(length_digits..length_digits + length) else {
+            break;
+        };
+        path.push(identifier.to_owned());
+        rest = &rest[length_digits + length..];
+    }
+
+    // The last identifier is the hash of the symbol
+    if path.last().is_some_and(|last| {
+        last.len() == 17
+            && last.starts_with('h')
+            && last[1..].bytes().all(|byte| byte.is_ascii_hexdigit())
+    }) {
+        path.pop();
+    }
+    path
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demangle_path() {
+        assert_eq!(
+            demangle_path("_ZN5hello5hello5Hello10free_token17h0123456789abcdefE"),
+            vec!["hello", "hello", "Hello", "free_token"]
+        );
+        assert_eq!(
+            demangle_path(
+                "_ZN5hello5hello5Hello3new28_$u7b$$u7b$closure$u7d$$u7d$17h0123456789abcdefE"
+            ),
+            vec![
+                "hello",
+                "hello",
+                "Hello",
+                "new",
+                "_$u7b$$u7b$closure$u7d$$u7d$"
+            ]
+        );
+        assert!(demangle_path("Hello_free_token").is_empty());
+    }
+
+    #[test]
+    fn test_blueprint_summaries() {
+        let export = serde_json::json!({
+            "data": [{
+                "functions": [
+                    {
+                        "name": "_ZN5hello5hello5Hello10free_token17h0123456789abcdefE",
+                        "count": 1,
+                        // An `if` on line 11 whose body on lines 12 to 13 never runs
+                        "regions": [
+                            [10, 5, 15, 6, 1, 0, 0, 0],
+                            [11, 9, 11, 20, 1, 0, 0, 0],
+                            [11, 21, 13, 10, 0, 0, 0, 0],
+                            [14, 9, 14, 20, 1, 0, 0, 0]
+                        ],
+                        "filenames": ["/package/src/lib.rs"]
+                    },
+                    {
+                        "name": "_ZN5hello5hello5Hello3new17h0123456789abcdefE",
+                        "count": 0,
+                        "regions": [[20, 5, 22, 6, 0, 0, 0, 0]],
+                        "filenames": ["/package/src/lib.rs"]
+                    },
+                    {
+                        "name": "_ZN4core3fmt5write17h0123456789abcdefE",
+                        "count": 1,
+                        "regions": [[1, 1, 9, 1, 1, 0, 0, 0]],
+                        "filenames": ["/rustc/library/core/src/fmt/mod.rs"]
+                    }
+                ]
+            }]
+        });
+        let coverage =
+            CoverageData::from_llvm_cov_export(&export, &[PathBuf::from("/package")]).unwrap();
+
+        assert_eq!(
+            coverage.total(),
+            LinesSummary {
+                covered: 4,
+                total: 9
+            }
+        );
+        let blueprints = [BlueprintFunctions {
+            blueprint_name: "Hello".to_owned(),
+            function_names: vec![
+                "new".to_owned(),
+                "free_token".to_owned(),
+                "other".to_owned(),
+            ],
+        }];
+        let summaries = coverage.blueprint_summaries(&blueprints);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].lines, coverage.total());
+        assert_eq!(
+            summaries[0].functions,
+            vec![
+                (
+                    "new".to_owned(),
+                    Some(LinesSummary {
+                        covered: 0,
+                        total: 3
+                    })
+                ),
+                (
+                    "free_token".to_owned(),
+                    Some(LinesSummary {
+                        covered: 4,
+                        total: 6
+                    })
+                ),
+                ("other".to_owned(), None),
+            ]
+        );
+
+        let cobertura = coverage.to_cobertura(
+            Path::new("/package"),
+            &[("hello".to_owned(), PathBuf::from("/package"))],
+            &blueprints,
+            0,
+        );
+        assert!(cobertura.contains("lines-covered=\"4\" lines-valid=\"9\""));
+        assert!(cobertura.contains("<class name=\"src/lib.rs\" filename=\"src/lib.rs\""));
+        assert!(cobertura.contains("<method name=\"Hello::free_token\""));
+        assert!(cobertura.contains("<line number=\"12\" hits=\"0\" branch=\"false\"/>"));
+    }
+}
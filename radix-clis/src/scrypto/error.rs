@@ -16,6 +16,10 @@ pub enum Error {
     PackageAlreadyExists,
 
     CoverageError(CoverageError),
+
+    VerifyError(VerifyError),
+
+    CheckError(CheckError),
 }
 
 impl fmt::Display for Error {
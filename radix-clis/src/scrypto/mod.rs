@@ -1,15 +1,27 @@
+mod blueprint_lints;
+mod build_report;
 mod cmd_build;
+mod cmd_check;
 mod cmd_coverage;
 mod cmd_fmt;
+mod cmd_fuzz;
 mod cmd_new_package;
 mod cmd_test;
+mod cmd_verify;
+mod coverage_report;
 mod error;
 
+pub use blueprint_lints::*;
+pub use build_report::*;
 pub use cmd_build::*;
+pub use cmd_check::*;
 pub use cmd_coverage::*;
 pub use cmd_fmt::*;
+pub use cmd_fuzz::*;
 pub use cmd_new_package::*;
 pub use cmd_test::*;
+pub use cmd_verify::*;
+pub use coverage_report::*;
 pub use error::*;
 
 use clap::{Parser, Subcommand};
@@ -25,10 +37,13 @@ pub struct ScryptoCli {
 #[derive(Subcommand, Debug)]
 pub enum Command {
     Build(Build),
+    Check(Check),
     Coverage(Coverage),
     Fmt(Fmt),
+    Fuzz(Fuzz),
     NewPackage(NewPackage),
     Test(Test),
+    Verify(Verify),
 }
 
 pub fn run() -> Result<(), String> {
@@ -36,9 +51,12 @@ pub fn run() -> Result<(), String> {
 
     match cli.command {
         Command::Build(cmd) => cmd.run(),
+        Command::Check(cmd) => cmd.run(),
         Command::Coverage(cmd) => cmd.run().map_err(|error| error.to_string()),
         Command::Fmt(cmd) => cmd.run(),
+        Command::Fuzz(cmd) => cmd.run(),
         Command::NewPackage(cmd) => cmd.run(),
         Command::Test(cmd) => cmd.run(),
+        Command::Verify(cmd) => cmd.run(),
     }
 }
@@ -0,0 +1,198 @@
+use scrypto_bindgen::schema::{PackageSchemaResolver, SchemaError};
+
+use radix_blueprint_schema_init::*;
+use radix_common::prelude::*;
+use radix_engine_interface::blueprints::package::*;
+use radix_engine_interface::types::Level;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use crate::utils::*;
+
+use super::Error;
+
+/// A package definition read from a `.rpd` file or built from a Scrypto package, in the form the
+/// package has once it is published.
+pub struct LocalPackageDefinition {
+    pub blueprints: BTreeMap<BlueprintVersionKey, BlueprintDefinition>,
+    pub schemas: IndexMap<SchemaHash, Rc<VersionedScryptoSchema>>,
+}
+
+impl LocalPackageDefinition {
+    /// Loads the package definition at the given path, which is either a `.rpd` file or the
+    /// directory of a Scrypto package, in which case the package is built first.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let definition_path = if path.extension() == Some(OsStr::new("rpd")) {
+            path.to_path_buf()
+        } else {
+            let build_artifacts = build_package(path, false, Level::default(), false, [])
+                .map_err(Error::BuildError)?;
+            if build_artifacts.len() > 1 {
+                return Err(Error::BuildError(BuildError::WorkspaceNotSupported));
+            }
+            build_artifacts
+                .into_iter()
+                .next()
+                .ok_or(Error::BuildError(BuildError::BuildArtifactsEmpty))?
+                .1
+        };
+
+        let package_definition = manifest_decode::<ManifestPackageDefinition>(
+            &fs::read(&definition_path).map_err(Error::IOError)?,
+        )
+        .map_err(Error::SborDecodeError)?
+        .try_into_typed()
+        .map_err(Error::PackageDefinitionConversionError)?;
+
+        Self::from_package_definition(package_definition).map_err(Error::SchemaError)
+    }
+
+    /// Converts the definition the same way the package blueprint does when publishing it.
+    pub fn from_package_definition(definition: PackageDefinition) -> Result<Self, SchemaError> {
+        let mut blueprints = BTreeMap::new();
+        let mut schemas = index_map_new();
+
+        for (blueprint, definition_init) in definition.blueprints {
+            let schema_hash = definition_init.schema.schema.generate_schema_hash();
+            schemas.insert(schema_hash, Rc::new(definition_init.schema.schema));
+
+            let mut functions = index_map_new();
+            for (function, function_schema_init) in definition_init.schema.functions.functions {
+                let (TypeRef::Static(input), TypeRef::Static(output)) =
+                    (function_schema_init.input, function_schema_init.output)
+                else {
+                    return Err(SchemaError::GenericTypeRefsNotSupported);
+                };
+                functions.insert(
+                    function,
+                    FunctionSchema {
+                        receiver: function_schema_init.receiver,
+                        input: BlueprintPayloadDef::Static(ScopedTypeId(schema_hash, input)),
+                        output: BlueprintPayloadDef::Static(ScopedTypeId(schema_hash, output)),
+                    },
+                );
+            }
+
+            let events = definition_init
+                .schema
+                .events
+                .event_schema
+                .into_iter()
+                .map(|(key, type_ref)| {
+                    (
+                        key,
+                        BlueprintPayloadDef::from_type_ref(type_ref, schema_hash),
+                    )
+                })
+                .collect();
+
+            let types = definition_init
+                .schema
+                .types
+                .type_schema
+                .into_iter()
+                .map(|(key, local_type_id)| (key, ScopedTypeId(schema_hash, local_type_id)))
+                .collect();
+
+            // The bindings only need the interface of the blueprints, so the exports, which point
+            // into the code, are left out.
+            let definition = BlueprintDefinition {
+                interface: BlueprintInterface {
+                    blueprint_type: definition_init.blueprint_type,
+                    is_transient: definition_init.is_transient,
+                    generics: definition_init.schema.generics,
+                    feature_set: definition_init.feature_set,
+                    functions,
+                    events,
+                    types,
+                    state: IndexedStateSchema::from_schema(
+                        schema_hash,
+                        definition_init.schema.state,
+                        index_map_new(),
+                    ),
+                },
+                function_exports: index_map_new(),
+                hook_exports: index_map_new(),
+            };
+            blueprints.insert(BlueprintVersionKey::new_default(blueprint), definition);
+        }
+
+        Ok(Self {
+            blueprints,
+            schemas,
+        })
+    }
+}
+
+pub struct DefinitionSchemaResolver<'d>(PackageAddress, &'d LocalPackageDefinition);
+
+impl<'d> DefinitionSchemaResolver<'d> {
+    pub fn new(package_address: PackageAddress, definition: &'d LocalPackageDefinition) -> Self {
+        Self(package_address, definition)
+    }
+}
+
+impl<'d> PackageSchemaResolver for DefinitionSchemaResolver<'d> {
+    fn lookup_schema(&self, schema_hash: &SchemaHash) -> Option<Rc<VersionedScryptoSchema>> {
+        self.1.schemas.get(schema_hash).cloned()
+    }
+
+    fn package_address(&self) -> PackageAddress {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn definition_schema_resolver_resolves_the_schemas_of_the_definition() {
+        let definition = LocalPackageDefinition::from_package_definition(
+            PackageDefinition::new_functions_only_test_definition(
+                "Counter",
+                vec![("new", "Counter_new", false), ("get", "Counter_get", true)],
+            ),
+        )
+        .unwrap();
+        let resolver = DefinitionSchemaResolver::new(FAUCET_PACKAGE, &definition);
+
+        assert_eq!(resolver.package_address(), FAUCET_PACKAGE);
+        let blueprint = definition
+            .blueprints
+            .get(&BlueprintVersionKey::new_default("Counter"))
+            .unwrap();
+        assert!(blueprint.function_exports.is_empty());
+        let function = blueprint.interface.functions.get("get").unwrap();
+        assert!(function.receiver.is_some());
+        let BlueprintPayloadDef::Static(ScopedTypeId(schema_hash, _)) = &function.input else {
+            panic!("The input of a function should be static");
+        };
+        assert!(resolver.lookup_schema(schema_hash).is_some());
+        assert!(resolver
+            .lookup_schema(&SchemaHash(Hash([0; Hash::LENGTH])))
+            .is_none());
+    }
+
+    #[test]
+    fn generic_type_refs_are_not_supported() {
+        let mut package_definition =
+            PackageDefinition::new_single_function_test_definition("Counter", "new");
+        package_definition
+            .blueprints
+            .get_mut("Counter")
+            .unwrap()
+            .schema
+            .functions
+            .functions
+            .get_mut("new")
+            .unwrap()
+            .input = TypeRef::Generic(0);
+
+        assert!(matches!(
+            LocalPackageDefinition::from_package_definition(package_definition),
+            Err(SchemaError::GenericTypeRefsNotSupported)
+        ));
+    }
+}
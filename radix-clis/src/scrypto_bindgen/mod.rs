@@ -1,23 +1,54 @@
+mod definition;
+
+use scrypto_bindgen::python;
 use scrypto_bindgen::schema;
 use scrypto_bindgen::translation;
 use scrypto_bindgen::types;
+use scrypto_bindgen::typescript;
 
 use clap::Parser;
 use radix_common::prelude::*;
 use radix_engine::system::system_db_reader::SystemDatabaseReader;
+use radix_engine_interface::blueprints::package::*;
 use radix_substate_store_interface::interface::SubstateDatabase;
+use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
 
 use crate::resim::*;
+use crate::utils::*;
 
+pub use self::definition::*;
 use self::schema::*;
 
 /// Generates interfaces for Scrypto packages to ease the use of external packages.
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None, name = "scrypto-bindgen")]
 pub struct Args {
-    /// The address of the package to generate the bindings for.
-    package_address: String,
+    /// The address of the package to generate the bindings for. When the bindings are generated
+    /// from a package definition, this is the address the bindings will call into, which defaults
+    /// to a placeholder to be replaced once the package is published.
+    #[clap(required_unless_present = "definition")]
+    package_address: Option<String>,
+
+    /// The path to a package definition (`.rpd`) or to a Scrypto package, which is built, to
+    /// generate the bindings from instead of the ledger.
+    #[clap(long)]
+    definition: Option<PathBuf>,
+
+    /// The file to write the bindings to, instead of the standard output.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+
+    /// The language of the bindings: `rust` for Scrypto stubs, or `typescript` or `python` for
+    /// client modules which build manifest instructions and encode the types of the package.
+    #[clap(short, long, default_value = "rust")]
+    language: Language,
+
+    /// When enabled, the types of the events, the state and the registered types of each blueprint
+    /// are generated as well, along with the decoders of the events.
+    #[clap(long)]
+    types: bool,
 
     /// When enabled, the ledger will be cleared and bootstrapped again before being used to obtain
     /// the bindings.
@@ -28,6 +59,37 @@ pub struct Args {
     func_sig_change: Vec<types::FunctionSignatureReplacementsInput>,
 }
 
+/// The address the bindings generated from a package definition call into when no address is
+/// given, as the address of a package is only known once it is published.
+const PLACEHOLDER_PACKAGE_ADDRESS: PackageAddress = {
+    let mut raw = [0u8; NodeId::LENGTH];
+    raw[0] = EntityType::GlobalPackage as u8;
+    PackageAddress::new_or_panic(raw)
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    TypeScript,
+    Python,
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rust" => Ok(Self::Rust),
+            "typescript" => Ok(Self::TypeScript),
+            "python" => Ok(Self::Python),
+            _ => Err(format!(
+                "Invalid language `{}`, expected rust, typescript or python",
+                s
+            )),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     Bech32DecodeError(AddressBech32DecodeError),
@@ -35,59 +97,120 @@ pub enum Error {
     ResimError(crate::resim::Error),
     SchemaError(SchemaError),
     IOError(std::io::Error),
+    BuildError(BuildError),
+    SborDecodeError(DecodeError),
+    PackageDefinitionConversionError(ConversionError),
 }
 
 pub fn run() -> Result<(), Error> {
     let args = Args::parse();
 
-    // Everything will be written to the std-out
-    let mut out = std::io::stdout();
-
-    let env = if args.reset_ledger {
-        SimulatorEnvironment::new_reset().map_err(Error::ResimError)?
-    } else {
-        SimulatorEnvironment::new().map_err(Error::ResimError)?
-    };
-    let db = env.db;
-
     let blueprint_replacement_map = types::prepare_replacement_map(&args.func_sig_change);
 
     // Decode the package address without network context.
-    let package_address = {
-        let (_, _, bytes) =
-            AddressBech32Decoder::validate_and_decode_ignore_hrp(&args.package_address)
+    let (package_address, package_address_string) = match &args.package_address {
+        Some(address) => {
+            let (_, _, bytes) = AddressBech32Decoder::validate_and_decode_ignore_hrp(address)
                 .map_err(Error::Bech32DecodeError)?;
-        PackageAddress::try_from(bytes.as_slice()).map_err(Error::PackageAddressError)?
+            (
+                PackageAddress::try_from(bytes.as_slice()).map_err(Error::PackageAddressError)?,
+                address.clone(),
+            )
+        }
+        None => (
+            PLACEHOLDER_PACKAGE_ADDRESS,
+            PLACEHOLDER_PACKAGE_ADDRESS.to_string(&AddressBech32Encoder::for_simulator()),
+        ),
     };
 
-    // Generating the bindings
-    let bindings = {
+    // Generating the bindings, from the package definition if one is given, so that the ledger
+    // is not needed, or else from the package on the ledger.
+    let bindings = if let Some(path) = &args.definition {
+        let definition = LocalPackageDefinition::load(path)?;
+        let schema_resolver = DefinitionSchemaResolver::new(package_address, &definition);
+        generate_bindings(
+            definition.blueprints.clone(),
+            &schema_resolver,
+            &blueprint_replacement_map,
+            args.language,
+            args.types,
+        )?
+    } else {
+        let env = if args.reset_ledger {
+            SimulatorEnvironment::new_reset().map_err(Error::ResimError)?
+        } else {
+            SimulatorEnvironment::new().map_err(Error::ResimError)?
+        };
+        let db = env.db;
+
         let reader = SystemDatabaseReader::new(&db);
         let definition = reader.get_package_definition(package_address);
         let schema_resolver = SchemaResolver::new(package_address, &db);
-
-        let package_interface =
-            schema::package_interface_from_package_definition(definition, &schema_resolver)
-                .map_err(Error::SchemaError)?;
-        let mut ast_package_interface = translation::package_schema_interface_to_ast_interface(
-            package_interface,
-            package_address,
+        generate_bindings(
+            definition,
             &schema_resolver,
             &blueprint_replacement_map,
-        )
-        .map_err(Error::SchemaError)?;
-
-        // Scrypto-bindgen does not generate the aux-types. Only ledger-tools does.
-        ast_package_interface.auxiliary_types = Default::default();
-
-        ast_package_interface
+            args.language,
+            args.types,
+        )?
     };
 
-    writeln!(&mut out, "{}", quote::quote!(#bindings)).map_err(Error::IOError)?;
+    let bindings = match args.language {
+        Language::Rust => quote::quote!(#bindings).to_string(),
+        Language::TypeScript => {
+            typescript::package_stub_to_typescript(&bindings, &package_address_string)
+        }
+        Language::Python => python::package_stub_to_python(&bindings, &package_address_string),
+    };
+    match &args.output {
+        Some(path) => fs::write(path, format!("{}\n", bindings)).map_err(Error::IOError)?,
+        // Everything will be written to the std-out
+        None => writeln!(std::io::stdout(), "{}", bindings).map_err(Error::IOError)?,
+    }
 
     Ok(())
 }
 
+fn generate_bindings<S>(
+    definition: BTreeMap<BlueprintVersionKey, BlueprintDefinition>,
+    schema_resolver: &S,
+    blueprint_replacement_map: &types::BlueprintFunctionSignaturesReplacementMap,
+    language: Language,
+    blueprint_types: bool,
+) -> Result<scrypto_bindgen::ast::PackageStub, Error>
+where
+    S: PackageSchemaResolver,
+{
+    let package_interface =
+        schema::package_interface_from_package_definition(definition, schema_resolver)
+            .map_err(Error::SchemaError)?;
+    let blueprint_types = if blueprint_types {
+        translation::package_schema_interface_to_blueprint_types(
+            &package_interface,
+            schema_resolver,
+        )
+        .map_err(Error::SchemaError)?
+    } else {
+        Vec::new()
+    };
+    let mut ast_package_interface = translation::package_schema_interface_to_ast_interface(
+        package_interface,
+        schema_resolver.package_address(),
+        schema_resolver,
+        blueprint_replacement_map,
+    )
+    .map_err(Error::SchemaError)?;
+    ast_package_interface.blueprint_types = blueprint_types;
+
+    // Scrypto-bindgen does not generate the aux-types for Scrypto, only ledger-tools does. The
+    // clients in other languages need them to encode the arguments.
+    if language == Language::Rust {
+        ast_package_interface.auxiliary_types = Default::default();
+    }
+
+    Ok(ast_package_interface)
+}
+
 pub struct SchemaResolver<'s, S>(PackageAddress, SystemDatabaseReader<'s, S>)
 where
     S: SubstateDatabase;
@@ -110,51 +233,6 @@ where
         self.1.get_schema(self.0.as_node_id(), schema_hash).ok()
     }
 
-    fn resolve_type_kind(
-        &self,
-        type_identifier: &ScopedTypeId,
-    ) -> Result<LocalTypeKind<ScryptoCustomSchema>, schema::SchemaError> {
-        self.lookup_schema(&type_identifier.0)
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .as_latest_version()
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .resolve_type_kind(type_identifier.1)
-            .ok_or(schema::SchemaError::NonExistentLocalTypeIndex(
-                type_identifier.1,
-            ))
-            .cloned()
-    }
-
-    fn resolve_type_metadata(
-        &self,
-        type_identifier: &ScopedTypeId,
-    ) -> Result<TypeMetadata, schema::SchemaError> {
-        self.lookup_schema(&type_identifier.0)
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .as_latest_version()
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .resolve_type_metadata(type_identifier.1)
-            .ok_or(schema::SchemaError::NonExistentLocalTypeIndex(
-                type_identifier.1,
-            ))
-            .cloned()
-    }
-
-    fn resolve_type_validation(
-        &self,
-        type_identifier: &ScopedTypeId,
-    ) -> Result<TypeValidation<ScryptoCustomTypeValidation>, schema::SchemaError> {
-        self.lookup_schema(&type_identifier.0)
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .as_latest_version()
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .resolve_type_validation(type_identifier.1)
-            .ok_or(schema::SchemaError::NonExistentLocalTypeIndex(
-                type_identifier.1,
-            ))
-            .cloned()
-    }
-
     fn package_address(&self) -> PackageAddress {
         self.0
     }
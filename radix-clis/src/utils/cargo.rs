@@ -6,6 +6,7 @@ use std::process::Command;
 use std::process::ExitStatus;
 
 use radix_engine::utils::*;
+use radix_engine::vm::wasm::PrepareError;
 use radix_engine_interface::types::Level;
 use scrypto_compiler::*;
 
@@ -24,6 +25,8 @@ pub enum BuildError {
     WorkspaceNotSupported,
 
     EnvParsingError,
+
+    WasmValidationError(PrepareError),
 }
 
 #[derive(Debug)]
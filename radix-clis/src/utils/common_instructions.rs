@@ -6,6 +6,7 @@
 use radix_common::prelude::*;
 use radix_engine_interface::prelude::*;
 use radix_transactions::data::{from_decimal, from_non_fungible_local_id, from_precise_decimal};
+use radix_transactions::manifest::BuildableManifest;
 use radix_transactions::prelude::*;
 
 use super::{parse_resource_specifier, ResourceSpecifier};
@@ -72,12 +73,15 @@ impl From<RustToManifestValueError> for BuildCallArgumentsError {
 }
 
 /// Creates resource proof from an account.
-pub fn create_proof_from_account(
-    builder: ManifestBuilder,
+pub fn create_proof_from_account<M: BuildableManifest>(
+    builder: ManifestBuilder<M>,
     address_bech32_decoder: &AddressBech32Decoder,
     account: ComponentAddress,
     resource_specifier: String,
-) -> Result<ManifestBuilder, BuildCallArgumentError> {
+) -> Result<ManifestBuilder<M>, BuildCallArgumentError>
+where
+    M::Instruction: From<InstructionV1>,
+{
     let resource_specifier = parse_resource_specifier(&resource_specifier, address_bech32_decoder)
         .map_err(|_| BuildCallArgumentError::InvalidResourceSpecifier(resource_specifier))?;
     let builder = match resource_specifier {
@@ -94,14 +98,17 @@ pub fn create_proof_from_account(
     Ok(builder)
 }
 
-pub fn build_call_arguments(
-    mut builder: ManifestBuilder,
+pub fn build_call_arguments<M: BuildableManifest>(
+    mut builder: ManifestBuilder<M>,
     address_bech32_decoder: &AddressBech32Decoder,
     schema: &VersionedScryptoSchema,
     type_id: LocalTypeId,
     args: Vec<String>,
     account: Option<ComponentAddress>,
-) -> Result<(ManifestBuilder, ManifestValue), BuildCallArgumentsError> {
+) -> Result<(ManifestBuilder<M>, ManifestValue), BuildCallArgumentsError>
+where
+    M::Instruction: From<InstructionV1>,
+{
     let mut built_args = Vec::<ManifestValue>::new();
     match schema.v1().resolve_type_kind(type_id) {
         Some(TypeKind::Tuple { field_types }) => {
@@ -171,14 +178,17 @@ macro_rules! matches_bucket {
     };
 }
 
-fn build_call_argument(
-    mut builder: ManifestBuilder,
+fn build_call_argument<M: BuildableManifest>(
+    mut builder: ManifestBuilder<M>,
     address_bech32_decoder: &AddressBech32Decoder,
     type_kind: &ScryptoTypeKind<LocalTypeId>,
     type_validation: &TypeValidation<ScryptoCustomTypeValidation>,
     argument: String,
     account: Option<ComponentAddress>,
-) -> Result<(ManifestBuilder, ManifestValue), BuildCallArgumentError> {
+) -> Result<(ManifestBuilder<M>, ManifestValue), BuildCallArgumentError>
+where
+    M::Instruction: From<InstructionV1>,
+{
     match type_kind {
         ScryptoTypeKind::Bool => parse_basic_type!(builder, argument, Bool),
         ScryptoTypeKind::I8 => parse_basic_type!(builder, argument, I8),
@@ -72,6 +72,22 @@ $resim publish ../examples/hello-world/target/wasm32-unknown-unknown/release/hel
 # Test - export package definition
 $resim export-package-definition $package ../examples/hello-world/target/temp.rpd
 
+# Test - generate bindings from the ledger and from the package definition
+scrypto_bindgen="cargo run --bin scrypto-bindgen $@ --"
+$scrypto_bindgen $package > ./target/temp_bindings.rs
+$scrypto_bindgen $package --definition ../examples/hello-world/target/temp.rpd --output ./target/temp_bindings2.rs
+diff ./target/temp_bindings.rs ./target/temp_bindings2.rs
+$scrypto_bindgen $package --definition ../examples/hello-world/target/temp.rpd --language typescript --output ./target/temp_bindings.ts
+$scrypto_bindgen $package --definition ../examples/hello-world/target/temp.rpd --language python --output ./target/temp_bindings.py
+grep -q "export class Hello" ./target/temp_bindings.ts
+grep -q "class Hello:" ./target/temp_bindings.py
+$scrypto_bindgen $package --definition ../examples/hello-world/target/temp.rpd --types --output ./target/temp_types.rs
+grep -q "pub mod hello_types" ./target/temp_types.rs
+$scrypto_bindgen $package --definition ../examples/hello-world/target/temp.rpd --types --language typescript --output ./target/temp_types.ts
+grep -q "export namespace HelloTypes" ./target/temp_types.ts
+$scrypto_bindgen $package --definition ../examples/hello-world/target/temp.rpd --types --language python --output ./target/temp_types.py
+grep -q "class HelloTypes:" ./target/temp_types.py
+
 # Test - dump component state
 $resim show $package
 $resim show $component
@@ -128,3 +144,135 @@ token_address=`$resim new-token-mutable $non_fungible_global_id | awk '/Resource
 
 # Test - mint and transfer (Mintable that requires a `NonFungibleGlobalId`)
 $resim mint 777 $token_address --proofs "$non_fungible_global_id"
+
+# Test - snapshots
+$resim snapshot save before-mint --force
+supply_before=`$resim show $token_address | awk '/Total Supply:/ {print $NF}'`
+nonce_before=`$resim show-configs | awk '/Next Transaction Nonce:/ {print $NF}'`
+$resim mint 1 $token_address --proofs "$non_fungible_global_id"
+$resim snapshot save after-mint --force
+$resim snapshot list
+snapshot_diff=`$resim snapshot diff before-mint after-mint`
+if [[ ${snapshot_diff} != *"updated"* ]];then
+    echo "Snapshot diff is empty!"
+    exit 1
+fi
+$resim snapshot restore before-mint
+supply_restored=`$resim show $token_address | awk '/Total Supply:/ {print $NF}'`
+nonce_restored=`$resim show-configs | awk '/Next Transaction Nonce:/ {print $NF}'`
+if [[ "${supply_restored}" != "${supply_before}" || "${nonce_restored}" != "${nonce_before}" ]];then
+    echo "Snapshot restore did not bring back the saved ledger state!"
+    exit 1
+fi
+
+# Test - record and replay a session
+$resim reset
+$resim record start ./target/session.rec
+$resim new-account
+$resim set-current-epoch 200
+$resim set-current-time 2023-02-01T10:00:00Z
+$resim new-token-fixed 1000 --name 'RecordedToken'
+$resim show-ledger
+$resim reset
+$resim new-account
+$resim record stop
+$resim replay ./target/session.rec
+
+# Test - transaction history
+$resim history
+account=`$resim show-configs | awk '/Account Address:/ {print $NF}'`
+$resim history --address $account
+transaction_hash=`$resim new-token-fixed 100 --name 'HistoryToken' | awk '/Test Transaction Hash:/ {print $NF}'`
+$resim show-tx $transaction_hash
+
+# Test - JSON output
+json_output=`$resim new-token-fixed 100 --name 'JsonToken' --output json`
+if [[ ${json_output} != *'"committed_success"'* ]];then
+    echo "JSON output is missing the transaction status!"
+    exit 1
+fi
+$resim --output json show-configs
+
+# Test - V2 transaction with a signed subintent
+subintent_account_output=`$resim new-account`
+subintent_account=`echo "$subintent_account_output" | awk '/Account component address:/ {print $NF}'`
+subintent_private_key=`echo "$subintent_account_output" | awk '/Private key:/ {print $NF}'`
+cat > ./target/child.rtm <<EOT
+CALL_METHOD Address("$subintent_account") "withdraw" Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Decimal("10");
+TAKE_ALL_FROM_WORKTOP Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3") Bucket("xrd");
+YIELD_TO_PARENT Bucket("xrd");
+EOT
+cat > ./target/parent.rtm <<EOT
+USE_CHILD NamedIntent("child") Intent("\${child}");
+CALL_METHOD Address("component_sim1cptxxxxxxxxxfaucetxxxxxxxxx000527798379xxxxxxxxxhkrefh") "lock_fee" Decimal("5000");
+YIELD_TO_CHILD NamedIntent("child");
+CALL_METHOD Address("$account") "deposit_batch" Expression("ENTIRE_WORKTOP");
+EOT
+nonce_before=`$resim show-configs | awk '/Next Transaction Nonce:/ {print $NF}'`
+$resim run ./target/parent.rtm --subintent ./target/child.rtm --subintent-signer $subintent_private_key
+nonce_after=`$resim show-configs | awk '/Next Transaction Nonce:/ {print $NF}'`
+if [[ $((nonce_after - nonce_before)) != 1 ]];then
+    echo "Running a transaction with subintents should increment the nonce by exactly one!"
+    exit 1
+fi
+$resim run ./target/parent.rtm --subintent ./target/child.rtm --subintent-signer $subintent_private_key --network simulator
+$resim call-method component_sim1cptxxxxxxxxxfaucetxxxxxxxxx000527798379xxxxxxxxxhkrefh free --subintent ./target/child.rtm --subintent-signer $subintent_private_key
+
+# Test - preview without committing
+preview_output=`$resim transfer resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3:1 $subintent_account --preview`
+if [[ ${preview_output} != *'Balance Changes:'* ]];then
+    echo "Preview output is missing the balance changes!"
+    exit 1
+fi
+$resim run ./target/parent.rtm --subintent ./target/child.rtm --subintent-signer $subintent_private_key --preview
+
+# Test - validators and staking
+validator=`$resim new-validator --accept-delegated-stake | awk '/Validator address:/ {print $NF}'`
+$resim register-validator $validator
+$resim stake $validator 1000
+$resim stake $validator 10 --as-owner
+$resim advance-epoch
+$resim unstake $validator 100
+if $resim claim-xrd $validator; then
+    echo "Claiming XRD before the claim epoch should fail!"
+    exit 1
+fi
+history_length_before=`$resim history | wc -l`
+$resim advance-epoch 2
+history_length_after=`$resim history | wc -l`
+if [[ $history_length_after -le $history_length_before ]];then
+    echo "Advancing the epoch should be listed in the transaction history!"
+    exit 1
+fi
+$resim claim-xrd $validator
+
+# Test - interactive shell
+$resim shell <<EOT
+show-configs
+CALL_METHOD Address("component_sim1cptxxxxxxxxxfaucetxxxxxxxxx000527798379xxxxxxxxxhkrefh") "lock_fee" Decimal("500");
+CALL_METHOD Address("component_sim1cptxxxxxxxxxfaucetxxxxxxxxx000527798379xxxxxxxxxhkrefh") "free";
+CALL_METHOD Address("$account") "deposit_batch" Expression("ENTIRE_WORKTOP");
+manifest
+preview
+commit
+exit
+EOT
+
+# Test - address aliases
+$resim new-account --alias alias_test
+if $resim new-account --alias alias_test; then
+    echo "Reusing an alias should fail!"
+    exit 1
+fi
+$resim new-token-fixed 100 --symbol ALIAS --alias alias_token
+$resim alias set alias_default $account
+$resim transfer @alias_token:10 @alias_test
+$resim show @alias_test
+cat > ./target/alias.rtm <<EOT
+CALL_METHOD Address("component_sim1cptxxxxxxxxxfaucetxxxxxxxxx000527798379xxxxxxxxxhkrefh") "lock_fee" Decimal("500");
+CALL_METHOD Address("\${@alias_default}") "withdraw" Address("\${@alias_token}") Decimal("1");
+CALL_METHOD Address("\${@alias_test}") "try_deposit_batch_or_abort" Expression("ENTIRE_WORKTOP") None;
+EOT
+$resim run ./target/alias.rtm
+$resim alias list
+$resim alias remove alias_default
@@ -7,7 +7,12 @@ cd "$(dirname "$0")/.."
 
 rtmc="cargo run --bin rtmc -- $@ "
 rtmd="cargo run --bin rtmd -- $@ "
+rtm="cargo run --bin rtm -- $@ "
 
 # Decompile and recompile a subintent
 $rtmc --output ./tests/out/subintent.bin --kind subintentv2 ./tests/subintent.rtm
 $rtmd --output ./tests/out/subintent.rtm ./tests/out/subintent.bin
+
+# Format the decompiled subintent, which should then pass the check
+$rtm fmt ./tests/out/subintent.rtm
+$rtm fmt --check ./tests/out/subintent.rtm
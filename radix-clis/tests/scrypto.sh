@@ -32,9 +32,49 @@ $scrypto test --path $test_pkg --locked
 $scrypto test --path $test_pkg --locked -- test_hello --nocapture
 $scrypto test --path $test_pkg --locked -- --nocapture
 
+# Lints
+$scrypto check --path $test_pkg
+$scrypto check --path $test_pkg --format json --allow missing_method_auth --deny-warnings
+
+# Build report
+$scrypto build --path $test_pkg --locked --report
+
 # Check envs parsing
 $scrypto build --path $test_pkg --locked --env ENV_NAME=foo=bar
 
+# Reproducible build and verification
+$scrypto build --path $test_pkg --reproducible
+test -f $test_pkg/target/wasm32-unknown-unknown/release/hello_world.build_info.json
+$scrypto verify $test_pkg/target/wasm32-unknown-unknown/release/hello_world.wasm $test_pkg --target-dir ./target/temp/verify
+
+# Fuzzing
+cat > $test_pkg/tests/fuzz.rs <<'EOF'
+use scrypto_test::prelude::*;
+
+#[test]
+fn fuzz_hello() {
+    fuzzer!("fuzz_hello")
+        .package(this_package!())
+        .run(|env, input| {
+            let output = input.call_function(env, "Hello", "instantiate_hello")?;
+            let component: ComponentAddress = scrypto_decode(&output).unwrap();
+            input.call_method(env, component, "Hello", "free_token")?;
+            Ok(())
+        });
+}
+EOF
+$scrypto fuzz fuzz_hello --path $test_pkg --locked --iterations 8 --seed 1
+
+# Templates
+for template in fungible-token nft-collection dex-pool account-locker-airdrop access-controlled-vault; do
+    template_pkg="./target/temp/$template"
+    rm -fr $template_pkg
+    $scrypto new-package $template --path $template_pkg --local --template $template
+    test -f $template_pkg/manifests/instantiate.rtm
+    $scrypto test --path $template_pkg --locked
+    rm -fr $template_pkg
+done
+
 # Logging
 $scrypto build --path ../examples/everything --log-level ERROR --locked
 size1=$(ls -la ../examples/everything/target/wasm32-unknown-unknown/release/everything.wasm | cut -d ' ' -f 5)
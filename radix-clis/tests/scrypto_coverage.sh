@@ -26,5 +26,17 @@ else
     exit 1
 fi
 
+# Generate coverage reports in the other formats, gated on a threshold
+$scrypto coverage --path $test_pkg --format lcov --fail-under 10
+test -f "$test_pkg/target/coverage/report/lcov.info"
+$scrypto coverage --path $test_pkg --format cobertura
+test -f "$test_pkg/target/coverage/report/cobertura.xml"
+
+# Check that a threshold above the coverage fails
+if $scrypto coverage --path $test_pkg --fail-under 100.1; then
+    echo "Error: Coverage threshold not enforced."
+    exit 1
+fi
+
 # Clean up
 rm -fr $test_pkg
\ No newline at end of file
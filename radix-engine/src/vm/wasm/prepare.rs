@@ -20,6 +20,23 @@ pub struct WasmModule {
     module: ModuleInfo,
 }
 
+/// The shape of a module, as checked by the validator against its limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmModuleStatistics {
+    /// The number of functions defined by the module, not counting the imported ones.
+    pub function_count: u32,
+    /// The number of globals defined by the module, not counting the imported ones.
+    pub global_count: u32,
+    pub table_count: usize,
+    pub initial_table_size: Option<u32>,
+    pub initial_memory_pages: Option<u64>,
+    pub maximum_memory_pages: Option<u64>,
+    /// The imported functions, as `(module, name)` pairs.
+    pub function_imports: Vec<(String, String)>,
+    /// The exported functions, along with the size in bytes of their body.
+    pub function_exports: Vec<(String, usize)>,
+}
+
 impl WasmModule {
     pub fn init(code: &[u8]) -> Result<Self, PrepareError> {
         // deserialize
@@ -1325,6 +1342,63 @@ impl WasmModule {
         Ok((code, function_exports))
     }
 
+    pub fn statistics(&self) -> Result<WasmModuleStatistics, PrepareError> {
+        let function_imports: Vec<(String, String)> = self
+            .module
+            .import_section()
+            .map_err(|err| PrepareError::ModuleInfoError(err.to_string()))?
+            .unwrap_or(vec![])
+            .into_iter()
+            .filter(|entry| matches!(entry.ty, TypeRef::Func(_)))
+            .map(|entry| (entry.module.to_string(), entry.name.to_string()))
+            .collect();
+        let function_bodies = self
+            .module
+            .code_section()
+            .map_err(|err| PrepareError::ModuleInfoError(err.to_string()))?
+            .unwrap_or(vec![]);
+        let function_exports = self
+            .module
+            .export_section()
+            .map_err(|err| PrepareError::ModuleInfoError(err.to_string()))?
+            .unwrap_or(vec![])
+            .into_iter()
+            .filter(|export| export.kind == ExternalKind::Func)
+            .map(|export| {
+                // Imported functions come first in the function index space, and have no body.
+                let body_size = (export.index as usize)
+                    .checked_sub(function_imports.len())
+                    .and_then(|index| function_bodies.get(index))
+                    .map(|body| body.range().len())
+                    .unwrap_or(0);
+                (export.name.to_string(), body_size)
+            })
+            .collect();
+        let tables = self
+            .module
+            .table_section()
+            .map_err(|err| PrepareError::ModuleInfoError(err.to_string()))?
+            .unwrap_or(vec![]);
+        let memory = self
+            .module
+            .memory_section()
+            .map_err(|err| PrepareError::ModuleInfoError(err.to_string()))?
+            .unwrap_or(vec![])
+            .first()
+            .copied();
+
+        Ok(WasmModuleStatistics {
+            function_count: self.module.num_local_functions(),
+            global_count: self.module.num_local_globals(),
+            table_count: tables.len(),
+            initial_table_size: tables.first().map(|table| table.ty.initial),
+            initial_memory_pages: memory.map(|memory| memory.initial),
+            maximum_memory_pages: memory.and_then(|memory| memory.maximum),
+            function_imports,
+            function_exports,
+        })
+    }
+
     fn function_matches(
         module: &ModuleInfo,
         func_index: usize,
@@ -1665,6 +1739,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_statistics() {
+        let code = wat2wasm!(
+            r#"
+            (module
+                (import "env" "buffer_consume" (func $consume (param i32 i32)))
+                (global $g (mut i32) (i32.const 0))
+                (memory $0 17)
+                (table 2 funcref)
+                (func $Test_f (param $0 i64) (result i64)
+                    (local.get $0)
+                )
+                (export "memory" (memory $0))
+                (export "Test_f" (func $Test_f))
+            )
+            "#
+        );
+
+        assert_eq!(
+            WasmModule::init(&code).unwrap().statistics().unwrap(),
+            WasmModuleStatistics {
+                function_count: 1,
+                global_count: 1,
+                table_count: 1,
+                initial_table_size: Some(2),
+                initial_memory_pages: Some(17),
+                maximum_memory_pages: None,
+                function_imports: vec![("env".to_string(), "buffer_consume".to_string())],
+                // The number of local declarations, `local.get 0` and `end`
+                function_exports: vec![("Test_f".to_string(), 4)],
+            }
+        );
+    }
+
     #[test]
     fn test_br_table() {
         assert_invalid_wasm!(
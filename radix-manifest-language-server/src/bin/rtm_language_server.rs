@@ -0,0 +1,15 @@
+use lsp_server::Connection;
+use radix_manifest_language_server::run;
+
+pub fn main() {
+    // Messages are exchanged over stdin and stdout, so errors can only be reported on stderr
+    let (connection, io_threads) = Connection::stdio();
+    if let Err(err) = run(connection) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+    if let Err(err) = io_threads.join() {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
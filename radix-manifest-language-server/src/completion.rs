@@ -0,0 +1,236 @@
+use crate::definition::{find_declarations, find_named_values, NamedKind};
+use crate::document::Document;
+use crate::instructions::INSTRUCTIONS;
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionTextEdit, Documentation, MarkupContent,
+    MarkupKind, Position as LspPosition, Range, TextEdit,
+};
+use radix_transactions::manifest::token::{Token, TokenWithSpan};
+
+/// The value kinds, as used in `Array<...>`, `Map<..., ...>` and `Enum<...>`.
+const VALUE_KINDS: &[&str] = &[
+    "Bool",
+    "I8",
+    "I16",
+    "I32",
+    "I64",
+    "I128",
+    "U8",
+    "U16",
+    "U32",
+    "U64",
+    "U128",
+    "String",
+    "Enum",
+    "Array",
+    "Tuple",
+    "Map",
+    "Bytes",
+    "NonFungibleGlobalId",
+    "Address",
+    "Bucket",
+    "Proof",
+    "Expression",
+    "Blob",
+    "Decimal",
+    "PreciseDecimal",
+    "NonFungibleLocalId",
+    "AddressReservation",
+    "NamedAddress",
+    "Intent",
+    "NamedIntent",
+];
+
+/// The values which are written as a call, e.g. `Decimal("1")`.
+const VALUE_CONSTRUCTORS: &[&str] = &[
+    "Enum",
+    "Array",
+    "Tuple",
+    "Map",
+    "Some",
+    "None",
+    "Ok",
+    "Err",
+    "Bytes",
+    "NonFungibleGlobalId",
+    "Address",
+    "Bucket",
+    "Proof",
+    "Expression",
+    "Blob",
+    "Decimal",
+    "PreciseDecimal",
+    "NonFungibleLocalId",
+    "AddressReservation",
+    "NamedAddress",
+    "Intent",
+    "NamedIntent",
+];
+
+pub fn complete(document: &Document, position: LspPosition) -> Vec<CompletionItem> {
+    let index = document.char_index(position);
+    let tokens = document.tokens();
+    let mut preceding: Vec<&TokenWithSpan> = tokens
+        .iter()
+        .filter(|token| token.span.end.full_index <= index)
+        .collect();
+    // The word being typed isn't part of the context
+    if let Some(last) = preceding.last() {
+        if matches!(last.token, Token::Ident(_)) && last.span.end.full_index == index {
+            preceding.pop();
+        }
+    }
+    let instruction_start = preceding
+        .iter()
+        .rposition(|token| token.token == Token::Semicolon)
+        .map(|position| position + 1)
+        .unwrap_or(0);
+    let instruction = &preceding[instruction_start..];
+
+    match instruction {
+        [] => complete_instructions(),
+        [.., kind, open] if open.token == Token::OpenParenthesis => match &kind.token {
+            Token::Ident(ident) => match NamedKind::from_ident(ident) {
+                Some(named_kind) => {
+                    let range = Range::new(
+                        document.lsp_position(open.span.end.full_index),
+                        document.lsp_position(index),
+                    );
+                    complete_names(&tokens, named_kind, index, range)
+                }
+                None => complete_values(),
+            },
+            _ => complete_values(),
+        },
+        _ => {
+            let in_value_kinds = instruction
+                .iter()
+                .rev()
+                .find(|token| matches!(token.token, Token::LessThan | Token::GreaterThan))
+                .is_some_and(|token| token.token == Token::LessThan);
+            if in_value_kinds {
+                complete_value_kinds()
+            } else {
+                complete_values()
+            }
+        }
+    }
+}
+
+fn complete_instructions() -> Vec<CompletionItem> {
+    INSTRUCTIONS
+        .iter()
+        .map(|instruction| CompletionItem {
+            label: instruction.name.to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            detail: Some(instruction.signature()),
+            documentation: Some(Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: instruction.description.to_string(),
+            })),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn complete_values() -> Vec<CompletionItem> {
+    VALUE_CONSTRUCTORS
+        .iter()
+        .map(|value| CompletionItem {
+            label: value.to_string(),
+            kind: Some(CompletionItemKind::CONSTRUCTOR),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn complete_value_kinds() -> Vec<CompletionItem> {
+    VALUE_KINDS
+        .iter()
+        .map(|value_kind| CompletionItem {
+            label: value_kind.to_string(),
+            kind: Some(CompletionItemKind::TYPE_PARAMETER),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Completes the names declared before the cursor. The completion replaces everything typed after
+/// the parenthesis, so that it works whether or not the opening quote was typed.
+fn complete_names(
+    tokens: &[TokenWithSpan],
+    named_kind: NamedKind,
+    index: usize,
+    range: Range,
+) -> Vec<CompletionItem> {
+    let named_values = find_named_values(tokens);
+    find_declarations(&named_values)
+        .into_iter()
+        .filter(|declaration| {
+            declaration.kind == named_kind && declaration.span.end.full_index < index
+        })
+        .map(|declaration| {
+            let quoted_name = format!("\"{}\"", declaration.name);
+            CompletionItem {
+                label: declaration.name.to_string(),
+                kind: Some(CompletionItemKind::VARIABLE),
+                filter_text: Some(quoted_name.clone()),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(range, quoted_name))),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels_at(text: &str, position: LspPosition) -> Vec<String> {
+        let document = Document::new(text.to_string(), 1);
+        complete(&document, position)
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+    }
+
+    #[test]
+    fn test_instructions_are_completed_at_the_start_of_an_instruction() {
+        let labels = labels_at("DROP_ALL_PROOFS;\nTAKE", LspPosition::new(1, 4));
+        assert!(labels.contains(&"TAKE_ALL_FROM_WORKTOP".to_string()));
+        assert!(!labels.contains(&"Decimal".to_string()));
+    }
+
+    #[test]
+    fn test_values_and_value_kinds_are_completed_in_arguments() {
+        let labels = labels_at(
+            "CALL_METHOD Address(\"a\") \"m\" Dec",
+            LspPosition::new(0, 32),
+        );
+        assert!(labels.contains(&"Decimal".to_string()));
+        assert!(!labels.contains(&"U8".to_string()));
+
+        let labels = labels_at(
+            "CALL_METHOD Address(\"a\") \"m\" Array<",
+            LspPosition::new(0, 35),
+        );
+        assert!(labels.contains(&"U8".to_string()));
+    }
+
+    #[test]
+    fn test_declared_names_are_completed() {
+        let text =
+            "TAKE_ALL_FROM_WORKTOP Address(\"a\") Bucket(\"xrd\");\nRETURN_TO_WORKTOP Bucket(\"x";
+        let document = Document::new(text.to_string(), 1);
+        let items = complete(&document, LspPosition::new(1, 27));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "xrd");
+        assert_eq!(
+            items[0].text_edit,
+            Some(CompletionTextEdit::Edit(TextEdit::new(
+                Range::new(LspPosition::new(1, 25), LspPosition::new(1, 27)),
+                "\"xrd\"".to_string()
+            )))
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use crate::document::Document;
+use lsp_types::{Location, Position as LspPosition, Uri};
+use radix_transactions::manifest::token::{Span, Token, TokenWithSpan};
+
+/// The kinds of values which an instruction declares by name, for later instructions to refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamedKind {
+    Bucket,
+    Proof,
+    AddressReservation,
+    NamedAddress,
+    NamedIntent,
+}
+
+impl NamedKind {
+    pub fn from_ident(ident: &str) -> Option<Self> {
+        let kind = match ident {
+            "Bucket" => Self::Bucket,
+            "Proof" => Self::Proof,
+            "AddressReservation" => Self::AddressReservation,
+            "NamedAddress" => Self::NamedAddress,
+            "NamedIntent" => Self::NamedIntent,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// A named value, e.g. `Bucket("xrd")`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedValue<'a> {
+    pub kind: NamedKind,
+    pub name: &'a str,
+    /// The span of the whole value, from the value kind to the name.
+    pub span: Span,
+    /// The span of the name, including its quotes.
+    pub name_span: Span,
+}
+
+impl NamedValue<'_> {
+    fn contains(&self, index: usize) -> bool {
+        self.span.start.full_index <= index && index <= self.span.end.full_index
+    }
+}
+
+pub fn find_named_values(tokens: &[TokenWithSpan]) -> Vec<NamedValue<'_>> {
+    tokens
+        .windows(3)
+        .filter_map(|window| match window {
+            [TokenWithSpan {
+                token: Token::Ident(ident),
+                span: ident_span,
+            }, TokenWithSpan {
+                token: Token::OpenParenthesis,
+                ..
+            }, TokenWithSpan {
+                token: Token::StringLiteral(name),
+                span: name_span,
+            }] => Some(NamedValue {
+                kind: NamedKind::from_ident(ident)?,
+                name,
+                span: Span {
+                    start: ident_span.start,
+                    end: name_span.end,
+                },
+                name_span: *name_span,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the declarations among the named values. The compiler rejects any use of a name before
+/// the instruction declaring it, so a declaration is the first value with its kind and name.
+pub fn find_declarations<'a>(named_values: &[NamedValue<'a>]) -> Vec<NamedValue<'a>> {
+    let mut declarations: Vec<NamedValue<'a>> = Vec::new();
+    for value in named_values {
+        let declared = declarations
+            .iter()
+            .any(|declaration| declaration.kind == value.kind && declaration.name == value.name);
+        if !declared {
+            declarations.push(value.clone());
+        }
+    }
+    declarations
+}
+
+pub fn goto_definition(document: &Document, uri: &Uri, position: LspPosition) -> Option<Location> {
+    let tokens = document.tokens();
+    let index = document.char_index(position);
+    let named_values = find_named_values(&tokens);
+    let reference = named_values.iter().find(|value| value.contains(index))?;
+    let declaration = find_declarations(&named_values)
+        .into_iter()
+        .find(|declaration| {
+            declaration.kind == reference.kind && declaration.name == reference.name
+        })?;
+    Some(Location::new(
+        uri.clone(),
+        document.lsp_range(&declaration.name_span),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declarations_are_the_first_use_of_a_name() {
+        let document = Document::new(
+            r#"
+TAKE_ALL_FROM_WORKTOP Address("resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxakj8n3") Bucket("xrd");
+CREATE_PROOF_FROM_BUCKET_OF_ALL Bucket("xrd") Proof("proof");
+DROP_PROOF Proof("proof");
+RETURN_TO_WORKTOP Bucket("xrd");
+"#
+            .to_string(),
+            1,
+        );
+        let tokens = document.tokens();
+        let named_values = find_named_values(&tokens);
+        assert_eq!(named_values.len(), 5);
+
+        let declarations = find_declarations(&named_values);
+        assert_eq!(
+            declarations
+                .iter()
+                .map(|declaration| (declaration.kind, declaration.name))
+                .collect::<Vec<_>>(),
+            vec![(NamedKind::Bucket, "xrd"), (NamedKind::Proof, "proof")]
+        );
+        assert_eq!(declarations[0].name_span.start.line_idx, 1);
+        assert_eq!(declarations[1].name_span.start.line_idx, 2);
+
+        let uri: Uri = "file:///manifest.rtm".parse().unwrap();
+        let location = goto_definition(&document, &uri, LspPosition::new(4, 20)).unwrap();
+        assert_eq!(
+            location.range,
+            document.lsp_range(&declarations[0].name_span)
+        );
+    }
+}
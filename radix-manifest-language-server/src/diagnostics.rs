@@ -0,0 +1,160 @@
+use crate::document::Document;
+use crate::server::Settings;
+use lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+use radix_transactions::manifest::ast::{Instruction, InstructionWithSpan};
+use radix_transactions::manifest::lexer::tokenize;
+use radix_transactions::manifest::parser::{Parser, PARSER_MAX_DEPTH};
+use radix_transactions::manifest::token::Span;
+use radix_transactions::manifest::*;
+use std::ops::ControlFlow;
+
+/// Compiles and validates a manifest, and reports the first error. Blobs aren't available to the
+/// server, so any blob reference is accepted.
+pub fn diagnose(document: &Document, settings: &Settings) -> Vec<Diagnostic> {
+    let manifest = match compile_any_manifest(
+        &document.text,
+        settings.manifest_kind,
+        &settings.network,
+        MockBlobProvider::new(),
+    ) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            let (title, _) = error.title_and_label();
+            return vec![error_diagnostic(document.lsp_range(&error.span()), title)];
+        }
+    };
+
+    let mut tracker = InstructionTracker::default();
+    match StaticManifestInterpreter::new(ValidationRuleset::all(), &manifest)
+        .validate_and_apply_visitor(&mut tracker)
+    {
+        Ok(()) => vec![],
+        Err(error) => {
+            // Errors found after the last instruction, e.g. a dangling bucket, point at the end
+            let range = match tracker.current_instruction {
+                Some(index) => instruction_span(&document.text, index)
+                    .map(|span| document.lsp_range(&span))
+                    .unwrap_or_default(),
+                None => {
+                    let end = document.full_range().end;
+                    Range::new(end, end)
+                }
+            };
+            vec![error_diagnostic(range, format!("{:?}", error))]
+        }
+    }
+}
+
+fn error_diagnostic(range: Range, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("rtm".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Records the instruction being validated, to locate validation errors.
+#[derive(Default)]
+struct InstructionTracker {
+    current_instruction: Option<usize>,
+}
+
+impl ManifestInterpretationVisitor for InstructionTracker {
+    type Output = ManifestValidationError;
+
+    fn on_start_instruction(&mut self, details: OnStartInstruction) -> ControlFlow<Self::Output> {
+        self.current_instruction = Some(details.index);
+        ControlFlow::Continue(())
+    }
+
+    fn on_end_instruction(&mut self, _: OnEndInstruction) -> ControlFlow<Self::Output> {
+        self.current_instruction = None;
+        ControlFlow::Continue(())
+    }
+}
+
+/// Returns the span of the instruction at an index of a compiled manifest, which doesn't include
+/// the pseudo-instructions declaring preallocated addresses and children.
+fn instruction_span(text: &str, index: usize) -> Option<Span> {
+    let instructions = Parser::new(tokenize(text).ok()?, PARSER_MAX_DEPTH)
+        .ok()?
+        .parse_manifest()
+        .ok()?;
+    instructions
+        .into_iter()
+        .filter(|InstructionWithSpan { instruction, .. }| {
+            !matches!(
+                instruction,
+                Instruction::UsePreallocatedAddress { .. } | Instruction::UseChild { .. }
+            )
+        })
+        .nth(index)
+        .map(|instruction| instruction.span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Position as LspPosition;
+    use radix_common::prelude::NetworkDefinition;
+
+    fn diagnose_text(text: &str) -> Vec<Diagnostic> {
+        let settings = Settings {
+            network: NetworkDefinition::simulator(),
+            manifest_kind: ManifestKind::parse_or_latest(None).unwrap(),
+        };
+        diagnose(&Document::new(text.to_string(), 1), &settings)
+    }
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+        Range::new(
+            LspPosition::new(start.0, start.1),
+            LspPosition::new(end.0, end.1),
+        )
+    }
+
+    #[test]
+    fn test_a_valid_manifest_has_no_diagnostics() {
+        assert_eq!(diagnose_text("DROP_ALL_PROOFS;\n"), vec![]);
+    }
+
+    #[test]
+    fn test_lexer_errors_are_located() {
+        let diagnostics = diagnose_text("DROP_ALL_PROOFS;\nDROP_PROOF Proof(\"p);\n");
+        assert_eq!(diagnostics.len(), 1);
+        // The unterminated string is only detected at the end of the input
+        assert_eq!(diagnostics[0].range, range((2, 0), (2, 0)));
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+    }
+
+    #[test]
+    fn test_parser_errors_are_located() {
+        let diagnostics = diagnose_text("DROP_ALL_PROOFS;\nDROP_ALL_PROOFS DROP_ALL_PROOFS;\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, range((1, 16), (1, 31)));
+    }
+
+    #[test]
+    fn test_generator_errors_are_located() {
+        let diagnostics = diagnose_text(
+            "DROP_ALL_PROOFS;\nTAKE_ALL_FROM_WORKTOP Address(\"invalid\") Bucket(\"b\");\n",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, range((1, 30), (1, 39)));
+    }
+
+    #[test]
+    fn test_validation_errors_are_located() {
+        let text = "DROP_ALL_PROOFS;\n\
+            TAKE_ALL_FROM_WORKTOP\n    \
+                Address(\"resource_sim1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxakj8n3\")\n    \
+                Bucket(\"b\");\n";
+        let diagnostics = diagnose_text(text);
+        assert_eq!(diagnostics.len(), 1);
+        // The bucket is only found to be dangling once all instructions are validated
+        assert_eq!(diagnostics[0].range, range((4, 0), (4, 0)));
+        assert!(diagnostics[0].message.contains("DanglingBucket"));
+    }
+}
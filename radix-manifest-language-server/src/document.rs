@@ -0,0 +1,110 @@
+use lsp_types::{Position as LspPosition, Range};
+use radix_transactions::manifest::lexer::Lexer;
+use radix_transactions::manifest::token::{Span, TokenWithSpan};
+
+/// An open manifest. The manifest lexer locates tokens by their index in unicode chars, whereas the
+/// protocol uses lines and UTF-16 offsets, so the start of each line is kept to convert between
+/// the two.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub text: String,
+    pub version: i32,
+    chars: Vec<char>,
+    line_starts: Vec<usize>,
+}
+
+impl Document {
+    pub fn new(text: String, version: i32) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut line_starts = vec![0];
+        for (index, c) in chars.iter().enumerate() {
+            if *c == '\n' {
+                line_starts.push(index + 1);
+            }
+        }
+        Self {
+            text,
+            version,
+            chars,
+            line_starts,
+        }
+    }
+
+    /// The tokens of the manifest, up to the first one which can't be lexed. Most of the features
+    /// keep working while a manifest is being typed, so a syntax error doesn't disable them.
+    pub fn tokens(&self) -> Vec<TokenWithSpan> {
+        let mut lexer = Lexer::new(&self.text);
+        let mut tokens = Vec::new();
+        while let Ok(Some(token)) = lexer.next_token() {
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    /// Returns the char index of a position, clamped to the end of its line.
+    pub fn char_index(&self, position: LspPosition) -> usize {
+        let line = position.line as usize;
+        let Some(line_start) = self.line_starts.get(line).copied() else {
+            return self.chars.len();
+        };
+        let line_end = self
+            .line_starts
+            .get(line + 1)
+            .map(|next_line_start| next_line_start - 1)
+            .unwrap_or(self.chars.len());
+
+        let mut index = line_start;
+        let mut character = 0;
+        while index < line_end && character < position.character as usize {
+            character += self.chars[index].len_utf16();
+            index += 1;
+        }
+        index
+    }
+
+    pub fn lsp_position(&self, index: usize) -> LspPosition {
+        let index = index.min(self.chars.len());
+        let line = self.line_starts.partition_point(|start| *start <= index) - 1;
+        let character: usize = self.chars[self.line_starts[line]..index]
+            .iter()
+            .map(|c| c.len_utf16())
+            .sum();
+        LspPosition::new(line as u32, character as u32)
+    }
+
+    pub fn lsp_range(&self, span: &Span) -> Range {
+        Range::new(
+            self.lsp_position(span.start.full_index),
+            self.lsp_position(span.end.full_index),
+        )
+    }
+
+    pub fn full_range(&self) -> Range {
+        Range::new(LspPosition::new(0, 0), self.lsp_position(self.chars.len()))
+    }
+}
+
+/// Returns the token at a char index, including the token ending right before it, as the cursor
+/// is usually just after the word it refers to.
+pub fn token_at(tokens: &[TokenWithSpan], index: usize) -> Option<&TokenWithSpan> {
+    tokens
+        .iter()
+        .find(|token| token.span.start.full_index <= index && index <= token.span.end.full_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions_are_converted_to_utf16() {
+        let document = Document::new("A\n\"😀\" B;\n".to_string(), 1);
+
+        // The emoji is a single char but two UTF-16 code units
+        assert_eq!(document.lsp_position(5), LspPosition::new(1, 4));
+        assert_eq!(document.char_index(LspPosition::new(1, 4)), 5);
+        assert_eq!(document.lsp_position(9), LspPosition::new(2, 0));
+        assert_eq!(document.char_index(LspPosition::new(0, 10)), 1);
+        assert_eq!(document.char_index(LspPosition::new(5, 0)), 9);
+    }
+}
@@ -0,0 +1,12 @@
+use crate::document::Document;
+use lsp_types::TextEdit;
+use radix_transactions::manifest::{format_manifest, ManifestFormatterOptions};
+
+/// Formats a manifest as `rtm fmt` does. Returns `None` if the manifest can't be parsed.
+pub fn format_document(document: &Document) -> Option<Vec<TextEdit>> {
+    let formatted = format_manifest(&document.text, &ManifestFormatterOptions::default()).ok()?;
+    if formatted == document.text {
+        return Some(vec![]);
+    }
+    Some(vec![TextEdit::new(document.full_range(), formatted)])
+}
@@ -0,0 +1,55 @@
+use crate::document::{token_at, Document};
+use crate::instructions::find_instruction;
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position as LspPosition};
+use radix_transactions::manifest::token::Token;
+
+pub fn hover(document: &Document, position: LspPosition) -> Option<Hover> {
+    let tokens = document.tokens();
+    let token = token_at(&tokens, document.char_index(position))?;
+    let Token::Ident(ident) = &token.token else {
+        return None;
+    };
+    let instruction = find_instruction(ident)?;
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: instruction.markdown(),
+        }),
+        range: Some(document.lsp_range(&token.span)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Range;
+
+    #[test]
+    fn test_instructions_are_documented_on_hover() {
+        let document = Document::new("DROP_ALL_PROOFS;\nDROP_AUTH_ZONE_PROOFS;\n".to_string(), 1);
+
+        let hover = hover(&document, LspPosition::new(1, 3)).unwrap();
+
+        assert_eq!(
+            hover.range,
+            Some(Range::new(LspPosition::new(1, 0), LspPosition::new(1, 21)))
+        );
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup contents");
+        };
+        assert_eq!(
+            content.value,
+            find_instruction("DROP_AUTH_ZONE_PROOFS")
+                .unwrap()
+                .markdown()
+        );
+    }
+
+    #[test]
+    fn test_only_instructions_have_a_hover() {
+        let document = Document::new("DROP_PROOF Proof(\"p\");\n".to_string(), 1);
+
+        assert!(hover(&document, LspPosition::new(0, 13)).is_none());
+        assert!(hover(&document, LspPosition::new(1, 0)).is_none());
+    }
+}
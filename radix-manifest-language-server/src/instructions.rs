@@ -0,0 +1,456 @@
+/// The documentation of an instruction, or an alias of an instruction, of the manifest language.
+pub struct InstructionDoc {
+    pub name: &'static str,
+    /// The arguments as `(name, value kind)` pairs. A name ending with `...` stands for any number
+    /// of arguments.
+    pub arguments: &'static [(&'static str, &'static str)],
+    pub description: &'static str,
+}
+
+impl InstructionDoc {
+    pub fn signature(&self) -> String {
+        let mut signature = self.name.to_string();
+        for (name, kind) in self.arguments {
+            signature.push_str(&format!("\n    {}: {}", name, kind));
+        }
+        signature.push_str(if self.arguments.is_empty() {
+            ";"
+        } else {
+            "\n;"
+        });
+        signature
+    }
+
+    pub fn markdown(&self) -> String {
+        format!("```\n{}\n```\n\n{}", self.signature(), self.description)
+    }
+}
+
+pub fn find_instruction(name: &str) -> Option<&'static InstructionDoc> {
+    INSTRUCTIONS
+        .iter()
+        .find(|instruction| instruction.name == name)
+}
+
+const ARGS: (&str, &str) = ("args...", "Any");
+const ADDRESS: (&str, &str) = ("address", "Address");
+const RESOURCE_ADDRESS: (&str, &str) = ("resource_address", "Address");
+const AMOUNT: (&str, &str) = ("amount", "Decimal");
+const IDS: (&str, &str) = ("ids", "Array<NonFungibleLocalId>");
+const BUCKET: (&str, &str) = ("bucket", "Bucket");
+const NEW_BUCKET: (&str, &str) = ("new_bucket", "Bucket");
+const PROOF: (&str, &str) = ("proof", "Proof");
+const NEW_PROOF: (&str, &str) = ("new_proof", "Proof");
+const CONSTRAINTS: (&str, &str) = ("constraints", "Map<Address, Enum>");
+const VAULT_ID: (&str, &str) = ("vault_id", "Address");
+
+/// All instructions, in the order of the parser.
+pub const INSTRUCTIONS: &[InstructionDoc] = &[
+    // Pseudo-instructions
+    InstructionDoc {
+        name: "USE_PREALLOCATED_ADDRESS",
+        arguments: &[
+            ("package_address", "Address"),
+            ("blueprint_name", "String"),
+            ("address_reservation", "AddressReservation"),
+            ("preallocated_address", "Address"),
+        ],
+        description: "Declares an address which was preallocated for the transaction, along with the address reservation for it. System transactions only, and must come before all other instructions.",
+    },
+    InstructionDoc {
+        name: "USE_CHILD",
+        arguments: &[("named_intent", "NamedIntent"), ("subintent_hash", "Intent")],
+        description: "Declares a child subintent of this intent, and names it for use in `YIELD_TO_CHILD`. Must come before all other instructions.",
+    },
+    // Bucket Lifecycle
+    InstructionDoc {
+        name: "TAKE_FROM_WORKTOP",
+        arguments: &[RESOURCE_ADDRESS, AMOUNT, NEW_BUCKET],
+        description: "Takes an amount of a resource from the worktop, and puts it in a new named bucket.",
+    },
+    InstructionDoc {
+        name: "TAKE_NON_FUNGIBLES_FROM_WORKTOP",
+        arguments: &[RESOURCE_ADDRESS, IDS, NEW_BUCKET],
+        description: "Takes the given non-fungibles of a resource from the worktop, and puts them in a new named bucket.",
+    },
+    InstructionDoc {
+        name: "TAKE_ALL_FROM_WORKTOP",
+        arguments: &[RESOURCE_ADDRESS, NEW_BUCKET],
+        description: "Takes all of a resource from the worktop, and puts it in a new named bucket.",
+    },
+    InstructionDoc {
+        name: "RETURN_TO_WORKTOP",
+        arguments: &[BUCKET],
+        description: "Returns the contents of a bucket to the worktop. The bucket can't be used afterwards.",
+    },
+    InstructionDoc {
+        name: "BURN_RESOURCE",
+        arguments: &[BUCKET],
+        description: "Burns the contents of a bucket. The bucket can't be used afterwards.",
+    },
+    // Resource Assertions
+    InstructionDoc {
+        name: "ASSERT_WORKTOP_CONTAINS",
+        arguments: &[RESOURCE_ADDRESS, AMOUNT],
+        description: "Fails the transaction unless the worktop contains at least the given amount of a resource.",
+    },
+    InstructionDoc {
+        name: "ASSERT_WORKTOP_CONTAINS_NON_FUNGIBLES",
+        arguments: &[RESOURCE_ADDRESS, IDS],
+        description: "Fails the transaction unless the worktop contains all of the given non-fungibles of a resource.",
+    },
+    InstructionDoc {
+        name: "ASSERT_WORKTOP_CONTAINS_ANY",
+        arguments: &[RESOURCE_ADDRESS],
+        description: "Fails the transaction unless the worktop contains a non-zero amount of a resource.",
+    },
+    InstructionDoc {
+        name: "ASSERT_WORKTOP_IS_EMPTY",
+        arguments: &[],
+        description: "Fails the transaction unless the worktop is empty. An alias of `ASSERT_WORKTOP_RESOURCES_ONLY` without any constraints.",
+    },
+    InstructionDoc {
+        name: "ASSERT_WORKTOP_RESOURCES_ONLY",
+        arguments: &[CONSTRAINTS],
+        description: "Fails the transaction unless the worktop only contains the given resources, each meeting its constraint.",
+    },
+    InstructionDoc {
+        name: "ASSERT_WORKTOP_RESOURCES_INCLUDE",
+        arguments: &[CONSTRAINTS],
+        description: "Fails the transaction unless the worktop contains the given resources, each meeting its constraint. Other resources may also be present.",
+    },
+    InstructionDoc {
+        name: "ASSERT_NEXT_CALL_RETURNS_ONLY",
+        arguments: &[CONSTRAINTS],
+        description: "Fails the transaction unless the next invocation only returns the given resources, each meeting its constraint.",
+    },
+    InstructionDoc {
+        name: "ASSERT_NEXT_CALL_RETURNS_INCLUDE",
+        arguments: &[CONSTRAINTS],
+        description: "Fails the transaction unless the next invocation returns the given resources, each meeting its constraint. Other resources may also be returned.",
+    },
+    InstructionDoc {
+        name: "ASSERT_BUCKET_CONTENTS",
+        arguments: &[BUCKET, ("constraint", "Enum")],
+        description: "Fails the transaction unless the contents of a bucket meet the constraint.",
+    },
+    // Proof Lifecycle
+    InstructionDoc {
+        name: "CREATE_PROOF_FROM_BUCKET_OF_AMOUNT",
+        arguments: &[BUCKET, AMOUNT, NEW_PROOF],
+        description: "Creates a new named proof of an amount of the resource in a bucket.",
+    },
+    InstructionDoc {
+        name: "CREATE_PROOF_FROM_BUCKET_OF_NON_FUNGIBLES",
+        arguments: &[BUCKET, IDS, NEW_PROOF],
+        description: "Creates a new named proof of the given non-fungibles in a bucket.",
+    },
+    InstructionDoc {
+        name: "CREATE_PROOF_FROM_BUCKET_OF_ALL",
+        arguments: &[BUCKET, NEW_PROOF],
+        description: "Creates a new named proof of all the contents of a bucket.",
+    },
+    InstructionDoc {
+        name: "CREATE_PROOF_FROM_AUTH_ZONE_OF_AMOUNT",
+        arguments: &[RESOURCE_ADDRESS, AMOUNT, NEW_PROOF],
+        description: "Creates a new named proof of an amount of a resource, from the proofs in the auth zone.",
+    },
+    InstructionDoc {
+        name: "CREATE_PROOF_FROM_AUTH_ZONE_OF_NON_FUNGIBLES",
+        arguments: &[RESOURCE_ADDRESS, IDS, NEW_PROOF],
+        description: "Creates a new named proof of the given non-fungibles of a resource, from the proofs in the auth zone.",
+    },
+    InstructionDoc {
+        name: "CREATE_PROOF_FROM_AUTH_ZONE_OF_ALL",
+        arguments: &[RESOURCE_ADDRESS, NEW_PROOF],
+        description: "Creates a new named proof of all of a resource proven by the proofs in the auth zone.",
+    },
+    InstructionDoc {
+        name: "CLONE_PROOF",
+        arguments: &[PROOF, NEW_PROOF],
+        description: "Creates a new named proof with the same contents as a proof.",
+    },
+    InstructionDoc {
+        name: "DROP_PROOF",
+        arguments: &[PROOF],
+        description: "Drops a proof. The proof can't be used afterwards.",
+    },
+    InstructionDoc {
+        name: "PUSH_TO_AUTH_ZONE",
+        arguments: &[PROOF],
+        description: "Moves a proof to the auth zone, where it is used for the authorization of later invocations.",
+    },
+    InstructionDoc {
+        name: "POP_FROM_AUTH_ZONE",
+        arguments: &[NEW_PROOF],
+        description: "Moves the last proof pushed to the auth zone to a new named proof.",
+    },
+    InstructionDoc {
+        name: "DROP_AUTH_ZONE_PROOFS",
+        arguments: &[],
+        description: "Drops all proofs in the auth zone.",
+    },
+    InstructionDoc {
+        name: "DROP_AUTH_ZONE_REGULAR_PROOFS",
+        arguments: &[],
+        description: "Drops all proofs in the auth zone, except for the signature proofs.",
+    },
+    InstructionDoc {
+        name: "DROP_AUTH_ZONE_SIGNATURE_PROOFS",
+        arguments: &[],
+        description: "Drops the signature proofs in the auth zone, which are created from the signers of the transaction.",
+    },
+    InstructionDoc {
+        name: "DROP_NAMED_PROOFS",
+        arguments: &[],
+        description: "Drops all named proofs.",
+    },
+    InstructionDoc {
+        name: "DROP_ALL_PROOFS",
+        arguments: &[],
+        description: "Drops all named proofs and all proofs in the auth zone.",
+    },
+    // Invocations
+    InstructionDoc {
+        name: "CALL_FUNCTION",
+        arguments: &[
+            ("package_address", "Address"),
+            ("blueprint_name", "String"),
+            ("function_name", "String"),
+            ARGS,
+        ],
+        description: "Calls a function of a blueprint. The returned resources are put on the worktop.",
+    },
+    InstructionDoc {
+        name: "CALL_METHOD",
+        arguments: &[ADDRESS, ("method_name", "String"), ARGS],
+        description: "Calls a method of the main module of a component. The returned resources are put on the worktop.",
+    },
+    InstructionDoc {
+        name: "CALL_ROYALTY_METHOD",
+        arguments: &[ADDRESS, ("method_name", "String"), ARGS],
+        description: "Calls a method of the royalty module of a component.",
+    },
+    InstructionDoc {
+        name: "CALL_METADATA_METHOD",
+        arguments: &[ADDRESS, ("method_name", "String"), ARGS],
+        description: "Calls a method of the metadata module of a global entity.",
+    },
+    InstructionDoc {
+        name: "CALL_ROLE_ASSIGNMENT_METHOD",
+        arguments: &[ADDRESS, ("method_name", "String"), ARGS],
+        description: "Calls a method of the role assignment module of a global entity.",
+    },
+    InstructionDoc {
+        name: "CALL_DIRECT_VAULT_METHOD",
+        arguments: &[VAULT_ID, ("method_name", "String"), ARGS],
+        description: "Calls a method of a vault directly, bypassing its owner, e.g. to recall or freeze it.",
+    },
+    // Address Allocation
+    InstructionDoc {
+        name: "ALLOCATE_GLOBAL_ADDRESS",
+        arguments: &[
+            ("package_address", "Address"),
+            ("blueprint_name", "String"),
+            ("address_reservation", "AddressReservation"),
+            ("named_address", "NamedAddress"),
+        ],
+        description: "Allocates an address for a global entity of a blueprint. The named address can be used straight away, and the address reservation is consumed when the entity is globalized.",
+    },
+    // Interaction with other intents
+    InstructionDoc {
+        name: "YIELD_TO_PARENT",
+        arguments: &[ARGS],
+        description: "Passes control and the arguments to the parent intent. A subintent must end with this instruction.",
+    },
+    InstructionDoc {
+        name: "YIELD_TO_CHILD",
+        arguments: &[("child", "NamedIntent"), ARGS],
+        description: "Passes control and the arguments to a child subintent declared with `USE_CHILD`.",
+    },
+    InstructionDoc {
+        name: "VERIFY_PARENT",
+        arguments: &[("access_rule", "Enum")],
+        description: "Fails the transaction unless the auth zone of the parent intent meets the access rule.",
+    },
+    // Direct vault aliases
+    InstructionDoc {
+        name: "RECALL_FROM_VAULT",
+        arguments: &[VAULT_ID, AMOUNT],
+        description: "Recalls an amount of a recallable resource from a vault. An alias of `CALL_DIRECT_VAULT_METHOD` with `\"recall\"`.",
+    },
+    InstructionDoc {
+        name: "FREEZE_VAULT",
+        arguments: &[VAULT_ID, ("flags", "Tuple")],
+        description: "Freezes a vault of a freezable resource. An alias of `CALL_DIRECT_VAULT_METHOD` with `\"freeze\"`.",
+    },
+    InstructionDoc {
+        name: "UNFREEZE_VAULT",
+        arguments: &[VAULT_ID, ("flags", "Tuple")],
+        description: "Unfreezes a vault of a freezable resource. An alias of `CALL_DIRECT_VAULT_METHOD` with `\"unfreeze\"`.",
+    },
+    InstructionDoc {
+        name: "RECALL_NON_FUNGIBLES_FROM_VAULT",
+        arguments: &[VAULT_ID, IDS],
+        description: "Recalls the given non-fungibles from a vault. An alias of `CALL_DIRECT_VAULT_METHOD` with `\"recall_non_fungibles\"`.",
+    },
+    // Call function aliases
+    InstructionDoc {
+        name: "PUBLISH_PACKAGE",
+        arguments: &[ARGS],
+        description: "Publishes a package. An alias of `CALL_FUNCTION` on the package package with `\"publish_wasm\"`.",
+    },
+    InstructionDoc {
+        name: "PUBLISH_PACKAGE_ADVANCED",
+        arguments: &[ARGS],
+        description: "Publishes a package with an owner role and address reservation. An alias of `CALL_FUNCTION` on the package package with `\"publish_wasm_advanced\"`.",
+    },
+    InstructionDoc {
+        name: "CREATE_FUNGIBLE_RESOURCE",
+        arguments: &[ARGS],
+        description: "Creates a fungible resource. An alias of `CALL_FUNCTION` on the resource package.",
+    },
+    InstructionDoc {
+        name: "CREATE_FUNGIBLE_RESOURCE_WITH_INITIAL_SUPPLY",
+        arguments: &[ARGS],
+        description: "Creates a fungible resource and mints its initial supply to the worktop. An alias of `CALL_FUNCTION` on the resource package.",
+    },
+    InstructionDoc {
+        name: "CREATE_NON_FUNGIBLE_RESOURCE",
+        arguments: &[ARGS],
+        description: "Creates a non-fungible resource. An alias of `CALL_FUNCTION` on the resource package.",
+    },
+    InstructionDoc {
+        name: "CREATE_NON_FUNGIBLE_RESOURCE_WITH_INITIAL_SUPPLY",
+        arguments: &[ARGS],
+        description: "Creates a non-fungible resource and mints its initial supply to the worktop. An alias of `CALL_FUNCTION` on the resource package.",
+    },
+    InstructionDoc {
+        name: "CREATE_ACCESS_CONTROLLER",
+        arguments: &[ARGS],
+        description: "Creates an access controller. An alias of `CALL_FUNCTION` on the access controller package.",
+    },
+    InstructionDoc {
+        name: "CREATE_IDENTITY",
+        arguments: &[],
+        description: "Creates an identity, whose owner badge is put on the worktop. An alias of `CALL_FUNCTION` on the identity package.",
+    },
+    InstructionDoc {
+        name: "CREATE_IDENTITY_ADVANCED",
+        arguments: &[("owner_role", "Enum")],
+        description: "Creates an identity with the given owner role. An alias of `CALL_FUNCTION` on the identity package.",
+    },
+    InstructionDoc {
+        name: "CREATE_ACCOUNT",
+        arguments: &[],
+        description: "Creates an account, whose owner badge is put on the worktop. An alias of `CALL_FUNCTION` on the account package.",
+    },
+    InstructionDoc {
+        name: "CREATE_ACCOUNT_ADVANCED",
+        arguments: &[("owner_role", "Enum"), ("address_reservation", "Enum")],
+        description: "Creates an account with the given owner role. An alias of `CALL_FUNCTION` on the account package.",
+    },
+    // Non-main method aliases
+    InstructionDoc {
+        name: "SET_METADATA",
+        arguments: &[ADDRESS, ("key", "String"), ("value", "Enum")],
+        description: "Sets a metadata entry of a global entity. An alias of `CALL_METADATA_METHOD` with `\"set\"`.",
+    },
+    InstructionDoc {
+        name: "REMOVE_METADATA",
+        arguments: &[ADDRESS, ("key", "String")],
+        description: "Removes a metadata entry of a global entity. An alias of `CALL_METADATA_METHOD` with `\"remove\"`.",
+    },
+    InstructionDoc {
+        name: "LOCK_METADATA",
+        arguments: &[ADDRESS, ("key", "String")],
+        description: "Locks a metadata entry of a global entity, so it can't be changed anymore. An alias of `CALL_METADATA_METHOD` with `\"lock\"`.",
+    },
+    InstructionDoc {
+        name: "SET_COMPONENT_ROYALTY",
+        arguments: &[ADDRESS, ("method", "String"), ("amount", "Enum")],
+        description: "Sets the royalty of a method of a component. An alias of `CALL_ROYALTY_METHOD` with `\"set_royalty\"`.",
+    },
+    InstructionDoc {
+        name: "LOCK_COMPONENT_ROYALTY",
+        arguments: &[ADDRESS, ("method", "String")],
+        description: "Locks the royalty of a method of a component. An alias of `CALL_ROYALTY_METHOD` with `\"lock_royalty\"`.",
+    },
+    InstructionDoc {
+        name: "CLAIM_COMPONENT_ROYALTIES",
+        arguments: &[ADDRESS],
+        description: "Claims the royalties accumulated by a component, and puts them on the worktop. An alias of `CALL_ROYALTY_METHOD` with `\"claim_royalties\"`.",
+    },
+    InstructionDoc {
+        name: "SET_OWNER_ROLE",
+        arguments: &[ADDRESS, ("rule", "Enum")],
+        description: "Sets the owner role of a global entity. An alias of `CALL_ROLE_ASSIGNMENT_METHOD` with `\"set_owner\"`.",
+    },
+    InstructionDoc {
+        name: "LOCK_OWNER_ROLE",
+        arguments: &[ADDRESS],
+        description: "Locks the owner role of a global entity. An alias of `CALL_ROLE_ASSIGNMENT_METHOD` with `\"lock_owner\"`.",
+    },
+    InstructionDoc {
+        name: "SET_ROLE",
+        arguments: &[ADDRESS, ("module", "Enum"), ("role_key", "String"), ("rule", "Enum")],
+        description: "Sets a role of a module of a global entity. An alias of `CALL_ROLE_ASSIGNMENT_METHOD` with `\"set\"`.",
+    },
+    // Main method aliases
+    InstructionDoc {
+        name: "MINT_FUNGIBLE",
+        arguments: &[RESOURCE_ADDRESS, AMOUNT],
+        description: "Mints an amount of a fungible resource to the worktop. An alias of `CALL_METHOD` with `\"mint\"`.",
+    },
+    InstructionDoc {
+        name: "MINT_NON_FUNGIBLE",
+        arguments: &[RESOURCE_ADDRESS, ("entries", "Map<NonFungibleLocalId, Tuple>")],
+        description: "Mints non-fungibles with the given ids and data to the worktop. An alias of `CALL_METHOD` with `\"mint\"`.",
+    },
+    InstructionDoc {
+        name: "MINT_RUID_NON_FUNGIBLE",
+        arguments: &[RESOURCE_ADDRESS, ("entries", "Array<Tuple>")],
+        description: "Mints non-fungibles with random ids and the given data to the worktop. An alias of `CALL_METHOD` with `\"mint_ruid\"`.",
+    },
+    InstructionDoc {
+        name: "CLAIM_PACKAGE_ROYALTIES",
+        arguments: &[("package_address", "Address")],
+        description: "Claims the royalties accumulated by a package, and puts them on the worktop. An alias of `CALL_METHOD` with `\"PackageRoyalty_claim_royalties\"`.",
+    },
+    InstructionDoc {
+        name: "CREATE_VALIDATOR",
+        arguments: &[("key", "Bytes"), ("fee_factor", "Decimal"), ("xrd_payment", "Bucket")],
+        description: "Creates a validator, whose owner badge is put on the worktop. An alias of `CALL_METHOD` on the consensus manager with `\"create_validator\"`.",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_transactions::manifest::parser::InstructionIdent;
+
+    #[test]
+    fn test_all_documented_instructions_are_parsed() {
+        for instruction in INSTRUCTIONS {
+            assert!(
+                InstructionIdent::from_ident(instruction.name).is_some(),
+                "{} is not an instruction",
+                instruction.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_signature() {
+        assert_eq!(
+            find_instruction("TAKE_ALL_FROM_WORKTOP")
+                .unwrap()
+                .signature(),
+            "TAKE_ALL_FROM_WORKTOP\n    resource_address: Address\n    new_bucket: Bucket\n;"
+        );
+        assert_eq!(
+            find_instruction("DROP_ALL_PROOFS").unwrap().signature(),
+            "DROP_ALL_PROOFS;"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! A language server for the transaction manifest language, built on the lexer, parser and
+//! compiler of `radix-transactions`. It reports compile and validation errors as you type, shows
+//! the documentation of instructions on hover, goes to the declaration of named buckets, proofs,
+//! address reservations, addresses and intents, completes instructions, values and declared names,
+//! and formats manifests in the canonical style.
+
+mod completion;
+mod definition;
+mod diagnostics;
+mod document;
+mod formatting;
+mod hover;
+mod instructions;
+mod server;
+
+pub use server::*;
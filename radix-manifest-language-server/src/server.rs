@@ -0,0 +1,218 @@
+use crate::completion::complete;
+use crate::definition::goto_definition;
+use crate::diagnostics::diagnose;
+use crate::document::Document;
+use crate::formatting::format_document;
+use crate::hover::hover;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{Completion, Formatting, GotoDefinition, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionOptions, CompletionParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentFormattingParams, GotoDefinitionParams, HoverParams,
+    HoverProviderCapability, InitializeParams, OneOf, PublishDiagnosticsParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Uri,
+};
+use radix_common::prelude::NetworkDefinition;
+use radix_transactions::manifest::ManifestKind;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::str::FromStr;
+
+pub type ServerError = Box<dyn Error + Sync + Send>;
+
+/// The options a client can pass as `initializationOptions`, e.g.
+/// `{ "network": "stokenet", "manifestKind": "SubintentV2" }`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializationOptions {
+    /// The network of the addresses in the manifests, defaults to the simulator
+    network: Option<String>,
+    /// The manifest type [V1 | SystemV1 | V2 | SubintentV2], defaults to V2
+    manifest_kind: Option<String>,
+}
+
+/// How the manifests are compiled for diagnostics, which is the same for all documents.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub network: NetworkDefinition,
+    pub manifest_kind: ManifestKind,
+}
+
+impl Settings {
+    pub fn from_initialization_options(
+        options: Option<serde_json::Value>,
+    ) -> Result<Self, ServerError> {
+        let options: InitializationOptions = match options {
+            Some(options) => serde_json::from_value(options)?,
+            None => InitializationOptions::default(),
+        };
+        let network = match &options.network {
+            Some(network) => NetworkDefinition::from_str(network)
+                .map_err(|err| format!("invalid network: {:?}", err))?,
+            None => NetworkDefinition::simulator(),
+        };
+        let manifest_kind = ManifestKind::parse_or_latest(options.manifest_kind.as_deref())?;
+        Ok(Self {
+            network,
+            manifest_kind,
+        })
+    }
+}
+
+/// Runs the language server until the client shuts it down.
+pub fn run(connection: Connection) -> Result<(), ServerError> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["(".to_string(), "<".to_string()]),
+            ..Default::default()
+        }),
+        definition_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+    let initialize_params: InitializeParams =
+        serde_json::from_value(connection.initialize(serde_json::to_value(capabilities)?)?)?;
+    let settings = Settings::from_initialization_options(initialize_params.initialization_options)?;
+
+    Server {
+        connection,
+        settings,
+        documents: HashMap::new(),
+    }
+    .main_loop()
+}
+
+struct Server {
+    connection: Connection,
+    settings: Settings,
+    documents: HashMap<Uri, Document>,
+}
+
+impl Server {
+    fn main_loop(mut self) -> Result<(), ServerError> {
+        let receiver = self.connection.receiver.clone();
+        for message in receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    self.connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let Request { id, method, params } = request;
+        let result = match method.as_str() {
+            HoverRequest::METHOD => respond(params, |params: HoverParams| {
+                let params = params.text_document_position_params;
+                self.documents
+                    .get(&params.text_document.uri)
+                    .and_then(|document| hover(document, params.position))
+            }),
+            GotoDefinition::METHOD => respond(params, |params: GotoDefinitionParams| {
+                let params = params.text_document_position_params;
+                self.documents
+                    .get(&params.text_document.uri)
+                    .and_then(|document| {
+                        goto_definition(document, &params.text_document.uri, params.position)
+                    })
+            }),
+            Completion::METHOD => respond(params, |params: CompletionParams| {
+                let params = params.text_document_position;
+                self.documents
+                    .get(&params.text_document.uri)
+                    .map(|document| complete(document, params.position))
+            }),
+            Formatting::METHOD => respond(params, |params: DocumentFormattingParams| {
+                self.documents
+                    .get(&params.text_document.uri)
+                    .and_then(format_document)
+            }),
+            _ => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request: {}", method),
+                )
+            }
+        };
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), ServerError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                self.update_document(document.uri, Document::new(document.text, document.version))?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let mut params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                // Documents are synced in full, so the last change holds the whole text
+                if let Some(change) = params.content_changes.pop() {
+                    let document = Document::new(change.text, params.text_document.version);
+                    self.update_document(params.text_document.uri, document)?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                self.publish_diagnostics(PublishDiagnosticsParams::new(
+                    params.text_document.uri,
+                    vec![],
+                    None,
+                ))?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn update_document(&mut self, uri: Uri, document: Document) -> Result<(), ServerError> {
+        let diagnostics = diagnose(&document, &self.settings);
+        let version = document.version;
+        self.documents.insert(uri.clone(), document);
+        self.publish_diagnostics(PublishDiagnosticsParams::new(
+            uri,
+            diagnostics,
+            Some(version),
+        ))
+    }
+
+    fn publish_diagnostics(&self, params: PublishDiagnosticsParams) -> Result<(), ServerError> {
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection
+            .sender
+            .send(Message::Notification(notification))?;
+        Ok(())
+    }
+}
+
+fn respond<P: DeserializeOwned, R: Serialize>(
+    params: serde_json::Value,
+    handler: impl FnOnce(P) -> R,
+) -> Result<serde_json::Value, serde_json::Error> {
+    let params = serde_json::from_value(params)?;
+    serde_json::to_value(handler(params))
+}
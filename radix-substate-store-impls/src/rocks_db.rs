@@ -37,6 +37,21 @@ impl RocksdbSubstateStore {
         Self { db }
     }
 
+    /// Opens an existing database without taking a write lock on it, failing if there is none.
+    /// Any attempt to commit to the returned store will panic.
+    pub fn read_only(root: PathBuf) -> Result<Self, rocksdb::Error> {
+        let db = DB::open_cf_descriptors_read_only(
+            &Options::default(),
+            root.as_path(),
+            vec![ColumnFamilyDescriptor::new(
+                Self::THE_ONLY_CF,
+                Options::default(),
+            )],
+            false,
+        )?;
+        Ok(Self { db })
+    }
+
     fn cf(&self) -> &ColumnFamily {
         self.db.cf_handle(Self::THE_ONLY_CF).unwrap()
     }
@@ -62,6 +62,29 @@ impl RocksDBWithMerkleTreeSubstateStore {
         }
     }
 
+    /// Opens an existing database without taking a write lock on it, failing if there is none.
+    /// Any attempt to commit to the returned store will panic.
+    pub fn read_only(root: PathBuf) -> Result<Self, rocksdb::Error> {
+        let db = DB::open_cf_descriptors_read_only(
+            &Options::default(),
+            root.as_path(),
+            [
+                META_CF,
+                SUBSTATES_CF,
+                MERKLE_NODES_CF,
+                STALE_MERKLE_TREE_PARTS_CF,
+            ]
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name, Options::default()))
+            .collect::<Vec<_>>(),
+            false,
+        )?;
+        Ok(Self {
+            db,
+            pruning_enabled: false,
+        })
+    }
+
     fn cf(&self, cf: &str) -> &ColumnFamily {
         self.db.cf_handle(cf).unwrap()
     }
@@ -167,14 +190,12 @@ impl CommittableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
                             let key_bytes = encode_to_rocksdb_bytes(&partition_key, sort_key);
                             match update {
                                 DatabaseUpdate::Set(value_bytes) => {
-                                    self.db
-                                        .put_cf(self.cf(SUBSTATES_CF), key_bytes, value_bytes)
+                                    batch.put_cf(self.cf(SUBSTATES_CF), key_bytes, value_bytes)
                                 }
                                 DatabaseUpdate::Delete => {
-                                    self.db.delete_cf(self.cf(SUBSTATES_CF), key_bytes)
+                                    batch.delete_cf(self.cf(SUBSTATES_CF), key_bytes)
                                 }
                             }
-                            .expect("IO error");
                         }
                     }
                     PartitionDatabaseUpdates::Reset {
@@ -182,21 +203,17 @@ impl CommittableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
                     } => {
                         // Note: a plain `delete_range()` is missing from rocksdb's API, and
                         // (at the moment of writing) this is the only reason of having CF.
-                        self.db
-                            .delete_range_cf(
-                                self.cf(SUBSTATES_CF),
-                                encode_to_rocksdb_bytes(&partition_key, &DbSortKey(vec![])),
-                                encode_to_rocksdb_bytes(
-                                    &partition_key,
-                                    &DbSortKey(vec![u8::MAX; 2 * MAX_SUBSTATE_KEY_SIZE]),
-                                ),
-                            )
-                            .expect("IO error");
+                        batch.delete_range_cf(
+                            self.cf(SUBSTATES_CF),
+                            encode_to_rocksdb_bytes(&partition_key, &DbSortKey(vec![])),
+                            encode_to_rocksdb_bytes(
+                                &partition_key,
+                                &DbSortKey(vec![u8::MAX; 2 * MAX_SUBSTATE_KEY_SIZE]),
+                            ),
+                        );
                         for (sort_key, value_bytes) in new_substate_values {
                             let key_bytes = encode_to_rocksdb_bytes(&partition_key, sort_key);
-                            self.db
-                                .put_cf(self.cf(SUBSTATES_CF), key_bytes, value_bytes)
-                                .expect("IO error");
+                            batch.put_cf(self.cf(SUBSTATES_CF), key_bytes, value_bytes);
                         }
                     }
                 }
@@ -233,7 +250,8 @@ impl CommittableSubstateDatabase for RocksDBWithMerkleTreeSubstateStore {
             .unwrap(),
         );
 
-        // flush the batch
+        // flush the batch, so that the substates, the tree and the metadata are all updated
+        // atomically (i.e. an interrupted commit leaves the database at its previous version)
         self.db.write(batch).unwrap();
 
         if self.pruning_enabled {
@@ -318,8 +336,8 @@ pub struct Metadata {
 mod tests {
     use super::*;
     use radix_substate_store_interface::interface::{
-        CommittableSubstateDatabase, DatabaseUpdates, DbSortKey, NodeDatabaseUpdates,
-        PartitionDatabaseUpdates,
+        CommittableSubstateDatabase, DatabaseUpdates, DbPartitionKey, DbSortKey,
+        NodeDatabaseUpdates, PartitionDatabaseUpdates, SubstateDatabase,
     };
 
     #[cfg(not(feature = "alloc"))]
@@ -368,4 +386,60 @@ mod tests {
         });
         assert_eq!(db.list_partition_keys().count(), 8);
     }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn test_commit_is_written_in_a_single_batch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let partition_key = DbPartitionKey {
+            node_key: vec![1],
+            partition_num: 0,
+        };
+        let mut db = RocksDBWithMerkleTreeSubstateStore::standard(temp_dir.path().to_path_buf());
+        db.commit(&DatabaseUpdates {
+            node_updates: indexmap! {
+                vec![1] => NodeDatabaseUpdates {
+                    partition_updates: indexmap! {
+                        0 => PartitionDatabaseUpdates::Delta {
+                            substate_updates: indexmap! {
+                                DbSortKey(vec![1]) => DatabaseUpdate::Set(vec![1]),
+                                DbSortKey(vec![2]) => DatabaseUpdate::Set(vec![2]),
+                            }
+                        }
+                    }
+                }
+            },
+        });
+
+        // The range deletion of a reset is batched along with the new values, so it must not
+        // remove the values written after it, even under the same keys.
+        db.commit(&DatabaseUpdates {
+            node_updates: indexmap! {
+                vec![1] => NodeDatabaseUpdates {
+                    partition_updates: indexmap! {
+                        0 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap! {
+                                DbSortKey(vec![2]) => vec![20],
+                                DbSortKey(vec![3]) => vec![30],
+                            }
+                        }
+                    }
+                }
+            },
+        });
+        let root_hash = db.get_current_root_hash();
+        drop(db);
+
+        let db = RocksDBWithMerkleTreeSubstateStore::standard(temp_dir.path().to_path_buf());
+        assert_eq!(db.get_current_version(), 2);
+        assert_eq!(db.get_current_root_hash(), root_hash);
+        assert_eq!(
+            db.list_raw_values_from_db_key(&partition_key, None)
+                .collect::<Vec<_>>(),
+            vec![
+                (DbSortKey(vec![2]), vec![20]),
+                (DbSortKey(vec![3]), vec![30])
+            ]
+        );
+    }
 }
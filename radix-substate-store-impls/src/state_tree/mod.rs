@@ -68,3 +68,36 @@ pub fn list_substate_hashes_at_version<S: ReadableTreeStore>(
         })
         .collect()
 }
+
+/// Returns the substates whose hashes differ between two trees, such as those of two separate
+/// databases, at the given versions, along with their value hashes in each tree.
+/// Only the subtrees whose hashes differ are visited, so the cost depends on the number of
+/// differences rather than on the size of the state.
+pub fn diff_substate_hashes_at_versions<A: ReadableTreeStore, B: ReadableTreeStore>(
+    tree_store_a: &A,
+    root_state_version_a: Version,
+    tree_store_b: &B,
+    root_state_version_b: Version,
+) -> Vec<(DbPartitionKey, DbSortKey, Option<Hash>, Option<Hash>)> {
+    let entity_tier_a = EntityTier::new(tree_store_a, Some(root_state_version_a));
+    let entity_tier_b = EntityTier::new(tree_store_b, Some(root_state_version_b));
+
+    let mut differences = Vec::new();
+    for (entity_key, _, _) in tier_framework::diff_leaf_hashes(&entity_tier_a, &entity_tier_b) {
+        let partition_tier_a = entity_tier_a.get_entity_partition_tier(entity_key.clone());
+        let partition_tier_b = entity_tier_b.get_entity_partition_tier(entity_key);
+        for (partition_num, _, _) in
+            tier_framework::diff_leaf_hashes(&partition_tier_a, &partition_tier_b)
+        {
+            let substate_tier_a = partition_tier_a.get_partition_substate_tier(partition_num);
+            let substate_tier_b = partition_tier_b.get_partition_substate_tier(partition_num);
+            let partition_key = substate_tier_a.partition_key().clone();
+            for (sort_key, hash_a, hash_b) in
+                tier_framework::diff_leaf_hashes(&substate_tier_a, &substate_tier_b)
+            {
+                differences.push((partition_key.clone(), sort_key, hash_a, hash_b));
+            }
+        }
+    }
+    differences
+}
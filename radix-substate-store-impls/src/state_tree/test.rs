@@ -1,3 +1,4 @@
+use super::diff_substate_hashes_at_versions;
 use super::jellyfish::JellyfishMerkleTree;
 use super::tier_framework::{StateTreeTier, TIER_SEPARATOR};
 use super::tree_store::*;
@@ -863,6 +864,62 @@ fn lists_historical_substate_summaries() {
     );
 }
 
+#[test]
+fn diffs_substate_hashes_of_separate_trees() {
+    let common_changes = (1..40)
+        .map(|seed| change(seed, seed % 3, seed, Some(seed)))
+        .collect::<Vec<_>>();
+    let mut tester_a = StateTreeTester::new_empty();
+    tester_a.put_substate_changes(common_changes.clone());
+    tester_a.put_substate_changes(vec![change(5, 2, 7, Some(8)), change(9, 0, 4, Some(2))]);
+    let mut tester_b = StateTreeTester::new_empty();
+    tester_b.put_substate_changes(common_changes);
+    tester_b.put_substate_changes(vec![change(5, 2, 7, Some(9)), change(12, 0, 12, None)]);
+    tester_b.put_substate_changes(vec![change(41, 1, 3, Some(1))]);
+
+    let differences = diff_substate_hashes_at_versions(
+        &tester_a.tree_store,
+        tester_a.current_version.unwrap(),
+        &tester_b.tree_store,
+        tester_b.current_version.unwrap(),
+    );
+
+    let hash_of = |seed: u8| Some(hash(from_seed(seed)));
+    assert_eq!(
+        differences.into_iter().collect::<HashSet<_>>(),
+        hashset!(
+            (
+                partition_key(from_seed(5), 2),
+                DbSortKey(from_seed(7)),
+                hash_of(8),
+                hash_of(9)
+            ),
+            (
+                partition_key(from_seed(9), 0),
+                DbSortKey(from_seed(4)),
+                hash_of(2),
+                None
+            ),
+            (
+                partition_key(from_seed(12), 0),
+                DbSortKey(from_seed(12)),
+                hash_of(12),
+                None
+            ),
+            (
+                partition_key(from_seed(41), 1),
+                DbSortKey(from_seed(3)),
+                None,
+                hash_of(1)
+            )
+        )
+    );
+    assert!(
+        diff_substate_hashes_at_versions(&tester_a.tree_store, 1, &tester_b.tree_store, 1)
+            .is_empty()
+    );
+}
+
 type SingleSubstateChange = (DbSubstateKey, DatabaseUpdate);
 
 fn change(
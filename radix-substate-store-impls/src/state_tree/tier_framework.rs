@@ -1,3 +1,4 @@
+use core::cmp::Ordering;
 use core::iter;
 
 use super::jellyfish::JellyfishMerkleTree;
@@ -164,6 +165,129 @@ where
     }
 }
 
+/// Returns the keys of the leaves which differ between two tiers, such as the same tier of two
+/// separate trees, along with their value hashes in each tier, in a lexicographic order.
+///
+/// Equal hashes mean equal contents of the subtrees, so only the subtrees whose hashes differ are
+/// visited.
+pub fn diff_leaf_hashes<A, B>(
+    tier_a: &A,
+    tier_b: &B,
+) -> Vec<(A::TypedLeafKey, Option<Hash>, Option<Hash>)>
+where
+    A: ReadableTier<StoredNode = TreeNode, Payload = Version>,
+    B: ReadableTier<StoredNode = TreeNode, Payload = Version, TypedLeafKey = A::TypedLeafKey>,
+{
+    let mut differences = Vec::new();
+    diff_subtrees(
+        tier_a,
+        tier_a.root_version().map(TreeNodeKey::new_empty_path),
+        tier_b,
+        tier_b.root_version().map(TreeNodeKey::new_empty_path),
+        &mut differences,
+    );
+    differences
+}
+
+/// Collects the differing leaves below the nodes at the same nibble path of two tiers, either of
+/// which may be missing.
+fn diff_subtrees<A, B>(
+    tier_a: &A,
+    key_a: Option<TreeNodeKey>,
+    tier_b: &B,
+    key_b: Option<TreeNodeKey>,
+    differences: &mut Vec<(A::TypedLeafKey, Option<Hash>, Option<Hash>)>,
+) where
+    A: ReadableTier<StoredNode = TreeNode, Payload = Version>,
+    B: ReadableTier<StoredNode = TreeNode, Payload = Version, TypedLeafKey = A::TypedLeafKey>,
+{
+    let node_a = key_a.as_ref().and_then(|key| tier_a.get_local_node(key));
+    let node_b = key_b.as_ref().and_then(|key| tier_b.get_local_node(key));
+    let key_a = key_a.filter(|_| node_a.is_some());
+    let key_b = key_b.filter(|_| node_b.is_some());
+
+    if let (
+        Some(key_a),
+        Some(key_b),
+        Some(TreeNode::Internal(internal_a)),
+        Some(TreeNode::Internal(internal_b)),
+    ) = (&key_a, &key_b, &node_a, &node_b)
+    {
+        let mut nibbles = internal_a
+            .children
+            .iter()
+            .chain(internal_b.children.iter())
+            .map(|child| child.nibble)
+            .collect::<Vec<_>>();
+        nibbles.sort();
+        nibbles.dedup();
+        for nibble in nibbles {
+            let child_a = internal_a
+                .children
+                .iter()
+                .find(|child| child.nibble == nibble);
+            let child_b = internal_b
+                .children
+                .iter()
+                .find(|child| child.nibble == nibble);
+            if let (Some(child_a), Some(child_b)) = (child_a, child_b) {
+                if child_a.hash == child_b.hash {
+                    continue;
+                }
+            }
+            diff_subtrees(
+                tier_a,
+                child_a.map(|child| key_a.gen_child_node_key(child.version, nibble)),
+                tier_b,
+                child_b.map(|child| key_b.gen_child_node_key(child.version, nibble)),
+                differences,
+            );
+        }
+        return;
+    }
+
+    // The subtrees aren't shaped the same way, so their leaves are compared one by one.
+    let mut leaves_a = key_a
+        .map(|key| recurse_until_leaves(tier_a, key, VecDeque::new()))
+        .unwrap_or_else(|| Box::new(iter::empty()))
+        .peekable();
+    let mut leaves_b = key_b
+        .map(|key| recurse_until_leaves(tier_b, key, VecDeque::new()))
+        .unwrap_or_else(|| Box::new(iter::empty()))
+        .peekable();
+    loop {
+        let ordering = match (leaves_a.peek(), leaves_b.peek()) {
+            (None, None) => return,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(leaf_a), Some(leaf_b)) => {
+                A::to_leaf_key(&leaf_a.key).cmp(&B::to_leaf_key(&leaf_b.key))
+            }
+        };
+        match ordering {
+            Ordering::Less => {
+                let leaf_a = leaves_a.next().unwrap();
+                differences.push((leaf_a.key, Some(leaf_a.value_hash), None));
+            }
+            Ordering::Greater => {
+                let leaf_b = leaves_b.next().unwrap();
+                differences.push((leaf_b.key, None, Some(leaf_b.value_hash)));
+            }
+            Ordering::Equal => {
+                let leaf_a = leaves_a.next().unwrap();
+                let leaf_b = leaves_b.next().unwrap();
+                if leaf_a.value_hash != leaf_b.value_hash {
+                    differences.push((
+                        leaf_a.key,
+                        Some(leaf_a.value_hash),
+                        Some(leaf_b.value_hash),
+                    ));
+                }
+            }
+        }
+    }
+}
+
 impl<R: ReadableTier + ?Sized> TreeReader<<R::StoredNode as StoredNode>::Payload> for R {
     fn get_node_option(
         &self,
@@ -23,6 +23,17 @@ pub fn to_typed_native_event(
     to_typed_event_with_event_key(&typed_native_event_key, event_data)
 }
 
+/// Given an [`EventTypeIdentifier`], this function returns the schema of the typed model of the
+/// event provided that the event is registered to a native blueprint. This can be used to serialize
+/// the raw event data with the names of its fields and variants.
+pub fn typed_native_event_schema(
+    event_type_identifier: &EventTypeIdentifier,
+) -> Result<ScryptoSingleTypeSchema, TypedNativeEventError> {
+    let typed_native_event_key =
+        resolve_typed_event_key_from_event_type_identifier(event_type_identifier)?;
+    Ok(typed_event_schema_with_event_key(&typed_native_event_key))
+}
+
 fn resolve_typed_event_key_from_event_type_identifier(
     event_type_identifier: &EventTypeIdentifier,
 ) -> Result<TypedNativeEventKey, TypedNativeEventError> {
@@ -573,6 +584,29 @@ macro_rules! define_structure {
                     _ => panic!("Illegal State! Matching over enum was not exhaustive.")
                 }
             }
+
+            // The implementation of a function that returns the schema of the typed event type of
+            // any `TypedNativeEventKey`.
+            fn typed_event_schema_with_event_key(
+                event_key: &TypedNativeEventKey,
+            ) -> ScryptoSingleTypeSchema {
+                match event_key {
+                    $(
+                        $(
+                            $(
+                                TypedNativeEventKey::$package_ident(
+                                    [< Typed $package_ident PackageEventKey >]::$blueprint_ident(
+                                        [< Typed $blueprint_ident BlueprintEventKey >]::$event_ty
+                                    )
+                                ) => generate_single_type_schema::<$event_ty, ScryptoCustomSchema>(),
+                            )*
+                        )*
+                    )*
+
+                    // See `to_typed_event_with_event_key`.
+                    _ => panic!("Illegal State! Matching over enum was not exhaustive.")
+                }
+            }
         }
     };
 }
@@ -306,6 +306,7 @@ impl ReadableManifest for AnyManifest {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ManifestKind {
     V1,
     SystemV1,
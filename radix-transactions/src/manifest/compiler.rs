@@ -7,6 +7,28 @@ pub enum CompileError {
     GeneratorError(generator::GeneratorError),
 }
 
+impl CompileError {
+    pub fn span(&self) -> token::Span {
+        match self {
+            CompileError::LexerError(err) => err.span,
+            CompileError::ParserError(err) => err.span,
+            CompileError::GeneratorError(err) => err.span,
+        }
+    }
+
+    /// The title and the short inline label of the error, as rendered by
+    /// [`compile_error_diagnostics`], for tools which display the span themselves.
+    pub fn title_and_label(&self) -> (String, String) {
+        match self.clone() {
+            CompileError::LexerError(err) => lexer::lexer_error_title_and_label(err.error_kind),
+            CompileError::ParserError(err) => parser::parser_error_title_and_label(err.error_kind),
+            CompileError::GeneratorError(err) => {
+                generator::generator_error_title_and_label(err.error_kind)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum CompileErrorDiagnosticsStyle {
     PlainText,
@@ -2068,6 +2068,11 @@ pub fn generator_error_diagnostics(
     err: GeneratorError,
     style: CompileErrorDiagnosticsStyle,
 ) -> String {
+    let (title, label) = generator_error_title_and_label(err.error_kind);
+    create_snippet(s, &err.span, &title, &label, style)
+}
+
+pub fn generator_error_title_and_label(error_kind: GeneratorErrorKind) -> (String, String) {
     // The title should be a little longer, and include context about what triggered
     // the error. The label is inline next to arrows pointing to the span which is
     // invalid, so can be shorter.
@@ -2077,7 +2082,7 @@ pub fn generator_error_diagnostics(
     //     ...
     //   12 |       Bytes(1u32),
     //      |       ^^^^^ <LABEL>
-    let (title, label) = match err.error_kind {
+    match error_kind {
         GeneratorErrorKind::InvalidAstType {
             expected_value_kind,
             actual,
@@ -2295,9 +2300,7 @@ pub fn generator_error_diagnostics(
             let description = format!("cannot be decoded as a {type_name}");
             (title, description)
         }
-    };
-
-    create_snippet(s, &err.span, &title, &label, style)
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use crate::manifest::compiler::CompileErrorDiagnosticsStyle;
 use crate::manifest::diagnostic_snippets::create_snippet;
-use crate::manifest::token::{Position, Span, Token, TokenWithSpan};
+use crate::manifest::token::{CommentWithSpan, Position, Span, Token, TokenWithSpan};
 use sbor::prelude::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -53,6 +53,8 @@ pub struct Lexer {
     text: Vec<char>,
     /// The current position in the text (in case of end of file it equals to text length)
     current: Position,
+    /// The comments skipped so far
+    comments: Vec<CommentWithSpan>,
 }
 
 pub fn tokenize(s: &str) -> Result<Vec<TokenWithSpan>, LexerError> {
@@ -64,6 +66,18 @@ pub fn tokenize(s: &str) -> Result<Vec<TokenWithSpan>, LexerError> {
     Ok(tokens)
 }
 
+/// Like [`tokenize`], but also returns the comments, in the order they appear in the text.
+pub fn tokenize_with_comments(
+    s: &str,
+) -> Result<(Vec<TokenWithSpan>, Vec<CommentWithSpan>), LexerError> {
+    let mut lexer = Lexer::new(s);
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token()? {
+        tokens.push(token);
+    }
+    Ok((tokens, lexer.comments))
+}
+
 impl Lexer {
     pub fn new(text: &str) -> Self {
         Self {
@@ -73,6 +87,7 @@ impl Lexer {
                 line_idx: 0,
                 line_char_index: 0,
             },
+            comments: Vec::new(),
         }
     }
 
@@ -132,14 +147,9 @@ impl Lexer {
 
     pub fn next_token(&mut self) -> Result<Option<TokenWithSpan>, LexerError> {
         // skip comment and whitespace
-        let mut in_comment = false;
         while !self.is_eof() {
-            if in_comment {
-                if self.advance()? == '\n' {
-                    in_comment = false;
-                }
-            } else if self.peek()? == '#' {
-                in_comment = true;
+            if self.peek()? == '#' {
+                self.skip_comment()?;
             } else if Self::is_whitespace(self.peek()?) {
                 self.advance()?;
             } else {
@@ -169,6 +179,22 @@ impl Lexer {
         .map(Option::from)
     }
 
+    fn skip_comment(&mut self) -> Result<(), LexerError> {
+        let start = self.current;
+        let mut comment = String::new();
+        while !self.is_eof() && self.peek()? != '\n' {
+            self.advance_and_append(&mut comment)?;
+        }
+        self.comments.push(CommentWithSpan {
+            comment,
+            span: Span {
+                start,
+                end: self.current,
+            },
+        });
+        Ok(())
+    }
+
     // TODO: consider using DFA
     fn tokenize_number(&mut self) -> Result<TokenWithSpan, LexerError> {
         let literal_start = self.current;
@@ -406,7 +432,12 @@ pub fn lexer_error_diagnostics(
     err: LexerError,
     style: CompileErrorDiagnosticsStyle,
 ) -> String {
-    let (title, label) = match err.error_kind {
+    let (title, label) = lexer_error_title_and_label(err.error_kind);
+    create_snippet(s, &err.span, &title, &label, style)
+}
+
+pub fn lexer_error_title_and_label(error_kind: LexerErrorKind) -> (String, String) {
+    match error_kind {
         LexerErrorKind::UnexpectedEof => (
             "unexpected end of file".to_string(),
             "unexpected end of file".to_string(),
@@ -451,8 +482,7 @@ pub fn lexer_error_diagnostics(
             format!("missing unicode '{:X}' surrogate pair", value),
             "missing unicode surrogate pair".to_string(),
         ),
-    };
-    create_snippet(s, &err.span, &title, &label, style)
+    }
 }
 
 #[cfg(test)]
@@ -538,6 +568,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_comments_are_recorded() {
+        let (tokens, comments) = tokenize_with_comments("# first\n1u8 # second").unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            comments,
+            vec![
+                CommentWithSpan {
+                    comment: "# first".to_string(),
+                    span: span!(start = (0, 0, 0), end = (7, 0, 7)),
+                },
+                CommentWithSpan {
+                    comment: "# second".to_string(),
+                    span: span!(start = (12, 1, 4), end = (20, 1, 12)),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_string() {
         lex_ok!(
@@ -0,0 +1,1008 @@
+use crate::data::ManifestCustomCharEscaper;
+use crate::internal_prelude::*;
+use crate::manifest::ast::{Instruction, InstructionWithSpan, Value, ValueWithSpan};
+use crate::manifest::lexer::tokenize_with_comments;
+use crate::manifest::parser::{Parser, PARSER_MAX_DEPTH};
+use crate::manifest::token::{CommentWithSpan, Span, Token, TokenWithSpan};
+use radix_rust::unicode::CustomCharEscaper;
+
+const INDENT: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestFormatterOptions {
+    /// The line width beyond which an instruction is written with one argument per line, and a
+    /// value with one element per line
+    pub max_width: usize,
+    /// Whether to rename the buckets, proofs, address reservations, named addresses and named
+    /// intents to the names the decompiler gives them, e.g. `bucket1`, in the order they are used
+    pub normalize_names: bool,
+}
+
+impl Default for ManifestFormatterOptions {
+    fn default() -> Self {
+        Self {
+            max_width: 100,
+            normalize_names: false,
+        }
+    }
+}
+
+/// Formats a manifest in the canonical style, keeping its comments.
+///
+/// An instruction is written on a single line if it fits, and otherwise with one argument per line
+/// as in decompiled manifests. Values are split over several lines in the same way, and an
+/// instruction or value containing comments is always split. Blank lines between instructions are
+/// kept, but runs of them are collapsed.
+pub fn format_manifest(
+    s: &str,
+    options: &ManifestFormatterOptions,
+) -> Result<String, CompileError> {
+    let (tokens, comments) = tokenize_with_comments(s).map_err(CompileError::LexerError)?;
+    let instructions = if tokens.is_empty() {
+        vec![]
+    } else {
+        Parser::new(tokens.clone(), PARSER_MAX_DEPTH)
+            .and_then(|mut parser| parser.parse_manifest())
+            .map_err(CompileError::ParserError)?
+    };
+    Ok(ManifestFormatter::new(tokens, comments, &instructions, options).format(&instructions))
+}
+
+struct Comment {
+    text: String,
+    span: Span,
+    /// Whether the comment is alone on its line, rather than following some code
+    own_line: bool,
+}
+
+#[derive(Default)]
+struct ItemComments<'c> {
+    /// The comments written on the lines before the item
+    leading: Vec<&'c Comment>,
+    /// The comments written after the item, the first of them on the same line
+    trailing: Vec<&'c Comment>,
+}
+
+/// An element of a value, or an argument of an instruction.
+enum Item<'v> {
+    Value(&'v ValueWithSpan),
+    MapEntry(&'v ValueWithSpan, &'v ValueWithSpan),
+}
+
+enum Shape<'v> {
+    Leaf(String),
+    /// A value holding a single value, e.g. `Decimal("1")` or `Some(1u8)`
+    Wrapper(&'static str, &'v ValueWithSpan),
+    /// A value holding a list of elements, e.g. `Tuple(1u8, 2u8)`
+    List(String, Vec<Item<'v>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum NameKind {
+    Bucket,
+    Proof,
+    AddressReservation,
+    NamedAddress,
+    NamedIntent,
+}
+
+impl NameKind {
+    fn of(value: &Value) -> Option<(Self, &ValueWithSpan)> {
+        match value {
+            Value::Bucket(inner) => Some((Self::Bucket, inner)),
+            Value::Proof(inner) => Some((Self::Proof, inner)),
+            Value::AddressReservation(inner) => Some((Self::AddressReservation, inner)),
+            Value::NamedAddress(inner) => Some((Self::NamedAddress, inner)),
+            Value::NamedIntent(inner) => Some((Self::NamedIntent, inner)),
+            _ => None,
+        }
+    }
+
+    fn default_name(self, id: u32) -> String {
+        let names = ManifestObjectNamesRef::Unknown;
+        match self {
+            Self::Bucket => names.bucket_name(ManifestBucket(id)),
+            Self::Proof => names.proof_name(ManifestProof(id)),
+            Self::AddressReservation => {
+                names.address_reservation_name(ManifestAddressReservation(id))
+            }
+            Self::NamedAddress => names.address_name(ManifestNamedAddress(id)),
+            Self::NamedIntent => names.intent_name(ManifestNamedIntent(id)),
+        }
+    }
+}
+
+struct ManifestFormatter<'a> {
+    options: &'a ManifestFormatterOptions,
+    tokens: Vec<TokenWithSpan>,
+    /// The index of each token, by the index of its first char
+    token_indices: BTreeMap<usize, usize>,
+    comments: Vec<Comment>,
+    /// The new names, by the index of the first char of the string holding the old name
+    renames: BTreeMap<usize, String>,
+}
+
+impl<'a> ManifestFormatter<'a> {
+    fn new(
+        tokens: Vec<TokenWithSpan>,
+        comments: Vec<CommentWithSpan>,
+        instructions: &[InstructionWithSpan],
+        options: &'a ManifestFormatterOptions,
+    ) -> Self {
+        let token_indices = tokens
+            .iter()
+            .enumerate()
+            .map(|(index, token)| (token.span.start.full_index, index))
+            .collect();
+        let comments = comments
+            .into_iter()
+            .map(|CommentWithSpan { comment, span }| {
+                let previous = tokens
+                    .partition_point(|token| token.span.end.full_index <= span.start.full_index);
+                let own_line =
+                    previous == 0 || tokens[previous - 1].span.end.line_idx < span.start.line_idx;
+                Comment {
+                    text: comment.trim_end().to_string(),
+                    span,
+                    own_line,
+                }
+            })
+            .collect();
+        let mut renames = BTreeMap::new();
+        if options.normalize_names {
+            let mut names = BTreeMap::new();
+            let mut counts = BTreeMap::new();
+            for instruction in instructions {
+                for argument in instruction_parts(&instruction.instruction).1 {
+                    collect_renames(argument, &mut names, &mut counts, &mut renames);
+                }
+            }
+        }
+        Self {
+            options,
+            tokens,
+            token_indices,
+            comments,
+            renames,
+        }
+    }
+
+    fn format(&self, instructions: &[InstructionWithSpan]) -> String {
+        let mut output = String::new();
+        let mut previous_line = None;
+        let mut previous_end = 0;
+        for instruction in instructions {
+            let start = instruction.span.start;
+            self.write_top_level_comments(
+                &mut output,
+                &mut previous_line,
+                previous_end,
+                start.full_index,
+            );
+            write_blank_line_if_separated(&mut output, previous_line, start.line_idx);
+            output.push_str(&self.format_instruction(instruction));
+            output.push('\n');
+            previous_line = Some(instruction.span.end.line_idx);
+            previous_end = instruction.span.end.full_index;
+        }
+        self.write_top_level_comments(&mut output, &mut previous_line, previous_end, usize::MAX);
+        output
+    }
+
+    fn write_top_level_comments(
+        &self,
+        output: &mut String,
+        previous_line: &mut Option<usize>,
+        start: usize,
+        end: usize,
+    ) {
+        for comment in self.comments_between(start, end) {
+            if comment.own_line || output.is_empty() {
+                write_blank_line_if_separated(output, *previous_line, comment.span.start.line_idx);
+            } else {
+                // A comment following an instruction stays on the line of its semicolon
+                output.pop();
+                output.push(' ');
+            }
+            output.push_str(&comment.text);
+            output.push('\n');
+            *previous_line = Some(comment.span.start.line_idx);
+        }
+    }
+
+    fn format_instruction(&self, instruction: &InstructionWithSpan) -> String {
+        let (name, arguments) = instruction_parts(&instruction.instruction);
+        let start = instruction.span.start.full_index;
+        let end = instruction.span.end.full_index;
+        if self.comments_between(start, end).is_empty() {
+            let mut line = name.to_string();
+            for argument in &arguments {
+                line.push(' ');
+                line.push_str(&self.format_flat(argument));
+            }
+            line.push(';');
+            if line.chars().count() <= self.options.max_width {
+                return line;
+            }
+        }
+        let items: Vec<Item> = arguments.into_iter().map(Item::Value).collect();
+        let mut output = name.to_string();
+        self.write_items(&mut output, start, end, &items, INDENT, "");
+        output.push_str("\n;");
+        output
+    }
+
+    fn format_value(&self, value: &ValueWithSpan, indent: usize) -> String {
+        let start = value.span.start.full_index;
+        let end = self.value_end(value);
+        let has_comments = !self.comments_between(start, end).is_empty();
+        let shape = self.shape(value);
+        if !has_comments {
+            let flat = self.format_flat(value);
+            let can_split = match &shape {
+                Shape::Leaf(_) => false,
+                Shape::Wrapper(..) => true,
+                Shape::List(_, items) => !items.is_empty(),
+            };
+            if !can_split || indent + flat.chars().count() <= self.options.max_width {
+                return flat;
+            }
+        }
+        match shape {
+            Shape::Leaf(text) => text,
+            Shape::Wrapper(head, inner) => {
+                // The inner value stays next to the parentheses, unless there are comments around it
+                let inner_end = self.value_end(inner);
+                if self
+                    .comments_between(start, inner.span.start.full_index)
+                    .is_empty()
+                    && self.comments_between(inner_end, end).is_empty()
+                {
+                    format!("{}({})", head, self.format_value(inner, indent))
+                } else {
+                    self.format_list(head, &[Item::Value(inner)], start, end, indent)
+                }
+            }
+            Shape::List(head, items) => self.format_list(&head, &items, start, end, indent),
+        }
+    }
+
+    fn format_flat(&self, value: &ValueWithSpan) -> String {
+        match self.shape(value) {
+            Shape::Leaf(text) => text,
+            Shape::Wrapper(head, inner) => format!("{}({})", head, self.format_flat(inner)),
+            Shape::List(head, items) => {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|item| match item {
+                        Item::Value(value) => self.format_flat(value),
+                        Item::MapEntry(key, value) => {
+                            format!("{} => {}", self.format_flat(key), self.format_flat(value))
+                        }
+                    })
+                    .collect();
+                format!("{}({})", head, items.join(", "))
+            }
+        }
+    }
+
+    fn format_list(
+        &self,
+        head: &str,
+        items: &[Item],
+        start: usize,
+        end: usize,
+        indent: usize,
+    ) -> String {
+        let mut output = format!("{}(", head);
+        self.write_items(&mut output, start, end, items, indent + INDENT, ",");
+        output.push('\n');
+        output.push_str(&" ".repeat(indent));
+        output.push(')');
+        output
+    }
+
+    /// Writes each item on new lines, with the comments between `start` and `end` which aren't
+    /// inside the items.
+    fn write_items(
+        &self,
+        output: &mut String,
+        start: usize,
+        end: usize,
+        items: &[Item],
+        indent: usize,
+        separator: &str,
+    ) {
+        let prefix = " ".repeat(indent);
+        let ranges: Vec<(usize, usize)> = items.iter().map(|item| self.item_range(item)).collect();
+        let (items_comments, dangling_comments) = self.distribute_comments(start, end, &ranges);
+        for (index, (item, mut comments)) in items.iter().zip(items_comments).enumerate() {
+            // Comments between the key and the value of a map entry go before the entry
+            if let Item::MapEntry(key, value) = item {
+                let key_end = self.value_end(key);
+                comments
+                    .leading
+                    .extend(self.comments_between(key_end, value.span.start.full_index));
+            }
+            for comment in comments.leading {
+                output.push('\n');
+                output.push_str(&prefix);
+                output.push_str(&comment.text);
+            }
+            output.push('\n');
+            output.push_str(&prefix);
+            match item {
+                Item::Value(value) => output.push_str(&self.format_value(value, indent)),
+                Item::MapEntry(key, value) => {
+                    output.push_str(&self.format_value(key, indent));
+                    output.push_str(" => ");
+                    output.push_str(&self.format_value(value, indent));
+                }
+            }
+            if index + 1 < items.len() {
+                output.push_str(separator);
+            }
+            for (index, comment) in comments.trailing.iter().enumerate() {
+                if index == 0 {
+                    output.push(' ');
+                } else {
+                    output.push('\n');
+                    output.push_str(&prefix);
+                }
+                output.push_str(&comment.text);
+            }
+        }
+        for comment in dangling_comments {
+            output.push('\n');
+            output.push_str(&prefix);
+            output.push_str(&comment.text);
+        }
+    }
+
+    /// Attaches the comments between `start` and `end` which aren't inside the given item ranges
+    /// to the items: a comment on its own line to the next item, and a comment following some code
+    /// to the previous item. The comments after the last item which can't be attached to it are
+    /// returned separately.
+    fn distribute_comments(
+        &self,
+        start: usize,
+        end: usize,
+        ranges: &[(usize, usize)],
+    ) -> (Vec<ItemComments<'_>>, Vec<&Comment>) {
+        let mut items_comments: Vec<ItemComments> =
+            ranges.iter().map(|_| ItemComments::default()).collect();
+        let mut dangling_comments = Vec::new();
+        let mut gap_start = start;
+        for index in 0..=ranges.len() {
+            let gap_end = ranges.get(index).map_or(end, |(item_start, _)| *item_start);
+            for comment in self.comments_between(gap_start, gap_end) {
+                if index > 0 && !comment.own_line {
+                    items_comments[index - 1].trailing.push(comment);
+                } else if index < ranges.len() {
+                    items_comments[index].leading.push(comment);
+                } else {
+                    dangling_comments.push(comment);
+                }
+            }
+            if let Some((_, item_end)) = ranges.get(index) {
+                gap_start = *item_end;
+            }
+        }
+        (items_comments, dangling_comments)
+    }
+
+    fn item_range(&self, item: &Item) -> (usize, usize) {
+        match item {
+            Item::Value(value) => (value.span.start.full_index, self.value_end(value)),
+            Item::MapEntry(key, value) => (key.span.start.full_index, self.value_end(value)),
+        }
+    }
+
+    fn shape<'v>(&self, value: &'v ValueWithSpan) -> Shape<'v> {
+        let leaf = match &value.value {
+            Value::Bool(value) => value.to_string(),
+            Value::I8(value) => format!("{}i8", value),
+            Value::I16(value) => format!("{}i16", value),
+            Value::I32(value) => format!("{}i32", value),
+            Value::I64(value) => format!("{}i64", value),
+            Value::I128(value) => format!("{}i128", value),
+            Value::U8(value) => format!("{}u8", value),
+            Value::U16(value) => format!("{}u16", value),
+            Value::U32(value) => format!("{}u32", value),
+            Value::U64(value) => format!("{}u64", value),
+            Value::U128(value) => format!("{}u128", value),
+            Value::String(string) => {
+                let string = self
+                    .renames
+                    .get(&value.span.start.full_index)
+                    .unwrap_or(string);
+                ManifestCustomCharEscaper::escaped(string).to_string()
+            }
+            Value::None => "None".to_string(),
+
+            Value::Enum(discriminator, fields) => {
+                let head = format!("Enum<{}>", self.enum_discriminator(value, *discriminator));
+                return Shape::List(head, fields.iter().map(Item::Value).collect());
+            }
+            Value::Array(element_value_kind, elements) => {
+                let head = format!("Array<{}>", element_value_kind.value_kind);
+                return Shape::List(head, elements.iter().map(Item::Value).collect());
+            }
+            Value::Tuple(fields) => {
+                return Shape::List(
+                    "Tuple".to_string(),
+                    fields.iter().map(Item::Value).collect(),
+                );
+            }
+            Value::Map(key_value_kind, value_value_kind, entries) => {
+                let head = format!(
+                    "Map<{}, {}>",
+                    key_value_kind.value_kind, value_value_kind.value_kind
+                );
+                let entries = entries
+                    .iter()
+                    .map(|(key, value)| Item::MapEntry(key, value))
+                    .collect();
+                return Shape::List(head, entries);
+            }
+
+            Value::Some(inner) => return Shape::Wrapper("Some", inner),
+            Value::Ok(inner) => return Shape::Wrapper("Ok", inner),
+            Value::Err(inner) => return Shape::Wrapper("Err", inner),
+            Value::Bytes(inner) => return Shape::Wrapper("Bytes", inner),
+            Value::NonFungibleGlobalId(inner) => {
+                return Shape::Wrapper("NonFungibleGlobalId", inner)
+            }
+            Value::Address(inner) => return Shape::Wrapper("Address", inner),
+            Value::NamedAddress(inner) => return Shape::Wrapper("NamedAddress", inner),
+            Value::Bucket(inner) => return Shape::Wrapper("Bucket", inner),
+            Value::Proof(inner) => return Shape::Wrapper("Proof", inner),
+            Value::Expression(inner) => return Shape::Wrapper("Expression", inner),
+            Value::Blob(inner) => return Shape::Wrapper("Blob", inner),
+            Value::Decimal(inner) => return Shape::Wrapper("Decimal", inner),
+            Value::PreciseDecimal(inner) => return Shape::Wrapper("PreciseDecimal", inner),
+            Value::NonFungibleLocalId(inner) => return Shape::Wrapper("NonFungibleLocalId", inner),
+            Value::AddressReservation(inner) => return Shape::Wrapper("AddressReservation", inner),
+            Value::Intent(inner) => return Shape::Wrapper("Intent", inner),
+            Value::NamedIntent(inner) => return Shape::Wrapper("NamedIntent", inner),
+        };
+        Shape::Leaf(leaf)
+    }
+
+    /// The AST only keeps the number of an enum discriminator, so an alias such as
+    /// `Metadata::String` is read back from the tokens.
+    fn enum_discriminator(&self, value: &ValueWithSpan, discriminator: u8) -> String {
+        match self
+            .token_indices
+            .get(&value.span.start.full_index)
+            .and_then(|index| self.tokens.get(index + 2))
+        {
+            Some(TokenWithSpan {
+                token: Token::Ident(alias),
+                ..
+            }) => alias.clone(),
+            _ => format!("{}u8", discriminator),
+        }
+    }
+
+    /// The span of a value only covers its first token, so the end of the value is found by
+    /// matching its brackets.
+    fn value_end(&self, value: &ValueWithSpan) -> usize {
+        let Some(&first) = self.token_indices.get(&value.span.start.full_index) else {
+            return value.span.end.full_index;
+        };
+        let mut last = first;
+        if let Token::Ident(_) = self.tokens[first].token {
+            for open in [Token::LessThan, Token::OpenParenthesis] {
+                if self.tokens.get(last + 1).map(|token| &token.token) == Some(&open) {
+                    last = self.closing_token(last + 1);
+                }
+            }
+        }
+        self.tokens[last].span.end.full_index
+    }
+
+    fn closing_token(&self, open: usize) -> usize {
+        let mut depth = 0usize;
+        for (index, token) in self.tokens.iter().enumerate().skip(open) {
+            match token.token {
+                Token::OpenParenthesis | Token::LessThan => depth += 1,
+                Token::CloseParenthesis | Token::GreaterThan => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return index;
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.tokens.len() - 1
+    }
+
+    fn comments_between(&self, start: usize, end: usize) -> &[Comment] {
+        let from = self
+            .comments
+            .partition_point(|comment| comment.span.start.full_index < start);
+        let to = self
+            .comments
+            .partition_point(|comment| comment.span.start.full_index < end);
+        &self.comments[from..to.max(from)]
+    }
+}
+
+fn write_blank_line_if_separated(output: &mut String, previous_line: Option<usize>, line: usize) {
+    if previous_line.is_some_and(|previous_line| line > previous_line + 1) {
+        output.push('\n');
+    }
+}
+
+fn collect_renames(
+    value: &ValueWithSpan,
+    names: &mut BTreeMap<(NameKind, String), String>,
+    counts: &mut BTreeMap<NameKind, u32>,
+    renames: &mut BTreeMap<usize, String>,
+) {
+    if let Some((kind, inner)) = NameKind::of(&value.value) {
+        if let Value::String(name) = &inner.value {
+            let new_name = names.entry((kind, name.clone())).or_insert_with(|| {
+                let count = counts.entry(kind).or_default();
+                *count += 1;
+                kind.default_name(*count - 1)
+            });
+            renames.insert(inner.span.start.full_index, new_name.clone());
+        }
+        return;
+    }
+    match &value.value {
+        Value::Enum(_, values) | Value::Array(_, values) | Value::Tuple(values) => {
+            for value in values {
+                collect_renames(value, names, counts, renames);
+            }
+        }
+        Value::Map(_, _, entries) => {
+            for (key, value) in entries {
+                collect_renames(key, names, counts, renames);
+                collect_renames(value, names, counts, renames);
+            }
+        }
+        Value::Some(inner)
+        | Value::Ok(inner)
+        | Value::Err(inner)
+        | Value::Bytes(inner)
+        | Value::NonFungibleGlobalId(inner) => collect_renames(inner, names, counts, renames),
+        _ => {}
+    }
+}
+
+/// Returns the name of an instruction and its arguments, in the order they are written.
+fn instruction_parts(instruction: &Instruction) -> (&'static str, Vec<&ValueWithSpan>) {
+    let (name, mut arguments): (&'static str, Vec<&ValueWithSpan>) = match instruction {
+        Instruction::UsePreallocatedAddress {
+            package_address,
+            blueprint_name,
+            address_reservation,
+            preallocated_address,
+        } => (
+            "USE_PREALLOCATED_ADDRESS",
+            vec![
+                package_address,
+                blueprint_name,
+                address_reservation,
+                preallocated_address,
+            ],
+        ),
+        Instruction::UseChild {
+            named_intent,
+            subintent_hash,
+        } => ("USE_CHILD", vec![named_intent, subintent_hash]),
+
+        // Bucket Lifecycle
+        Instruction::TakeFromWorktop {
+            resource_address,
+            amount,
+            new_bucket,
+        } => (
+            TakeFromWorktop::IDENT,
+            vec![resource_address, amount, new_bucket],
+        ),
+        Instruction::TakeNonFungiblesFromWorktop {
+            ids,
+            resource_address,
+            new_bucket,
+        } => (
+            TakeNonFungiblesFromWorktop::IDENT,
+            vec![resource_address, ids, new_bucket],
+        ),
+        Instruction::TakeAllFromWorktop {
+            resource_address,
+            new_bucket,
+        } => (
+            TakeAllFromWorktop::IDENT,
+            vec![resource_address, new_bucket],
+        ),
+        Instruction::ReturnToWorktop { bucket } => (ReturnToWorktop::IDENT, vec![bucket]),
+        Instruction::BurnResource { bucket } => (BurnResource::IDENT, vec![bucket]),
+
+        // Resource Assertions
+        Instruction::AssertWorktopContains {
+            resource_address,
+            amount,
+        } => (AssertWorktopContains::IDENT, vec![resource_address, amount]),
+        Instruction::AssertWorktopContainsNonFungibles {
+            resource_address,
+            ids,
+        } => (
+            AssertWorktopContainsNonFungibles::IDENT,
+            vec![resource_address, ids],
+        ),
+        Instruction::AssertWorktopContainsAny { resource_address } => {
+            (AssertWorktopContainsAny::IDENT, vec![resource_address])
+        }
+        Instruction::AssertWorktopIsEmpty => ("ASSERT_WORKTOP_IS_EMPTY", vec![]),
+        Instruction::AssertWorktopResourcesOnly { constraints } => {
+            (AssertWorktopResourcesOnly::IDENT, vec![constraints])
+        }
+        Instruction::AssertWorktopResourcesInclude { constraints } => {
+            (AssertWorktopResourcesInclude::IDENT, vec![constraints])
+        }
+        Instruction::AssertNextCallReturnsOnly { constraints } => {
+            (AssertNextCallReturnsOnly::IDENT, vec![constraints])
+        }
+        Instruction::AssertNextCallReturnsInclude { constraints } => {
+            (AssertNextCallReturnsInclude::IDENT, vec![constraints])
+        }
+        Instruction::AssertBucketContents { bucket, constraint } => {
+            (AssertBucketContents::IDENT, vec![bucket, constraint])
+        }
+
+        // Proof Lifecycle
+        Instruction::CreateProofFromBucketOfAmount {
+            bucket,
+            amount,
+            new_proof,
+        } => (
+            CreateProofFromBucketOfAmount::IDENT,
+            vec![bucket, amount, new_proof],
+        ),
+        Instruction::CreateProofFromBucketOfNonFungibles {
+            bucket,
+            ids,
+            new_proof,
+        } => (
+            CreateProofFromBucketOfNonFungibles::IDENT,
+            vec![bucket, ids, new_proof],
+        ),
+        Instruction::CreateProofFromBucketOfAll { bucket, new_proof } => {
+            (CreateProofFromBucketOfAll::IDENT, vec![bucket, new_proof])
+        }
+        Instruction::CreateProofFromAuthZoneOfAmount {
+            resource_address,
+            amount,
+            new_proof,
+        } => (
+            CreateProofFromAuthZoneOfAmount::IDENT,
+            vec![resource_address, amount, new_proof],
+        ),
+        Instruction::CreateProofFromAuthZoneOfNonFungibles {
+            resource_address,
+            ids,
+            new_proof,
+        } => (
+            CreateProofFromAuthZoneOfNonFungibles::IDENT,
+            vec![resource_address, ids, new_proof],
+        ),
+        Instruction::CreateProofFromAuthZoneOfAll {
+            resource_address,
+            new_proof,
+        } => (
+            CreateProofFromAuthZoneOfAll::IDENT,
+            vec![resource_address, new_proof],
+        ),
+        Instruction::CloneProof { proof, new_proof } => (CloneProof::IDENT, vec![proof, new_proof]),
+        Instruction::DropProof { proof } => (DropProof::IDENT, vec![proof]),
+        Instruction::PushToAuthZone { proof } => (PushToAuthZone::IDENT, vec![proof]),
+        Instruction::PopFromAuthZone { new_proof } => (PopFromAuthZone::IDENT, vec![new_proof]),
+        Instruction::DropAuthZoneSignatureProofs => (DropAuthZoneSignatureProofs::IDENT, vec![]),
+        Instruction::DropAuthZoneRegularProofs => (DropAuthZoneRegularProofs::IDENT, vec![]),
+        Instruction::DropAuthZoneProofs => (DropAuthZoneProofs::IDENT, vec![]),
+        Instruction::DropNamedProofs => (DropNamedProofs::IDENT, vec![]),
+        Instruction::DropAllProofs => (DropAllProofs::IDENT, vec![]),
+
+        // Invocations
+        Instruction::CallFunction {
+            package_address,
+            blueprint_name,
+            function_name,
+            args,
+        } => (
+            CallFunction::IDENT,
+            with_args(vec![package_address, blueprint_name, function_name], args),
+        ),
+        Instruction::CallMethod {
+            address,
+            method_name,
+            args,
+        } => (
+            CallMethod::IDENT,
+            with_args(vec![address, method_name], args),
+        ),
+        Instruction::CallRoyaltyMethod {
+            address,
+            method_name,
+            args,
+        } => (
+            CallRoyaltyMethod::IDENT,
+            with_args(vec![address, method_name], args),
+        ),
+        Instruction::CallMetadataMethod {
+            address,
+            method_name,
+            args,
+        } => (
+            CallMetadataMethod::IDENT,
+            with_args(vec![address, method_name], args),
+        ),
+        Instruction::CallRoleAssignmentMethod {
+            address,
+            method_name,
+            args,
+        } => (
+            CallRoleAssignmentMethod::IDENT,
+            with_args(vec![address, method_name], args),
+        ),
+        Instruction::CallDirectVaultMethod {
+            address,
+            method_name,
+            args,
+        } => (
+            CallDirectVaultMethod::IDENT,
+            with_args(vec![address, method_name], args),
+        ),
+
+        // Address Allocation
+        Instruction::AllocateGlobalAddress {
+            package_address,
+            blueprint_name,
+            address_reservation,
+            named_address,
+        } => (
+            AllocateGlobalAddress::IDENT,
+            vec![
+                package_address,
+                blueprint_name,
+                address_reservation,
+                named_address,
+            ],
+        ),
+
+        // Interaction with other intents
+        Instruction::YieldToParent { args } => (YieldToParent::IDENT, with_args(vec![], args)),
+        Instruction::YieldToChild { child, args } => {
+            (YieldToChild::IDENT, with_args(vec![child], args))
+        }
+        Instruction::VerifyParent { access_rule } => (VerifyParent::IDENT, vec![access_rule]),
+
+        // Direct vault aliases
+        Instruction::RecallFromVault { vault_id, args } => {
+            ("RECALL_FROM_VAULT", with_args(vec![vault_id], args))
+        }
+        Instruction::FreezeVault { vault_id, args } => {
+            ("FREEZE_VAULT", with_args(vec![vault_id], args))
+        }
+        Instruction::UnfreezeVault { vault_id, args } => {
+            ("UNFREEZE_VAULT", with_args(vec![vault_id], args))
+        }
+        Instruction::RecallNonFungiblesFromVault { vault_id, args } => (
+            "RECALL_NON_FUNGIBLES_FROM_VAULT",
+            with_args(vec![vault_id], args),
+        ),
+
+        // Call function aliases
+        Instruction::PublishPackage { args } => ("PUBLISH_PACKAGE", with_args(vec![], args)),
+        Instruction::PublishPackageAdvanced { args } => {
+            ("PUBLISH_PACKAGE_ADVANCED", with_args(vec![], args))
+        }
+        Instruction::CreateFungibleResource { args } => {
+            ("CREATE_FUNGIBLE_RESOURCE", with_args(vec![], args))
+        }
+        Instruction::CreateFungibleResourceWithInitialSupply { args } => (
+            "CREATE_FUNGIBLE_RESOURCE_WITH_INITIAL_SUPPLY",
+            with_args(vec![], args),
+        ),
+        Instruction::CreateNonFungibleResource { args } => {
+            ("CREATE_NON_FUNGIBLE_RESOURCE", with_args(vec![], args))
+        }
+        Instruction::CreateNonFungibleResourceWithInitialSupply { args } => (
+            "CREATE_NON_FUNGIBLE_RESOURCE_WITH_INITIAL_SUPPLY",
+            with_args(vec![], args),
+        ),
+        Instruction::CreateAccessController { args } => {
+            ("CREATE_ACCESS_CONTROLLER", with_args(vec![], args))
+        }
+        Instruction::CreateIdentity { args } => ("CREATE_IDENTITY", with_args(vec![], args)),
+        Instruction::CreateIdentityAdvanced { args } => {
+            ("CREATE_IDENTITY_ADVANCED", with_args(vec![], args))
+        }
+        Instruction::CreateAccount { args } => ("CREATE_ACCOUNT", with_args(vec![], args)),
+        Instruction::CreateAccountAdvanced { args } => {
+            ("CREATE_ACCOUNT_ADVANCED", with_args(vec![], args))
+        }
+
+        // Non-main method aliases
+        Instruction::SetMetadata { address, args } => {
+            ("SET_METADATA", with_args(vec![address], args))
+        }
+        Instruction::RemoveMetadata { address, args } => {
+            ("REMOVE_METADATA", with_args(vec![address], args))
+        }
+        Instruction::LockMetadata { address, args } => {
+            ("LOCK_METADATA", with_args(vec![address], args))
+        }
+        Instruction::SetComponentRoyalty { address, args } => {
+            ("SET_COMPONENT_ROYALTY", with_args(vec![address], args))
+        }
+        Instruction::SetOwnerRole { address, args } => {
+            ("SET_OWNER_ROLE", with_args(vec![address], args))
+        }
+        Instruction::LockOwnerRole { address, args } => {
+            ("LOCK_OWNER_ROLE", with_args(vec![address], args))
+        }
+        Instruction::SetRole { address, args } => ("SET_ROLE", with_args(vec![address], args)),
+        Instruction::LockComponentRoyalty { address, args } => {
+            ("LOCK_COMPONENT_ROYALTY", with_args(vec![address], args))
+        }
+        Instruction::ClaimComponentRoyalties { address, args } => {
+            ("CLAIM_COMPONENT_ROYALTIES", with_args(vec![address], args))
+        }
+
+        // Main method aliases
+        Instruction::ClaimPackageRoyalties { address, args } => {
+            ("CLAIM_PACKAGE_ROYALTIES", with_args(vec![address], args))
+        }
+        Instruction::MintFungible { address, args } => {
+            ("MINT_FUNGIBLE", with_args(vec![address], args))
+        }
+        Instruction::MintNonFungible { address, args } => {
+            ("MINT_NON_FUNGIBLE", with_args(vec![address], args))
+        }
+        Instruction::MintRuidNonFungible { address, args } => {
+            ("MINT_RUID_NON_FUNGIBLE", with_args(vec![address], args))
+        }
+        Instruction::CreateValidator { args } => ("CREATE_VALIDATOR", with_args(vec![], args)),
+    };
+    arguments.sort_by_key(|argument| argument.span.start.full_index);
+    (name, arguments)
+}
+
+fn with_args<'a>(
+    mut arguments: Vec<&'a ValueWithSpan>,
+    args: &'a [ValueWithSpan],
+) -> Vec<&'a ValueWithSpan> {
+    arguments.extend(args);
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::e2e::apply_address_replacements;
+
+    fn format(s: &str) -> String {
+        format_manifest(s, &ManifestFormatterOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_instructions_are_split_when_too_long() {
+        let manifest = r#"
+# Lock fees
+CALL_METHOD
+    Address("faucet")
+    "lock_fee"
+    Decimal("5000");
+
+
+TAKE_ALL_FROM_WORKTOP Address("xrd")   Bucket("xrd"); # the fees
+CALL_METHOD Address("account") "deposit_batch" Array<Bucket>(Bucket("xrd"), Bucket("other_bucket_with_a_long_name"), Bucket("yet_another_bucket"));
+CALL_METHOD Address("account")
+    # the bucket
+    "deposit" Bucket("xrd")
+;
+"#;
+        let expected = r#"# Lock fees
+CALL_METHOD Address("faucet") "lock_fee" Decimal("5000");
+
+TAKE_ALL_FROM_WORKTOP Address("xrd") Bucket("xrd"); # the fees
+CALL_METHOD
+    Address("account")
+    "deposit_batch"
+    Array<Bucket>(
+        Bucket("xrd"),
+        Bucket("other_bucket_with_a_long_name"),
+        Bucket("yet_another_bucket")
+    )
+;
+CALL_METHOD
+    Address("account")
+    # the bucket
+    "deposit"
+    Bucket("xrd")
+;
+"#;
+        assert_eq!(format(manifest), expected);
+        assert_eq!(format(expected), expected);
+    }
+
+    #[test]
+    fn test_comments_inside_values_are_kept() {
+        let manifest = r#"CALL_METHOD Address("component") "set" Map<String, Enum>("a" => Enum<Metadata::String>("x"), # first
+"b" => Enum<1u8>()) Tuple(
+    # nothing
+);"#;
+        let expected = r#"CALL_METHOD
+    Address("component")
+    "set"
+    Map<String, Enum>(
+        "a" => Enum<Metadata::String>("x"), # first
+        "b" => Enum<1u8>()
+    )
+    Tuple(
+        # nothing
+    )
+;
+"#;
+        assert_eq!(format(manifest), expected);
+        assert_eq!(format(expected), expected);
+    }
+
+    #[test]
+    fn test_names_can_be_normalized() {
+        let manifest = r#"TAKE_ALL_FROM_WORKTOP Address("a") Bucket("xrd");
+CREATE_PROOF_FROM_BUCKET_OF_ALL Bucket("xrd") Proof("xrd_proof");
+TAKE_ALL_FROM_WORKTOP Address("b") Bucket("other");
+RETURN_TO_WORKTOP Bucket("other");
+"#;
+        let expected = r#"TAKE_ALL_FROM_WORKTOP Address("a") Bucket("bucket1");
+CREATE_PROOF_FROM_BUCKET_OF_ALL Bucket("bucket1") Proof("proof1");
+TAKE_ALL_FROM_WORKTOP Address("b") Bucket("bucket2");
+RETURN_TO_WORKTOP Bucket("bucket2");
+"#;
+        let options = ManifestFormatterOptions {
+            normalize_names: true,
+            ..Default::default()
+        };
+        assert_eq!(format_manifest(manifest, &options).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_formatted_examples_compile_to_the_same_manifest() {
+        let examples = [
+            include_str!("../../examples/access_rule/access_rule.rtm"),
+            include_str!("../../examples/call/call_method.rtm"),
+            include_str!("../../examples/metadata/metadata.rtm"),
+            include_str!("../../examples/resources/auth_zone.rtm"),
+            include_str!("../../examples/resources/worktop.rtm"),
+            include_str!("../../examples/royalty/royalty.rtm"),
+            include_str!("../../examples/values/values.rtm"),
+        ];
+        let network = NetworkDefinition::simulator();
+        for example in examples {
+            let manifest = apply_address_replacements(example);
+            let formatted = format(&manifest);
+            assert_eq!(format(&formatted), formatted);
+            assert_eq!(
+                compile_manifest::<TransactionManifestV1>(
+                    &formatted,
+                    &network,
+                    MockBlobProvider::new()
+                )
+                .unwrap(),
+                compile_manifest::<TransactionManifestV1>(
+                    &manifest,
+                    &network,
+                    MockBlobProvider::new()
+                )
+                .unwrap()
+            );
+        }
+    }
+}
@@ -11,6 +11,7 @@ pub mod e2e;
 pub mod generator;
 pub mod lexer;
 mod manifest_enums;
+mod manifest_formatter;
 mod manifest_instruction_effects;
 mod manifest_instructions;
 mod manifest_naming;
@@ -26,6 +27,7 @@ pub use blob_provider::*;
 pub use compiler::*;
 pub use decompiler::{decompile, decompile_any, DecompileError};
 pub use manifest_enums::*;
+pub use manifest_formatter::*;
 pub use manifest_instruction_effects::*;
 pub use manifest_instructions::*;
 pub use manifest_naming::*;
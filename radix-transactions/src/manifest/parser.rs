@@ -1113,7 +1113,12 @@ pub fn parser_error_diagnostics(
     err: ParserError,
     style: CompileErrorDiagnosticsStyle,
 ) -> String {
-    let (title, label) = match err.error_kind {
+    let (title, label) = parser_error_title_and_label(err.error_kind);
+    create_snippet(s, &err.span, &title, &label, style)
+}
+
+pub fn parser_error_title_and_label(error_kind: ParserErrorKind) -> (String, String) {
+    match error_kind {
         ParserErrorKind::UnexpectedEof => (
             "unexpected end of file".to_string(),
             "unexpected end of file".to_string(),
@@ -1149,9 +1154,7 @@ pub fn parser_error_diagnostics(
             let title = format!("unknown enum discriminator found '{}'", actual);
             (title, "unknown enum discriminator".to_string())
         }
-    };
-
-    create_snippet(s, &err.span, &title, &label, style)
+    }
 }
 
 #[cfg(test)]
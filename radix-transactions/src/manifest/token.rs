@@ -123,3 +123,11 @@ pub struct TokenWithSpan {
     pub token: Token,
     pub span: Span,
 }
+
+/// A `#` comment, which the lexer skips but records for tools which keep them, such as the
+/// formatter. The text includes the leading `#` but not the line break.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentWithSpan {
+    pub comment: String,
+    pub span: Span,
+}
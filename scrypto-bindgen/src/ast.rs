@@ -7,15 +7,24 @@ use crate::token_stream_from_str;
 pub struct PackageStub {
     pub blueprints: Vec<BlueprintStub>,
     pub auxiliary_types: Vec<AuxiliaryType>,
+    pub blueprint_types: Vec<BlueprintTypesStub>,
 }
 
 impl ToTokens for PackageStub {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let blueprints = &self.blueprints;
-        let auxiliary_types = &self.auxiliary_types;
+        // The fields are only public when the types of the blueprints are bound, as the events and
+        // the state which are then decoded can refer to these types.
+        let public_fields = !self.blueprint_types.is_empty();
+        let auxiliary_types = self
+            .auxiliary_types
+            .iter()
+            .map(|auxiliary_type| auxiliary_type.to_token_stream_with_fields(public_fields));
+        let blueprint_types = &self.blueprint_types;
         quote! {
             #(#blueprints)*
             #(#auxiliary_types)*
+            #(#blueprint_types)*
         }
         .to_tokens(tokens)
     }
@@ -73,6 +82,162 @@ impl ToTokens for BlueprintStub {
     }
 }
 
+/// The types of the events, state and registered types of a blueprint, which are generated in a
+/// module of their own so that they don't clash with the stubs or with the types of other
+/// blueprints.
+pub struct BlueprintTypesStub {
+    pub blueprint_name: String,
+    /// The types of the events of the blueprint, by event name.
+    pub events: IndexMap<String, String>,
+    /// The types of the fields of the blueprint state.
+    pub state_fields: Vec<String>,
+    /// The key and value types of the collections of the blueprint state.
+    pub collections: Vec<(String, String)>,
+    /// The definitions of the types above, and of the types found within them.
+    pub auxiliary_types: Vec<AuxiliaryType>,
+}
+
+impl BlueprintTypesStub {
+    /// The name of the module the types are generated in, such as `radiswap_types`.
+    pub fn module_name(&self) -> String {
+        format!("{}_types", to_snake_case(&self.blueprint_name))
+    }
+
+    /// The aliases of the state and collection types, as pairs of the alias and the type.
+    pub fn state_aliases(&self) -> Vec<(String, String)> {
+        let mut aliases = Vec::new();
+        match self.state_fields.as_slice() {
+            [field] => aliases.push(("State".to_owned(), field.clone())),
+            fields => {
+                for (index, field) in fields.iter().enumerate() {
+                    aliases.push((format!("StateField{}", index), field.clone()));
+                }
+            }
+        }
+        for (index, (key, value)) in self.collections.iter().enumerate() {
+            aliases.push((format!("Collection{}Key", index), key.clone()));
+            aliases.push((format!("Collection{}Value", index), value.clone()));
+        }
+        aliases
+    }
+
+    /// The name of the variant of an event in the event enum.
+    pub fn event_variant_name(event_name: &str) -> String {
+        event_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+}
+
+impl ToTokens for BlueprintTypesStub {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let doc = format!(
+            " The events, state and registered types of the `{}` blueprint.",
+            self.blueprint_name
+        );
+        let module_name = Ident::new(&self.module_name(), Span::call_site());
+        let auxiliary_types = self
+            .auxiliary_types
+            .iter()
+            .map(|auxiliary_type| auxiliary_type.to_token_stream_with_fields(true));
+
+        let aliases = self.state_aliases().into_iter().map(|(alias, ty)| {
+            let alias = Ident::new(&alias, Span::call_site());
+            let ty = token_stream_from_str!(&ty);
+            quote! {
+                pub type #alias = #ty;
+            }
+        });
+
+        // The events are decoded by name, and the event types which are generated here can be
+        // used with the `LedgerSimulator` to find events of their type. A type shared by several
+        // events has no single event name, so it is only found through the `Event` enum.
+        let event_impls = self
+            .events
+            .iter()
+            .filter(|(_, ty)| {
+                self.auxiliary_types
+                    .iter()
+                    .any(|auxiliary_type| auxiliary_type.name() == ty.as_str())
+                    && self.events.values().filter(|other| other == ty).count() == 1
+            })
+            .map(|(event_name, ty)| {
+                let ty = token_stream_from_str!(ty);
+                quote! {
+                    impl ::scrypto::prelude::ScryptoEvent for #ty {
+                        const EVENT_NAME: &'static str = #event_name;
+                    }
+                }
+            });
+        let event_enum = if self.events.is_empty() {
+            quote! {}
+        } else {
+            let event_names = self.events.keys();
+            let variants = self
+                .events
+                .keys()
+                .map(|event_name| {
+                    Ident::new(&Self::event_variant_name(event_name), Span::call_site())
+                })
+                .collect::<Vec<_>>();
+            let types = self.events.values().map(|ty| token_stream_from_str!(ty));
+            quote! {
+                pub enum Event {
+                    #( #variants(#types) ),*
+                }
+
+                impl Event {
+                    /// Decodes the payload of an event of the blueprint, given the name of the
+                    /// event.
+                    pub fn decode(event_name: &str, payload: &[u8]) -> Option<Self> {
+                        match event_name {
+                            #(
+                                #event_names => ::scrypto::prelude::scrypto_decode(payload)
+                                    .ok()
+                                    .map(Self::#variants),
+                            )*
+                            _ => None,
+                        }
+                    }
+                }
+            }
+        };
+
+        quote! {
+            #[doc = #doc]
+            pub mod #module_name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #(#auxiliary_types)*
+                #(#aliases)*
+                #(#event_impls)*
+                #event_enum
+            }
+        }
+        .to_tokens(tokens)
+    }
+}
+
+/// Converts a `PascalCase` name to `snake_case`, keeping acronyms together, such as `KVStore` to
+/// `kv_store`.
+pub fn to_snake_case(name: &str) -> String {
+    let characters = name.chars().collect::<Vec<_>>();
+    let mut snake_case = String::with_capacity(name.len());
+    for (index, character) in characters.iter().enumerate() {
+        if character.is_uppercase() && index != 0 {
+            let previous = characters[index - 1];
+            let next = characters.get(index + 1);
+            if !previous.is_uppercase() || next.is_some_and(|next| next.is_lowercase()) {
+                snake_case.push('_');
+            }
+        }
+        snake_case.extend(character.to_lowercase());
+    }
+    snake_case
+}
+
 pub struct FnSignature {
     pub ident: syn::Ident,
     pub inputs: Vec<(syn::Ident, TokenStream)>,
@@ -135,8 +300,23 @@ pub enum AuxiliaryType {
     },
 }
 
-impl ToTokens for AuxiliaryType {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
+impl AuxiliaryType {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::TupleStruct { struct_name, .. } | Self::NamedFieldsStruct { struct_name, .. } => {
+                struct_name
+            }
+            Self::Enum { enum_name, .. } => enum_name,
+        }
+    }
+
+    /// The definition of the type, whose struct fields are public if `public_fields` is set.
+    pub fn to_token_stream_with_fields(&self, public_fields: bool) -> TokenStream {
+        let visibility = if public_fields {
+            quote! { pub }
+        } else {
+            quote! {}
+        };
         match self {
             Self::TupleStruct {
                 struct_name,
@@ -151,11 +331,10 @@ impl ToTokens for AuxiliaryType {
                     #[derive(::scrypto::prelude::ScryptoSbor)]
                     pub struct #struct_name(
                         #(
-                            #field_types
+                            #visibility #field_types
                         ),*
                     );
                 }
-                .to_tokens(tokens)
             }
             Self::NamedFieldsStruct {
                 struct_name,
@@ -169,11 +348,10 @@ impl ToTokens for AuxiliaryType {
                     #[derive(::scrypto::prelude::ScryptoSbor)]
                     pub struct #struct_name {
                         #(
-                            #field_names: #field_types
+                            #visibility #field_names: #field_types
                         ),*
                     }
                 }
-                .to_tokens(tokens)
             }
             Self::Enum {
                 enum_name,
@@ -189,12 +367,17 @@ impl ToTokens for AuxiliaryType {
                         ),*
                     }
                 }
-                .to_tokens(tokens)
             }
         }
     }
 }
 
+impl ToTokens for AuxiliaryType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.to_token_stream_with_fields(false).to_tokens(tokens)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EnumVariant {
     Unit {
@@ -268,3 +451,108 @@ impl ToTokens for EnumVariant {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blueprint_types(events: IndexMap<String, String>) -> BlueprintTypesStub {
+        BlueprintTypesStub {
+            blueprint_name: "Radiswap".to_owned(),
+            events,
+            state_fields: vec!["Radiswap".to_owned()],
+            collections: vec![("u32".to_owned(), "Vault".to_owned())],
+            auxiliary_types: vec![AuxiliaryType::TupleStruct {
+                struct_name: "SwapEvent".to_owned(),
+                field_types: vec!["Decimal".to_owned()],
+            }],
+        }
+    }
+
+    #[test]
+    fn converts_names_to_snake_case() {
+        assert_eq!(to_snake_case("Radiswap"), "radiswap");
+        assert_eq!(to_snake_case("HelloWorld"), "hello_world");
+        assert_eq!(to_snake_case("KVStore"), "kv_store");
+        assert_eq!(to_snake_case("NFTMarket2"), "nft_market2");
+        assert_eq!(to_snake_case("Collection0Key"), "collection0_key");
+    }
+
+    #[test]
+    fn aliases_the_state_and_collection_types() {
+        let mut stub = blueprint_types(IndexMap::default());
+        assert_eq!(
+            stub.state_aliases(),
+            vec![
+                ("State".to_owned(), "Radiswap".to_owned()),
+                ("Collection0Key".to_owned(), "u32".to_owned()),
+                ("Collection0Value".to_owned(), "Vault".to_owned()),
+            ]
+        );
+
+        stub.state_fields = vec!["Decimal".to_owned(), "Vault".to_owned()];
+        stub.collections.clear();
+        assert_eq!(
+            stub.state_aliases(),
+            vec![
+                ("StateField0".to_owned(), "Decimal".to_owned()),
+                ("StateField1".to_owned(), "Vault".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_events_by_name() {
+        let tokens = blueprint_types(indexmap!(
+            "SwapEvent".to_owned() => "SwapEvent".to_owned(),
+            "Other-Event".to_owned() => "u8".to_owned()
+        ))
+        .to_token_stream()
+        .to_string();
+
+        assert!(tokens.contains("pub mod radiswap_types"));
+        assert!(tokens.contains("pub struct SwapEvent (pub Decimal) ;"));
+        assert!(tokens.contains(
+            "impl :: scrypto :: prelude :: ScryptoEvent for SwapEvent { const EVENT_NAME : & 'static str = \"SwapEvent\" ; }"
+        ));
+        assert!(tokens.contains("pub enum Event { SwapEvent (SwapEvent) , Other_Event (u8) }"));
+        assert!(tokens.contains(
+            "\"Other-Event\" => :: scrypto :: prelude :: scrypto_decode (payload) . ok () . map (Self :: Other_Event) ,"
+        ));
+    }
+
+    #[test]
+    fn events_sharing_a_type_have_no_event_impl() {
+        let tokens = blueprint_types(indexmap!(
+            "BuyEvent".to_owned() => "SwapEvent".to_owned(),
+            "SellEvent".to_owned() => "SwapEvent".to_owned()
+        ))
+        .to_token_stream()
+        .to_string();
+
+        assert!(!tokens.contains("ScryptoEvent"));
+        assert!(tokens.contains("pub enum Event { BuyEvent (SwapEvent) , SellEvent (SwapEvent) }"));
+    }
+
+    #[test]
+    fn package_type_fields_are_only_public_with_blueprint_types() {
+        let mut package_stub = PackageStub {
+            blueprints: vec![],
+            auxiliary_types: vec![AuxiliaryType::NamedFieldsStruct {
+                struct_name: "Config".to_owned(),
+                fields: indexmap!("fee".to_owned() => "Decimal".to_owned()),
+            }],
+            blueprint_types: vec![],
+        };
+        assert!(package_stub
+            .to_token_stream()
+            .to_string()
+            .contains("pub struct Config { fee : Decimal }"));
+
+        package_stub.blueprint_types = vec![blueprint_types(IndexMap::default())];
+        assert!(package_stub
+            .to_token_stream()
+            .to_string()
+            .contains("pub struct Config { pub fee : Decimal }"));
+    }
+}
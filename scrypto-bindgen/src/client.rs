@@ -0,0 +1,391 @@
+//! The models shared by the backends which generate client bindings in other languages than Rust.
+//! The types in the `ast.rs` models are names of Rust types, which are mapped to the handful of
+//! types that a client needs to build a manifest or an SBOR JSON value.
+
+use super::ast;
+use radix_common::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientType {
+    Unit,
+    Bool,
+    /// An integer type, with the name of its value kind, such as `U8`.
+    Integer(&'static str),
+    String,
+    Decimal,
+    PreciseDecimal,
+    NonFungibleLocalId,
+    /// Any global address, including the typed ones such as `Global<Account>`.
+    Address,
+    InternalAddress,
+    Bucket,
+    Proof,
+    AddressReservation,
+    /// An owned object other than a bucket or a proof, which can't be passed through a manifest.
+    Own,
+    Array(Box<ClientType>),
+    Map(Box<ClientType>, Box<ClientType>),
+    Tuple(Vec<ClientType>),
+    Option(Box<ClientType>),
+    Result(Box<ClientType>, Box<ClientType>),
+    /// A struct among the auxiliary types in scope, by its path in the generated module.
+    Struct(String),
+    /// An enum among the auxiliary types in scope, by its path in the generated module.
+    Enum(String),
+    /// Any other type, which the client passes around as a value it has already encoded.
+    Any,
+}
+
+impl ClientType {
+    /// Maps the name of a Rust type, as found in the `ast.rs` models, to a client type.
+    pub fn from_rust_type(rust_type: &str, auxiliary_types: &AuxiliaryTypeScope) -> Self {
+        match syn::parse_str::<syn::Type>(rust_type) {
+            Ok(ty) => Self::from_syn_type(&ty, auxiliary_types),
+            Err(_) => Self::Any,
+        }
+    }
+
+    fn from_syn_type(ty: &syn::Type, auxiliary_types: &AuxiliaryTypeScope) -> Self {
+        let path = match ty {
+            syn::Type::Tuple(tuple) if tuple.elems.is_empty() => return Self::Unit,
+            syn::Type::Tuple(tuple) => {
+                return Self::Tuple(
+                    tuple
+                        .elems
+                        .iter()
+                        .map(|ty| Self::from_syn_type(ty, auxiliary_types))
+                        .collect(),
+                )
+            }
+            syn::Type::Paren(paren) => return Self::from_syn_type(&paren.elem, auxiliary_types),
+            syn::Type::Path(path) => path,
+            _ => return Self::Any,
+        };
+        let Some(segment) = path.path.segments.last() else {
+            return Self::Any;
+        };
+        let generic_types = match &segment.arguments {
+            syn::PathArguments::AngleBracketed(arguments) => arguments
+                .args
+                .iter()
+                .filter_map(|argument| match argument {
+                    syn::GenericArgument::Type(ty) => {
+                        Some(Self::from_syn_type(ty, auxiliary_types))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        match (segment.ident.to_string().as_str(), generic_types.as_slice()) {
+            ("bool", []) => Self::Bool,
+            ("i8", []) => Self::Integer("I8"),
+            ("i16", []) => Self::Integer("I16"),
+            ("i32", []) => Self::Integer("I32"),
+            ("i64", []) => Self::Integer("I64"),
+            ("i128", []) => Self::Integer("I128"),
+            ("u8", []) => Self::Integer("U8"),
+            ("u16", []) => Self::Integer("U16"),
+            ("u32", []) => Self::Integer("U32"),
+            ("u64", []) => Self::Integer("U64"),
+            ("u128", []) => Self::Integer("U128"),
+            ("String", []) => Self::String,
+            ("Decimal", []) => Self::Decimal,
+            ("PreciseDecimal", []) => Self::PreciseDecimal,
+            ("NonFungibleLocalId", []) => Self::NonFungibleLocalId,
+            ("GlobalAddress" | "ComponentAddress" | "ResourceAddress" | "PackageAddress", [])
+            | ("Reference", [])
+            | ("Global", [_]) => Self::Address,
+            ("InternalAddress", []) => Self::InternalAddress,
+            ("Bucket" | "FungibleBucket" | "NonFungibleBucket", []) => Self::Bucket,
+            ("Proof" | "FungibleProof" | "NonFungibleProof", []) => Self::Proof,
+            ("GlobalAddressReservation", []) => Self::AddressReservation,
+            ("Vault" | "FungibleVault" | "NonFungibleVault" | "Own", [])
+            | ("Own", [_])
+            | ("KeyValueStore", [_, _]) => Self::Own,
+            ("Vec", [element]) => Self::Array(Box::new(element.clone())),
+            ("IndexMap" | "HashMap" | "BTreeMap" | "NonIterMap", [key, value]) => {
+                Self::Map(Box::new(key.clone()), Box::new(value.clone()))
+            }
+            ("Option", [some]) => Self::Option(Box::new(some.clone())),
+            ("Result", [ok, err]) => Self::Result(Box::new(ok.clone()), Box::new(err.clone())),
+            (name, []) => match auxiliary_types.get(name) {
+                Some((AuxiliaryTypeKind::Struct, path)) => Self::Struct(path.clone()),
+                Some((AuxiliaryTypeKind::Enum, path)) => Self::Enum(path.clone()),
+                None => Self::Any,
+            },
+            _ => Self::Any,
+        }
+    }
+
+    /// The name of the manifest value kind of this type, as used in `Array<..>` and `Map<..>`.
+    pub fn manifest_value_kind(&self) -> &'static str {
+        match self {
+            Self::Unit | Self::Tuple(..) | Self::Struct(..) | Self::Any => "Tuple",
+            Self::Bool => "Bool",
+            Self::Integer(kind) => kind,
+            Self::String => "String",
+            Self::Decimal => "Decimal",
+            Self::PreciseDecimal => "PreciseDecimal",
+            Self::NonFungibleLocalId => "NonFungibleLocalId",
+            Self::Address | Self::InternalAddress | Self::Own => "Address",
+            Self::Bucket => "Bucket",
+            Self::Proof => "Proof",
+            Self::AddressReservation => "AddressReservation",
+            Self::Array(..) => "Array",
+            Self::Map(..) => "Map",
+            Self::Option(..) | Self::Result(..) | Self::Enum(..) => "Enum",
+        }
+    }
+
+    /// The name of the SBOR value kind of this type, as used in the programmatic SBOR JSON.
+    pub fn sbor_value_kind(&self) -> &'static str {
+        match self {
+            Self::Unit | Self::Tuple(..) | Self::Struct(..) | Self::Any => "Tuple",
+            Self::Bool => "Bool",
+            Self::Integer(kind) => kind,
+            Self::String => "String",
+            Self::Decimal => "Decimal",
+            Self::PreciseDecimal => "PreciseDecimal",
+            Self::NonFungibleLocalId => "NonFungibleLocalId",
+            Self::Address | Self::InternalAddress => "Reference",
+            Self::Bucket | Self::Proof | Self::AddressReservation | Self::Own => "Own",
+            Self::Array(..) => "Array",
+            Self::Map(..) => "Map",
+            Self::Option(..) | Self::Result(..) | Self::Enum(..) => "Enum",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuxiliaryTypeKind {
+    Struct,
+    Enum,
+}
+
+/// The auxiliary types which the types of a generated module can refer to, as their kind and their
+/// path in the module, by the name of their Rust type. The types of a blueprint are generated in a
+/// namespace, and their path is prefixed with it, such as `RadiswapTypes.SwapEvent`.
+pub type AuxiliaryTypeScope = IndexMap<String, (AuxiliaryTypeKind, String)>;
+
+/// Returns the auxiliary types for which client types are generated. The state structs of the
+/// blueprints are left out, as they are named after the blueprints themselves.
+pub fn client_auxiliary_types(package_stub: &ast::PackageStub) -> Vec<&ast::AuxiliaryType> {
+    package_stub
+        .auxiliary_types
+        .iter()
+        .filter(|auxiliary_type| {
+            !package_stub
+                .blueprints
+                .iter()
+                .any(|blueprint| blueprint.blueprint_name == auxiliary_type.name())
+        })
+        .collect()
+}
+
+/// Adds the given auxiliary types to a scope, within the given namespace if any.
+pub fn extend_auxiliary_type_scope<'a>(
+    scope: &mut AuxiliaryTypeScope,
+    auxiliary_types: impl IntoIterator<Item = &'a ast::AuxiliaryType>,
+    namespace: Option<&str>,
+) {
+    for auxiliary_type in auxiliary_types {
+        let kind = match auxiliary_type {
+            ast::AuxiliaryType::TupleStruct { .. }
+            | ast::AuxiliaryType::NamedFieldsStruct { .. } => AuxiliaryTypeKind::Struct,
+            ast::AuxiliaryType::Enum { .. } => AuxiliaryTypeKind::Enum,
+        };
+        let name = auxiliary_type.name();
+        let path = match namespace {
+            Some(namespace) => format!("{}.{}", namespace, name),
+            None => name.to_owned(),
+        };
+        scope.insert(name.to_owned(), (kind, path));
+    }
+}
+
+/// Prefixes the last segment of the path of an auxiliary type, such as `RadiswapTypes.SwapEvent`
+/// to `RadiswapTypes.manifestSwapEvent` for its manifest encoder.
+pub fn prefixed_path(path: &str, prefix: &str) -> String {
+    match path.rsplit_once('.') {
+        Some((namespace, name)) => format!("{}.{}{}", namespace, prefix, name),
+        None => format!("{}{}", prefix, path),
+    }
+}
+
+/// The name of the namespace the types of a blueprint are generated in.
+pub fn blueprint_types_namespace(blueprint_types: &ast::BlueprintTypesStub) -> String {
+    format!("{}Types", blueprint_types.blueprint_name)
+}
+
+/// Converts a `snake_case` name to `camelCase`.
+pub fn to_camel_case(name: &str) -> String {
+    let mut camel_case = String::with_capacity(name.len());
+    let mut capitalize_next = false;
+    for character in name.chars() {
+        if character == '_' && !camel_case.is_empty() {
+            capitalize_next = true;
+        } else if capitalize_next {
+            camel_case.extend(character.to_uppercase());
+            capitalize_next = false;
+        } else {
+            camel_case.push(character);
+        }
+    }
+    camel_case
+}
+
+/// Returns the inputs of a function signature as names and client types.
+pub fn client_inputs(
+    fn_signature: &ast::FnSignature,
+    auxiliary_types: &AuxiliaryTypeScope,
+) -> Vec<(String, ClientType)> {
+    fn_signature
+        .inputs
+        .iter()
+        .map(|(name, ty)| {
+            (
+                name.to_string(),
+                ClientType::from_rust_type(&ty.to_string(), auxiliary_types),
+            )
+        })
+        .collect()
+}
+
+pub fn client_output(
+    fn_signature: &ast::FnSignature,
+    auxiliary_types: &AuxiliaryTypeScope,
+) -> ClientType {
+    ClientType::from_rust_type(&fn_signature.output.to_string(), auxiliary_types)
+}
+
+/// A package covering the types the backends generate code for, whose generated modules are
+/// compared against the golden files of the backends.
+#[cfg(test)]
+pub(crate) fn example_package_stub() -> ast::PackageStub {
+    use crate::{ident, token_stream_from_str};
+
+    let fn_signature =
+        |name: &str, inputs: &[(&str, &str)], output: &str, fn_type| ast::FnSignature {
+            ident: ident!(name),
+            inputs: inputs
+                .iter()
+                .map(|(name, ty)| (ident!(name), token_stream_from_str!(ty)))
+                .collect(),
+            output: token_stream_from_str!(output),
+            fn_type,
+        };
+    let config = ast::AuxiliaryType::NamedFieldsStruct {
+        struct_name: "Config".to_owned(),
+        fields: indexmap!(
+            "fee".to_owned() => "Decimal".to_owned(),
+            "limit".to_owned() => "Option<Option<u32>>".to_owned(),
+            "owner".to_owned() => "Option<ResourceAddress>".to_owned()
+        ),
+    };
+    let color = ast::AuxiliaryType::Enum {
+        enum_name: "Color".to_owned(),
+        variants: vec![
+            ast::EnumVariant::Unit {
+                variant_name: "Red".to_owned(),
+                variant_index: 0,
+            },
+            ast::EnumVariant::Tuple {
+                variant_name: "Custom".to_owned(),
+                variant_index: 1,
+                field_types: vec!["u8".to_owned(), "u8".to_owned(), "u8".to_owned()],
+            },
+        ],
+    };
+    let painted_event = ast::AuxiliaryType::TupleStruct {
+        struct_name: "PaintedEvent".to_owned(),
+        field_types: vec!["Color".to_owned()],
+    };
+
+    ast::PackageStub {
+        blueprints: vec![ast::BlueprintStub {
+            blueprint_name: "Hello".to_owned(),
+            fn_signatures: vec![
+                fn_signature(
+                    "instantiate",
+                    &[("config", "Config")],
+                    "Global<Hello>",
+                    ast::FnType::Function,
+                ),
+                fn_signature(
+                    "paint",
+                    &[("bucket", "Bucket"), ("colors", "Vec<Color>")],
+                    "Option<Option<Bucket>>",
+                    ast::FnType::Method {
+                        is_mutable_receiver: true,
+                    },
+                ),
+                fn_signature(
+                    "config",
+                    &[],
+                    "Config",
+                    ast::FnType::Method {
+                        is_mutable_receiver: false,
+                    },
+                ),
+            ],
+            package_address: PackageAddress::new_or_panic(
+                [EntityType::GlobalPackage as u8; NodeId::LENGTH],
+            ),
+        }],
+        auxiliary_types: vec![config, color],
+        blueprint_types: vec![ast::BlueprintTypesStub {
+            blueprint_name: "Hello".to_owned(),
+            events: indexmap!("PaintedEvent".to_owned() => "PaintedEvent".to_owned()),
+            state_fields: vec!["Config".to_owned()],
+            collections: vec![],
+            auxiliary_types: vec![painted_event],
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_rust_types_to_client_types() {
+        let auxiliary_types = indexmap!(
+            "Config".to_owned() => (AuxiliaryTypeKind::Struct, "Config".to_owned()),
+            "Color".to_owned() => (AuxiliaryTypeKind::Enum, "HelloTypes.Color".to_owned())
+        );
+        let client_type = |rust_type| ClientType::from_rust_type(rust_type, &auxiliary_types);
+
+        assert_eq!(client_type("()"), ClientType::Unit);
+        assert_eq!(client_type("Global<Account>"), ClientType::Address);
+        assert_eq!(
+            client_type("Vec<Option<Decimal>>"),
+            ClientType::Array(Box::new(ClientType::Option(Box::new(ClientType::Decimal))))
+        );
+        assert_eq!(
+            client_type("IndexMap<u8, Color>"),
+            ClientType::Map(
+                Box::new(ClientType::Integer("U8")),
+                Box::new(ClientType::Enum("HelloTypes.Color".to_owned()))
+            )
+        );
+        assert_eq!(
+            client_type("(Config, Bucket,)"),
+            ClientType::Tuple(vec![
+                ClientType::Struct("Config".to_owned()),
+                ClientType::Bucket
+            ])
+        );
+        assert_eq!(client_type("Unknown"), ClientType::Any);
+        assert_eq!(
+            prefixed_path("HelloTypes.Color", "manifest"),
+            "HelloTypes.manifestColor"
+        );
+        assert_eq!(prefixed_path("Config", "sborJson"), "sborJsonConfig");
+        assert_eq!(to_camel_case("free_token"), "freeToken");
+        assert_eq!(ast::to_snake_case("KVStore"), "kv_store");
+        assert_eq!(ast::to_snake_case("Collection0Key"), "collection0_key");
+        assert_eq!(to_camel_case("_private"), "_private");
+    }
+}
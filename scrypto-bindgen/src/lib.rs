@@ -1,5 +1,8 @@
 pub mod ast;
+pub mod client;
 pub mod macros;
+pub mod python;
 pub mod schema;
 pub mod translation;
 pub mod types;
+pub mod typescript;
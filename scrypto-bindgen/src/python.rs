@@ -0,0 +1,807 @@
+//! Generates a Python module from the `ast.rs` models, with a class per blueprint whose functions
+//! and methods return manifest instructions, and the auxiliary types of the package as dataclasses
+//! which encode themselves to the manifest and SBOR JSON, and decode themselves from SBOR JSON. The
+//! events, state and registered types of a blueprint, when requested, are generated in a class
+//! which serves as their namespace.
+
+use super::ast;
+use super::client::*;
+use radix_common::prelude::*;
+use std::fmt::Write;
+
+const PRELUDE: &str = r#"# This file was generated by scrypto-bindgen, do not edit it by hand.
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, Generic, List, Optional, Tuple, TypeVar, Union
+
+Decimal = str
+PreciseDecimal = str
+NonFungibleLocalId = str
+Address = str
+Own = str
+# The names of buckets, proofs and address reservations in the manifest
+Bucket = str
+Proof = str
+AddressReservation = str
+# A value rendered in the manifest syntax, such as `Decimal("1")`
+ManifestValue = str
+# A value in the programmatic SBOR JSON format
+SborJson = Dict[str, Any]
+
+T = TypeVar("T")
+E = TypeVar("E")
+
+
+@dataclass
+class Ok(Generic[T]):
+    value: T
+
+
+@dataclass
+class Err(Generic[E]):
+    value: E
+
+
+Result = Union[Ok[T], Err[E]]
+
+
+@dataclass
+class Some(Generic[T]):
+    """The value of an option whose value can itself be None, such as an option of an option"""
+
+    value: T
+
+
+@dataclass
+class Instruction(Generic[T]):
+    """A manifest instruction calling a function or method which returns `T`"""
+
+    instruction: str
+
+
+# Values of the types which the bindings can't describe are passed already encoded.
+
+
+def _manifest_bool(value: bool) -> ManifestValue:
+    return "true" if value else "false"
+
+
+def _manifest_integer(suffix: str) -> Callable[[int], ManifestValue]:
+    return lambda value: f"{value}{suffix}"
+
+
+def _manifest_string(value: str) -> ManifestValue:
+    return json.dumps(value)
+
+
+def _manifest_custom(kind: str) -> Callable[[str], ManifestValue]:
+    return lambda value: f"{kind}({json.dumps(value)})"
+
+
+def _manifest_array(kind: str, element: Callable) -> Callable[[List], ManifestValue]:
+    return lambda value: f"Array<{kind}>(" + ", ".join(element(item) for item in value) + ")"
+
+
+def _manifest_map(key_kind: str, value_kind: str, key: Callable, entry: Callable) -> Callable[[Dict], ManifestValue]:
+    return lambda value: (
+        f"Map<{key_kind}, {value_kind}>(" + ", ".join(key(k) + " => " + entry(v) for k, v in value.items()) + ")"
+    )
+
+
+def _manifest_tuple(fields: List[Callable]) -> Callable[[Tuple], ManifestValue]:
+    return lambda value: "Tuple(" + ", ".join(field(item) for field, item in zip(fields, value)) + ")"
+
+
+def _manifest_option(some: Callable) -> Callable[[Optional[Any]], ManifestValue]:
+    return lambda value: "Enum<0u8>()" if value is None else f"Enum<1u8>({some(value)})"
+
+
+def _manifest_nested_option(some: Callable) -> Callable[[Optional[Some]], ManifestValue]:
+    return lambda value: "Enum<0u8>()" if value is None else f"Enum<1u8>({some(value.value)})"
+
+
+def _manifest_result(ok: Callable, err: Callable) -> Callable[[Result], ManifestValue]:
+    return lambda value: f"Enum<0u8>({ok(value.value)})" if isinstance(value, Ok) else f"Enum<1u8>({err(value.value)})"
+
+
+def _manifest_object(value: Any) -> ManifestValue:
+    return value.to_manifest()
+
+
+def _manifest_raw(value: Any) -> ManifestValue:
+    return str(value)
+
+
+def _sbor_json_bool(value: bool) -> SborJson:
+    return {"kind": "Bool", "value": value}
+
+
+def _sbor_json_value(kind: str) -> Callable[[Any], SborJson]:
+    return lambda value: {"kind": kind, "value": str(value)}
+
+
+def _sbor_json_array(kind: str, element: Callable) -> Callable[[List], SborJson]:
+    return lambda value: {"kind": "Array", "element_kind": kind, "elements": [element(item) for item in value]}
+
+
+def _sbor_json_map(key_kind: str, value_kind: str, key: Callable, entry: Callable) -> Callable[[Dict], SborJson]:
+    return lambda value: {
+        "kind": "Map",
+        "key_kind": key_kind,
+        "value_kind": value_kind,
+        "entries": [{"key": key(k), "value": entry(v)} for k, v in value.items()],
+    }
+
+
+def _sbor_json_tuple(fields: List[Callable]) -> Callable[[Tuple], SborJson]:
+    return lambda value: {"kind": "Tuple", "fields": [field(item) for field, item in zip(fields, value)]}
+
+
+def _sbor_json_option(some: Callable) -> Callable[[Optional[Any]], SborJson]:
+    return lambda value: (
+        {"kind": "Enum", "variant_id": "0", "fields": []}
+        if value is None
+        else {"kind": "Enum", "variant_id": "1", "fields": [some(value)]}
+    )
+
+
+def _sbor_json_nested_option(some: Callable) -> Callable[[Optional[Some]], SborJson]:
+    return lambda value: (
+        {"kind": "Enum", "variant_id": "0", "fields": []}
+        if value is None
+        else {"kind": "Enum", "variant_id": "1", "fields": [some(value.value)]}
+    )
+
+
+def _sbor_json_result(ok: Callable, err: Callable) -> Callable[[Result], SborJson]:
+    return lambda value: (
+        {"kind": "Enum", "variant_id": "0", "fields": [ok(value.value)]}
+        if isinstance(value, Ok)
+        else {"kind": "Enum", "variant_id": "1", "fields": [err(value.value)]}
+    )
+
+
+def _sbor_json_object(value: Any) -> SborJson:
+    return value.to_sbor_json()
+
+
+def _sbor_json_raw(value: Any) -> SborJson:
+    return value
+
+
+def _field(value: SborJson, index: int) -> SborJson:
+    return value["fields"][index]
+
+
+def _from_sbor_json_unit(value: SborJson) -> None:
+    return None
+
+
+def _from_sbor_json_value(value: SborJson) -> Any:
+    return value["value"]
+
+
+def _from_sbor_json_int(value: SborJson) -> int:
+    return int(value["value"])
+
+
+def _from_sbor_json_array(element: Callable) -> Callable[[SborJson], List]:
+    return lambda value: [element(item) for item in value["elements"]]
+
+
+def _from_sbor_json_map(key: Callable, entry: Callable) -> Callable[[SborJson], Dict]:
+    return lambda value: {key(item["key"]): entry(item["value"]) for item in value["entries"]}
+
+
+def _from_sbor_json_tuple(fields: List[Callable]) -> Callable[[SborJson], Tuple]:
+    return lambda value: tuple(field(item) for field, item in zip(fields, value["fields"]))
+
+
+def _from_sbor_json_option(some: Callable) -> Callable[[SborJson], Optional[Any]]:
+    return lambda value: None if value["variant_id"] == "0" else some(_field(value, 0))
+
+
+def _from_sbor_json_nested_option(some: Callable) -> Callable[[SborJson], Optional[Some]]:
+    return lambda value: None if value["variant_id"] == "0" else Some(some(_field(value, 0)))
+
+
+def _from_sbor_json_result(ok: Callable, err: Callable) -> Callable[[SborJson], Result]:
+    return lambda value: Ok(ok(_field(value, 0))) if value["variant_id"] == "0" else Err(err(_field(value, 0)))
+
+
+def _from_sbor_json_raw(value: SborJson) -> Any:
+    return value
+
+
+def _call_function(blueprint_name: str, function_name: str, args: List[ManifestValue]) -> Instruction:
+    lines = [f'Address("{PACKAGE_ADDRESS}")', json.dumps(blueprint_name), json.dumps(function_name), *args]
+    return Instruction("CALL_FUNCTION\n" + "".join(f"    {line}\n" for line in lines) + ";")
+
+
+def _call_method(address: Address, method_name: str, args: List[ManifestValue]) -> Instruction:
+    lines = [f'Address("{address}")', json.dumps(method_name), *args]
+    return Instruction("CALL_METHOD\n" + "".join(f"    {line}\n" for line in lines) + ";")
+"#;
+
+const RESERVED_WORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Generates the Python module of a package, whose functions are called on the package at the
+/// given address.
+pub fn package_stub_to_python(package_stub: &ast::PackageStub, package_address: &str) -> String {
+    let client_auxiliary_types = client_auxiliary_types(package_stub);
+    let mut auxiliary_types = AuxiliaryTypeScope::default();
+    extend_auxiliary_type_scope(
+        &mut auxiliary_types,
+        client_auxiliary_types.iter().copied(),
+        None,
+    );
+
+    let mut module = PRELUDE.to_owned();
+    writeln!(
+        module,
+        "\n\nPACKAGE_ADDRESS: Address = {:?}",
+        package_address
+    )
+    .unwrap();
+    for auxiliary_type in client_auxiliary_types {
+        module.push_str("\n\n");
+        module.push_str(&auxiliary_type_to_python(auxiliary_type, &auxiliary_types));
+    }
+    for blueprint in &package_stub.blueprints {
+        module.push_str("\n\n");
+        module.push_str(&blueprint_stub_to_python(blueprint, &auxiliary_types));
+    }
+    for blueprint_types in &package_stub.blueprint_types {
+        module.push_str("\n\n");
+        module.push_str(&blueprint_types_to_python(
+            blueprint_types,
+            &auxiliary_types,
+        ));
+    }
+    module
+}
+
+fn blueprint_types_to_python(
+    blueprint_types: &ast::BlueprintTypesStub,
+    package_auxiliary_types: &AuxiliaryTypeScope,
+) -> String {
+    let namespace = blueprint_types_namespace(blueprint_types);
+    let mut auxiliary_types = package_auxiliary_types.clone();
+    extend_auxiliary_type_scope(
+        &mut auxiliary_types,
+        &blueprint_types.auxiliary_types,
+        Some(&namespace),
+    );
+    let client_type = |rust_type: &String| ClientType::from_rust_type(rust_type, &auxiliary_types);
+
+    let mut members = Vec::new();
+    for auxiliary_type in &blueprint_types.auxiliary_types {
+        members.push(auxiliary_type_to_python(auxiliary_type, &auxiliary_types));
+    }
+    for (alias, ty) in blueprint_types.state_aliases() {
+        let ty = client_type(&ty);
+        members.push(format!(
+            "@staticmethod\ndef decode_{}(value: SborJson) -> {}:\n    return {}(value)\n",
+            ast::to_snake_case(&alias),
+            python_type(&ty),
+            sbor_json_decoder(&ty)
+        ));
+    }
+    if !blueprint_types.events.is_empty() {
+        let decoders = blueprint_types
+            .events
+            .iter()
+            .map(|(event_name, ty)| {
+                format!(
+                    "        {:?}: {},\n",
+                    event_name,
+                    sbor_json_decoder(&client_type(ty))
+                )
+            })
+            .collect::<String>();
+        members.push(format!(
+            "@staticmethod\ndef decode_event(event_name: str, payload: SborJson) -> Optional[Any]:\n    \"\"\"Decodes the SBOR JSON payload of an event of the blueprint, given the name of the event\"\"\"\n    decoders = {{\n{}    }}\n    decoder = decoders.get(event_name)\n    return decoder(payload) if decoder else None\n",
+            decoders
+        ));
+    }
+
+    let members = members
+        .join("\n")
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_owned()
+            } else {
+                format!("    {}\n", line)
+            }
+        })
+        .collect::<String>();
+    format!(
+        "class {}:\n    \"\"\"The events, state and registered types of the `{}` blueprint\"\"\"\n\n{}",
+        namespace, blueprint_types.blueprint_name, members
+    )
+}
+
+fn blueprint_stub_to_python(
+    blueprint: &ast::BlueprintStub,
+    auxiliary_types: &AuxiliaryTypeScope,
+) -> String {
+    let name = &blueprint.blueprint_name;
+    let mut functions = String::new();
+    let mut methods = String::new();
+    for fn_signature in &blueprint.fn_signatures {
+        let ident = fn_signature.ident.to_string();
+        let inputs = client_inputs(fn_signature, auxiliary_types);
+        let parameters = inputs
+            .iter()
+            .map(|(input, ty)| format!("{}: {}", field_name(input), python_type(ty)))
+            .collect::<Vec<_>>();
+        let arguments = inputs
+            .iter()
+            .map(|(input, ty)| format!("{}({})", manifest_encoder(ty), field_name(input)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let output = python_type(&client_output(fn_signature, auxiliary_types));
+
+        match fn_signature.fn_type {
+            ast::FnType::Function => writeln!(
+                functions,
+                "\n    @staticmethod\n    def {}({}) -> Instruction[{}]:\n        return _call_function({:?}, {:?}, [{}])",
+                ident,
+                parameters.join(", "),
+                output,
+                name,
+                ident,
+                arguments
+            ),
+            ast::FnType::Method { .. } => writeln!(
+                methods,
+                "\n    def {}({}) -> Instruction[{}]:\n        return _call_method(self.address, {:?}, [{}])",
+                ident,
+                ["self".to_owned()]
+                    .into_iter()
+                    .chain(parameters)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                output,
+                ident,
+                arguments
+            ),
+        }
+        .unwrap();
+    }
+
+    format!(
+        "class {name}Functions:\n    \"\"\"The functions of the `{name}` blueprint\"\"\"\n{functions}\
+         \n\n\
+         class {name}:\n    \"\"\"A component of the `{name}` blueprint\"\"\"\n\
+         \n    def __init__(self, address: Address):\n        self.address = address\n{methods}",
+    )
+}
+
+fn auxiliary_type_to_python(
+    auxiliary_type: &ast::AuxiliaryType,
+    auxiliary_types: &AuxiliaryTypeScope,
+) -> String {
+    let client_type = |rust_type: &String| ClientType::from_rust_type(rust_type, auxiliary_types);
+
+    match auxiliary_type {
+        ast::AuxiliaryType::TupleStruct {
+            struct_name,
+            field_types,
+        } => {
+            let field_types = field_types.iter().map(client_type).collect::<Vec<_>>();
+            let names = (0..field_types.len())
+                .map(|index| format!("field_{}", index))
+                .collect::<Vec<_>>();
+            dataclass(
+                struct_name,
+                None,
+                &names,
+                &field_types,
+                "Tuple()",
+                &format!("{{\"kind\": \"Tuple\", \"type_name\": {:?}", struct_name),
+                false,
+            )
+        }
+        ast::AuxiliaryType::NamedFieldsStruct {
+            struct_name,
+            fields,
+        } => {
+            let names = fields
+                .keys()
+                .map(|name| field_name(name))
+                .collect::<Vec<_>>();
+            let field_types = fields.values().map(client_type).collect::<Vec<_>>();
+            dataclass(
+                struct_name,
+                None,
+                &names,
+                &field_types,
+                "Tuple()",
+                &format!("{{\"kind\": \"Tuple\", \"type_name\": {:?}", struct_name),
+                true,
+            )
+        }
+        ast::AuxiliaryType::Enum {
+            enum_name,
+            variants,
+        } => {
+            // The variants are looked up when decoding, by their path in the module.
+            let enum_path = auxiliary_types
+                .get(enum_name)
+                .map(|(_, path)| path.clone())
+                .unwrap_or_else(|| enum_name.clone());
+            let variant_classes = variants
+                .iter()
+                .map(|variant| {
+                    let (variant_name, variant_index) = match variant {
+                        ast::EnumVariant::Unit {
+                            variant_name,
+                            variant_index,
+                        }
+                        | ast::EnumVariant::Tuple {
+                            variant_name,
+                            variant_index,
+                            ..
+                        }
+                        | ast::EnumVariant::NamedFields {
+                            variant_name,
+                            variant_index,
+                            ..
+                        } => (variant_name, variant_index),
+                    };
+                    format!("\"{}\": {}{}", variant_index, enum_path, variant_name)
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let mut module = format!(
+                "class {}:\n    \"\"\"The base class of the variants of the `{}` enum\"\"\"\n\n    def to_manifest(self) -> ManifestValue:\n        raise NotImplementedError\n\n    def to_sbor_json(self) -> SborJson:\n        raise NotImplementedError\n\n    @staticmethod\n    def from_sbor_json(value: SborJson) -> {}:\n        variants = {{{}}}\n        return variants[value[\"variant_id\"]].from_sbor_json(value)\n",
+                enum_name, enum_name, enum_name, variant_classes
+            );
+            for variant in variants {
+                let (variant_name, variant_index, names, field_types, named) = match variant {
+                    ast::EnumVariant::Unit {
+                        variant_name,
+                        variant_index,
+                    } => (variant_name, variant_index, vec![], vec![], false),
+                    ast::EnumVariant::Tuple {
+                        variant_name,
+                        variant_index,
+                        field_types,
+                    } => (
+                        variant_name,
+                        variant_index,
+                        (0..field_types.len())
+                            .map(|index| format!("field_{}", index))
+                            .collect(),
+                        field_types.iter().map(client_type).collect(),
+                        false,
+                    ),
+                    ast::EnumVariant::NamedFields {
+                        variant_name,
+                        variant_index,
+                        fields,
+                    } => (
+                        variant_name,
+                        variant_index,
+                        fields.keys().map(|name| field_name(name)).collect(),
+                        fields.values().map(client_type).collect(),
+                        true,
+                    ),
+                };
+                module.push_str("\n\n");
+                module.push_str(&dataclass(
+                    &format!("{}{}", enum_name, variant_name),
+                    Some(enum_name),
+                    &names,
+                    &field_types,
+                    &format!("Enum<{}u8>()", variant_index),
+                    &format!(
+                        "{{\"kind\": \"Enum\", \"type_name\": {:?}, \"variant_id\": \"{}\", \"variant_name\": {:?}",
+                        enum_name, variant_index, variant_name
+                    ),
+                    named,
+                ));
+            }
+            module
+        }
+    }
+}
+
+/// Renders a dataclass with the given fields, which encodes itself into the given manifest value,
+/// whose fields go between the parentheses, and into the given SBOR JSON object, which is opened
+/// but not closed.
+fn dataclass(
+    class_name: &str,
+    base_class: Option<&str>,
+    names: &[String],
+    field_types: &[ClientType],
+    manifest_value: &str,
+    sbor_json_object: &str,
+    named_fields: bool,
+) -> String {
+    let mut class = match base_class {
+        Some(base_class) => format!("@dataclass\nclass {}({}):\n", class_name, base_class),
+        None => format!("@dataclass\nclass {}:\n", class_name),
+    };
+    for (name, ty) in names.iter().zip(field_types) {
+        writeln!(class, "    {}: {}", name, python_type(ty)).unwrap();
+    }
+    if !names.is_empty() {
+        class.push('\n');
+    }
+
+    let manifest_fields = names
+        .iter()
+        .zip(field_types)
+        .map(|(name, ty)| format!("{}(self.{})", manifest_encoder(ty), name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let (manifest_prefix, _) = manifest_value.split_at(manifest_value.len() - 1);
+    let sbor_json_fields = names
+        .iter()
+        .zip(field_types)
+        .map(|(name, ty)| {
+            let encoded = format!("{}(self.{})", sbor_json_encoder(ty), name);
+            if named_fields {
+                format!(
+                    "{{\"field_name\": {:?}, **{}}}",
+                    rust_field_name(name),
+                    encoded
+                )
+            } else {
+                encoded
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let decoded_fields = field_types
+        .iter()
+        .enumerate()
+        .map(|(index, ty)| format!("{}(_field(value, {}))", sbor_json_decoder(ty), index))
+        .collect::<Vec<_>>()
+        .join(", ");
+    write!(
+        class,
+        "    def to_manifest(self) -> ManifestValue:\n        return {:?} + \", \".join([{}]) + \")\"\n\n    def to_sbor_json(self) -> SborJson:\n        return {}, \"fields\": [{}]}}\n\n    @classmethod\n    def from_sbor_json(cls, value: SborJson) -> {}:\n        return cls({})\n",
+        manifest_prefix, manifest_fields, sbor_json_object, sbor_json_fields, class_name, decoded_fields
+    )
+    .unwrap();
+    class
+}
+
+fn field_name(name: &str) -> String {
+    if RESERVED_WORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_owned()
+    }
+}
+
+/// Reverts the renaming of fields which are named after reserved words.
+fn rust_field_name(name: &str) -> &str {
+    match name.strip_suffix('_') {
+        Some(stripped) if RESERVED_WORDS.contains(&stripped) => stripped,
+        _ => name,
+    }
+}
+
+/// Whether `None` is a value of the type, in which case an option of the type wraps its value in
+/// `Some`, as `None` would otherwise stand for both `None` and `Some(None)`.
+fn is_nullable(client_type: &ClientType) -> bool {
+    matches!(
+        client_type,
+        ClientType::Option(_) | ClientType::Unit | ClientType::Any
+    )
+}
+
+fn python_type(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Unit => "None".to_owned(),
+        ClientType::Bool => "bool".to_owned(),
+        ClientType::Integer(_) => "int".to_owned(),
+        ClientType::String => "str".to_owned(),
+        ClientType::Decimal => "Decimal".to_owned(),
+        ClientType::PreciseDecimal => "PreciseDecimal".to_owned(),
+        ClientType::NonFungibleLocalId => "NonFungibleLocalId".to_owned(),
+        ClientType::Address | ClientType::InternalAddress => "Address".to_owned(),
+        ClientType::Bucket => "Bucket".to_owned(),
+        ClientType::Proof => "Proof".to_owned(),
+        ClientType::AddressReservation => "AddressReservation".to_owned(),
+        ClientType::Own => "Own".to_owned(),
+        ClientType::Array(element) => format!("List[{}]", python_type(element)),
+        ClientType::Map(key, value) => {
+            format!("Dict[{}, {}]", python_type(key), python_type(value))
+        }
+        ClientType::Tuple(fields) => format!(
+            "Tuple[{}]",
+            fields
+                .iter()
+                .map(python_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some) if is_nullable(some) => {
+            format!("Optional[Some[{}]]", python_type(some))
+        }
+        ClientType::Option(some) => format!("Optional[{}]", python_type(some)),
+        ClientType::Result(ok, err) => {
+            format!("Result[{}, {}]", python_type(ok), python_type(err))
+        }
+        ClientType::Struct(path) | ClientType::Enum(path) => path.clone(),
+        ClientType::Any => "Any".to_owned(),
+    }
+}
+
+fn manifest_encoder(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Unit => "_manifest_tuple([])".to_owned(),
+        ClientType::Bool => "_manifest_bool".to_owned(),
+        ClientType::Integer(kind) => format!("_manifest_integer(\"{}\")", kind.to_lowercase()),
+        ClientType::String => "_manifest_string".to_owned(),
+        ClientType::Decimal
+        | ClientType::PreciseDecimal
+        | ClientType::NonFungibleLocalId
+        | ClientType::Address
+        | ClientType::InternalAddress
+        | ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation => {
+            format!(
+                "_manifest_custom(\"{}\")",
+                client_type.manifest_value_kind()
+            )
+        }
+        ClientType::Own | ClientType::Any => "_manifest_raw".to_owned(),
+        ClientType::Array(element) => format!(
+            "_manifest_array(\"{}\", {})",
+            element.manifest_value_kind(),
+            manifest_encoder(element)
+        ),
+        ClientType::Map(key, value) => format!(
+            "_manifest_map(\"{}\", \"{}\", {}, {})",
+            key.manifest_value_kind(),
+            value.manifest_value_kind(),
+            manifest_encoder(key),
+            manifest_encoder(value)
+        ),
+        ClientType::Tuple(fields) => format!(
+            "_manifest_tuple([{}])",
+            fields
+                .iter()
+                .map(manifest_encoder)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some) if is_nullable(some) => {
+            format!("_manifest_nested_option({})", manifest_encoder(some))
+        }
+        ClientType::Option(some) => format!("_manifest_option({})", manifest_encoder(some)),
+        ClientType::Result(ok, err) => format!(
+            "_manifest_result({}, {})",
+            manifest_encoder(ok),
+            manifest_encoder(err)
+        ),
+        ClientType::Struct(_) | ClientType::Enum(_) => "_manifest_object".to_owned(),
+    }
+}
+
+fn sbor_json_encoder(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Unit => "_sbor_json_tuple([])".to_owned(),
+        ClientType::Bool => "_sbor_json_bool".to_owned(),
+        ClientType::Integer(..)
+        | ClientType::String
+        | ClientType::Decimal
+        | ClientType::PreciseDecimal
+        | ClientType::NonFungibleLocalId
+        | ClientType::Address
+        | ClientType::InternalAddress
+        | ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation
+        | ClientType::Own => format!("_sbor_json_value(\"{}\")", client_type.sbor_value_kind()),
+        ClientType::Any => "_sbor_json_raw".to_owned(),
+        ClientType::Array(element) => format!(
+            "_sbor_json_array(\"{}\", {})",
+            element.sbor_value_kind(),
+            sbor_json_encoder(element)
+        ),
+        ClientType::Map(key, value) => format!(
+            "_sbor_json_map(\"{}\", \"{}\", {}, {})",
+            key.sbor_value_kind(),
+            value.sbor_value_kind(),
+            sbor_json_encoder(key),
+            sbor_json_encoder(value)
+        ),
+        ClientType::Tuple(fields) => format!(
+            "_sbor_json_tuple([{}])",
+            fields
+                .iter()
+                .map(sbor_json_encoder)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some) if is_nullable(some) => {
+            format!("_sbor_json_nested_option({})", sbor_json_encoder(some))
+        }
+        ClientType::Option(some) => format!("_sbor_json_option({})", sbor_json_encoder(some)),
+        ClientType::Result(ok, err) => format!(
+            "_sbor_json_result({}, {})",
+            sbor_json_encoder(ok),
+            sbor_json_encoder(err)
+        ),
+        ClientType::Struct(_) | ClientType::Enum(_) => "_sbor_json_object".to_owned(),
+    }
+}
+
+fn sbor_json_decoder(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Unit => "_from_sbor_json_unit".to_owned(),
+        ClientType::Integer(_) => "_from_sbor_json_int".to_owned(),
+        ClientType::Bool
+        | ClientType::String
+        | ClientType::Decimal
+        | ClientType::PreciseDecimal
+        | ClientType::NonFungibleLocalId
+        | ClientType::Address
+        | ClientType::InternalAddress
+        | ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation
+        | ClientType::Own => "_from_sbor_json_value".to_owned(),
+        ClientType::Any => "_from_sbor_json_raw".to_owned(),
+        ClientType::Array(element) => {
+            format!("_from_sbor_json_array({})", sbor_json_decoder(element))
+        }
+        ClientType::Map(key, value) => format!(
+            "_from_sbor_json_map({}, {})",
+            sbor_json_decoder(key),
+            sbor_json_decoder(value)
+        ),
+        ClientType::Tuple(fields) => format!(
+            "_from_sbor_json_tuple([{}])",
+            fields
+                .iter()
+                .map(sbor_json_decoder)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some) if is_nullable(some) => {
+            format!("_from_sbor_json_nested_option({})", sbor_json_decoder(some))
+        }
+        ClientType::Option(some) => {
+            format!("_from_sbor_json_option({})", sbor_json_decoder(some))
+        }
+        ClientType::Result(ok, err) => format!(
+            "_from_sbor_json_result({}, {})",
+            sbor_json_decoder(ok),
+            sbor_json_decoder(err)
+        ),
+        ClientType::Struct(path) | ClientType::Enum(path) => format!("{}.from_sbor_json", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_the_golden_python_module() {
+        let module = package_stub_to_python(
+            &example_package_stub(),
+            "package_sim1pkgxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+        );
+
+        assert_eq!(module, include_str!("../tests/golden/hello.py"));
+    }
+}
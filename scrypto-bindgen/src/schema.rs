@@ -10,17 +10,41 @@ pub trait PackageSchemaResolver {
     fn resolve_type_kind(
         &self,
         type_identifier: &ScopedTypeId,
-    ) -> Result<LocalTypeKind<ScryptoCustomSchema>, SchemaError>;
+    ) -> Result<LocalTypeKind<ScryptoCustomSchema>, SchemaError> {
+        self.lookup_schema(&type_identifier.0)
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .as_latest_version()
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .resolve_type_kind(type_identifier.1)
+            .ok_or(SchemaError::NonExistentLocalTypeIndex(type_identifier.1))
+            .cloned()
+    }
 
     fn resolve_type_metadata(
         &self,
         type_identifier: &ScopedTypeId,
-    ) -> Result<TypeMetadata, SchemaError>;
+    ) -> Result<TypeMetadata, SchemaError> {
+        self.lookup_schema(&type_identifier.0)
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .as_latest_version()
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .resolve_type_metadata(type_identifier.1)
+            .ok_or(SchemaError::NonExistentLocalTypeIndex(type_identifier.1))
+            .cloned()
+    }
 
     fn resolve_type_validation(
         &self,
         type_identifier: &ScopedTypeId,
-    ) -> Result<TypeValidation<ScryptoCustomTypeValidation>, SchemaError>;
+    ) -> Result<TypeValidation<ScryptoCustomTypeValidation>, SchemaError> {
+        self.lookup_schema(&type_identifier.0)
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .as_latest_version()
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .resolve_type_validation(type_identifier.1)
+            .ok_or(SchemaError::NonExistentLocalTypeIndex(type_identifier.1))
+            .cloned()
+    }
 
     fn package_address(&self) -> PackageAddress;
 }
@@ -37,19 +61,42 @@ where
     for (blueprint_key, blueprint_definition) in package_definition.into_iter() {
         let blueprint_name = blueprint_key.blueprint;
 
+        let blueprint_interface = package_interface
+            .blueprints
+            .entry(blueprint_name)
+            .or_default();
+
         if let Some((_, fields)) = blueprint_definition.interface.state.fields {
             for field in fields {
                 if let BlueprintPayloadDef::Static(scoped_type_id) = field.field {
                     package_interface.auxiliary_types.insert(scoped_type_id);
+                    blueprint_interface.state_fields.push(scoped_type_id);
                 }
             }
         }
 
-        let functions = &mut package_interface
-            .blueprints
-            .entry(blueprint_name)
-            .or_default()
-            .functions;
+        // The events, collections and registered types only use the schema of the blueprint, the
+        // generic ones are left out.
+        for (_, collection) in blueprint_definition.interface.state.collections {
+            let (BlueprintCollectionSchema::KeyValueStore(schema)
+            | BlueprintCollectionSchema::Index(schema)
+            | BlueprintCollectionSchema::SortedIndex(schema)) = collection;
+            if let (BlueprintPayloadDef::Static(key), BlueprintPayloadDef::Static(value)) =
+                (schema.key, schema.value)
+            {
+                blueprint_interface.collections.push((key, value));
+            }
+        }
+        for (event_name, event) in blueprint_definition.interface.events {
+            if let BlueprintPayloadDef::Static(scoped_type_id) = event {
+                blueprint_interface
+                    .events
+                    .insert(event_name, scoped_type_id);
+            }
+        }
+        blueprint_interface.registered_types = blueprint_definition.interface.types;
+
+        let functions = &mut blueprint_interface.functions;
 
         for (function_name, function_schema) in blueprint_definition.interface.functions {
             let BlueprintPayloadDef::Static(input_type_identifier) = &function_schema.input else {
@@ -147,6 +194,44 @@ pub struct PackageInterface {
 pub struct BlueprintInterface {
     /// The functions and methods encountered in the blueprint interface.
     pub functions: Vec<Function>,
+    /// The events emitted by the blueprint. The key is the event name and the value is the type of
+    /// the event.
+    pub events: IndexMap<String, ScopedTypeId>,
+    /// The types of the fields of the blueprint state.
+    pub state_fields: Vec<ScopedTypeId>,
+    /// The key and value types of the collections of the blueprint state.
+    pub collections: Vec<(ScopedTypeId, ScopedTypeId)>,
+    /// The types registered by the blueprint, such as the data of its non-fungibles or the keys and
+    /// values of its key-value stores. The key is the name the type is registered under.
+    pub registered_types: IndexMap<String, ScopedTypeId>,
+}
+
+impl BlueprintInterface {
+    /// Returns the types of the events, state and registered types of the blueprint, along with the
+    /// types found within them.
+    pub fn type_definitions<S>(
+        &self,
+        schema_resolver: &S,
+    ) -> Result<HashSet<ScopedTypeId>, SchemaError>
+    where
+        S: PackageSchemaResolver,
+    {
+        let mut type_definitions = HashSet::default();
+        for type_id in self
+            .events
+            .values()
+            .chain(self.state_fields.iter())
+            .chain(
+                self.collections
+                    .iter()
+                    .flat_map(|(key, value)| [key, value]),
+            )
+            .chain(self.registered_types.values())
+        {
+            get_scoped_type_ids_in_path(type_id, schema_resolver, &mut type_definitions)?;
+        }
+        Ok(type_definitions)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -46,9 +46,56 @@ where
             schema_interface.auxiliary_types,
             schema_resolver,
         )?,
+        blueprint_types: Vec::new(),
     })
 }
 
+/// Translates the events, state and registered types of the blueprints of the package, which are
+/// only generated on request.
+pub fn package_schema_interface_to_blueprint_types<S>(
+    schema_interface: &schema::PackageInterface,
+    schema_resolver: &S,
+) -> Result<Vec<ast::BlueprintTypesStub>, schema::SchemaError>
+where
+    S: schema::PackageSchemaResolver,
+{
+    schema_interface
+        .blueprints
+        .iter()
+        .map(|(blueprint_name, blueprint_interface)| {
+            Ok(ast::BlueprintTypesStub {
+                blueprint_name: blueprint_name.clone(),
+                events: blueprint_interface
+                    .events
+                    .iter()
+                    .map(|(event_name, type_id)| {
+                        Ok((event_name.clone(), type_name(type_id, schema_resolver)?))
+                    })
+                    .collect::<Result<_, _>>()?,
+                state_fields: blueprint_interface
+                    .state_fields
+                    .iter()
+                    .map(|type_id| type_name(type_id, schema_resolver))
+                    .collect::<Result<_, _>>()?,
+                collections: blueprint_interface
+                    .collections
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            type_name(key, schema_resolver)?,
+                            type_name(value, schema_resolver)?,
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+                auxiliary_types: schema_auxiliary_types_to_ast_types(
+                    blueprint_interface.type_definitions(schema_resolver)?,
+                    schema_resolver,
+                )?,
+            })
+        })
+        .collect()
+}
+
 pub fn blueprint_schema_interface_to_ast_interface<S>(
     schema_interface: schema::BlueprintInterface,
     package_address: PackageAddress,
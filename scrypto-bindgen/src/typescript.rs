@@ -0,0 +1,778 @@
+//! Generates a TypeScript module from the `ast.rs` models, with a class per blueprint whose
+//! functions and methods return manifest instructions, and the auxiliary types of the package
+//! along with their manifest and SBOR JSON encoders and their SBOR JSON decoders. The events, state
+//! and registered types of a blueprint, when requested, are generated in a namespace of their own.
+
+use super::ast;
+use super::client::*;
+use radix_common::prelude::*;
+use std::fmt::Write;
+
+const PRELUDE: &str = r#"// This file was generated by scrypto-bindgen, do not edit it by hand.
+
+export type Decimal = string;
+export type PreciseDecimal = string;
+export type NonFungibleLocalId = string;
+export type Address = string;
+export type Own = string;
+/** The name of a bucket in the manifest */
+export type Bucket = string;
+/** The name of a proof in the manifest */
+export type Proof = string;
+/** The name of an address reservation in the manifest */
+export type AddressReservation = string;
+/** A value rendered in the manifest syntax, such as `Decimal("1")` */
+export type ManifestValue = string;
+/** A value in the programmatic SBOR JSON format */
+export type SborJson = { kind: string; [key: string]: unknown };
+export type Result<T, E> = { ok: T } | { err: E };
+/** The value of an option whose value can itself be null, such as an option of an option */
+export type Some<T> = { some: T };
+
+/** A manifest instruction calling a function or method which returns `Output` */
+export interface Instruction<Output> {
+  readonly instruction: string;
+  readonly output?: Output;
+}
+
+type Encoder<T, E> = (value: T) => E;
+export type Decoder<T> = (value: SborJson) => T;
+
+// Values of the types which the bindings can't describe are passed already encoded.
+const manifest = {
+  bool: (value: boolean): ManifestValue => `${value}`,
+  integer: (suffix: string) => (value: number | bigint): ManifestValue => `${value}${suffix}`,
+  string: (value: string): ManifestValue => JSON.stringify(value),
+  custom: (kind: string) => (value: string): ManifestValue => `${kind}(${JSON.stringify(value)})`,
+  array: <T>(kind: string, element: Encoder<T, ManifestValue>) => (value: T[]): ManifestValue =>
+    `Array<${kind}>(${value.map(element).join(", ")})`,
+  map: <K, V>(keyKind: string, valueKind: string, key: Encoder<K, ManifestValue>, entry: Encoder<V, ManifestValue>) =>
+    (value: Map<K, V>): ManifestValue =>
+      `Map<${keyKind}, ${valueKind}>(${[...value].map(([k, v]) => `${key(k)} => ${entry(v)}`).join(", ")})`,
+  tuple: (fields: Encoder<any, ManifestValue>[]) => (value: any[]): ManifestValue =>
+    `Tuple(${value.map((field, index) => fields[index](field)).join(", ")})`,
+  option: <T>(some: Encoder<T, ManifestValue>) => (value: T | null): ManifestValue =>
+    value === null ? "Enum<0u8>()" : `Enum<1u8>(${some(value)})`,
+  nestedOption: <T>(some: Encoder<T, ManifestValue>) => (value: Some<T> | null): ManifestValue =>
+    value === null ? "Enum<0u8>()" : `Enum<1u8>(${some(value.some)})`,
+  result: <T, E>(ok: Encoder<T, ManifestValue>, err: Encoder<E, ManifestValue>) => (value: Result<T, E>): ManifestValue =>
+    "ok" in value ? `Enum<0u8>(${ok(value.ok)})` : `Enum<1u8>(${err(value.err)})`,
+  raw: (value: unknown): ManifestValue => String(value),
+};
+
+const sborJson = {
+  bool: (value: boolean): SborJson => ({ kind: "Bool", value }),
+  value: (kind: string) => (value: number | bigint | string): SborJson => ({ kind, value: `${value}` }),
+  array: <T>(kind: string, element: Encoder<T, SborJson>) => (value: T[]): SborJson =>
+    ({ kind: "Array", element_kind: kind, elements: value.map(element) }),
+  map: <K, V>(keyKind: string, valueKind: string, key: Encoder<K, SborJson>, entry: Encoder<V, SborJson>) =>
+    (value: Map<K, V>): SborJson =>
+      ({ kind: "Map", key_kind: keyKind, value_kind: valueKind, entries: [...value].map(([k, v]) => ({ key: key(k), value: entry(v) })) }),
+  tuple: (fields: Encoder<any, SborJson>[]) => (value: any[]): SborJson =>
+    ({ kind: "Tuple", fields: value.map((field, index) => fields[index](field)) }),
+  option: <T>(some: Encoder<T, SborJson>) => (value: T | null): SborJson =>
+    value === null ? { kind: "Enum", variant_id: "0", fields: [] } : { kind: "Enum", variant_id: "1", fields: [some(value)] },
+  nestedOption: <T>(some: Encoder<T, SborJson>) => (value: Some<T> | null): SborJson =>
+    value === null ? { kind: "Enum", variant_id: "0", fields: [] } : { kind: "Enum", variant_id: "1", fields: [some(value.some)] },
+  result: <T, E>(ok: Encoder<T, SborJson>, err: Encoder<E, SborJson>) => (value: Result<T, E>): SborJson =>
+    "ok" in value ? { kind: "Enum", variant_id: "0", fields: [ok(value.ok)] } : { kind: "Enum", variant_id: "1", fields: [err(value.err)] },
+  raw: (value: unknown): SborJson => value as SborJson,
+};
+
+const field = (value: SborJson, index: number): SborJson => (value.fields as SborJson[])[index];
+
+const fromSborJson = {
+  bool: (value: SborJson): boolean => value.value as boolean,
+  number: (value: SborJson): number => Number(value.value),
+  bigint: (value: SborJson): bigint => BigInt(value.value as string),
+  string: (value: SborJson): string => value.value as string,
+  array: <T>(element: Decoder<T>) => (value: SborJson): T[] => (value.elements as SborJson[]).map(element),
+  map: <K, V>(key: Decoder<K>, entry: Decoder<V>) => (value: SborJson): Map<K, V> =>
+    new Map((value.entries as { key: SborJson; value: SborJson }[]).map((e) => [key(e.key), entry(e.value)])),
+  tuple: <T extends unknown[]>(fields: Decoder<unknown>[]) => (value: SborJson): T =>
+    (value.fields as SborJson[]).map((item, index) => fields[index](item)) as T,
+  option: <T>(some: Decoder<T>) => (value: SborJson): T | null =>
+    value.variant_id === "0" ? null : some(field(value, 0)),
+  nestedOption: <T>(some: Decoder<T>) => (value: SborJson): Some<T> | null =>
+    value.variant_id === "0" ? null : { some: some(field(value, 0)) },
+  result: <T, E>(ok: Decoder<T>, err: Decoder<E>) => (value: SborJson): Result<T, E> =>
+    value.variant_id === "0" ? { ok: ok(field(value, 0)) } : { err: err(field(value, 0)) },
+  raw: (value: SborJson): unknown => value,
+};
+
+function callFunction<Output>(blueprintName: string, functionName: string, args: ManifestValue[]): Instruction<Output> {
+  const lines = [`Address("${PACKAGE_ADDRESS}")`, JSON.stringify(blueprintName), JSON.stringify(functionName), ...args];
+  return { instruction: `CALL_FUNCTION\n${lines.map((line) => `    ${line}\n`).join("")};` };
+}
+
+function callMethod<Output>(address: Address, methodName: string, args: ManifestValue[]): Instruction<Output> {
+  const lines = [`Address("${address}")`, JSON.stringify(methodName), ...args];
+  return { instruction: `CALL_METHOD\n${lines.map((line) => `    ${line}\n`).join("")};` };
+}
+"#;
+
+const RESERVED_WORDS: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+    "let",
+    "static",
+    "implements",
+    "interface",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "await",
+];
+
+/// Generates the TypeScript module of a package, whose functions are called on the package at the
+/// given address.
+pub fn package_stub_to_typescript(
+    package_stub: &ast::PackageStub,
+    package_address: &str,
+) -> String {
+    let client_auxiliary_types = client_auxiliary_types(package_stub);
+    let mut auxiliary_types = AuxiliaryTypeScope::default();
+    extend_auxiliary_type_scope(
+        &mut auxiliary_types,
+        client_auxiliary_types.iter().copied(),
+        None,
+    );
+
+    let mut module = PRELUDE.to_owned();
+    writeln!(
+        module,
+        "\nexport const PACKAGE_ADDRESS: Address = {:?};",
+        package_address
+    )
+    .unwrap();
+    for auxiliary_type in client_auxiliary_types {
+        module.push('\n');
+        module.push_str(&auxiliary_type_to_typescript(
+            auxiliary_type,
+            &auxiliary_types,
+        ));
+    }
+    for blueprint in &package_stub.blueprints {
+        module.push('\n');
+        module.push_str(&blueprint_stub_to_typescript(blueprint, &auxiliary_types));
+    }
+    for blueprint_types in &package_stub.blueprint_types {
+        module.push('\n');
+        module.push_str(&blueprint_types_to_typescript(
+            blueprint_types,
+            &auxiliary_types,
+        ));
+    }
+    module
+}
+
+fn blueprint_types_to_typescript(
+    blueprint_types: &ast::BlueprintTypesStub,
+    package_auxiliary_types: &AuxiliaryTypeScope,
+) -> String {
+    let namespace = blueprint_types_namespace(blueprint_types);
+    let mut auxiliary_types = package_auxiliary_types.clone();
+    extend_auxiliary_type_scope(
+        &mut auxiliary_types,
+        &blueprint_types.auxiliary_types,
+        Some(&namespace),
+    );
+    let client_type = |rust_type: &String| ClientType::from_rust_type(rust_type, &auxiliary_types);
+
+    let mut members = Vec::new();
+    for auxiliary_type in &blueprint_types.auxiliary_types {
+        members.push(auxiliary_type_to_typescript(
+            auxiliary_type,
+            &auxiliary_types,
+        ));
+    }
+    for (alias, ty) in blueprint_types.state_aliases() {
+        let ty = client_type(&ty);
+        members.push(format!(
+            "export type {alias} = {ty};\nexport const decode{alias}: Decoder<{alias}> = {decoder};\n",
+            ty = typescript_type(&ty),
+            decoder = sbor_json_decoder(&ty),
+        ));
+    }
+    if !blueprint_types.events.is_empty() {
+        let mut variants = Vec::new();
+        let mut cases = String::new();
+        for (event_name, ty) in &blueprint_types.events {
+            let ty = client_type(ty);
+            variants.push(format!(
+                "{{ name: {:?}; value: {} }}",
+                event_name,
+                typescript_type(&ty)
+            ));
+            writeln!(
+                cases,
+                "    case {:?}:\n      return {{ name: {:?}, value: {}(payload) }};",
+                event_name,
+                event_name,
+                sbor_json_decoder(&ty)
+            )
+            .unwrap();
+        }
+        members.push(format!(
+            "export type Event =\n  | {variants};\n\n\
+             /** Decodes the SBOR JSON payload of an event of the blueprint, given the name of the event */\n\
+             export function decodeEvent(eventName: string, payload: SborJson): Event | null {{\n  switch (eventName) {{\n{cases}    default:\n      return null;\n  }}\n}}\n",
+            variants = variants.join("\n  | "),
+        ));
+    }
+
+    let members = members
+        .join("\n")
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_owned()
+            } else {
+                format!("  {}\n", line)
+            }
+        })
+        .collect::<String>();
+    format!(
+        "/** The events, state and registered types of the `{}` blueprint */\n\
+         export namespace {} {{\n{}}}\n",
+        blueprint_types.blueprint_name, namespace, members
+    )
+}
+
+fn blueprint_stub_to_typescript(
+    blueprint: &ast::BlueprintStub,
+    auxiliary_types: &AuxiliaryTypeScope,
+) -> String {
+    let name = &blueprint.blueprint_name;
+    let mut functions = String::new();
+    let mut methods = String::new();
+    for fn_signature in &blueprint.fn_signatures {
+        let ident = fn_signature.ident.to_string();
+        let inputs = client_inputs(fn_signature, auxiliary_types);
+        let parameters = inputs
+            .iter()
+            .map(|(input, ty)| format!("{}: {}", parameter_name(input), typescript_type(ty)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let arguments = inputs
+            .iter()
+            .map(|(input, ty)| format!("{}({})", manifest_encoder(ty), parameter_name(input)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let output = match client_output(fn_signature, auxiliary_types) {
+            ClientType::Unit => "void".to_owned(),
+            output => typescript_type(&output),
+        };
+
+        match fn_signature.fn_type {
+            ast::FnType::Function => writeln!(
+                functions,
+                "  {}({}): Instruction<{}> {{\n    return callFunction({:?}, {:?}, [{}]);\n  }},",
+                to_camel_case(&ident),
+                parameters,
+                output,
+                name,
+                ident,
+                arguments
+            ),
+            ast::FnType::Method { .. } => writeln!(
+                methods,
+                "\n  {}({}): Instruction<{}> {{\n    return callMethod(this.address, {:?}, [{}]);\n  }}",
+                to_camel_case(&ident),
+                parameters,
+                output,
+                ident,
+                arguments
+            ),
+        }
+        .unwrap();
+    }
+
+    format!(
+        "/** The functions of the `{name}` blueprint */\n\
+         export const {name}Functions = {{\n{functions}}};\n\
+         \n\
+         /** A component of the `{name}` blueprint */\n\
+         export class {name} {{\n  constructor(readonly address: Address) {{}}\n{methods}}}\n",
+    )
+}
+
+fn auxiliary_type_to_typescript(
+    auxiliary_type: &ast::AuxiliaryType,
+    auxiliary_types: &AuxiliaryTypeScope,
+) -> String {
+    let client_type = |rust_type: &String| ClientType::from_rust_type(rust_type, auxiliary_types);
+
+    match auxiliary_type {
+        ast::AuxiliaryType::TupleStruct {
+            struct_name,
+            field_types,
+        } => {
+            let field_types = field_types.iter().map(client_type).collect::<Vec<_>>();
+            let fields = (0..field_types.len())
+                .map(|index| format!("value[{}]", index))
+                .collect::<Vec<_>>();
+            format!(
+                "export type {name} = [{types}];\n\
+                 \n\
+                 export function manifest{name}(value: {name}): ManifestValue {{\n  return `Tuple(${{[{manifest}].join(\", \")}})`;\n}}\n\
+                 \n\
+                 export function sborJson{name}(value: {name}): SborJson {{\n  return {{ kind: \"Tuple\", type_name: {name:?}, fields: [{sbor_json}] }};\n}}\n\
+                 \n\
+                 export function fromSborJson{name}(value: SborJson): {name} {{\n  return [{decoded}];\n}}\n",
+                name = struct_name,
+                types = field_types.iter().map(typescript_type).collect::<Vec<_>>().join(", "),
+                decoded = decoded_fields(&field_types).join(", "),
+                manifest = encoded_fields(&field_types, &fields, None, manifest_encoder),
+                sbor_json = encoded_fields(&field_types, &fields, None, sbor_json_encoder),
+            )
+        }
+        ast::AuxiliaryType::NamedFieldsStruct {
+            struct_name,
+            fields,
+        } => {
+            let names = fields.keys().cloned().collect::<Vec<_>>();
+            let field_types = fields.values().map(client_type).collect::<Vec<_>>();
+            let values = names
+                .iter()
+                .map(|name| format!("value.{}", name))
+                .collect::<Vec<_>>();
+            format!(
+                "export interface {name} {{\n{members}}}\n\
+                 \n\
+                 export function manifest{name}(value: {name}): ManifestValue {{\n  return `Tuple(${{[{manifest}].join(\", \")}})`;\n}}\n\
+                 \n\
+                 export function sborJson{name}(value: {name}): SborJson {{\n  return {{ kind: \"Tuple\", type_name: {name:?}, fields: [{sbor_json}] }};\n}}\n\
+                 \n\
+                 export function fromSborJson{name}(value: SborJson): {name} {{\n  return {{ {decoded} }};\n}}\n",
+                name = struct_name,
+                decoded = named_decoded_fields(&names, &field_types),
+                members = names
+                    .iter()
+                    .zip(&field_types)
+                    .map(|(name, ty)| format!("  {}: {};\n", name, typescript_type(ty)))
+                    .collect::<String>(),
+                manifest = encoded_fields(&field_types, &values, None, manifest_encoder),
+                sbor_json = encoded_fields(&field_types, &values, Some(&names), sbor_json_encoder),
+            )
+        }
+        ast::AuxiliaryType::Enum {
+            enum_name,
+            variants,
+        } => {
+            let mut variant_types = Vec::new();
+            let mut manifest_cases = String::new();
+            let mut sbor_json_cases = String::new();
+            let mut decoder_cases = String::new();
+            for variant in variants {
+                let (variant_name, variant_index, fields_type, field_names, field_types) =
+                    match variant {
+                        ast::EnumVariant::Unit {
+                            variant_name,
+                            variant_index,
+                        } => (variant_name, variant_index, None, None, vec![]),
+                        ast::EnumVariant::Tuple {
+                            variant_name,
+                            variant_index,
+                            field_types,
+                        } => {
+                            let field_types =
+                                field_types.iter().map(client_type).collect::<Vec<_>>();
+                            let fields_type = format!(
+                                "[{}]",
+                                field_types
+                                    .iter()
+                                    .map(typescript_type)
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            );
+                            (
+                                variant_name,
+                                variant_index,
+                                Some(fields_type),
+                                None,
+                                field_types,
+                            )
+                        }
+                        ast::EnumVariant::NamedFields {
+                            variant_name,
+                            variant_index,
+                            fields,
+                        } => {
+                            let names = fields.keys().cloned().collect::<Vec<_>>();
+                            let field_types = fields.values().map(client_type).collect::<Vec<_>>();
+                            let fields_type = format!(
+                                "{{ {} }}",
+                                names
+                                    .iter()
+                                    .zip(&field_types)
+                                    .map(|(name, ty)| format!("{}: {}", name, typescript_type(ty)))
+                                    .collect::<Vec<_>>()
+                                    .join("; ")
+                            );
+                            (
+                                variant_name,
+                                variant_index,
+                                Some(fields_type),
+                                Some(names),
+                                field_types,
+                            )
+                        }
+                    };
+
+                variant_types.push(match fields_type {
+                    Some(fields_type) => {
+                        format!("{{ variant: {:?}; fields: {} }}", variant_name, fields_type)
+                    }
+                    None => format!("{{ variant: {:?} }}", variant_name),
+                });
+                let values = match &field_names {
+                    Some(names) => names
+                        .iter()
+                        .map(|name| format!("value.fields.{}", name))
+                        .collect::<Vec<_>>(),
+                    None => (0..field_types.len())
+                        .map(|index| format!("value.fields[{}]", index))
+                        .collect(),
+                };
+                writeln!(
+                    manifest_cases,
+                    "    case {:?}:\n      return `Enum<{}u8>(${{[{}].join(\", \")}})`;",
+                    variant_name,
+                    variant_index,
+                    encoded_fields(&field_types, &values, None, manifest_encoder)
+                )
+                .unwrap();
+                writeln!(
+                    sbor_json_cases,
+                    "    case {:?}:\n      return {{ kind: \"Enum\", type_name: {:?}, variant_id: \"{}\", variant_name: {:?}, fields: [{}] }};",
+                    variant_name,
+                    enum_name,
+                    variant_index,
+                    variant_name,
+                    encoded_fields(&field_types, &values, field_names.as_ref(), sbor_json_encoder)
+                )
+                .unwrap();
+                let decoded = match &field_names {
+                    Some(names) => format!(
+                        ", fields: {{ {} }}",
+                        named_decoded_fields(names, &field_types)
+                    ),
+                    None if field_types.is_empty() => String::new(),
+                    None => format!(", fields: [{}]", decoded_fields(&field_types).join(", ")),
+                };
+                writeln!(
+                    decoder_cases,
+                    "    case \"{}\":\n      return {{ variant: {:?}{} }};",
+                    variant_index, variant_name, decoded
+                )
+                .unwrap();
+            }
+
+            format!(
+                "export type {name} =\n  | {variants};\n\
+                 \n\
+                 export function manifest{name}(value: {name}): ManifestValue {{\n  switch (value.variant) {{\n{manifest}  }}\n}}\n\
+                 \n\
+                 export function sborJson{name}(value: {name}): SborJson {{\n  switch (value.variant) {{\n{sbor_json}  }}\n}}\n\
+                 \n\
+                 export function fromSborJson{name}(value: SborJson): {name} {{\n  switch (value.variant_id) {{\n{decoders}    default:\n      throw new Error(`Unknown variant ${{value.variant_id}} of {name}`);\n  }}\n}}\n",
+                name = enum_name,
+                decoders = decoder_cases,
+                variants = variant_types.join("\n  | "),
+                manifest = manifest_cases,
+                sbor_json = sbor_json_cases,
+            )
+        }
+    }
+}
+
+/// Renders the encoding of each of the given values, adding the field names to the SBOR JSON of
+/// named fields.
+fn encoded_fields(
+    field_types: &[ClientType],
+    values: &[String],
+    field_names: Option<&Vec<String>>,
+    encoder: fn(&ClientType) -> String,
+) -> String {
+    field_types
+        .iter()
+        .zip(values)
+        .enumerate()
+        .map(|(index, (ty, value))| {
+            let encoded = format!("{}({})", encoder(ty), value);
+            match field_names {
+                Some(names) => format!("{{ field_name: {:?}, ...{} }}", names[index], encoded),
+                None => encoded,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Renders the decoding of each of the fields of an SBOR JSON value.
+fn decoded_fields(field_types: &[ClientType]) -> Vec<String> {
+    field_types
+        .iter()
+        .enumerate()
+        .map(|(index, ty)| format!("{}(field(value, {}))", sbor_json_decoder(ty), index))
+        .collect()
+}
+
+fn named_decoded_fields(names: &[String], field_types: &[ClientType]) -> String {
+    names
+        .iter()
+        .zip(decoded_fields(field_types))
+        .map(|(name, decoded)| format!("{}: {}", name, decoded))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parameter_name(name: &str) -> String {
+    let name = to_camel_case(name);
+    if RESERVED_WORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// Whether `null` is a value of the type, in which case an option of the type wraps its value in
+/// `Some`, as `null` would otherwise stand for both `None` and `Some(null)`.
+fn is_nullable(client_type: &ClientType) -> bool {
+    matches!(client_type, ClientType::Option(_) | ClientType::Any)
+}
+
+fn typescript_type(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Unit => "[]".to_owned(),
+        ClientType::Bool => "boolean".to_owned(),
+        ClientType::Integer("I64" | "I128" | "U64" | "U128") => "bigint".to_owned(),
+        ClientType::Integer(_) => "number".to_owned(),
+        ClientType::String => "string".to_owned(),
+        ClientType::Decimal => "Decimal".to_owned(),
+        ClientType::PreciseDecimal => "PreciseDecimal".to_owned(),
+        ClientType::NonFungibleLocalId => "NonFungibleLocalId".to_owned(),
+        ClientType::Address | ClientType::InternalAddress => "Address".to_owned(),
+        ClientType::Bucket => "Bucket".to_owned(),
+        ClientType::Proof => "Proof".to_owned(),
+        ClientType::AddressReservation => "AddressReservation".to_owned(),
+        ClientType::Own => "Own".to_owned(),
+        ClientType::Array(element) => format!("Array<{}>", typescript_type(element)),
+        ClientType::Map(key, value) => {
+            format!("Map<{}, {}>", typescript_type(key), typescript_type(value))
+        }
+        ClientType::Tuple(fields) => format!(
+            "[{}]",
+            fields
+                .iter()
+                .map(typescript_type)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some) if is_nullable(some) => {
+            format!("Some<{}> | null", typescript_type(some))
+        }
+        ClientType::Option(some) => format!("{} | null", typescript_type(some)),
+        ClientType::Result(ok, err) => {
+            format!("Result<{}, {}>", typescript_type(ok), typescript_type(err))
+        }
+        ClientType::Struct(path) | ClientType::Enum(path) => path.clone(),
+        ClientType::Any => "unknown".to_owned(),
+    }
+}
+
+fn manifest_encoder(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Unit => "manifest.tuple([])".to_owned(),
+        ClientType::Bool => "manifest.bool".to_owned(),
+        ClientType::Integer(kind) => format!("manifest.integer(\"{}\")", kind.to_lowercase()),
+        ClientType::String => "manifest.string".to_owned(),
+        ClientType::Decimal
+        | ClientType::PreciseDecimal
+        | ClientType::NonFungibleLocalId
+        | ClientType::Address
+        | ClientType::InternalAddress
+        | ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation => {
+            format!("manifest.custom(\"{}\")", client_type.manifest_value_kind())
+        }
+        ClientType::Own | ClientType::Any => "manifest.raw".to_owned(),
+        ClientType::Array(element) => format!(
+            "manifest.array(\"{}\", {})",
+            element.manifest_value_kind(),
+            manifest_encoder(element)
+        ),
+        ClientType::Map(key, value) => format!(
+            "manifest.map(\"{}\", \"{}\", {}, {})",
+            key.manifest_value_kind(),
+            value.manifest_value_kind(),
+            manifest_encoder(key),
+            manifest_encoder(value)
+        ),
+        ClientType::Tuple(fields) => format!(
+            "manifest.tuple([{}])",
+            fields
+                .iter()
+                .map(manifest_encoder)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some) if is_nullable(some) => {
+            format!("manifest.nestedOption({})", manifest_encoder(some))
+        }
+        ClientType::Option(some) => format!("manifest.option({})", manifest_encoder(some)),
+        ClientType::Result(ok, err) => format!(
+            "manifest.result({}, {})",
+            manifest_encoder(ok),
+            manifest_encoder(err)
+        ),
+        ClientType::Struct(path) | ClientType::Enum(path) => prefixed_path(path, "manifest"),
+    }
+}
+
+fn sbor_json_encoder(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Unit => "sborJson.tuple([])".to_owned(),
+        ClientType::Bool => "sborJson.bool".to_owned(),
+        ClientType::Integer(..)
+        | ClientType::String
+        | ClientType::Decimal
+        | ClientType::PreciseDecimal
+        | ClientType::NonFungibleLocalId
+        | ClientType::Address
+        | ClientType::InternalAddress
+        | ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation
+        | ClientType::Own => format!("sborJson.value(\"{}\")", client_type.sbor_value_kind()),
+        ClientType::Any => "sborJson.raw".to_owned(),
+        ClientType::Array(element) => format!(
+            "sborJson.array(\"{}\", {})",
+            element.sbor_value_kind(),
+            sbor_json_encoder(element)
+        ),
+        ClientType::Map(key, value) => format!(
+            "sborJson.map(\"{}\", \"{}\", {}, {})",
+            key.sbor_value_kind(),
+            value.sbor_value_kind(),
+            sbor_json_encoder(key),
+            sbor_json_encoder(value)
+        ),
+        ClientType::Tuple(fields) => format!(
+            "sborJson.tuple([{}])",
+            fields
+                .iter()
+                .map(sbor_json_encoder)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some) if is_nullable(some) => {
+            format!("sborJson.nestedOption({})", sbor_json_encoder(some))
+        }
+        ClientType::Option(some) => format!("sborJson.option({})", sbor_json_encoder(some)),
+        ClientType::Result(ok, err) => format!(
+            "sborJson.result({}, {})",
+            sbor_json_encoder(ok),
+            sbor_json_encoder(err)
+        ),
+        ClientType::Struct(path) | ClientType::Enum(path) => prefixed_path(path, "sborJson"),
+    }
+}
+
+fn sbor_json_decoder(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Unit => "fromSborJson.tuple<[]>([])".to_owned(),
+        ClientType::Bool => "fromSborJson.bool".to_owned(),
+        ClientType::Integer("I64" | "I128" | "U64" | "U128") => "fromSborJson.bigint".to_owned(),
+        ClientType::Integer(_) => "fromSborJson.number".to_owned(),
+        ClientType::String
+        | ClientType::Decimal
+        | ClientType::PreciseDecimal
+        | ClientType::NonFungibleLocalId
+        | ClientType::Address
+        | ClientType::InternalAddress
+        | ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation
+        | ClientType::Own => "fromSborJson.string".to_owned(),
+        ClientType::Any => "fromSborJson.raw".to_owned(),
+        ClientType::Array(element) => format!("fromSborJson.array({})", sbor_json_decoder(element)),
+        ClientType::Map(key, value) => format!(
+            "fromSborJson.map({}, {})",
+            sbor_json_decoder(key),
+            sbor_json_decoder(value)
+        ),
+        ClientType::Tuple(fields) => format!(
+            "fromSborJson.tuple<{}>([{}])",
+            typescript_type(client_type),
+            fields
+                .iter()
+                .map(sbor_json_decoder)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some) if is_nullable(some) => {
+            format!("fromSborJson.nestedOption({})", sbor_json_decoder(some))
+        }
+        ClientType::Option(some) => format!("fromSborJson.option({})", sbor_json_decoder(some)),
+        ClientType::Result(ok, err) => format!(
+            "fromSborJson.result({}, {})",
+            sbor_json_decoder(ok),
+            sbor_json_decoder(err)
+        ),
+        ClientType::Struct(path) | ClientType::Enum(path) => prefixed_path(path, "fromSborJson"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_the_golden_typescript_module() {
+        let module = package_stub_to_typescript(
+            &example_package_stub(),
+            "package_sim1pkgxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+        );
+
+        assert_eq!(module, include_str!("../tests/golden/hello.ts"));
+    }
+}
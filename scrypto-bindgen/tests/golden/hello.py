@@ -0,0 +1,332 @@
+# This file was generated by scrypto-bindgen, do not edit it by hand.
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, Generic, List, Optional, Tuple, TypeVar, Union
+
+Decimal = str
+PreciseDecimal = str
+NonFungibleLocalId = str
+Address = str
+Own = str
+# The names of buckets, proofs and address reservations in the manifest
+Bucket = str
+Proof = str
+AddressReservation = str
+# A value rendered in the manifest syntax, such as `Decimal("1")`
+ManifestValue = str
+# A value in the programmatic SBOR JSON format
+SborJson = Dict[str, Any]
+
+T = TypeVar("T")
+E = TypeVar("E")
+
+
+@dataclass
+class Ok(Generic[T]):
+    value: T
+
+
+@dataclass
+class Err(Generic[E]):
+    value: E
+
+
+Result = Union[Ok[T], Err[E]]
+
+
+@dataclass
+class Some(Generic[T]):
+    """The value of an option whose value can itself be None, such as an option of an option"""
+
+    value: T
+
+
+@dataclass
+class Instruction(Generic[T]):
+    """A manifest instruction calling a function or method which returns `T`"""
+
+    instruction: str
+
+
+# Values of the types which the bindings can't describe are passed already encoded.
+
+
+def _manifest_bool(value: bool) -> ManifestValue:
+    return "true" if value else "false"
+
+
+def _manifest_integer(suffix: str) -> Callable[[int], ManifestValue]:
+    return lambda value: f"{value}{suffix}"
+
+
+def _manifest_string(value: str) -> ManifestValue:
+    return json.dumps(value)
+
+
+def _manifest_custom(kind: str) -> Callable[[str], ManifestValue]:
+    return lambda value: f"{kind}({json.dumps(value)})"
+
+
+def _manifest_array(kind: str, element: Callable) -> Callable[[List], ManifestValue]:
+    return lambda value: f"Array<{kind}>(" + ", ".join(element(item) for item in value) + ")"
+
+
+def _manifest_map(key_kind: str, value_kind: str, key: Callable, entry: Callable) -> Callable[[Dict], ManifestValue]:
+    return lambda value: (
+        f"Map<{key_kind}, {value_kind}>(" + ", ".join(key(k) + " => " + entry(v) for k, v in value.items()) + ")"
+    )
+
+
+def _manifest_tuple(fields: List[Callable]) -> Callable[[Tuple], ManifestValue]:
+    return lambda value: "Tuple(" + ", ".join(field(item) for field, item in zip(fields, value)) + ")"
+
+
+def _manifest_option(some: Callable) -> Callable[[Optional[Any]], ManifestValue]:
+    return lambda value: "Enum<0u8>()" if value is None else f"Enum<1u8>({some(value)})"
+
+
+def _manifest_nested_option(some: Callable) -> Callable[[Optional[Some]], ManifestValue]:
+    return lambda value: "Enum<0u8>()" if value is None else f"Enum<1u8>({some(value.value)})"
+
+
+def _manifest_result(ok: Callable, err: Callable) -> Callable[[Result], ManifestValue]:
+    return lambda value: f"Enum<0u8>({ok(value.value)})" if isinstance(value, Ok) else f"Enum<1u8>({err(value.value)})"
+
+
+def _manifest_object(value: Any) -> ManifestValue:
+    return value.to_manifest()
+
+
+def _manifest_raw(value: Any) -> ManifestValue:
+    return str(value)
+
+
+def _sbor_json_bool(value: bool) -> SborJson:
+    return {"kind": "Bool", "value": value}
+
+
+def _sbor_json_value(kind: str) -> Callable[[Any], SborJson]:
+    return lambda value: {"kind": kind, "value": str(value)}
+
+
+def _sbor_json_array(kind: str, element: Callable) -> Callable[[List], SborJson]:
+    return lambda value: {"kind": "Array", "element_kind": kind, "elements": [element(item) for item in value]}
+
+
+def _sbor_json_map(key_kind: str, value_kind: str, key: Callable, entry: Callable) -> Callable[[Dict], SborJson]:
+    return lambda value: {
+        "kind": "Map",
+        "key_kind": key_kind,
+        "value_kind": value_kind,
+        "entries": [{"key": key(k), "value": entry(v)} for k, v in value.items()],
+    }
+
+
+def _sbor_json_tuple(fields: List[Callable]) -> Callable[[Tuple], SborJson]:
+    return lambda value: {"kind": "Tuple", "fields": [field(item) for field, item in zip(fields, value)]}
+
+
+def _sbor_json_option(some: Callable) -> Callable[[Optional[Any]], SborJson]:
+    return lambda value: (
+        {"kind": "Enum", "variant_id": "0", "fields": []}
+        if value is None
+        else {"kind": "Enum", "variant_id": "1", "fields": [some(value)]}
+    )
+
+
+def _sbor_json_nested_option(some: Callable) -> Callable[[Optional[Some]], SborJson]:
+    return lambda value: (
+        {"kind": "Enum", "variant_id": "0", "fields": []}
+        if value is None
+        else {"kind": "Enum", "variant_id": "1", "fields": [some(value.value)]}
+    )
+
+
+def _sbor_json_result(ok: Callable, err: Callable) -> Callable[[Result], SborJson]:
+    return lambda value: (
+        {"kind": "Enum", "variant_id": "0", "fields": [ok(value.value)]}
+        if isinstance(value, Ok)
+        else {"kind": "Enum", "variant_id": "1", "fields": [err(value.value)]}
+    )
+
+
+def _sbor_json_object(value: Any) -> SborJson:
+    return value.to_sbor_json()
+
+
+def _sbor_json_raw(value: Any) -> SborJson:
+    return value
+
+
+def _field(value: SborJson, index: int) -> SborJson:
+    return value["fields"][index]
+
+
+def _from_sbor_json_unit(value: SborJson) -> None:
+    return None
+
+
+def _from_sbor_json_value(value: SborJson) -> Any:
+    return value["value"]
+
+
+def _from_sbor_json_int(value: SborJson) -> int:
+    return int(value["value"])
+
+
+def _from_sbor_json_array(element: Callable) -> Callable[[SborJson], List]:
+    return lambda value: [element(item) for item in value["elements"]]
+
+
+def _from_sbor_json_map(key: Callable, entry: Callable) -> Callable[[SborJson], Dict]:
+    return lambda value: {key(item["key"]): entry(item["value"]) for item in value["entries"]}
+
+
+def _from_sbor_json_tuple(fields: List[Callable]) -> Callable[[SborJson], Tuple]:
+    return lambda value: tuple(field(item) for field, item in zip(fields, value["fields"]))
+
+
+def _from_sbor_json_option(some: Callable) -> Callable[[SborJson], Optional[Any]]:
+    return lambda value: None if value["variant_id"] == "0" else some(_field(value, 0))
+
+
+def _from_sbor_json_nested_option(some: Callable) -> Callable[[SborJson], Optional[Some]]:
+    return lambda value: None if value["variant_id"] == "0" else Some(some(_field(value, 0)))
+
+
+def _from_sbor_json_result(ok: Callable, err: Callable) -> Callable[[SborJson], Result]:
+    return lambda value: Ok(ok(_field(value, 0))) if value["variant_id"] == "0" else Err(err(_field(value, 0)))
+
+
+def _from_sbor_json_raw(value: SborJson) -> Any:
+    return value
+
+
+def _call_function(blueprint_name: str, function_name: str, args: List[ManifestValue]) -> Instruction:
+    lines = [f'Address("{PACKAGE_ADDRESS}")', json.dumps(blueprint_name), json.dumps(function_name), *args]
+    return Instruction("CALL_FUNCTION\n" + "".join(f"    {line}\n" for line in lines) + ";")
+
+
+def _call_method(address: Address, method_name: str, args: List[ManifestValue]) -> Instruction:
+    lines = [f'Address("{address}")', json.dumps(method_name), *args]
+    return Instruction("CALL_METHOD\n" + "".join(f"    {line}\n" for line in lines) + ";")
+
+
+PACKAGE_ADDRESS: Address = "package_sim1pkgxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"
+
+
+@dataclass
+class Config:
+    fee: Decimal
+    limit: Optional[Some[Optional[int]]]
+    owner: Optional[Address]
+
+    def to_manifest(self) -> ManifestValue:
+        return "Tuple(" + ", ".join([_manifest_custom("Decimal")(self.fee), _manifest_nested_option(_manifest_option(_manifest_integer("u32")))(self.limit), _manifest_option(_manifest_custom("Address"))(self.owner)]) + ")"
+
+    def to_sbor_json(self) -> SborJson:
+        return {"kind": "Tuple", "type_name": "Config", "fields": [{"field_name": "fee", **_sbor_json_value("Decimal")(self.fee)}, {"field_name": "limit", **_sbor_json_nested_option(_sbor_json_option(_sbor_json_value("U32")))(self.limit)}, {"field_name": "owner", **_sbor_json_option(_sbor_json_value("Reference"))(self.owner)}]}
+
+    @classmethod
+    def from_sbor_json(cls, value: SborJson) -> Config:
+        return cls(_from_sbor_json_value(_field(value, 0)), _from_sbor_json_nested_option(_from_sbor_json_option(_from_sbor_json_int))(_field(value, 1)), _from_sbor_json_option(_from_sbor_json_value)(_field(value, 2)))
+
+
+class Color:
+    """The base class of the variants of the `Color` enum"""
+
+    def to_manifest(self) -> ManifestValue:
+        raise NotImplementedError
+
+    def to_sbor_json(self) -> SborJson:
+        raise NotImplementedError
+
+    @staticmethod
+    def from_sbor_json(value: SborJson) -> Color:
+        variants = {"0": ColorRed, "1": ColorCustom}
+        return variants[value["variant_id"]].from_sbor_json(value)
+
+
+@dataclass
+class ColorRed(Color):
+    def to_manifest(self) -> ManifestValue:
+        return "Enum<0u8>(" + ", ".join([]) + ")"
+
+    def to_sbor_json(self) -> SborJson:
+        return {"kind": "Enum", "type_name": "Color", "variant_id": "0", "variant_name": "Red", "fields": []}
+
+    @classmethod
+    def from_sbor_json(cls, value: SborJson) -> ColorRed:
+        return cls()
+
+
+@dataclass
+class ColorCustom(Color):
+    field_0: int
+    field_1: int
+    field_2: int
+
+    def to_manifest(self) -> ManifestValue:
+        return "Enum<1u8>(" + ", ".join([_manifest_integer("u8")(self.field_0), _manifest_integer("u8")(self.field_1), _manifest_integer("u8")(self.field_2)]) + ")"
+
+    def to_sbor_json(self) -> SborJson:
+        return {"kind": "Enum", "type_name": "Color", "variant_id": "1", "variant_name": "Custom", "fields": [_sbor_json_value("U8")(self.field_0), _sbor_json_value("U8")(self.field_1), _sbor_json_value("U8")(self.field_2)]}
+
+    @classmethod
+    def from_sbor_json(cls, value: SborJson) -> ColorCustom:
+        return cls(_from_sbor_json_int(_field(value, 0)), _from_sbor_json_int(_field(value, 1)), _from_sbor_json_int(_field(value, 2)))
+
+
+class HelloFunctions:
+    """The functions of the `Hello` blueprint"""
+
+    @staticmethod
+    def instantiate(config: Config) -> Instruction[Address]:
+        return _call_function("Hello", "instantiate", [_manifest_object(config)])
+
+
+class Hello:
+    """A component of the `Hello` blueprint"""
+
+    def __init__(self, address: Address):
+        self.address = address
+
+    def paint(self, bucket: Bucket, colors: List[Color]) -> Instruction[Optional[Some[Optional[Bucket]]]]:
+        return _call_method(self.address, "paint", [_manifest_custom("Bucket")(bucket), _manifest_array("Enum", _manifest_object)(colors)])
+
+    def config(self) -> Instruction[Config]:
+        return _call_method(self.address, "config", [])
+
+
+class HelloTypes:
+    """The events, state and registered types of the `Hello` blueprint"""
+
+    @dataclass
+    class PaintedEvent:
+        field_0: Color
+
+        def to_manifest(self) -> ManifestValue:
+            return "Tuple(" + ", ".join([_manifest_object(self.field_0)]) + ")"
+
+        def to_sbor_json(self) -> SborJson:
+            return {"kind": "Tuple", "type_name": "PaintedEvent", "fields": [_sbor_json_object(self.field_0)]}
+
+        @classmethod
+        def from_sbor_json(cls, value: SborJson) -> PaintedEvent:
+            return cls(Color.from_sbor_json(_field(value, 0)))
+
+    @staticmethod
+    def decode_state(value: SborJson) -> Config:
+        return Config.from_sbor_json(value)
+
+    @staticmethod
+    def decode_event(event_name: str, payload: SborJson) -> Optional[Any]:
+        """Decodes the SBOR JSON payload of an event of the blueprint, given the name of the event"""
+        decoders = {
+            "PaintedEvent": HelloTypes.PaintedEvent.from_sbor_json,
+        }
+        decoder = decoders.get(event_name)
+        return decoder(payload) if decoder else None
@@ -0,0 +1,207 @@
+// This file was generated by scrypto-bindgen, do not edit it by hand.
+
+export type Decimal = string;
+export type PreciseDecimal = string;
+export type NonFungibleLocalId = string;
+export type Address = string;
+export type Own = string;
+/** The name of a bucket in the manifest */
+export type Bucket = string;
+/** The name of a proof in the manifest */
+export type Proof = string;
+/** The name of an address reservation in the manifest */
+export type AddressReservation = string;
+/** A value rendered in the manifest syntax, such as `Decimal("1")` */
+export type ManifestValue = string;
+/** A value in the programmatic SBOR JSON format */
+export type SborJson = { kind: string; [key: string]: unknown };
+export type Result<T, E> = { ok: T } | { err: E };
+/** The value of an option whose value can itself be null, such as an option of an option */
+export type Some<T> = { some: T };
+
+/** A manifest instruction calling a function or method which returns `Output` */
+export interface Instruction<Output> {
+  readonly instruction: string;
+  readonly output?: Output;
+}
+
+type Encoder<T, E> = (value: T) => E;
+export type Decoder<T> = (value: SborJson) => T;
+
+// Values of the types which the bindings can't describe are passed already encoded.
+const manifest = {
+  bool: (value: boolean): ManifestValue => `${value}`,
+  integer: (suffix: string) => (value: number | bigint): ManifestValue => `${value}${suffix}`,
+  string: (value: string): ManifestValue => JSON.stringify(value),
+  custom: (kind: string) => (value: string): ManifestValue => `${kind}(${JSON.stringify(value)})`,
+  array: <T>(kind: string, element: Encoder<T, ManifestValue>) => (value: T[]): ManifestValue =>
+    `Array<${kind}>(${value.map(element).join(", ")})`,
+  map: <K, V>(keyKind: string, valueKind: string, key: Encoder<K, ManifestValue>, entry: Encoder<V, ManifestValue>) =>
+    (value: Map<K, V>): ManifestValue =>
+      `Map<${keyKind}, ${valueKind}>(${[...value].map(([k, v]) => `${key(k)} => ${entry(v)}`).join(", ")})`,
+  tuple: (fields: Encoder<any, ManifestValue>[]) => (value: any[]): ManifestValue =>
+    `Tuple(${value.map((field, index) => fields[index](field)).join(", ")})`,
+  option: <T>(some: Encoder<T, ManifestValue>) => (value: T | null): ManifestValue =>
+    value === null ? "Enum<0u8>()" : `Enum<1u8>(${some(value)})`,
+  nestedOption: <T>(some: Encoder<T, ManifestValue>) => (value: Some<T> | null): ManifestValue =>
+    value === null ? "Enum<0u8>()" : `Enum<1u8>(${some(value.some)})`,
+  result: <T, E>(ok: Encoder<T, ManifestValue>, err: Encoder<E, ManifestValue>) => (value: Result<T, E>): ManifestValue =>
+    "ok" in value ? `Enum<0u8>(${ok(value.ok)})` : `Enum<1u8>(${err(value.err)})`,
+  raw: (value: unknown): ManifestValue => String(value),
+};
+
+const sborJson = {
+  bool: (value: boolean): SborJson => ({ kind: "Bool", value }),
+  value: (kind: string) => (value: number | bigint | string): SborJson => ({ kind, value: `${value}` }),
+  array: <T>(kind: string, element: Encoder<T, SborJson>) => (value: T[]): SborJson =>
+    ({ kind: "Array", element_kind: kind, elements: value.map(element) }),
+  map: <K, V>(keyKind: string, valueKind: string, key: Encoder<K, SborJson>, entry: Encoder<V, SborJson>) =>
+    (value: Map<K, V>): SborJson =>
+      ({ kind: "Map", key_kind: keyKind, value_kind: valueKind, entries: [...value].map(([k, v]) => ({ key: key(k), value: entry(v) })) }),
+  tuple: (fields: Encoder<any, SborJson>[]) => (value: any[]): SborJson =>
+    ({ kind: "Tuple", fields: value.map((field, index) => fields[index](field)) }),
+  option: <T>(some: Encoder<T, SborJson>) => (value: T | null): SborJson =>
+    value === null ? { kind: "Enum", variant_id: "0", fields: [] } : { kind: "Enum", variant_id: "1", fields: [some(value)] },
+  nestedOption: <T>(some: Encoder<T, SborJson>) => (value: Some<T> | null): SborJson =>
+    value === null ? { kind: "Enum", variant_id: "0", fields: [] } : { kind: "Enum", variant_id: "1", fields: [some(value.some)] },
+  result: <T, E>(ok: Encoder<T, SborJson>, err: Encoder<E, SborJson>) => (value: Result<T, E>): SborJson =>
+    "ok" in value ? { kind: "Enum", variant_id: "0", fields: [ok(value.ok)] } : { kind: "Enum", variant_id: "1", fields: [err(value.err)] },
+  raw: (value: unknown): SborJson => value as SborJson,
+};
+
+const field = (value: SborJson, index: number): SborJson => (value.fields as SborJson[])[index];
+
+const fromSborJson = {
+  bool: (value: SborJson): boolean => value.value as boolean,
+  number: (value: SborJson): number => Number(value.value),
+  bigint: (value: SborJson): bigint => BigInt(value.value as string),
+  string: (value: SborJson): string => value.value as string,
+  array: <T>(element: Decoder<T>) => (value: SborJson): T[] => (value.elements as SborJson[]).map(element),
+  map: <K, V>(key: Decoder<K>, entry: Decoder<V>) => (value: SborJson): Map<K, V> =>
+    new Map((value.entries as { key: SborJson; value: SborJson }[]).map((e) => [key(e.key), entry(e.value)])),
+  tuple: <T extends unknown[]>(fields: Decoder<unknown>[]) => (value: SborJson): T =>
+    (value.fields as SborJson[]).map((item, index) => fields[index](item)) as T,
+  option: <T>(some: Decoder<T>) => (value: SborJson): T | null =>
+    value.variant_id === "0" ? null : some(field(value, 0)),
+  nestedOption: <T>(some: Decoder<T>) => (value: SborJson): Some<T> | null =>
+    value.variant_id === "0" ? null : { some: some(field(value, 0)) },
+  result: <T, E>(ok: Decoder<T>, err: Decoder<E>) => (value: SborJson): Result<T, E> =>
+    value.variant_id === "0" ? { ok: ok(field(value, 0)) } : { err: err(field(value, 0)) },
+  raw: (value: SborJson): unknown => value,
+};
+
+function callFunction<Output>(blueprintName: string, functionName: string, args: ManifestValue[]): Instruction<Output> {
+  const lines = [`Address("${PACKAGE_ADDRESS}")`, JSON.stringify(blueprintName), JSON.stringify(functionName), ...args];
+  return { instruction: `CALL_FUNCTION\n${lines.map((line) => `    ${line}\n`).join("")};` };
+}
+
+function callMethod<Output>(address: Address, methodName: string, args: ManifestValue[]): Instruction<Output> {
+  const lines = [`Address("${address}")`, JSON.stringify(methodName), ...args];
+  return { instruction: `CALL_METHOD\n${lines.map((line) => `    ${line}\n`).join("")};` };
+}
+
+export const PACKAGE_ADDRESS: Address = "package_sim1pkgxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx";
+
+export interface Config {
+  fee: Decimal;
+  limit: Some<number | null> | null;
+  owner: Address | null;
+}
+
+export function manifestConfig(value: Config): ManifestValue {
+  return `Tuple(${[manifest.custom("Decimal")(value.fee), manifest.nestedOption(manifest.option(manifest.integer("u32")))(value.limit), manifest.option(manifest.custom("Address"))(value.owner)].join(", ")})`;
+}
+
+export function sborJsonConfig(value: Config): SborJson {
+  return { kind: "Tuple", type_name: "Config", fields: [{ field_name: "fee", ...sborJson.value("Decimal")(value.fee) }, { field_name: "limit", ...sborJson.nestedOption(sborJson.option(sborJson.value("U32")))(value.limit) }, { field_name: "owner", ...sborJson.option(sborJson.value("Reference"))(value.owner) }] };
+}
+
+export function fromSborJsonConfig(value: SborJson): Config {
+  return { fee: fromSborJson.string(field(value, 0)), limit: fromSborJson.nestedOption(fromSborJson.option(fromSborJson.number))(field(value, 1)), owner: fromSborJson.option(fromSborJson.string)(field(value, 2)) };
+}
+
+export type Color =
+  | { variant: "Red" }
+  | { variant: "Custom"; fields: [number, number, number] };
+
+export function manifestColor(value: Color): ManifestValue {
+  switch (value.variant) {
+    case "Red":
+      return `Enum<0u8>(${[].join(", ")})`;
+    case "Custom":
+      return `Enum<1u8>(${[manifest.integer("u8")(value.fields[0]), manifest.integer("u8")(value.fields[1]), manifest.integer("u8")(value.fields[2])].join(", ")})`;
+  }
+}
+
+export function sborJsonColor(value: Color): SborJson {
+  switch (value.variant) {
+    case "Red":
+      return { kind: "Enum", type_name: "Color", variant_id: "0", variant_name: "Red", fields: [] };
+    case "Custom":
+      return { kind: "Enum", type_name: "Color", variant_id: "1", variant_name: "Custom", fields: [sborJson.value("U8")(value.fields[0]), sborJson.value("U8")(value.fields[1]), sborJson.value("U8")(value.fields[2])] };
+  }
+}
+
+export function fromSborJsonColor(value: SborJson): Color {
+  switch (value.variant_id) {
+    case "0":
+      return { variant: "Red" };
+    case "1":
+      return { variant: "Custom", fields: [fromSborJson.number(field(value, 0)), fromSborJson.number(field(value, 1)), fromSborJson.number(field(value, 2))] };
+    default:
+      throw new Error(`Unknown variant ${value.variant_id} of Color`);
+  }
+}
+
+/** The functions of the `Hello` blueprint */
+export const HelloFunctions = {
+  instantiate(config: Config): Instruction<Address> {
+    return callFunction("Hello", "instantiate", [manifestConfig(config)]);
+  },
+};
+
+/** A component of the `Hello` blueprint */
+export class Hello {
+  constructor(readonly address: Address) {}
+
+  paint(bucket: Bucket, colors: Array<Color>): Instruction<Some<Bucket | null> | null> {
+    return callMethod(this.address, "paint", [manifest.custom("Bucket")(bucket), manifest.array("Enum", manifestColor)(colors)]);
+  }
+
+  config(): Instruction<Config> {
+    return callMethod(this.address, "config", []);
+  }
+}
+
+/** The events, state and registered types of the `Hello` blueprint */
+export namespace HelloTypes {
+  export type PaintedEvent = [Color];
+
+  export function manifestPaintedEvent(value: PaintedEvent): ManifestValue {
+    return `Tuple(${[manifestColor(value[0])].join(", ")})`;
+  }
+
+  export function sborJsonPaintedEvent(value: PaintedEvent): SborJson {
+    return { kind: "Tuple", type_name: "PaintedEvent", fields: [sborJsonColor(value[0])] };
+  }
+
+  export function fromSborJsonPaintedEvent(value: SborJson): PaintedEvent {
+    return [fromSborJsonColor(field(value, 0))];
+  }
+
+  export type State = Config;
+  export const decodeState: Decoder<State> = fromSborJsonConfig;
+
+  export type Event =
+    | { name: "PaintedEvent"; value: HelloTypes.PaintedEvent };
+
+  /** Decodes the SBOR JSON payload of an event of the blueprint, given the name of the event */
+  export function decodeEvent(eventName: string, payload: SborJson): Event | null {
+    switch (eventName) {
+      case "PaintedEvent":
+        return { name: "PaintedEvent", value: HelloTypes.fromSborJsonPaintedEvent(payload) };
+      default:
+        return null;
+    }
+  }
+}
@@ -0,0 +1,397 @@
+use crate::{Profile, ScryptoCompilerError};
+use radix_common::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const BUILD_INFO_VERSION: u64 = 1;
+const TOOLCHAIN_FILES: [&str; 2] = ["rust-toolchain.toml", "rust-toolchain"];
+
+/// The toolchain a reproducible build was made with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolchainInfo {
+    /// The channel pinned by the `rust-toolchain.toml` file of the package, such as `1.81.0`.
+    pub channel: String,
+    /// The version reported by `rustc --version`.
+    pub rustc_version: String,
+    /// The commit hash of `rustc`.
+    pub commit_hash: String,
+    /// The host triple of `rustc`.
+    pub host: String,
+}
+
+impl ToolchainInfo {
+    /// Reads the toolchain pinned by the package in the given directory, and checks that it is the
+    /// one `rustc` resolves to there.
+    pub fn pinned(package_directory: &Path) -> Result<Self, ScryptoCompilerError> {
+        let channel = pinned_channel(package_directory)?.ok_or_else(|| {
+            ScryptoCompilerError::ToolchainNotPinned(package_directory.to_path_buf())
+        })?;
+
+        let output = rustc_output(package_directory, &["-vV"])?;
+        let field = |name: &str| {
+            output
+                .lines()
+                .find_map(|line| line.strip_prefix(name))
+                .map(|value| value.trim().to_owned())
+                .unwrap_or_default()
+        };
+        let toolchain = Self {
+            rustc_version: output.lines().next().unwrap_or_default().to_owned(),
+            commit_hash: field("commit-hash:"),
+            host: field("host:"),
+            channel,
+        };
+
+        // A dated channel can't be matched against the release, as the commit date of a nightly
+        // is usually the day before the one it is named after.
+        let release = field("release:");
+        if is_version(&toolchain.channel) && release != toolchain.channel {
+            return Err(ScryptoCompilerError::ToolchainMismatch(
+                toolchain.channel,
+                release,
+            ));
+        }
+        Ok(toolchain)
+    }
+
+    /// The path of the sysroot of the toolchain, which ends up in the paths embedded in the WASM.
+    pub fn sysroot(package_directory: &Path) -> Result<PathBuf, ScryptoCompilerError> {
+        rustc_output(package_directory, &["--print", "sysroot"])
+            .map(|output| PathBuf::from(output.trim()))
+    }
+}
+
+/// The information about a reproducible build, which is written next to the WASM and RPD files so
+/// that anyone with the same source tree can rebuild the package and compare the code hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub package_name: String,
+    pub toolchain: ToolchainInfo,
+    /// The version of the Scrypto compiler which made the build.
+    pub scrypto_compiler_version: String,
+    pub profile: Profile,
+    pub features: Vec<String>,
+    pub no_default_features: bool,
+    pub all_features: bool,
+    /// The hash of the source tree, see [`source_tree_hash`].
+    pub source_tree_hash: Hash,
+    /// The hash of the WASM, which is the code hash of the package once it is published.
+    pub code_hash: Hash,
+}
+
+impl BuildInfo {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "version": BUILD_INFO_VERSION,
+            "package_name": self.package_name,
+            "toolchain": {
+                "channel": self.toolchain.channel,
+                "rustc_version": self.toolchain.rustc_version,
+                "commit_hash": self.toolchain.commit_hash,
+                "host": self.toolchain.host,
+            },
+            "scrypto_compiler_version": self.scrypto_compiler_version,
+            "profile": profile_name(&self.profile),
+            "features": self.features,
+            "no_default_features": self.no_default_features,
+            "all_features": self.all_features,
+            "source_tree_hash": self.source_tree_hash.to_string(),
+            "code_hash": self.code_hash.to_string(),
+        })
+    }
+
+    pub fn from_json(json: &serde_json::Value) -> Option<Self> {
+        if json.get("version")?.as_u64()? != BUILD_INFO_VERSION {
+            return None;
+        }
+        let string = |value: &serde_json::Value, name: &str| -> Option<String> {
+            value.get(name)?.as_str().map(str::to_owned)
+        };
+        let toolchain = json.get("toolchain")?;
+        Some(Self {
+            package_name: string(json, "package_name")?,
+            toolchain: ToolchainInfo {
+                channel: string(toolchain, "channel")?,
+                rustc_version: string(toolchain, "rustc_version")?,
+                commit_hash: string(toolchain, "commit_hash")?,
+                host: string(toolchain, "host")?,
+            },
+            scrypto_compiler_version: string(json, "scrypto_compiler_version")?,
+            profile: string(json, "profile")?.parse().ok()?,
+            features: json
+                .get("features")?
+                .as_array()?
+                .iter()
+                .map(|feature| feature.as_str().map(str::to_owned))
+                .collect::<Option<_>>()?,
+            no_default_features: json.get("no_default_features")?.as_bool()?,
+            all_features: json.get("all_features")?.as_bool()?,
+            source_tree_hash: string(json, "source_tree_hash")?.parse().ok()?,
+            code_hash: string(json, "code_hash")?.parse().ok()?,
+        })
+    }
+
+    /// The path of the build info of the given WASM file, such as `hello.build_info.json` for
+    /// `hello.wasm`.
+    pub fn path_for_wasm(wasm_path: &Path) -> PathBuf {
+        wasm_path.with_extension("build_info.json")
+    }
+
+    pub fn read(path: &Path) -> Result<Self, ScryptoCompilerError> {
+        let content = fs::read(path).map_err(|err| {
+            ScryptoCompilerError::IOErrorWithPath(
+                err,
+                path.to_path_buf(),
+                Some(String::from("Read build info failed.")),
+            )
+        })?;
+        serde_json::from_slice(&content)
+            .ok()
+            .as_ref()
+            .and_then(Self::from_json)
+            .ok_or_else(|| ScryptoCompilerError::BuildInfoParseError(path.to_path_buf()))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), ScryptoCompilerError> {
+        let content = serde_json::to_string_pretty(&self.to_json())
+            .expect("Build info is always serializable");
+        fs::write(path, content + "\n").map_err(|err| {
+            ScryptoCompilerError::IOErrorWithPath(
+                err,
+                path.to_path_buf(),
+                Some(String::from("Write build info failed.")),
+            )
+        })
+    }
+}
+
+/// Hashes the files of a source tree, by their path relative to its root and their content, so
+/// that the hash does not depend on where the tree is checked out. Hidden files and directories,
+/// such as `.git`, and Cargo target directories are left out.
+pub fn source_tree_hash(root: &Path) -> Result<Hash, ScryptoCompilerError> {
+    let mut files = Vec::new();
+    collect_source_files(root, root, &mut files)?;
+    files.sort();
+
+    let mut tree = Vec::new();
+    for (relative_path, path) in files {
+        let content = fs::read(&path).map_err(|err| {
+            ScryptoCompilerError::IOErrorWithPath(
+                err,
+                path,
+                Some(String::from("Read source file failed.")),
+            )
+        })?;
+        tree.extend(relative_path.as_bytes());
+        tree.push(0);
+        tree.extend(hash(content).as_slice());
+    }
+    Ok(hash(tree))
+}
+
+fn collect_source_files(
+    root: &Path,
+    directory: &Path,
+    files: &mut Vec<(String, PathBuf)>,
+) -> Result<(), ScryptoCompilerError> {
+    let io_error = |err| {
+        ScryptoCompilerError::IOErrorWithPath(
+            err,
+            directory.to_path_buf(),
+            Some(String::from("Read source directory failed.")),
+        )
+    };
+    // Cargo tags the target directories it creates, wherever they are placed.
+    if directory != root && directory.join("CACHEDIR.TAG").exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(directory).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || (directory == root && name == "target") {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            collect_source_files(root, &path, files)?;
+        } else {
+            let relative_path = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative_path, path));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the channel of the toolchain file of a package, if it pins one to a version, such as
+/// `1.81.0`, or to a dated channel, such as `nightly-2024-09-01`.
+fn pinned_channel(package_directory: &Path) -> Result<Option<String>, ScryptoCompilerError> {
+    let Some(path) = TOOLCHAIN_FILES
+        .iter()
+        .map(|name| package_directory.join(name))
+        .find(|path| path.exists())
+    else {
+        return Ok(None);
+    };
+    let content = fs::read_to_string(&path).map_err(|err| {
+        ScryptoCompilerError::IOErrorWithPath(
+            err,
+            path.clone(),
+            Some(String::from("Read toolchain file failed.")),
+        )
+    })?;
+
+    // The legacy `rust-toolchain` file may only hold the channel.
+    let channel = content
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "channel").then(|| value.trim().trim_matches('"').to_owned())
+        })
+        .or_else(|| {
+            let content = content.trim();
+            (!content.is_empty() && !content.contains('\n')).then(|| content.to_owned())
+        });
+
+    Ok(channel.filter(|channel| {
+        is_version(channel)
+            || channel
+                .split_once('-')
+                .is_some_and(|(_, date)| is_date(date))
+    }))
+}
+
+fn is_version(channel: &str) -> bool {
+    let parts = channel.split('.').collect::<Vec<_>>();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn is_date(date: &str) -> bool {
+    let parts = date.split('-').map(str::len).collect::<Vec<_>>();
+    parts == [4, 2, 2] && date.chars().all(|c| c.is_ascii_digit() || c == '-')
+}
+
+fn rustc_output(package_directory: &Path, args: &[&str]) -> Result<String, ScryptoCompilerError> {
+    let output = Command::new("rustc")
+        .args(args)
+        .current_dir(package_directory)
+        .output()
+        .map_err(|err| {
+            ScryptoCompilerError::IOError(err, Some(String::from("Running rustc failed.")))
+        })?;
+    if !output.status.success() {
+        return Err(ScryptoCompilerError::ToolchainQueryFailure(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn profile_name(profile: &Profile) -> String {
+    match profile {
+        Profile::Release => String::from("release"),
+        Profile::Debug => String::from("debug"),
+        Profile::Test => String::from("test"),
+        Profile::Bench => String::from("bench"),
+        Profile::Custom(name) => name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_channel() {
+        let directory = tempdir::TempDir::new("scrypto-compiler").unwrap();
+        let toolchain_path = directory.path().join("rust-toolchain.toml");
+
+        for (content, expected) in [
+            ("[toolchain]\nchannel = \"1.81.0\"\n", Some("1.81.0")),
+            (
+                "[toolchain]\nchannel = \"nightly-2024-09-01\"\n",
+                Some("nightly-2024-09-01"),
+            ),
+            ("[toolchain]\nchannel = \"stable\"\n", None),
+            ("[toolchain]\nchannel = \"1.81\"\n", None),
+        ] {
+            fs::write(&toolchain_path, content).unwrap();
+            assert_eq!(
+                pinned_channel(directory.path()).unwrap().as_deref(),
+                expected
+            );
+        }
+
+        fs::remove_file(&toolchain_path).unwrap();
+        assert_eq!(pinned_channel(directory.path()).unwrap(), None);
+        fs::write(directory.path().join("rust-toolchain"), "1.80.1\n").unwrap();
+        assert_eq!(
+            pinned_channel(directory.path()).unwrap().as_deref(),
+            Some("1.80.1")
+        );
+    }
+
+    #[test]
+    fn test_source_tree_hash_ignores_location_and_build_outputs() {
+        let write_tree = |root: &Path| {
+            fs::create_dir_all(root.join("src")).unwrap();
+            fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+            fs::write(root.join("src/lib.rs"), "fn main() {}").unwrap();
+        };
+        let first = tempdir::TempDir::new("scrypto-compiler").unwrap();
+        let second = tempdir::TempDir::new("scrypto-compiler").unwrap();
+        write_tree(first.path());
+        write_tree(second.path());
+
+        // Build outputs and hidden files are not part of the tree.
+        fs::create_dir_all(second.path().join("target/release")).unwrap();
+        fs::write(second.path().join("target/release/lib.wasm"), [0u8]).unwrap();
+        fs::create_dir_all(second.path().join("coverage")).unwrap();
+        fs::write(second.path().join("coverage/CACHEDIR.TAG"), "").unwrap();
+        fs::write(second.path().join(".gitignore"), "target").unwrap();
+        assert_eq!(
+            source_tree_hash(first.path()).unwrap(),
+            source_tree_hash(second.path()).unwrap()
+        );
+
+        fs::write(second.path().join("src/lib.rs"), "fn main() { }").unwrap();
+        assert_ne!(
+            source_tree_hash(first.path()).unwrap(),
+            source_tree_hash(second.path()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_build_info_json_round_trip() {
+        let build_info = BuildInfo {
+            package_name: String::from("hello_world"),
+            toolchain: ToolchainInfo {
+                channel: String::from("1.81.0"),
+                rustc_version: String::from("rustc 1.81.0 (eeb90cda1 2024-09-04)"),
+                commit_hash: String::from("eeb90cda1969383f56a2637cbd3037bdf598841c"),
+                host: String::from("x86_64-unknown-linux-gnu"),
+            },
+            scrypto_compiler_version: String::from("1.4.0"),
+            profile: Profile::Release,
+            features: vec![String::from("scrypto/log-error")],
+            no_default_features: false,
+            all_features: false,
+            source_tree_hash: hash("source"),
+            code_hash: hash("code"),
+        };
+
+        assert_eq!(
+            BuildInfo::from_json(&build_info.to_json()),
+            Some(build_info)
+        );
+    }
+}
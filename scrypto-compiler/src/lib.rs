@@ -13,6 +13,10 @@ use std::path::{Path, PathBuf};
 use std::process::{Command, ExitStatus, Stdio};
 use std::{env, io};
 
+mod build_info;
+
+pub use build_info::*;
+
 const MANIFEST_FILE: &str = "Cargo.toml";
 const BUILD_TARGET: &str = "wasm32-unknown-unknown";
 const SCRYPTO_NO_SCHEMA: &str = "scrypto/no-schema";
@@ -69,6 +73,9 @@ pub enum ScryptoCompilerError {
     /// Returns path to Cargo.toml for which results of cargo metadata command is not not valid json
     /// or target directory field is missing.
     CargoTargetDirectoryResolutionError(String),
+    /// Returns path to Cargo.toml for which results of cargo metadata command is not not valid json
+    /// or workspace root field is missing.
+    CargoWorkspaceRootResolutionError(String),
     /// Compiler is unable to generate target binary file name.
     CargoTargetBinaryResolutionError,
     /// Returns path to Cargo.toml which was failed to load.
@@ -89,6 +96,17 @@ pub enum ScryptoCompilerError {
     NothingToCompile,
     /// A conversion error when trying to read the package definition.
     PackageDefinitionConversionError(ConversionError),
+    /// Returns path to the Cargo.lock file, which a reproducible build requires but is missing.
+    CargoLockFileNotFound(PathBuf),
+    /// Returns path to the package directory, whose toolchain is not pinned to a version by a
+    /// `rust-toolchain.toml` file as a reproducible build requires.
+    ToolchainNotPinned(PathBuf),
+    /// Returns the pinned toolchain channel and the release of the toolchain actually in use.
+    ToolchainMismatch(String, String),
+    /// Returns `rustc` stderr output, when querying the toolchain failed.
+    ToolchainQueryFailure(String),
+    /// Returns path to the build info file which could not be parsed.
+    BuildInfoParseError(PathBuf),
 }
 
 #[derive(Debug, Clone)]
@@ -129,6 +147,11 @@ pub struct ScryptoCompilerInputParams {
     pub verbose: bool,
     /// A flag that's set to true if this is a coverage compilation.
     pub coverage: bool,
+    /// If set to true then the build is reproducible: the toolchain must be pinned by a
+    /// `rust-toolchain.toml` file, the Cargo.lock file of the workspace is required and used as-is,
+    /// the paths embedded in the WASM are remapped, the default wasm-opt configuration is used and a
+    /// build info file is written next to each WASM file. The default value is false.
+    pub reproducible: bool,
 }
 impl Default for ScryptoCompilerInputParams {
     /// Definition of default `ScryptoCompiler` configuration.
@@ -163,6 +186,7 @@ impl Default for ScryptoCompilerInputParams {
             wasm_optimization,
             verbose: false,
             coverage: false,
+            reproducible: false,
         };
         // Apply default log level features
         ret.features
@@ -192,7 +216,7 @@ impl ScryptoCompilerInputParams {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub enum Profile {
     #[default]
     Release,
@@ -293,6 +317,8 @@ impl EnvironmentVariableAction {
 pub struct BuildArtifacts {
     pub wasm: BuildArtifact<Vec<u8>>,
     pub package_definition: BuildArtifact<PackageDefinition>,
+    /// The build info, for reproducible builds only.
+    pub build_info: Option<BuildArtifact<BuildInfo>>,
 }
 
 #[derive(Debug, Clone)]
@@ -523,6 +549,23 @@ impl ScryptoCompiler {
     }
 
     fn get_default_target_directory(manifest_path: &Path) -> Result<String, ScryptoCompilerError> {
+        Self::get_cargo_metadata_field(manifest_path, "target_directory", |path| {
+            ScryptoCompilerError::CargoTargetDirectoryResolutionError(path)
+        })
+    }
+
+    fn get_workspace_root(manifest_path: &Path) -> Result<String, ScryptoCompilerError> {
+        Self::get_cargo_metadata_field(manifest_path, "workspace_root", |path| {
+            ScryptoCompilerError::CargoWorkspaceRootResolutionError(path)
+        })
+    }
+
+    // Returns a string field of the `cargo metadata` output for the manifest
+    fn get_cargo_metadata_field(
+        manifest_path: &Path,
+        field: &str,
+        resolution_error: fn(String) -> ScryptoCompilerError,
+    ) -> Result<String, ScryptoCompilerError> {
         let output = Command::new("cargo")
             .arg("metadata")
             .arg("--manifest-path")
@@ -539,20 +582,14 @@ impl ScryptoCompiler {
                 )
             })?;
         if output.status.success() {
-            let parsed =
-                serde_json::from_slice::<serde_json::Value>(&output.stdout).map_err(|_| {
-                    ScryptoCompilerError::CargoTargetDirectoryResolutionError(
-                        manifest_path.display().to_string(),
-                    )
-                })?;
-            let target_directory = parsed
+            let parsed = serde_json::from_slice::<serde_json::Value>(&output.stdout)
+                .map_err(|_| resolution_error(manifest_path.display().to_string()))?;
+            let value = parsed
                 .as_object()
-                .and_then(|o| o.get("target_directory"))
+                .and_then(|o| o.get(field))
                 .and_then(|o| o.as_str())
-                .ok_or(ScryptoCompilerError::CargoTargetDirectoryResolutionError(
-                    manifest_path.display().to_string(),
-                ))?;
-            Ok(target_directory.to_owned())
+                .ok_or_else(|| resolution_error(manifest_path.display().to_string()))?;
+            Ok(value.to_owned())
         } else {
             Err(ScryptoCompilerError::CargoMetadataFailure(
                 String::from_utf8_lossy(&output.stderr).to_string(),
@@ -939,6 +976,12 @@ impl ScryptoCompiler {
         stdout: Option<T>,
         stderr: Option<T>,
     ) -> Result<Vec<BuildArtifacts>, ScryptoCompilerError> {
+        let toolchain = if self.input_params.reproducible {
+            Some(self.prepare_reproducible_build()?)
+        } else {
+            None
+        };
+
         let package_locks = self.lock_packages()?;
 
         // If we're building for coverage then there is some post-processing we need to do.
@@ -1066,10 +1109,113 @@ impl ScryptoCompiler {
             artifacts
         };
 
+        let artifacts = match toolchain {
+            Some(toolchain) => self.write_build_info(artifacts, &toolchain)?,
+            None => artifacts,
+        };
+
         self.unlock_packages(package_locks)?;
         Ok(artifacts)
     }
 
+    // Checks the inputs of a reproducible build, and configures the build so that its output does
+    // not depend on the machine it is made on.
+    fn prepare_reproducible_build(&mut self) -> Result<ToolchainInfo, ScryptoCompilerError> {
+        let package_directory = self.main_manifest_directory();
+        // The lock file of a workspace member is the one of its workspace, and the sources of the
+        // other members can be compiled into it.
+        let workspace_root =
+            PathBuf::from(Self::get_workspace_root(&self.main_manifest.manifest_path)?);
+
+        let lock_file_path = workspace_root.join("Cargo.lock");
+        if !lock_file_path.exists() {
+            return Err(ScryptoCompilerError::CargoLockFileNotFound(lock_file_path));
+        }
+        self.input_params.locked = true;
+
+        let toolchain = ToolchainInfo::pinned(&package_directory)?;
+
+        // The paths of the sources, of the dependencies and of the standard library are embedded
+        // in the WASM, in panic messages for instance.
+        let cargo_home = env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cargo")));
+        let mut remapped_paths = vec![(workspace_root, "/scrypto/package")];
+        if let Some(cargo_home) = cargo_home {
+            remapped_paths.push((cargo_home, "/scrypto/cargo"));
+        }
+        remapped_paths.push((
+            ToolchainInfo::sysroot(&package_directory)?,
+            "/scrypto/rustc",
+        ));
+        let remap_flags = remapped_paths
+            .into_iter()
+            .map(|(from, to)| format!("--remap-path-prefix={}={}", from.display(), to))
+            .collect::<Vec<_>>();
+        for (name, separator) in [
+            ("RUSTFLAGS", " "),
+            ("CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUSTFLAGS", " "),
+            ("CARGO_ENCODED_RUSTFLAGS", "\x1f"),
+        ] {
+            if let Some(EnvironmentVariableAction::Set(value)) =
+                self.input_params.environment_variables.get_mut(name)
+            {
+                for flag in &remap_flags {
+                    if !value.is_empty() {
+                        value.push_str(separator);
+                    }
+                    value.push_str(flag);
+                }
+            }
+        }
+
+        self.input_params.wasm_optimization =
+            ScryptoCompilerInputParams::default().wasm_optimization;
+
+        Ok(toolchain)
+    }
+
+    // Writes the build info next to the WASM file of each of the built packages
+    fn write_build_info(
+        &self,
+        artifacts: Vec<BuildArtifacts>,
+        toolchain: &ToolchainInfo,
+    ) -> Result<Vec<BuildArtifacts>, ScryptoCompilerError> {
+        let source_tree_hash = source_tree_hash(&self.main_manifest_directory())?;
+
+        self.iter_manifests()
+            .zip(artifacts)
+            .map(|(manifest_def, mut artifacts)| {
+                let build_info = BuildInfo {
+                    package_name: manifest_def.target_binary_name.clone(),
+                    toolchain: toolchain.clone(),
+                    scrypto_compiler_version: env!("CARGO_PKG_VERSION").to_string(),
+                    profile: self.input_params.profile.clone(),
+                    features: self.input_params.features.iter().cloned().collect(),
+                    no_default_features: self.input_params.no_default_features,
+                    all_features: self.input_params.all_features,
+                    source_tree_hash,
+                    code_hash: hash(&artifacts.wasm.content),
+                };
+                let path = BuildInfo::path_for_wasm(&artifacts.wasm.path);
+                build_info.write(&path)?;
+                artifacts.build_info = Some(BuildArtifact {
+                    path,
+                    content: build_info,
+                });
+                Ok(artifacts)
+            })
+            .collect()
+    }
+
+    fn main_manifest_directory(&self) -> PathBuf {
+        self.main_manifest
+            .manifest_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
     pub fn compile(&mut self) -> Result<Vec<BuildArtifacts>, ScryptoCompilerError> {
         self.compile_with_stdio::<Stdio>(None, None, None)
     }
@@ -1182,6 +1328,7 @@ impl ScryptoCompiler {
         let artifacts = BuildArtifacts {
             wasm,
             package_definition,
+            build_info: None,
         };
 
         self.store_artifacts_in_cache(manifest_def, code_hash, &artifacts)?;
@@ -1410,6 +1557,7 @@ impl ScryptoCompiler {
             Ok(Some(BuildArtifacts {
                 wasm,
                 package_definition,
+                build_info: None,
             }))
         } else {
             Ok(None)
@@ -1546,6 +1694,11 @@ impl ScryptoCompilerBuilder {
         self
     }
 
+    pub fn reproducible(&mut self) -> &mut Self {
+        self.input_params.reproducible = true;
+        self
+    }
+
     pub fn coverage(&mut self) -> &mut Self {
         self.input_params
             .features
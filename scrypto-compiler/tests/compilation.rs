@@ -152,6 +152,27 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_reproducible_compilation_of_workspace_member_uses_workspace_lock_file() {
+        // Arrange
+        let (blueprint_manifest_path, target_directory) = prepare();
+
+        // Act
+        let status = ScryptoCompiler::builder()
+            .manifest_path(blueprint_manifest_path)
+            .target_directory(target_directory.path())
+            .reproducible()
+            .compile();
+
+        // Assert
+        // The lock file of the workspace is found, so the build only fails on the toolchain
+        assert!(
+            matches!(status, Err(ScryptoCompilerError::ToolchainNotPinned(_))),
+            "{:?}",
+            status
+        );
+    }
+
     #[test]
     fn test_compilation_workspace() {
         // Arrange
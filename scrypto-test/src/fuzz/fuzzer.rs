@@ -0,0 +1,408 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use crate::prelude::*;
+
+/// The environment variable which overrides the number of random inputs a fuzz test runs with.
+pub const FUZZ_ITERATIONS_ENV: &str = "SCRYPTO_FUZZ_ITERATIONS";
+
+/// The environment variable which overrides the seed of the random inputs of a fuzz test.
+pub const FUZZ_SEED_ENV: &str = "SCRYPTO_FUZZ_SEED";
+
+const DEFAULT_ITERATIONS: u64 = 64;
+
+/// The maximum number of runs spent minimizing a failing input.
+const MAX_MINIMIZATION_RUNS: usize = 1024;
+
+/// Runs a property test against many arbitrary inputs, each run in a fresh [`TestEnvironment`].
+///
+/// The ledger is bootstrapped once and every run starts from a snapshot of it, the fuzzed package,
+/// if any, being compiled once and published at the start of each run. The inputs which failed
+/// before are replayed first, from the regression file at `fuzz/regressions/<name>.rs` in the
+/// crate of the test. When a random input makes the test fail, it is minimized, added to the
+/// regression file and reported.
+///
+/// The fuzzer is created with the [`fuzzer!`](crate::fuzzer) macro, which locates the regression
+/// file in the crate the test is written in.
+///
+/// ```no_run
+/// use scrypto_test::prelude::*;
+///
+/// #[test]
+/// fn fuzz_hello() {
+///     fuzzer!("fuzz_hello")
+///         .package(this_package!())
+///         .run(|env, input| {
+///             let output = input.call_function(env, "Hello", "instantiate_hello")?;
+///             let component: ComponentAddress = scrypto_decode(&output).unwrap();
+///             input.call_method(env, component, "Hello", "free_token")?;
+///             Ok(())
+///         });
+/// }
+/// ```
+pub struct Fuzzer {
+    name: String,
+    manifest_dir: PathBuf,
+    package_path: Option<PathBuf>,
+    iterations: u64,
+    seed: u64,
+}
+
+/// The failure of a run, with the panic or error message.
+struct Failure {
+    message: String,
+    log: Vec<String>,
+}
+
+impl Fuzzer {
+    /// Creates a fuzzer for the test of the given name, which names its regression file in the
+    /// `fuzz/regressions` directory of the crate at `manifest_dir`.
+    ///
+    /// The number of iterations and the seed are read from the [`FUZZ_ITERATIONS_ENV`] and
+    /// [`FUZZ_SEED_ENV`] environment variables, which `scrypto fuzz` sets.
+    pub fn new<P: AsRef<Path>>(name: &str, manifest_dir: P) -> Self {
+        let from_env = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.parse::<u64>().ok())
+        };
+        Self {
+            name: name.to_string(),
+            manifest_dir: manifest_dir.as_ref().to_path_buf(),
+            package_path: None,
+            iterations: from_env(FUZZ_ITERATIONS_ENV).unwrap_or(DEFAULT_ITERATIONS),
+            seed: from_env(FUZZ_SEED_ENV).unwrap_or_default(),
+        }
+    }
+
+    /// Sets the package to publish before each run, whose function schemas the arguments are
+    /// generated from.
+    pub fn package<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.package_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn iterations(mut self, iterations: u64) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The path of the regression file of the test.
+    pub fn regression_file_path(&self) -> PathBuf {
+        self.manifest_dir
+            .join("fuzz")
+            .join("regressions")
+            .join(format!("{}.rs", self.name))
+    }
+
+    /// Runs the test with the regression inputs, then with random inputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the test panics or returns an error for any input, after minimizing the input.
+    pub fn run<F>(self, test: F)
+    where
+        F: Fn(&mut DefaultTestEnvironment, &mut FuzzInput) -> Result<(), RuntimeError>,
+    {
+        let mut snapshot = InMemorySubstateDatabase::standard();
+        ProtocolBuilder::for_simulator()
+            .from_bootstrap_to_latest()
+            .commit_each_protocol_update(&mut snapshot);
+        let package = self
+            .package_path
+            .as_ref()
+            .map(|path| PackageFactory::compile(path, CompileProfile::Fast));
+
+        let run_input = |input: FuzzInput| -> Result<(), Failure> {
+            run_once(&snapshot, package.as_ref(), input, &test)
+        };
+
+        let regression_file_path = self.regression_file_path();
+        for choices in read_regressions(&regression_file_path) {
+            if let Err(failure) = run_input(FuzzInput::replay(choices.clone())) {
+                fail(
+                    &format!("regression input from {}", regression_file_path.display()),
+                    &choices,
+                    failure,
+                );
+            }
+        }
+
+        let mut seeds = super::input::SplitMix64(self.seed);
+        for iteration in 0..self.iterations {
+            let mut choices = Vec::new();
+            let result = run_once_recording(
+                &snapshot,
+                package.as_ref(),
+                FuzzInput::random(seeds.next()),
+                &test,
+                &mut choices,
+            );
+            if result.is_ok() {
+                continue;
+            }
+
+            let (choices, failure) = minimize(choices, |choices| {
+                run_input(FuzzInput::replay(choices.to_vec())).err()
+            });
+            save_regression(
+                &regression_file_path,
+                &self.name,
+                &format!("seed {}, iteration {}", self.seed, iteration),
+                &failure.message,
+                &choices,
+            );
+            fail(
+                &format!(
+                    "iteration {} (seed {}), saved to {}",
+                    iteration,
+                    self.seed,
+                    regression_file_path.display()
+                ),
+                &choices,
+                failure,
+            );
+        }
+    }
+}
+
+fn run_once<F>(
+    snapshot: &InMemorySubstateDatabase,
+    package: Option<&(Vec<u8>, PackageDefinition)>,
+    input: FuzzInput,
+    test: &F,
+) -> Result<(), Failure>
+where
+    F: Fn(&mut DefaultTestEnvironment, &mut FuzzInput) -> Result<(), RuntimeError>,
+{
+    run_once_recording(snapshot, package, input, test, &mut Vec::new())
+}
+
+fn run_once_recording<F>(
+    snapshot: &InMemorySubstateDatabase,
+    package: Option<&(Vec<u8>, PackageDefinition)>,
+    mut input: FuzzInput,
+    test: &F,
+    choices: &mut Vec<u64>,
+) -> Result<(), Failure>
+where
+    F: Fn(&mut DefaultTestEnvironment, &mut FuzzInput) -> Result<(), RuntimeError>,
+{
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut env = TestEnvironmentBuilder::new()
+            .database(snapshot.clone())
+            .with_protocol(|builder| builder.from_current_to_latest())
+            .build();
+        if let Some((code, package_definition)) = package {
+            let (package_address, _) = PackageFactory::publish(
+                code.clone(),
+                package_definition.clone(),
+                MetadataInit::default(),
+                &mut env,
+            )
+            .expect("Publishing the fuzzed package failed");
+            input.set_package(package_address, package_definition.clone());
+        }
+        test(&mut env, &mut input)
+    }));
+    *choices = input.choices().to_vec();
+
+    let message = match result {
+        Ok(Ok(())) => return Ok(()),
+        Ok(Err(error)) => format!("{:?}", error),
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_string()),
+    };
+    Err(Failure {
+        message,
+        log: input.log().to_vec(),
+    })
+}
+
+/// Shrinks the choices of a failing input while it keeps failing, first by deleting chunks of
+/// choices and then by making the remaining choices smaller.
+fn minimize<F>(choices: Vec<u64>, mut fails: F) -> (Vec<u64>, Failure)
+where
+    F: FnMut(&[u64]) -> Option<Failure>,
+{
+    let mut runs = 0;
+    let mut try_choices = |candidate: &[u64]| {
+        if runs >= MAX_MINIMIZATION_RUNS {
+            return None;
+        }
+        runs += 1;
+        fails(candidate)
+    };
+
+    let mut failure = try_choices(&choices);
+    let mut choices = choices;
+    if failure.is_none() {
+        // A flaky failure, which is reported without being minimized.
+        return (
+            choices,
+            Failure {
+                message: "The input failed once but passed when replayed".to_string(),
+                log: Vec::new(),
+            },
+        );
+    }
+
+    for chunk_size in [8, 4, 2, 1] {
+        let mut start = 0;
+        while start + chunk_size <= choices.len() {
+            let mut candidate = choices.clone();
+            candidate.drain(start..start + chunk_size);
+            match try_choices(&candidate) {
+                Some(candidate_failure) => {
+                    choices = candidate;
+                    failure = Some(candidate_failure);
+                }
+                None => start += 1,
+            }
+        }
+    }
+
+    for index in 0..choices.len() {
+        'shrinking: loop {
+            for smaller in [0, choices[index] / 2, choices[index].saturating_sub(1)] {
+                if smaller >= choices[index] {
+                    continue;
+                }
+                let mut candidate = choices.clone();
+                candidate[index] = smaller;
+                if let Some(candidate_failure) = try_choices(&candidate) {
+                    choices = candidate;
+                    failure = Some(candidate_failure);
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+    }
+
+    while choices.last() == Some(&0) {
+        choices.pop();
+    }
+
+    (choices, failure.expect("Checked above"))
+}
+
+fn fail(context: &str, choices: &[u64], failure: Failure) -> ! {
+    let mut message = format!(
+        "Fuzz test failed on {}\nInput: {}\nError: {}",
+        context,
+        format_choices(choices),
+        failure.message
+    );
+    for entry in failure.log {
+        message.push_str("\n  ");
+        message.push_str(&entry);
+    }
+    panic!("{}", message);
+}
+
+fn format_choices(choices: &[u64]) -> String {
+    choices
+        .iter()
+        .map(|choice| choice.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Reads the inputs of a regression file, each of which is a line with the slice of its choices.
+fn read_regressions(path: &Path) -> Vec<Vec<u64>> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix("&[")
+                .and_then(|choices| choices.strip_suffix("],"))
+        })
+        .map(|choices| {
+            choices
+                .split(',')
+                .filter_map(|choice| choice.trim().parse::<u64>().ok())
+                .collect()
+        })
+        .collect()
+}
+
+/// Adds a minimized input to the regression file, which is a Rust file declaring the inputs as a
+/// constant, so that they can be reviewed and checked in along with the test.
+fn save_regression(path: &Path, name: &str, context: &str, message: &str, choices: &[u64]) {
+    let mut content = std::fs::read_to_string(path).unwrap_or_else(|_| {
+        format!(
+            "//! The minimized inputs which made the fuzz test `{}` fail, which are replayed before\n\
+             //! the random inputs. It is recommended to check this file in to source control.\n\n\
+             pub const REGRESSIONS: &[&[u64]] = &[\n];\n",
+            name
+        )
+    });
+    let end = content.rfind("];").unwrap_or(content.len());
+    let error = message.lines().next().unwrap_or_default();
+    content.insert_str(
+        end,
+        &format!(
+            "    // {}: {}\n    &[{}],\n",
+            context,
+            error,
+            format_choices(choices)
+        ),
+    );
+    // Failing to save the regression doesn't hide the failure, which is reported right after.
+    let _ = path
+        .parent()
+        .map(std::fs::create_dir_all)
+        .unwrap_or(Ok(()))
+        .and_then(|_| std::fs::write(path, content));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimization_shrinks_the_failing_choices() {
+        // Fails whenever a choice is at least 100.
+        let (choices, _) = minimize(vec![5, 7, 250, 3, 1000, 9], |choices| {
+            choices
+                .iter()
+                .any(|choice| *choice >= 100)
+                .then(|| Failure {
+                    message: String::new(),
+                    log: Vec::new(),
+                })
+        });
+        assert_eq!(choices, vec![100]);
+    }
+
+    #[test]
+    fn regressions_are_read_back() {
+        let directory =
+            std::env::temp_dir().join(format!("fuzz-regressions-{}", std::process::id()));
+        let path = directory.join("test.rs");
+        save_regression(&path, "test", "seed 1", "Error\nDetails", &[1, 2, 3]);
+        save_regression(&path, "test", "seed 2", "Error", &[]);
+        assert_eq!(read_regressions(&path), vec![vec![1, 2, 3], vec![]]);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "//! The minimized inputs which made the fuzz test `test` fail, which are replayed before\n\
+             //! the random inputs. It is recommended to check this file in to source control.\n\n\
+             pub const REGRESSIONS: &[&[u64]] = &[\n    \
+             // seed 1: Error\n    &[1, 2, 3],\n    \
+             // seed 2: Error\n    &[],\n];\n"
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,594 @@
+use sbor::representations::*;
+use scrypto::radix_blueprint_schema_init::TypeRef;
+
+use crate::prelude::*;
+
+/// The maximum depth of the generated values, beyond which arrays and maps are empty.
+const MAX_VALUE_DEPTH: usize = 16;
+
+/// The characters which generated strings are made of, a few non-ASCII ones included.
+const STRING_CHARACTERS: &[char] = &[
+    'a', 'b', 'c', 'x', 'y', 'z', 'A', 'B', 'Z', '0', '1', '9', ' ', '_', '-', '.', 'é', '€', '😀',
+];
+
+/// The input of a single run of a fuzz test, from which the test draws arbitrary values.
+///
+/// An input is a sequence of choices: every value drawn from it consumes some choices, the first
+/// option of each choice being the simplest value. This is what makes inputs replayable from the
+/// regression files and minimizable, since shrinking the choices towards zero shrinks the values
+/// whatever their types.
+///
+/// The arguments of the functions and methods of the fuzzed package are generated from their
+/// schemas, buckets being minted from the resources registered with [`FuzzInput::add_resource`]
+/// (XRD by default) and references being picked among the addresses registered with
+/// [`FuzzInput::add_reference`].
+pub struct FuzzInput {
+    choices: Vec<u64>,
+    position: usize,
+    /// The generator of new choices, or `None` when replaying choices.
+    generator: Option<SplitMix64>,
+    package: Option<(PackageAddress, PackageDefinition)>,
+    resources: Vec<ResourceAddress>,
+    references: Vec<GlobalAddress>,
+    log: Vec<String>,
+}
+
+impl FuzzInput {
+    /// Creates an input made of random choices.
+    pub fn random(seed: u64) -> Self {
+        Self::new(Vec::new(), Some(SplitMix64(seed)))
+    }
+
+    /// Creates an input which replays the given choices, the choices beyond them being zero.
+    pub fn replay(choices: Vec<u64>) -> Self {
+        Self::new(choices, None)
+    }
+
+    fn new(choices: Vec<u64>, generator: Option<SplitMix64>) -> Self {
+        Self {
+            choices,
+            position: 0,
+            generator,
+            package: None,
+            resources: vec![XRD],
+            references: vec![XRD.into()],
+            log: Vec::new(),
+        }
+    }
+
+    pub(super) fn set_package(
+        &mut self,
+        package_address: PackageAddress,
+        package_definition: PackageDefinition,
+    ) {
+        self.references.push(package_address.into());
+        self.package = Some((package_address, package_definition));
+    }
+
+    /// The choices drawn so far, which is all that's needed to replay the input.
+    pub fn choices(&self) -> &[u64] {
+        &self.choices[..self.position.min(self.choices.len())]
+    }
+
+    /// The description of the calls made with this input and of their arguments.
+    pub fn log(&self) -> &[String] {
+        &self.log
+    }
+
+    /// Adds a line to the description of the input, which is printed when the test fails.
+    pub fn record(&mut self, entry: impl Into<String>) {
+        self.log.push(entry.into());
+    }
+
+    /// Registers a fungible resource to mint the generated buckets from, and which references to
+    /// resources may point to.
+    pub fn add_resource(&mut self, resource_address: ResourceAddress) {
+        self.resources.push(resource_address);
+        self.add_reference(resource_address);
+    }
+
+    /// Registers an address which the generated references may point to, such as the address of
+    /// a component instantiated by the test.
+    pub fn add_reference(&mut self, address: impl Into<GlobalAddress>) {
+        self.references.push(address.into());
+    }
+
+    /// The address of the fuzzed package, which the fuzzer published before the run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fuzzer was not given a package.
+    pub fn package_address(&self) -> PackageAddress {
+        self.package
+            .as_ref()
+            .map(|(package_address, _)| *package_address)
+            .expect("The fuzzer was not given a package")
+    }
+
+    // ===============
+    // region:choices
+    // ===============
+
+    fn draw(&mut self) -> u64 {
+        let choice = match (self.choices.get(self.position), &mut self.generator) {
+            (Some(choice), _) => *choice,
+            (None, Some(generator)) => {
+                let choice = generator.next();
+                self.choices.push(choice);
+                choice
+            }
+            (None, None) => 0,
+        };
+        self.position += 1;
+        choice
+    }
+
+    /// Chooses a number below `bound`, or zero if `bound` is zero.
+    pub fn choose(&mut self, bound: u64) -> u64 {
+        match bound {
+            0 => 0,
+            bound => self.draw() % bound,
+        }
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.choose(2) == 1
+    }
+
+    /// Chooses a number of `bits` bits, biased towards small numbers and towards the bounds.
+    fn integer_bits(&mut self, bits: u32, signed: bool) -> u128 {
+        let mask = u128::MAX >> (128 - bits);
+        match self.choose(5) {
+            0 => self.choose(16) as u128,
+            // All bits set, which is the maximum if unsigned and -1 if signed
+            1 => mask,
+            2 if signed => mask >> 1,
+            2 => mask - 1,
+            3 if signed => 1 << (bits - 1),
+            3 => self.choose(256) as u128,
+            _ => (((self.draw() as u128) << 64) | self.draw() as u128) & mask,
+        }
+    }
+
+    /// Chooses a decimal, biased towards zero, one, small numbers and the bounds.
+    pub fn decimal(&mut self) -> Decimal {
+        let decimal = match self.choose(6) {
+            0 => Decimal::ZERO,
+            1 => Decimal::ONE,
+            2 => Decimal::from(self.choose(1000)),
+            3 => Decimal::MAX,
+            4 => Decimal::MIN,
+            _ => Decimal::from_attos(I192::from(self.integer_bits(128, true) as i128)),
+        };
+        if self.bool() && decimal != Decimal::MIN {
+            -decimal
+        } else {
+            decimal
+        }
+    }
+
+    /// Chooses a non-negative amount, biased towards zero, one and small amounts.
+    pub fn amount(&mut self) -> Decimal {
+        match self.choose(4) {
+            0 => Decimal::ZERO,
+            1 => Decimal::ONE,
+            2 => Decimal::from(self.choose(1_000_000)),
+            _ => Decimal::from_attos(I192::from(self.draw())),
+        }
+    }
+
+    pub fn string(&mut self, max_length: u64) -> String {
+        let length = self.choose(max_length + 1);
+        (0..length)
+            .map(|_| STRING_CHARACTERS[self.choose(STRING_CHARACTERS.len() as u64) as usize])
+            .collect()
+    }
+
+    pub fn bytes(&mut self, max_length: u64) -> Vec<u8> {
+        let length = self.choose(max_length + 1);
+        (0..length).map(|_| self.choose(256) as u8).collect()
+    }
+
+    pub fn non_fungible_local_id(&mut self) -> NonFungibleLocalId {
+        match self.choose(4) {
+            0 => NonFungibleLocalId::integer(self.integer_bits(64, false) as u64),
+            1 => {
+                let length = 1 + self.choose(16);
+                let id = (0..length)
+                    .map(|_| {
+                        let characters = b"abcxyzABC019_";
+                        characters[self.choose(characters.len() as u64) as usize] as char
+                    })
+                    .collect::<String>();
+                NonFungibleLocalId::string(id).expect("Valid string id")
+            }
+            2 => {
+                let length = 1 + self.choose(16);
+                let id = (0..length)
+                    .map(|_| self.choose(256) as u8)
+                    .collect::<Vec<_>>();
+                NonFungibleLocalId::bytes(id).expect("Valid bytes id")
+            }
+            _ => {
+                let mut id = [0u8; 32];
+                id.iter_mut()
+                    .for_each(|byte| *byte = self.choose(256) as u8);
+                NonFungibleLocalId::ruid(id)
+            }
+        }
+    }
+
+    // ==============
+    // region:values
+    // ==============
+
+    /// Generates a value of the given type of the schema. Buckets and proofs are created in the
+    /// environment, which is why generating a value may fail.
+    ///
+    /// Values of the other owned types, such as vaults, can't be generated: an empty tuple is
+    /// generated in their place, which the engine rejects.
+    pub fn value<D>(
+        &mut self,
+        schema: &VersionedScryptoSchema,
+        type_id: LocalTypeId,
+        env: &mut TestEnvironment<D>,
+    ) -> Result<ScryptoValue, RuntimeError>
+    where
+        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+    {
+        self.value_at_depth(schema.v1(), type_id, 0, env)
+    }
+
+    fn value_at_depth<D>(
+        &mut self,
+        schema: &SchemaV1<ScryptoCustomSchema>,
+        type_id: LocalTypeId,
+        depth: usize,
+        env: &mut TestEnvironment<D>,
+    ) -> Result<ScryptoValue, RuntimeError>
+    where
+        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+    {
+        let Some(type_kind) = schema.resolve_type_kind(type_id) else {
+            return Ok(Value::Tuple { fields: vec![] });
+        };
+        let max_length = if depth >= MAX_VALUE_DEPTH { 0 } else { 4 };
+        let value = match type_kind {
+            TypeKind::Any => Value::Tuple { fields: vec![] },
+            TypeKind::Bool => Value::Bool { value: self.bool() },
+            TypeKind::I8 => Value::I8 {
+                value: self.integer_bits(8, true) as i8,
+            },
+            TypeKind::I16 => Value::I16 {
+                value: self.integer_bits(16, true) as i16,
+            },
+            TypeKind::I32 => Value::I32 {
+                value: self.integer_bits(32, true) as i32,
+            },
+            TypeKind::I64 => Value::I64 {
+                value: self.integer_bits(64, true) as i64,
+            },
+            TypeKind::I128 => Value::I128 {
+                value: self.integer_bits(128, true) as i128,
+            },
+            TypeKind::U8 => Value::U8 {
+                value: self.integer_bits(8, false) as u8,
+            },
+            TypeKind::U16 => Value::U16 {
+                value: self.integer_bits(16, false) as u16,
+            },
+            TypeKind::U32 => Value::U32 {
+                value: self.integer_bits(32, false) as u32,
+            },
+            TypeKind::U64 => Value::U64 {
+                value: self.integer_bits(64, false) as u64,
+            },
+            TypeKind::U128 => Value::U128 {
+                value: self.integer_bits(128, false),
+            },
+            TypeKind::String => Value::String {
+                value: self.string(4 * max_length),
+            },
+            TypeKind::Array { element_type } => {
+                let length = self.choose(max_length + 1);
+                let elements = (0..length)
+                    .map(|_| self.value_at_depth(schema, *element_type, depth + 1, env))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Value::Array {
+                    element_value_kind: value_kind(schema, *element_type),
+                    elements,
+                }
+            }
+            TypeKind::Tuple { field_types } => Value::Tuple {
+                fields: field_types
+                    .iter()
+                    .map(|field_type| self.value_at_depth(schema, *field_type, depth + 1, env))
+                    .collect::<Result<Vec<_>, _>>()?,
+            },
+            TypeKind::Enum { variants } => {
+                let index = self.choose(variants.len() as u64) as usize;
+                let Some((discriminator, field_types)) = variants.get_index(index) else {
+                    return Ok(Value::Tuple { fields: vec![] });
+                };
+                Value::Enum {
+                    discriminator: *discriminator,
+                    fields: field_types
+                        .iter()
+                        .map(|field_type| self.value_at_depth(schema, *field_type, depth + 1, env))
+                        .collect::<Result<Vec<_>, _>>()?,
+                }
+            }
+            TypeKind::Map {
+                key_type,
+                value_type,
+            } => {
+                let length = self.choose(max_length + 1);
+                let entries = (0..length)
+                    .map(|_| {
+                        Ok::<_, RuntimeError>((
+                            self.value_at_depth(schema, *key_type, depth + 1, env)?,
+                            self.value_at_depth(schema, *value_type, depth + 1, env)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Value::Map {
+                    key_value_kind: value_kind(schema, *key_type),
+                    value_value_kind: value_kind(schema, *value_type),
+                    entries,
+                }
+            }
+            TypeKind::Custom(ScryptoCustomTypeKind::Decimal) => Value::Custom {
+                value: ScryptoCustomValue::Decimal(self.decimal()),
+            },
+            TypeKind::Custom(ScryptoCustomTypeKind::PreciseDecimal) => Value::Custom {
+                value: ScryptoCustomValue::PreciseDecimal(PreciseDecimal::from(self.decimal())),
+            },
+            TypeKind::Custom(ScryptoCustomTypeKind::NonFungibleLocalId) => Value::Custom {
+                value: ScryptoCustomValue::NonFungibleLocalId(self.non_fungible_local_id()),
+            },
+            TypeKind::Custom(ScryptoCustomTypeKind::Reference) => {
+                let validation = match schema.resolve_type_validation(type_id) {
+                    Some(TypeValidation::Custom(ScryptoCustomTypeValidation::Reference(
+                        validation,
+                    ))) => Some(validation),
+                    _ => None,
+                };
+                Value::Custom {
+                    value: ScryptoCustomValue::Reference(Reference(self.reference(validation))),
+                }
+            }
+            TypeKind::Custom(ScryptoCustomTypeKind::Own) => {
+                let validation = match schema.resolve_type_validation(type_id) {
+                    Some(TypeValidation::Custom(ScryptoCustomTypeValidation::Own(validation))) => {
+                        Some(validation)
+                    }
+                    _ => None,
+                };
+                match validation {
+                    Some(OwnValidation::IsBucket) => self.bucket(env)?,
+                    Some(OwnValidation::IsTypedObject(_, blueprint_name))
+                        if blueprint_name == FUNGIBLE_BUCKET_BLUEPRINT =>
+                    {
+                        self.bucket(env)?
+                    }
+                    Some(OwnValidation::IsProof) => self.proof(env)?,
+                    Some(OwnValidation::IsTypedObject(_, blueprint_name))
+                        if blueprint_name == FUNGIBLE_PROOF_BLUEPRINT =>
+                    {
+                        self.proof(env)?
+                    }
+                    _ => Value::Tuple { fields: vec![] },
+                }
+            }
+        };
+        Ok(value)
+    }
+
+    fn reference(&mut self, validation: Option<&ReferenceValidation>) -> NodeId {
+        let candidates = self
+            .references
+            .iter()
+            .map(|address| *address.as_node_id())
+            .filter(|node_id| match validation {
+                Some(ReferenceValidation::IsGlobalPackage) => node_id.is_global_package(),
+                Some(ReferenceValidation::IsGlobalComponent) => node_id.is_global_component(),
+                Some(ReferenceValidation::IsGlobalResourceManager) => {
+                    node_id.is_global_resource_manager()
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+        match candidates.len() {
+            0 => *self.references[0].as_node_id(),
+            length => candidates[self.choose(length as u64) as usize],
+        }
+    }
+
+    fn bucket<D>(&mut self, env: &mut TestEnvironment<D>) -> Result<ScryptoValue, RuntimeError>
+    where
+        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+    {
+        let index = self.choose(self.resources.len() as u64) as usize;
+        let resource_address = self.resources[index];
+        let amount = self.amount();
+        let bucket = BucketFactory::create_fungible_bucket(
+            resource_address,
+            amount,
+            CreationStrategy::DisableAuthAndMint,
+            env,
+        )?;
+        Ok(Value::Custom {
+            value: ScryptoCustomValue::Own(bucket.0 .0),
+        })
+    }
+
+    fn proof<D>(&mut self, env: &mut TestEnvironment<D>) -> Result<ScryptoValue, RuntimeError>
+    where
+        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+    {
+        let index = self.choose(self.resources.len() as u64) as usize;
+        let resource_address = self.resources[index];
+        let amount = self.amount();
+        let bucket = BucketFactory::create_fungible_bucket(
+            resource_address,
+            amount,
+            CreationStrategy::DisableAuthAndMint,
+            env,
+        )?;
+        let proof = bucket.create_proof_of_all(env)?;
+        Ok(Value::Custom {
+            value: ScryptoCustomValue::Own(proof.0 .0),
+        })
+    }
+
+    // =============
+    // region:calls
+    // =============
+
+    /// Generates the arguments of a function or method of a blueprint of the fuzzed package.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fuzzer was not given a package, if the package has no such function or if
+    /// its input is generic.
+    pub fn arguments<D>(
+        &mut self,
+        blueprint_name: &str,
+        function_name: &str,
+        env: &mut TestEnvironment<D>,
+    ) -> Result<ScryptoValue, RuntimeError>
+    where
+        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+    {
+        let (_, package_definition) = self
+            .package
+            .as_ref()
+            .expect("The fuzzer was not given a package");
+        let blueprint = package_definition
+            .blueprints
+            .get(blueprint_name)
+            .unwrap_or_else(|| panic!("The package has no blueprint `{}`", blueprint_name));
+        let function = blueprint
+            .schema
+            .functions
+            .functions
+            .get(function_name)
+            .unwrap_or_else(|| {
+                panic!(
+                    "The blueprint `{}` has no function `{}`",
+                    blueprint_name, function_name
+                )
+            });
+        let TypeRef::Static(input_type_id) = function.input else {
+            panic!(
+                "The input of `{}::{}` is generic",
+                blueprint_name, function_name
+            )
+        };
+        let schema = blueprint.schema.schema.clone();
+
+        let arguments = self.value(&schema, input_type_id, env)?;
+        self.record(format!(
+            "{}::{}{}",
+            blueprint_name,
+            function_name,
+            display_value(&arguments)
+        ));
+        Ok(arguments)
+    }
+
+    /// Calls a function of a blueprint of the fuzzed package with generated arguments, and returns
+    /// its encoded output.
+    pub fn call_function<D>(
+        &mut self,
+        env: &mut TestEnvironment<D>,
+        blueprint_name: &str,
+        function_name: &str,
+    ) -> Result<Vec<u8>, RuntimeError>
+    where
+        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+    {
+        let arguments = self.arguments(blueprint_name, function_name, env)?;
+        env.call_function(
+            self.package_address(),
+            blueprint_name,
+            function_name,
+            scrypto_encode(&arguments).expect("Generated values are encodable"),
+        )
+    }
+
+    /// Calls a method of a component of the fuzzed package with generated arguments, and returns
+    /// its encoded output.
+    pub fn call_method<D>(
+        &mut self,
+        env: &mut TestEnvironment<D>,
+        component_address: ComponentAddress,
+        blueprint_name: &str,
+        method_name: &str,
+    ) -> Result<Vec<u8>, RuntimeError>
+    where
+        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+    {
+        let arguments = self.arguments(blueprint_name, method_name, env)?;
+        env.call_method(
+            component_address.as_node_id(),
+            method_name,
+            scrypto_encode(&arguments).expect("Generated values are encodable"),
+        )
+    }
+}
+
+fn value_kind(schema: &SchemaV1<ScryptoCustomSchema>, type_id: LocalTypeId) -> ScryptoValueKind {
+    match schema.resolve_type_kind(type_id) {
+        None | Some(TypeKind::Any) | Some(TypeKind::Tuple { .. }) => ValueKind::Tuple,
+        Some(TypeKind::Bool) => ValueKind::Bool,
+        Some(TypeKind::I8) => ValueKind::I8,
+        Some(TypeKind::I16) => ValueKind::I16,
+        Some(TypeKind::I32) => ValueKind::I32,
+        Some(TypeKind::I64) => ValueKind::I64,
+        Some(TypeKind::I128) => ValueKind::I128,
+        Some(TypeKind::U8) => ValueKind::U8,
+        Some(TypeKind::U16) => ValueKind::U16,
+        Some(TypeKind::U32) => ValueKind::U32,
+        Some(TypeKind::U64) => ValueKind::U64,
+        Some(TypeKind::U128) => ValueKind::U128,
+        Some(TypeKind::String) => ValueKind::String,
+        Some(TypeKind::Array { .. }) => ValueKind::Array,
+        Some(TypeKind::Enum { .. }) => ValueKind::Enum,
+        Some(TypeKind::Map { .. }) => ValueKind::Map,
+        Some(TypeKind::Custom(custom_type_kind)) => ValueKind::Custom(match custom_type_kind {
+            ScryptoCustomTypeKind::Reference => ScryptoCustomValueKind::Reference,
+            ScryptoCustomTypeKind::Own => ScryptoCustomValueKind::Own,
+            ScryptoCustomTypeKind::Decimal => ScryptoCustomValueKind::Decimal,
+            ScryptoCustomTypeKind::PreciseDecimal => ScryptoCustomValueKind::PreciseDecimal,
+            ScryptoCustomTypeKind::NonFungibleLocalId => ScryptoCustomValueKind::NonFungibleLocalId,
+        }),
+    }
+}
+
+fn display_value(value: &ScryptoValue) -> String {
+    let encoder = AddressBech32Encoder::for_simulator();
+    ScryptoRawPayload::new_from_valid_owned(
+        scrypto_encode(value).expect("Generated values are encodable"),
+    )
+    .to_string(ValueDisplayParameters::Schemaless {
+        display_mode: DisplayMode::RustLike(RustLikeOptions::full()),
+        print_mode: PrintMode::SingleLine,
+        custom_context: ScryptoValueDisplayContext::with_optional_bech32(Some(&encoder)),
+        depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+    })
+}
+
+/// The SplitMix64 generator, which is enough to generate the choices of the inputs.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+    pub fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,9 @@
+//! This module implements a fuzzing harness for property tests written against the
+//! [`TestEnvironment`](crate::environment::TestEnvironment), which generates the arguments of the
+//! blueprint functions from their schemas and minimizes the inputs which make the tests fail.
+
+mod fuzzer;
+mod input;
+
+pub use fuzzer::*;
+pub use input::*;
@@ -89,6 +89,7 @@
 )]
 
 pub mod environment;
+pub mod fuzz;
 pub mod ledger_simulator;
 pub mod prelude;
 pub mod sdk;
@@ -101,6 +102,22 @@ macro_rules! this_package {
     };
 }
 
+/// Creates a [`Fuzzer`](crate::fuzz::Fuzzer) for the fuzz test of the given name, whose
+/// regressions are saved in the `fuzz/regressions` directory of the crate the test is written in.
+///
+/// # Example
+/// ```no_run
+/// use scrypto_test::prelude::*;
+///
+/// let fuzzer = fuzzer!("fuzz_hello").package(this_package!());
+/// ```
+#[macro_export]
+macro_rules! fuzzer {
+    ($name: expr) => {
+        $crate::fuzz::Fuzzer::new($name, env!("CARGO_MANIFEST_DIR"))
+    };
+}
+
 /// Includes the WASM file of a Scrypto package.
 ///
 /// Notes:
@@ -103,6 +103,7 @@ pub use scrypto::prelude::{
 
 /* This Crate */
 pub use crate::environment::*;
+pub use crate::fuzz::*;
 pub use crate::ledger_simulator::*;
 pub use crate::sdk::*;
-pub use crate::{include_code, include_schema, this_package};
+pub use crate::{fuzzer, include_code, include_schema, this_package};